@@ -126,6 +126,15 @@ impl DetectionResult {
 pub trait ChallengeDetectorExt {
     /// 检测响应是否为验证页面
     fn detect(&self, response: &ResponseContext) -> DetectionResult;
+
+    /// 仅凭状态码与响应头检测，不读取响应体
+    ///
+    /// 只有能仅凭响应头判断的检测器会命中，依赖响应体特征的检测器总是返回未命中
+    fn detect_headers(
+        &self,
+        status_code: u16,
+        headers: &HashMap<String, String>,
+    ) -> DetectionResult;
 }
 
 impl ChallengeDetectorExt for ChallengeDetector {
@@ -137,6 +146,37 @@ impl ChallengeDetectorExt for ChallengeDetector {
             ChallengeDetector::Custom(config) => detect_custom(config, response),
         }
     }
+
+    fn detect_headers(
+        &self,
+        status_code: u16,
+        headers: &HashMap<String, String>,
+    ) -> DetectionResult {
+        match self {
+            ChallengeDetector::Cloudflare(_) => detect_cloudflare_headers(status_code, headers),
+            // 不依赖响应体与 URL 的自定义检测可直接以空响应体判断
+            ChallengeDetector::Custom(config)
+                if config.body_patterns.is_none() && config.url_pattern.is_none() =>
+            {
+                let response = ResponseContext::new(
+                    status_code,
+                    headers.clone(),
+                    String::new(),
+                    String::new(),
+                );
+                detect_custom(config, &response)
+            }
+            _ => DetectionResult::not_detected(),
+        }
+    }
+}
+
+/// 按名称获取响应头（不区分大小写）
+fn header<'a>(headers: &'a HashMap<String, String>, name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case(name))
+        .map(|(_, v)| v.as_str())
 }
 
 // ============================================================================
@@ -210,6 +250,7 @@ fn detect_cloudflare(config: &CloudflareDetector, response: &ResponseContext) ->
 
     // 综合判断
     if let Some(ct) = challenge_type {
+        let is_turnstile = ct == ChallengeType::CloudflareTurnstile;
         let mut result = DetectionResult::detected(ct);
 
         // 尝试提取 cf-ray
@@ -217,6 +258,13 @@ fn detect_cloudflare(config: &CloudflareDetector, response: &ResponseContext) ->
             result = result.with_info("cf_ray", ray);
         }
 
+        // Turnstile 需要 site_key 等参数才能交给外部打码服务
+        if is_turnstile {
+            for (key, value) in extract_turnstile_params(&response.body) {
+                result = result.with_info(key, value);
+            }
+        }
+
         return result;
     }
 
@@ -228,6 +276,81 @@ fn detect_cloudflare(config: &CloudflareDetector, response: &ResponseContext) ->
     DetectionResult::not_detected()
 }
 
+/// 仅凭响应头检测 Cloudflare 验证
+///
+/// `cf-mitigated: challenge` 是 Cloudflare 对验证响应的明确标记；
+/// 与完整检测一致，403/503 且带有 Cloudflare 响应头时也视为验证
+fn detect_cloudflare_headers(
+    status_code: u16,
+    headers: &HashMap<String, String>,
+) -> DetectionResult {
+    let mitigated =
+        header(headers, "cf-mitigated").is_some_and(|v| v.eq_ignore_ascii_case("challenge"));
+    let has_cf_header = CLOUDFLARE_HEADERS
+        .iter()
+        .any(|h| header(headers, h).is_some());
+
+    if mitigated || (has_cf_header && (status_code == 403 || status_code == 503)) {
+        let mut result = DetectionResult::detected(ChallengeType::CloudflareJs);
+        if let Some(ray) = header(headers, "cf-ray") {
+            result = result.with_info("cf_ray", ray);
+        }
+        return result;
+    }
+
+    DetectionResult::not_detected()
+}
+
+/// 提取 Turnstile 参数：`site_key`、`action`、`cdata`
+///
+/// 优先从 `cf-turnstile` 元素的 `data-sitekey` / `data-action` / `data-cdata` 属性提取，
+/// 其次从 `turnstile.render(..., { sitekey: '...', action: '...', cData: '...' })` 调用提取
+fn extract_turnstile_params(body: &str) -> Vec<(&'static str, String)> {
+    const PARAMS: &[(&str, &str, &str)] = &[
+        ("site_key", "data-sitekey", "sitekey"),
+        ("action", "data-action", "action"),
+        ("cdata", "data-cdata", "cData"),
+    ];
+
+    // 只在 widget 元素的开始标签内查找属性，避免误取页面上其他元素的 data-action
+    let widget = Regex::new(r#"<[^>]*\bcf-turnstile["'\s][^>]*>"#)
+        .ok()
+        .and_then(|re| re.find(body))
+        .map(|m| m.as_str());
+    let render = Regex::new(r#"turnstile\.render\([^,]+,\s*\{"#)
+        .ok()
+        .and_then(|re| re.find(body))
+        .map(|m| &body[m.end()..]);
+
+    let mut params = Vec::new();
+    for (key, attr, option) in PARAMS {
+        let from_attr = widget.and_then(|tag| capture_quoted(tag, &format!(r#"{}\s*="#, attr)));
+        let from_render = || {
+            render.and_then(|options| {
+                capture_quoted(options, &format!(r#"["']?{}["']?\s*:"#, option))
+            })
+        };
+        // 非 widget 元素上的 data-sitekey 也可作为 site_key 的兜底
+        let fallback = || {
+            (*key == "site_key")
+                .then(|| capture_quoted(body, r#"data-sitekey\s*="#))
+                .flatten()
+        };
+        if let Some(value) = from_attr.or_else(from_render).or_else(fallback) {
+            params.push((*key, value));
+        }
+    }
+    params
+}
+
+/// 匹配 `prefix` 后紧跟的引号字符串，返回引号内的内容
+fn capture_quoted(text: &str, prefix: &str) -> Option<String> {
+    let re = Regex::new(&format!(r#"{}\s*["']([^"']+)["']"#, prefix)).ok()?;
+    re.captures(text)
+        .and_then(|caps| caps.get(1))
+        .map(|m| m.as_str().to_string())
+}
+
 // ============================================================================
 // reCAPTCHA 检测
 // ============================================================================
@@ -2,23 +2,26 @@
 //!
 //! 处理检测到的人机验证，支持多种策略
 
-use super::{ChallengeType, DetectionResult, ResponseContext};
+use super::{ChallengeType, DetectionResult, ResponseContext, store};
 use crate::{
     Result,
     RuntimeError,
     webview::{SharedWebViewProvider, WebViewCloseReason, WebViewRequest},
 };
-use crawler_schema::config::{
-    CaptchaProvider,
-    ChallengeHandler,
-    CookieHandler,
-    CookieSource,
-    ExternalHandler,
-    RetryHandler,
-    ScriptHandler,
-    WebviewHandler,
+use crawler_schema::{
+    config::{
+        CaptchaProvider,
+        ChallengeHandler,
+        CookieHandler,
+        CookieSource,
+        ExternalHandler,
+        RetryHandler,
+        ScriptHandler,
+        WebviewHandler,
+    },
+    script::{Script, ScriptEngine as SchemaScriptEngine, ScriptSource},
 };
-use std::{collections::HashMap, time::Duration};
+use std::{collections::HashMap, path::PathBuf, time::Duration};
 use tokio::sync::RwLock;
 
 /// 验证凭证
@@ -78,6 +81,20 @@ impl ChallengeCredentials {
         }
     }
 
+    /// 剩余有效期，未设置 TTL 时返回 None，已过期时为零
+    pub fn remaining_ttl(&self) -> Option<Duration> {
+        let (obtained_at, ttl) = (self.obtained_at?, self.ttl_seconds?);
+        Some(Duration::from_secs(ttl as u64).saturating_sub(obtained_at.elapsed()))
+    }
+
+    /// 检查凭证是否需要提前刷新（剩余有效期低于 `threshold`）
+    ///
+    /// 未设置 TTL 的凭证永不需要刷新
+    pub fn needs_refresh(&self, threshold: Duration) -> bool {
+        self.remaining_ttl()
+            .is_some_and(|remaining| remaining < threshold)
+    }
+
     /// 检查是否为空
     pub fn is_empty(&self) -> bool {
         self.cookies.is_empty() && self.headers.is_empty() && self.extra.is_empty()
@@ -150,14 +167,12 @@ async fn handle_webview(
         };
     }
 
+    let script_result = response.script_result_map();
     let mut credentials = ChallengeCredentials::new().with_cookies(response.cookies);
 
-    // 如果有脚本结果，解析额外数据
-    if let Some(script_result) = response.script_result
-        && let Ok(extra) = serde_json::from_str::<HashMap<String, String>>(&script_result)
-    {
-        credentials.extra = extra;
-    }
+    // localStorage 数据作为额外数据保存，脚本结果中的同名键覆盖 localStorage
+    credentials.extra = response.local_storage;
+    credentials.extra.extend(script_result);
 
     Ok(credentials)
 }
@@ -186,9 +201,30 @@ fn build_webview_request(config: &WebviewHandler, ctx: &HandlerContext) -> WebVi
         request = request.with_extract_cookies(cookies.clone());
     }
 
+    if let Some(keys) = &config.extract_local_storage {
+        request = request.with_extract_local_storage(keys.clone());
+    }
+
+    if let Some(script) = config.finish_script.as_ref().and_then(webview_script) {
+        request = request.with_finish_script(script);
+    }
+
     request
 }
 
+/// 获取可在 WebView 内执行的脚本代码
+///
+/// WebView 内只能执行内联 JavaScript，远程脚本或其他引擎的脚本会被忽略
+pub(crate) fn webview_script(script: &Script) -> Option<&str> {
+    match (script.source(), script.engine()) {
+        (ScriptSource::Code(code), SchemaScriptEngine::JavaScript) => Some(code),
+        _ => {
+            tracing::warn!("WebView 仅支持内联 JavaScript 脚本，已忽略 finish_script");
+            None
+        }
+    }
+}
+
 // ============================================================================
 // 重试处理器
 // ============================================================================
@@ -677,8 +713,14 @@ async fn handle_script(
 // ============================================================================
 
 /// 凭证缓存
+///
+/// 默认仅保存在内存中；通过 [`CredentialsCache::persistent`] 创建时，
+/// 每次写入、删除和清理后都会同步到磁盘文件
+#[derive(Debug)]
 pub struct CredentialsCache {
     cache: RwLock<HashMap<String, ChallengeCredentials>>,
+    /// 持久化文件路径
+    path: Option<PathBuf>,
 }
 
 impl Default for CredentialsCache {
@@ -692,6 +734,28 @@ impl CredentialsCache {
     pub fn new() -> Self {
         Self {
             cache: RwLock::new(HashMap::new()),
+            path: None,
+        }
+    }
+
+    /// 创建持久化到文件的缓存，并加载文件中未过期的凭证
+    pub fn persistent(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let cache = store::load(&path)?;
+        Ok(Self {
+            cache: RwLock::new(cache),
+            path: Some(path),
+        })
+    }
+
+    /// 同步到磁盘（仅持久化缓存）
+    ///
+    /// 写入失败只记录警告，不影响内存中的凭证
+    fn persist(&self, cache: &HashMap<String, ChallengeCredentials>) {
+        if let Some(path) = &self.path
+            && let Err(e) = store::save(path, cache)
+        {
+            tracing::warn!("凭证持久化失败: {}", e);
         }
     }
 
@@ -711,17 +775,44 @@ impl CredentialsCache {
     pub async fn set(&self, domain: &str, credentials: ChallengeCredentials) {
         let mut cache = self.cache.write().await;
         cache.insert(domain.to_string(), credentials);
+        self.persist(&cache);
     }
 
     /// 删除凭证
     pub async fn remove(&self, domain: &str) {
         let mut cache = self.cache.write().await;
-        cache.remove(domain);
+        if cache.remove(domain).is_some() {
+            self.persist(&cache);
+        }
+    }
+
+    /// 检查域名的凭证是否即将过期
+    ///
+    /// 凭证不存在时返回 false
+    pub async fn needs_refresh(&self, domain: &str, threshold: Duration) -> bool {
+        let cache = self.cache.read().await;
+        cache
+            .get(domain)
+            .is_some_and(|c| c.needs_refresh(threshold))
+    }
+
+    /// 获取即将过期（含已过期）的凭证
+    pub async fn expiring(&self, threshold: Duration) -> Vec<(String, ChallengeCredentials)> {
+        let cache = self.cache.read().await;
+        cache
+            .iter()
+            .filter(|(_, c)| c.needs_refresh(threshold))
+            .map(|(domain, c)| (domain.clone(), c.clone()))
+            .collect()
     }
 
     /// 清理过期凭证
     pub async fn cleanup_expired(&self) {
         let mut cache = self.cache.write().await;
+        let len = cache.len();
         cache.retain(|_, v| !v.is_expired());
+        if cache.len() != len {
+            self.persist(&cache);
+        }
     }
 }
@@ -11,17 +11,42 @@ use super::{
     HandlerContext,
     ResponseContext,
 };
-use crate::{Result, RuntimeError, webview::SharedWebViewProvider};
+use crate::{
+    Result,
+    RuntimeError,
+    http::CredentialsProvider,
+    util::SharedProgressListener,
+    webview::SharedWebViewProvider,
+};
+use async_trait::async_trait;
 use crawler_schema::{
     config::ChallengeConfig,
     script::{Script, ScriptSource},
 };
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use tokio::task::JoinHandle;
 use url::Url;
 
+/// 凭证刷新钩子
+///
+/// 由集成方实现，在凭证即将过期时于后台重新获取凭证，
+/// 避免用户请求时才触发验证
+#[async_trait]
+pub trait CredentialsRefresher: Send + Sync {
+    /// 刷新域名的凭证
+    ///
+    /// `current` 为即将过期的旧凭证
+    async fn refresh(
+        &self,
+        domain: &str,
+        current: &ChallengeCredentials,
+    ) -> Result<ChallengeCredentials>;
+}
+
 /// 验证管理器
 ///
 /// 负责检测和处理人机验证
+#[derive(Debug)]
 pub struct ChallengeManager {
     /// 验证配置
     config: ChallengeConfig,
@@ -31,6 +56,8 @@ pub struct ChallengeManager {
     credentials_cache: Arc<CredentialsCache>,
     /// HTTP 客户端
     http_client: Option<reqwest::Client>,
+    /// 进度事件监听器
+    progress_listener: Option<SharedProgressListener>,
 }
 
 impl ChallengeManager {
@@ -41,6 +68,7 @@ impl ChallengeManager {
             webview_provider,
             credentials_cache: Arc::new(CredentialsCache::new()),
             http_client: None,
+            progress_listener: None,
         }
     }
 
@@ -50,6 +78,12 @@ impl ChallengeManager {
         self
     }
 
+    /// 设置进度事件监听器，检测到验证时触发 `on_challenge`
+    pub fn with_progress_listener(mut self, listener: SharedProgressListener) -> Self {
+        self.progress_listener = Some(listener);
+        self
+    }
+
     /// 设置凭证缓存
     pub fn with_credentials_cache(mut self, cache: Arc<CredentialsCache>) -> Self {
         self.credentials_cache = cache;
@@ -77,6 +111,35 @@ impl ChallengeManager {
         DetectionResult::not_detected()
     }
 
+    /// 仅凭状态码与响应头快速检测，不读取响应体
+    ///
+    /// 适用于流式响应或大响应体：命中时再读取响应体，调用 [`Self::detect`] 做完整检测
+    /// （区分验证类型、提取 site_key 等）；未命中不代表不是验证页面，
+    /// reCAPTCHA、hCaptcha 等依赖响应体特征的检测器在此总是未命中
+    pub fn detect_headers_only(
+        &self,
+        status_code: u16,
+        headers: &HashMap<String, String>,
+    ) -> DetectionResult {
+        if !self.config.enabled {
+            return DetectionResult::not_detected();
+        }
+
+        for detector in &self.config.detectors {
+            let result = detector.detect_headers(status_code, headers);
+            if result.detected {
+                tracing::debug!(
+                    "响应头命中人机验证: {:?}, 状态码: {}",
+                    result.challenge_type,
+                    status_code
+                );
+                return result;
+            }
+        }
+
+        DetectionResult::not_detected()
+    }
+
     /// 处理验证
     ///
     /// 返回验证凭证，调用方需要将凭证应用到后续请求
@@ -101,6 +164,11 @@ impl ChallengeManager {
         if !detection.detected {
             return Ok(ChallengeCredentials::new());
         }
+        if let (Some(listener), Some(challenge_type)) =
+            (&self.progress_listener, &detection.challenge_type)
+        {
+            listener.on_challenge(challenge_type);
+        }
 
         // 构建处理上下文
         let ctx = HandlerContext {
@@ -157,6 +225,51 @@ impl ChallengeManager {
         Ok(Some(credentials))
     }
 
+    /// 刷新所有即将过期的凭证
+    ///
+    /// 剩余有效期低于 `threshold` 的凭证交由 `refresher` 重新获取，
+    /// 单个域名刷新失败不影响其他域名；返回成功刷新的域名数
+    pub async fn refresh_expiring(
+        &self,
+        threshold: Duration,
+        refresher: &dyn CredentialsRefresher,
+    ) -> usize {
+        let mut refreshed = 0;
+        for (domain, current) in self.credentials_cache.expiring(threshold).await {
+            match refresher.refresh(&domain, &current).await {
+                Ok(mut credentials) => {
+                    if let Some(duration) = self.config.cache_duration {
+                        credentials = credentials.with_ttl(duration);
+                    }
+                    self.credentials_cache.set(&domain, credentials).await;
+                    refreshed += 1;
+                    tracing::debug!("凭证已提前刷新: {}", domain);
+                }
+                Err(e) => tracing::warn!("凭证提前刷新失败 ({}): {}", domain, e),
+            }
+        }
+        refreshed
+    }
+
+    /// 启动后台刷新任务
+    ///
+    /// 每隔 `interval` 检查一次，刷新剩余有效期低于 `threshold` 的凭证；
+    /// 通过返回的 `JoinHandle::abort` 停止任务
+    pub fn spawn_refresh_task(
+        self: Arc<Self>,
+        interval: Duration,
+        threshold: Duration,
+        refresher: Arc<dyn CredentialsRefresher>,
+    ) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                self.refresh_expiring(threshold, refresher.as_ref()).await;
+            }
+        })
+    }
+
     /// 获取域名的缓存凭证
     pub async fn get_cached_credentials(&self, url: &str) -> Option<ChallengeCredentials> {
         let domain = extract_domain(url)?;
@@ -171,6 +284,13 @@ impl ChallengeManager {
     }
 }
 
+#[async_trait]
+impl CredentialsProvider for ChallengeManager {
+    async fn credentials(&self, host: &str) -> Option<ChallengeCredentials> {
+        self.credentials_cache.get(host).await
+    }
+}
+
 /// 提取 URL 的域名
 fn extract_domain(url: &str) -> Option<String> {
     Url::parse(url)
@@ -200,6 +320,7 @@ pub fn default_cloudflare_config() -> ChallengeConfig {
             check_interval_ms: Some(500),
             finish_script: None,
             extract_cookies: Some(vec!["cf_clearance".to_string(), "__cf_bm".to_string()]),
+            extract_local_storage: None,
         }),
         cache_duration: Some(3600), // 1 小时
         max_attempts: 3,
@@ -238,6 +359,7 @@ pub fn default_recaptcha_config() -> ChallengeConfig {
                 security: None,
             }),
             extract_cookies: None,
+            extract_local_storage: None,
         }),
         cache_duration: None,
         max_attempts: 3,
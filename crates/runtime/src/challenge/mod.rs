@@ -21,6 +21,7 @@
 mod detector;
 mod handler;
 mod manager;
+mod store;
 
 pub use detector::*;
 pub use handler::*;
@@ -0,0 +1,127 @@
+//! # 凭证持久化
+//!
+//! 将验证凭证以 JSON 文件保存到磁盘，App 重启后可复用未过期的凭证
+
+use super::ChallengeCredentials;
+use crate::{Result, RuntimeError};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    path::Path,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+/// 落盘的凭证
+///
+/// `Instant` 无法跨进程使用，获取时间以 Unix 时间戳（秒）保存
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredCredentials {
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    cookies: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    headers: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    extra: HashMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    obtained_at: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ttl_seconds: Option<u32>,
+}
+
+impl StoredCredentials {
+    /// 按 Unix 时间判断是否过期
+    fn is_expired(&self, now: u64) -> bool {
+        match (self.obtained_at, self.ttl_seconds) {
+            (Some(obtained_at), Some(ttl)) => obtained_at + ttl as u64 <= now,
+            _ => false,
+        }
+    }
+}
+
+/// 当前 Unix 时间戳（秒）
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+impl From<&ChallengeCredentials> for StoredCredentials {
+    fn from(credentials: &ChallengeCredentials) -> Self {
+        Self {
+            cookies: credentials.cookies.clone(),
+            headers: credentials.headers.clone(),
+            extra: credentials.extra.clone(),
+            obtained_at: credentials
+                .obtained_at
+                .map(|t| unix_now().saturating_sub(t.elapsed().as_secs())),
+            ttl_seconds: credentials.ttl_seconds,
+        }
+    }
+}
+
+impl From<StoredCredentials> for ChallengeCredentials {
+    fn from(stored: StoredCredentials) -> Self {
+        // 换算回本进程的 Instant，无法表示时（早于系统启动）视为刚获取，
+        // 过期判断已在加载时按 Unix 时间完成
+        let obtained_at = stored.obtained_at.map(|ts| {
+            let age = Duration::from_secs(unix_now().saturating_sub(ts));
+            Instant::now().checked_sub(age).unwrap_or_else(Instant::now)
+        });
+        Self {
+            cookies: stored.cookies,
+            headers: stored.headers,
+            extra: stored.extra,
+            obtained_at,
+            ttl_seconds: stored.ttl_seconds,
+        }
+    }
+}
+
+/// 从文件读取凭证，文件不存在时返回空表，已过期的凭证会被丢弃
+pub(super) fn load(path: &Path) -> Result<HashMap<String, ChallengeCredentials>> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(HashMap::new()),
+        Err(e) => {
+            return Err(RuntimeError::Config(format!(
+                "读取凭证文件 '{}' 失败: {}",
+                path.display(),
+                e
+            )));
+        }
+    };
+
+    let stored: HashMap<String, StoredCredentials> =
+        serde_json::from_str(&content).map_err(|e| {
+            RuntimeError::Config(format!("解析凭证文件 '{}' 失败: {}", path.display(), e))
+        })?;
+    let now = unix_now();
+    Ok(stored
+        .into_iter()
+        .filter(|(_, c)| !c.is_expired(now))
+        .map(|(domain, c)| (domain, ChallengeCredentials::from(c)))
+        .collect())
+}
+
+/// 将凭证写入文件（先写临时文件再替换，避免写入中断导致文件损坏）
+pub(super) fn save(path: &Path, cache: &HashMap<String, ChallengeCredentials>) -> Result<()> {
+    let stored: HashMap<&String, StoredCredentials> = cache
+        .iter()
+        .filter(|(_, c)| !c.is_expired())
+        .map(|(domain, c)| (domain, StoredCredentials::from(c)))
+        .collect();
+    let content = serde_json::to_string_pretty(&stored)
+        .map_err(|e| RuntimeError::Config(format!("序列化凭证失败: {}", e)))?;
+
+    let write = || -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, content)?;
+        std::fs::rename(&tmp, path)
+    };
+    write()
+        .map_err(|e| RuntimeError::Config(format!("写入凭证文件 '{}' 失败: {}", path.display(), e)))
+}
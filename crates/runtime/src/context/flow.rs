@@ -2,10 +2,40 @@
 //!
 //! 每次流程调用时创建的临时上下文
 
-use super::RuntimeContext;
+use super::{LimitsExt, RuntimeContext};
 use crate::Result;
 use serde_json::{Map, Value};
-use std::sync::Arc;
+use std::sync::{
+    Arc,
+    atomic::{AtomicU32, Ordering},
+};
+
+/// 流程变量快照
+///
+/// 由 [`FlowContext::snapshot`] 创建，通过 [`FlowContext::restore`] 恢复
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FlowSnapshot {
+    data: Map<String, Value>,
+}
+
+/// 请求计数器
+///
+/// 由 [`FlowContext::request_counter`] 获取，与流程上下文共享同一计数，
+/// 使脚本内置函数发出的请求同样受 `limits.max_requests` 约束
+#[derive(Debug, Clone)]
+pub struct RequestCounter {
+    count: Arc<AtomicU32>,
+    runtime: Arc<RuntimeContext>,
+}
+
+impl RequestCounter {
+    /// 记录一次请求，超过 `limits.max_requests` 时返回
+    /// [`LimitExceeded`](crate::error::RuntimeError::LimitExceeded)
+    pub fn record(&self) -> Result<()> {
+        let count = self.count.fetch_add(1, Ordering::Relaxed) + 1;
+        self.runtime.limits().check_requests(count)
+    }
+}
 
 /// 流程上下文
 ///
@@ -37,6 +67,8 @@ pub struct FlowContext {
     data: Map<String, Value>,
     /// 运行时上下文引用
     runtime: Arc<RuntimeContext>,
+    /// 本次调用已发出的请求数（用于 `limits.max_requests`），克隆与 [`Self::fork`] 共享同一计数
+    requests: Arc<AtomicU32>,
 }
 
 impl FlowContext {
@@ -45,14 +77,50 @@ impl FlowContext {
         Self {
             data: Map::new(),
             runtime,
+            requests: Arc::default(),
+        }
+    }
+
+    /// 创建变量为空、与当前上下文共享请求计数的流程上下文
+    ///
+    /// 用于批量调用中的各个子流程，使 `limits.max_requests` 约束整批请求
+    pub fn fork(&self) -> Self {
+        Self {
+            data: Map::new(),
+            runtime: self.runtime.clone(),
+            requests: self.requests.clone(),
+        }
+    }
+
+    /// 记录一次请求，超过 `limits.max_requests` 时返回
+    /// [`LimitExceeded`](crate::error::RuntimeError::LimitExceeded)
+    pub fn record_request(&self) -> Result<()> {
+        self.request_counter().record()
+    }
+
+    /// 获取与本上下文共享计数的请求计数器
+    pub fn request_counter(&self) -> RequestCounter {
+        RequestCounter {
+            count: self.requests.clone(),
+            runtime: self.runtime.clone(),
         }
     }
 
+    /// 本次调用已发出的请求数
+    pub fn request_count(&self) -> u32 {
+        self.requests.load(Ordering::Relaxed)
+    }
+
     /// 设置流程变量
     pub fn set<K: Into<String>>(&mut self, key: K, value: Value) {
         self.data.insert(key.into(), value);
     }
 
+    /// 删除流程变量
+    pub fn remove(&mut self, key: &str) -> Option<Value> {
+        self.data.remove(key)
+    }
+
     /// 获取流程变量（仅查 Flow）
     pub fn get(&self, key: &str) -> Option<&Value> {
         self.data.get(key)
@@ -65,6 +133,19 @@ impl FlowContext {
             .or_else(|| self.runtime.globals().get(key))
     }
 
+    /// 当前页面 URL，用于补全页面中的相对链接
+    ///
+    /// 依次查找 `request_url`（本次请求的实际 URL）、`detail_url`、`content_url`，
+    /// 都不存在时退回 `base_url`
+    pub fn page_url(&self) -> Option<&str> {
+        ["request_url", "detail_url", "content_url"]
+            .iter()
+            .find_map(|key| self.data.get(*key))
+            .or_else(|| self.resolve("base_url"))
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+    }
+
     /// 获取运行时上下文
     pub fn runtime(&self) -> &Arc<RuntimeContext> {
         &self.runtime
@@ -106,6 +187,29 @@ impl FlowContext {
         })
     }
 
+    /// 保存当前流程变量
+    ///
+    /// 执行循环单次迭代或容错块等子过程前保存，结束后 [`Self::restore`]，
+    /// 子过程中设置的临时变量不会泄露到外层
+    pub fn snapshot(&self) -> FlowSnapshot {
+        FlowSnapshot {
+            data: self.data.clone(),
+        }
+    }
+
+    /// 恢复到快照时的流程变量，快照之后新增或修改的变量全部丢弃
+    pub fn restore(&mut self, snapshot: FlowSnapshot) {
+        self.data = snapshot.data;
+    }
+
+    /// 在隔离的变量作用域中执行 `f`，结束后恢复执行前的流程变量
+    pub fn scoped<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R {
+        let snapshot = self.snapshot();
+        let result = f(self);
+        self.restore(snapshot);
+        result
+    }
+
     /// 清空流程变量
     pub fn clear(&mut self) {
         self.data.clear();
@@ -0,0 +1,82 @@
+//! # 资源限制
+//!
+//! 为 schema 中的 [`LimitsConfig`] 提供运行时检查：
+//!
+//! - 最大页数：与分页配置自身的页数限制取较小值，到达上限后停止翻页
+//! - 最大请求数：单次调用内的请求总数，超限时中断流程
+//! - 最大深度：组件嵌套调用的层数，超限时报错
+
+use crate::{Result, error::RuntimeError};
+use crawler_schema::{
+    config::{DEFAULT_MAX_DEPTH, LimitsConfig},
+    flow::common::Pagination,
+};
+
+/// 未配置 `limits` 时使用的默认限制
+pub(crate) static DEFAULT_LIMITS: LimitsConfig = LimitsConfig {
+    max_pages: None,
+    max_requests: None,
+    max_depth: None,
+};
+
+/// 资源限制扩展 trait
+pub trait LimitsExt {
+    /// 生效的最大页数，`limits.max_pages` 与分页配置的限制取较小值
+    fn page_limit(&self, pagination: Option<&Pagination>) -> Option<u32>;
+
+    /// 生效的组件最大嵌套深度
+    fn depth_limit(&self) -> u32;
+
+    /// 检查第 `index` 页（从 0 开始计数）是否超过最大页数
+    fn check_page(&self, index: u32, pagination: Option<&Pagination>) -> Result<()>;
+
+    /// 检查已发出的请求数是否超过最大请求数
+    fn check_requests(&self, count: u32) -> Result<()>;
+
+    /// 检查组件嵌套深度是否超过上限
+    fn check_depth(&self, depth: u32) -> Result<()>;
+}
+
+impl LimitsExt for LimitsConfig {
+    fn page_limit(&self, pagination: Option<&Pagination>) -> Option<u32> {
+        let from_pagination = pagination.and_then(Pagination::max_pages);
+        match (self.max_pages, from_pagination) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+
+    fn depth_limit(&self) -> u32 {
+        self.max_depth.unwrap_or(DEFAULT_MAX_DEPTH)
+    }
+
+    fn check_page(&self, index: u32, pagination: Option<&Pagination>) -> Result<()> {
+        match self.page_limit(pagination) {
+            Some(max) if index >= max => Err(exceeded("页数", max)),
+            _ => Ok(()),
+        }
+    }
+
+    fn check_requests(&self, count: u32) -> Result<()> {
+        match self.max_requests {
+            Some(max) if count > max => Err(exceeded("请求数", max)),
+            _ => Ok(()),
+        }
+    }
+
+    fn check_depth(&self, depth: u32) -> Result<()> {
+        let max = self.depth_limit();
+        if depth > max {
+            Err(exceeded("组件嵌套深度", max))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+fn exceeded(limit: &str, max: u32) -> RuntimeError {
+    RuntimeError::LimitExceeded {
+        limit: limit.to_string(),
+        max,
+    }
+}
@@ -13,7 +13,9 @@
 //! | `{{ $.var }}` | 仅查 Runtime 全局变量 | `{{ $.base_url }}`、`{{ $.domain }}` |
 
 pub mod flow;
+pub mod limits;
 pub mod runtime;
 
-pub use flow::FlowContext;
+pub use flow::{FlowContext, FlowSnapshot, RequestCounter};
+pub use limits::LimitsExt;
 pub use runtime::RuntimeContext;
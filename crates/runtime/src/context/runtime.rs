@@ -3,14 +3,27 @@
 //! 爬虫实例级的共享资源和全局变量
 
 use crate::{
-    http::HttpClient,
-    script::{ScriptEngine, ScriptLanguage},
+    challenge::ChallengeManager,
+    context::limits::DEFAULT_LIMITS,
+    http::{HostRateLimiter, HttpClient},
+    script::{
+        RemoteScripts,
+        ScriptEngine,
+        ScriptEngineFactory,
+        ScriptLanguage,
+        ScriptModules,
+        remote::script_urls,
+    },
+    util::{MemoryCacheStore, ProgressListener, SharedCacheStore, SharedProgressListener},
     webview::{SharedWebViewProvider, noop_provider},
 };
-use crawler_schema::core::CrawlerRule;
+use crawler_schema::{
+    config::{HttpConfig, LimitsConfig},
+    core::CrawlerRule,
+};
 use dashmap::DashMap;
 use serde_json::{Map, Value};
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 
 /// 运行时上下文
 ///
@@ -21,22 +34,45 @@ use std::sync::Arc;
 ///
 /// - `rule`: 爬虫规则定义
 /// - `http_client`: HTTP 客户端（连接池复用）
+/// - `flow_http_clients`: 流程级 HTTP 客户端缓存（合并全局与流程级配置，共享全局限流器）
 /// - `extract_engine`: 数据提取引擎
 /// - `template_engine`: 模板渲染引擎
 /// - `globals`: 全局变量（base_url, domain 等）
 /// - `webview_provider`: WebView 提供者（可选）
+/// - `script_engines`: 脚本引擎缓存
+/// - `script_modules`: 脚本模块（首次调用时加载）
+/// - `remote_scripts`: 远程脚本缓存（`ScriptSource::Url`，流程开始前加载）
+/// - `cache_store`: 缓存存储（默认内存实现）
+/// - `progress_listener`: 进度事件监听器（可选）
+/// - `challenge_manager`: 验证管理器（规则配置了 `challenge` 时由构建器创建）
 #[derive(Debug)]
 pub struct RuntimeContext {
     /// 爬虫规则
     rule: Arc<CrawlerRule>,
     /// HTTP 客户端
     http_client: Arc<HttpClient>,
+    /// 流程级 HTTP 客户端缓存（按流程名）
+    flow_http_clients: DashMap<String, Arc<HttpClient>>,
     /// 全局变量
     globals: Map<String, Value>,
     /// WebView 提供者
     webview_provider: SharedWebViewProvider,
     /// 脚本引擎缓存（按语言类型懒加载）
     script_engines: Arc<DashMap<ScriptLanguage, Arc<dyn ScriptEngine>>>,
+    /// 脚本模块
+    script_modules: ScriptModules,
+    /// 远程脚本缓存
+    remote_scripts: RemoteScripts,
+    /// 规则引用的远程脚本地址
+    remote_script_urls: Vec<String>,
+    /// 默认脚本语言（脚本未显式指定引擎时使用）
+    default_script_language: ScriptLanguage,
+    /// 缓存存储
+    cache_store: SharedCacheStore,
+    /// 进度事件监听器
+    progress_listener: Option<SharedProgressListener>,
+    /// 验证管理器
+    challenge_manager: Option<Arc<ChallengeManager>>,
 }
 
 impl RuntimeContext {
@@ -54,6 +90,15 @@ impl RuntimeContext {
         let http_config = rule.http.clone().unwrap_or_default();
         let http_client = Arc::new(HttpClient::new(http_config)?);
 
+        Ok(Self::from_parts(rule, http_client, webview_provider))
+    }
+
+    /// 使用已创建的 HTTP 客户端组装运行时上下文
+    pub(crate) fn from_parts(
+        rule: CrawlerRule,
+        http_client: Arc<HttpClient>,
+        webview_provider: SharedWebViewProvider,
+    ) -> Self {
         // 初始化全局变量
         let mut globals = Map::new();
         globals.insert(
@@ -65,13 +110,30 @@ impl RuntimeContext {
             Value::String(rule.meta.domain.clone()),
         );
 
-        Ok(Self {
+        let default_script_language = rule
+            .meta
+            .script_engine
+            .map(ScriptLanguage::from)
+            .unwrap_or(ScriptLanguage::JavaScript);
+
+        let script_modules = ScriptModules::new(rule.scripting.as_ref());
+        let remote_script_urls = script_urls(&rule);
+
+        Self {
             rule: Arc::new(rule),
             http_client,
+            flow_http_clients: DashMap::new(),
             globals,
             webview_provider,
             script_engines: Arc::new(DashMap::new()),
-        })
+            script_modules,
+            remote_scripts: RemoteScripts::new(),
+            remote_script_urls,
+            default_script_language,
+            cache_store: Arc::new(MemoryCacheStore::default()),
+            progress_listener: None,
+            challenge_manager: None,
+        }
     }
 
     /// 获取爬虫规则
@@ -79,11 +141,136 @@ impl RuntimeContext {
         &self.rule
     }
 
+    /// 获取资源限制配置，未配置时返回默认限制
+    pub fn limits(&self) -> &LimitsConfig {
+        self.rule.limits.as_ref().unwrap_or(&DEFAULT_LIMITS)
+    }
+
     /// 获取 HTTP 客户端
     pub fn http_client(&self) -> &Arc<HttpClient> {
         &self.http_client
     }
 
+    /// 获取域名级限流器
+    ///
+    /// 全局客户端与所有流程级客户端共用，同一域名的请求统一排队
+    pub fn rate_limiter(&self) -> &Arc<HostRateLimiter> {
+        self.http_client.rate_limiter()
+    }
+
+    /// 获取流程使用的 HTTP 客户端
+    ///
+    /// 流程未配置 `http` 时返回全局客户端；否则以全局配置合并流程级配置
+    /// 派生客户端，并按流程名缓存复用
+    pub fn flow_http_client(
+        &self,
+        flow: &str,
+        http: Option<&HttpConfig>,
+    ) -> crate::Result<Arc<HttpClient>> {
+        let Some(http) = http else {
+            return Ok(self.http_client.clone());
+        };
+        if let Some(client) = self.flow_http_clients.get(flow) {
+            return Ok(client.clone());
+        }
+
+        let client = Arc::new(self.http_client.for_flow(http)?);
+        self.flow_http_clients
+            .insert(flow.to_string(), client.clone());
+        Ok(client)
+    }
+
+    /// 获取指定语言的脚本引擎（首次使用时创建并缓存）
+    pub fn script_engine(&self, language: ScriptLanguage) -> Arc<dyn ScriptEngine> {
+        self.script_engines
+            .entry(language)
+            .or_insert_with(|| ScriptEngineFactory::create(language))
+            .clone()
+    }
+
+    /// 获取脚本模块
+    pub fn script_modules(&self) -> &ScriptModules {
+        &self.script_modules
+    }
+
+    /// 获取远程脚本缓存
+    pub fn remote_scripts(&self) -> &RemoteScripts {
+        &self.remote_scripts
+    }
+
+    /// 获取已加载的远程脚本代码
+    ///
+    /// 脚本引擎同步执行，不在执行中下载；规则引用的远程脚本由
+    /// [`load_remote_scripts`](Self::load_remote_scripts) 在流程开始前加载
+    pub fn remote_script(&self, url: &str) -> crate::Result<Arc<str>> {
+        self.remote_scripts
+            .get(url)
+            .map(|script| script.code.clone())
+            .ok_or_else(|| {
+                crate::error::RuntimeError::ScriptRuntime(format!(
+                    "远程脚本 '{}' 未加载（下载失败且没有本地缓存）",
+                    url
+                ))
+            })
+    }
+
+    /// 通过全局 HTTP 客户端加载规则引用的全部远程脚本
+    ///
+    /// 已加载的脚本直接跳过；下载失败只记录日志，使用该脚本的步骤执行时报错，
+    /// 下次调用时重试
+    pub async fn load_remote_scripts(&self) {
+        for url in &self.remote_script_urls {
+            if let Err(e) = self.remote_scripts.load(url, &self.http_client).await {
+                tracing::warn!("加载远程脚本 '{}' 失败: {}", url, e);
+            }
+        }
+    }
+
+    /// 设置远程脚本的磁盘缓存目录
+    pub(crate) fn set_script_cache_dir(&mut self, dir: PathBuf) {
+        self.remote_scripts = RemoteScripts::new().with_cache_dir(dir);
+    }
+
+    /// 获取默认脚本语言
+    pub fn default_script_language(&self) -> ScriptLanguage {
+        self.default_script_language
+    }
+
+    /// 设置默认脚本语言
+    pub(crate) fn set_default_script_language(&mut self, language: ScriptLanguage) {
+        self.default_script_language = language;
+    }
+
+    /// 获取缓存存储
+    pub fn cache_store(&self) -> &SharedCacheStore {
+        &self.cache_store
+    }
+
+    /// 替换缓存存储
+    pub(crate) fn set_cache_store(&mut self, cache_store: SharedCacheStore) {
+        self.cache_store = cache_store;
+    }
+
+    /// 获取进度事件监听器
+    pub fn progress_listener(&self) -> Option<&dyn ProgressListener> {
+        self.progress_listener.as_deref()
+    }
+
+    /// 设置进度事件监听器
+    pub(crate) fn set_progress_listener(&mut self, listener: SharedProgressListener) {
+        self.progress_listener = Some(listener);
+    }
+
+    /// 获取验证管理器
+    pub fn challenge_manager(&self) -> Option<&Arc<ChallengeManager>> {
+        self.challenge_manager.as_ref()
+    }
+
+    /// 设置验证管理器
+    pub(crate) fn set_challenge_manager(&mut self, manager: Arc<ChallengeManager>) {
+        self.challenge_manager = Some(manager);
+    }
+
     /// 获取全局变量
     pub fn globals(&self) -> &Map<String, Value> {
         &self.globals
@@ -0,0 +1,159 @@
+//! # 运行时构建器
+//!
+//! 用于向 CrawlerRuntime 注入集成方自定义的依赖
+
+use crate::{
+    Result,
+    challenge::ChallengeManager,
+    context::RuntimeContext,
+    crawler::CrawlerRuntime,
+    http::HttpClient,
+    script::ScriptLanguage,
+    util::{MemoryCacheStore, SharedCacheStore, SharedProgressListener},
+    webview::{SharedWebViewProvider, noop_provider},
+};
+use crawler_schema::core::CrawlerRule;
+use std::{path::PathBuf, sync::Arc};
+
+/// CrawlerRuntime 构建器
+///
+/// 未注入的依赖使用默认实现：
+/// - HTTP 客户端：按规则 `http` 配置创建
+/// - 默认脚本引擎：规则 `meta.script_engine`，未配置时为 JavaScript
+/// - 缓存存储：内存缓存
+/// - 远程脚本：只在内存中缓存
+/// - HTTP 协商缓存：不开启
+///
+/// 规则配置了 `challenge` 时创建 [`ChallengeManager`]，使用注入的 WebView 提供者与进度监听器，
+/// 并作为 HTTP 客户端的凭证提供者，验证通过后的凭证自动应用到后续请求
+///
+/// # 示例
+///
+/// ```ignore
+/// let runtime = CrawlerRuntime::builder(rule)
+///     .with_webview_provider(provider)
+///     .with_default_script_engine(ScriptLanguage::Rhai)
+///     .build()?;
+/// ```
+pub struct CrawlerRuntimeBuilder {
+    rule: CrawlerRule,
+    webview_provider: Option<SharedWebViewProvider>,
+    http_client: Option<Arc<HttpClient>>,
+    default_script_language: Option<ScriptLanguage>,
+    cache_store: Option<SharedCacheStore>,
+    progress_listener: Option<SharedProgressListener>,
+    script_cache_dir: Option<PathBuf>,
+    http_cache: bool,
+}
+
+impl CrawlerRuntimeBuilder {
+    /// 创建构建器
+    pub fn new(rule: CrawlerRule) -> Self {
+        Self {
+            rule,
+            webview_provider: None,
+            http_client: None,
+            default_script_language: None,
+            cache_store: None,
+            progress_listener: None,
+            script_cache_dir: None,
+            http_cache: false,
+        }
+    }
+
+    /// 注入 WebView 提供者
+    pub fn with_webview_provider(mut self, provider: SharedWebViewProvider) -> Self {
+        self.webview_provider = Some(provider);
+        self
+    }
+
+    /// 注入 HTTP 客户端
+    ///
+    /// 可配合 [`HttpClient::with_client`] 复用已配置好的 `reqwest::Client`
+    pub fn with_http_client(mut self, http_client: Arc<HttpClient>) -> Self {
+        self.http_client = Some(http_client);
+        self
+    }
+
+    /// 指定默认脚本引擎（覆盖规则中的 `meta.script_engine`）
+    pub fn with_default_script_engine(mut self, language: ScriptLanguage) -> Self {
+        self.default_script_language = Some(language);
+        self
+    }
+
+    /// 注入缓存存储
+    pub fn with_cache_store(mut self, cache_store: SharedCacheStore) -> Self {
+        self.cache_store = Some(cache_store);
+        self
+    }
+
+    /// 注入进度事件监听器
+    pub fn with_progress_listener(mut self, listener: SharedProgressListener) -> Self {
+        self.progress_listener = Some(listener);
+        self
+    }
+
+    /// 设置远程脚本的磁盘缓存目录
+    ///
+    /// 远程脚本持久化后，重启时按 ETag 重新验证，离线时回退到缓存版本
+    pub fn with_script_cache_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.script_cache_dir = Some(dir.into());
+        self
+    }
+
+    /// 开启 HTTP 协商缓存
+    ///
+    /// 响应按 ETag/Last-Modified 缓存到缓存存储，后续请求自动带条件请求头，
+    /// 服务端返回 304 时使用缓存的响应，见 [`HttpClient::with_response_cache`]
+    pub fn with_http_cache(mut self, enabled: bool) -> Self {
+        self.http_cache = enabled;
+        self
+    }
+
+    /// 构建运行时
+    pub fn build(self) -> Result<CrawlerRuntime> {
+        let webview_provider = self.webview_provider.unwrap_or_else(noop_provider);
+        let mut http_client = match self.http_client {
+            Some(http_client) => http_client,
+            None => Arc::new(HttpClient::new(self.rule.http.clone().unwrap_or_default())?),
+        };
+        let cache_store: SharedCacheStore = self
+            .cache_store
+            .unwrap_or_else(|| Arc::new(MemoryCacheStore::default()));
+        if self.http_cache {
+            http_client =
+                Arc::new(HttpClient::clone(&http_client).with_response_cache(cache_store.clone()));
+        }
+        let challenge_manager = self.rule.challenge.clone().map(|config| {
+            let mut manager = ChallengeManager::new(config, webview_provider.clone())
+                .with_http_client(http_client.inner().clone());
+            if let Some(listener) = &self.progress_listener {
+                manager = manager.with_progress_listener(listener.clone());
+            }
+            Arc::new(manager)
+        });
+        if let Some(manager) = &challenge_manager {
+            http_client = Arc::new(
+                HttpClient::clone(&http_client).with_credentials_provider(manager.clone()),
+            );
+        }
+        let mut runtime_context =
+            RuntimeContext::from_parts(self.rule, http_client, webview_provider);
+        if let Some(manager) = challenge_manager {
+            runtime_context.set_challenge_manager(manager);
+        }
+
+        if let Some(language) = self.default_script_language {
+            runtime_context.set_default_script_language(language);
+        }
+        runtime_context.set_cache_store(cache_store);
+        if let Some(listener) = self.progress_listener {
+            runtime_context.set_progress_listener(listener);
+        }
+        if let Some(dir) = self.script_cache_dir {
+            runtime_context.set_script_cache_dir(dir);
+        }
+
+        Ok(CrawlerRuntime::from_context(Arc::new(runtime_context)))
+    }
+}
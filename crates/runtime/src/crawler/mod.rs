@@ -1,3 +1,9 @@
 //! # 爬虫运行时主入口模块
+pub mod builder;
+pub mod registry;
 pub mod runtime;
+pub mod smoke;
+pub use builder::CrawlerRuntimeBuilder;
+pub use registry::{AggregatedSearchResponse, RuleRegistry};
 pub use runtime::CrawlerRuntime;
+pub use smoke::{SmokeReport, smoke_test};
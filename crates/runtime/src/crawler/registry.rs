@@ -0,0 +1,142 @@
+//! # 多规则管理
+//!
+//! 宿主同时加载多个书源/影视源时，由 [`RuleRegistry`] 统一注册、查找，
+//! 并通过 [`RuleRegistry::search_all`] 并发聚合所有规则的搜索结果
+
+use crate::{
+    Result,
+    crawler::CrawlerRuntime,
+    error::RuntimeError,
+    flow::search::SearchResponse,
+    model::SearchItem,
+    rule::RuleFile,
+};
+use crawler_schema::config::MediaType;
+use std::collections::BTreeMap;
+use tokio::task::JoinSet;
+
+/// 聚合搜索结果
+#[derive(Debug, Default)]
+pub struct AggregatedSearchResponse {
+    /// 各规则的搜索结果（规则 id, 结果），按规则 id 排序
+    pub results: Vec<(String, SearchResponse)>,
+    /// 搜索失败的规则（规则 id, 错误），按规则 id 排序
+    pub errors: Vec<(String, RuntimeError)>,
+}
+
+impl AggregatedSearchResponse {
+    /// 按规则顺序遍历所有搜索结果项及其所属规则 id
+    pub fn items(&self) -> impl Iterator<Item = (&str, &SearchItem)> {
+        self.results
+            .iter()
+            .flat_map(|(id, response)| response.items.iter().map(move |item| (id.as_str(), item)))
+    }
+}
+
+/// 多规则注册表
+///
+/// 以宿主指定的 id 注册规则，每个规则持有独立的 [`CrawlerRuntime`]
+#[derive(Clone, Default)]
+pub struct RuleRegistry {
+    runtimes: BTreeMap<String, CrawlerRuntime>,
+}
+
+impl RuleRegistry {
+    /// 创建空的注册表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 注册规则，id 已存在时替换
+    pub fn register(&mut self, id: impl Into<String>, rule: RuleFile) -> Result<()> {
+        let runtime = CrawlerRuntime::new(rule.into_rule(), None)?;
+        self.register_runtime(id, runtime);
+        Ok(())
+    }
+
+    /// 注册已构建的运行时，适用于需要注入 WebView 等依赖的规则
+    pub fn register_runtime(&mut self, id: impl Into<String>, runtime: CrawlerRuntime) {
+        self.runtimes.insert(id.into(), runtime);
+    }
+
+    /// 移除规则
+    pub fn remove(&mut self, id: &str) -> Option<CrawlerRuntime> {
+        self.runtimes.remove(id)
+    }
+
+    /// 按 id 查找规则
+    pub fn get(&self, id: &str) -> Option<&CrawlerRuntime> {
+        self.runtimes.get(id)
+    }
+
+    /// 按域名查找规则（不区分大小写，忽略 `www.` 前缀）
+    pub fn find_by_domain(&self, domain: &str) -> Vec<(&str, &CrawlerRuntime)> {
+        let domain = normalize_domain(domain);
+        self.filter(|runtime| normalize_domain(&runtime.runtime_ctx().rule().meta.domain) == domain)
+    }
+
+    /// 按媒体类型查找规则
+    pub fn find_by_media_type(&self, media_type: MediaType) -> Vec<(&str, &CrawlerRuntime)> {
+        self.filter(|runtime| runtime.runtime_ctx().rule().meta.media_type == media_type)
+    }
+
+    /// 已注册的规则 id
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        self.runtimes.keys().map(String::as_str)
+    }
+
+    /// 已注册的规则数量
+    pub fn len(&self) -> usize {
+        self.runtimes.len()
+    }
+
+    /// 是否未注册任何规则
+    pub fn is_empty(&self) -> bool {
+        self.runtimes.is_empty()
+    }
+
+    /// 使用所有规则并发搜索第一页并聚合结果
+    ///
+    /// 单个规则失败不影响其他规则，错误记录在 [`AggregatedSearchResponse::errors`] 中
+    pub async fn search_all(&self, keyword: &str) -> AggregatedSearchResponse {
+        let mut tasks = JoinSet::new();
+        for (id, runtime) in &self.runtimes {
+            let id = id.clone();
+            let runtime = runtime.clone();
+            let keyword = keyword.to_string();
+            tasks.spawn(async move {
+                let result = runtime.search(&keyword, 1).await;
+                (id, result)
+            });
+        }
+
+        let mut aggregated = AggregatedSearchResponse::default();
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok((id, Ok(response))) => aggregated.results.push((id, response)),
+                Ok((id, Err(e))) => aggregated.errors.push((id, e)),
+                Err(e) => tracing::error!("聚合搜索任务异常退出: {}", e),
+            }
+        }
+        aggregated.results.sort_by(|a, b| a.0.cmp(&b.0));
+        aggregated.errors.sort_by(|a, b| a.0.cmp(&b.0));
+        aggregated
+    }
+
+    fn filter(&self, predicate: impl Fn(&CrawlerRuntime) -> bool) -> Vec<(&str, &CrawlerRuntime)> {
+        self.runtimes
+            .iter()
+            .filter(|(_, runtime)| predicate(runtime))
+            .map(|(id, runtime)| (id.as_str(), runtime))
+            .collect()
+    }
+}
+
+/// 域名规范化：小写并去除 `www.` 前缀
+fn normalize_domain(domain: &str) -> String {
+    let domain = domain.trim().to_ascii_lowercase();
+    match domain.strip_prefix("www.") {
+        Some(rest) => rest.to_string(),
+        None => domain,
+    }
+}
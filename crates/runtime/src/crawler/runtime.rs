@@ -5,14 +5,23 @@
 use crate::{
     Result,
     context::{FlowContext, RuntimeContext},
+    crawler::CrawlerRuntimeBuilder,
+    error::RuntimeError,
     flow::{
+        content::{ContentFlowExecutor, ContentRequest, ContentResponse},
         detail::{DetailFlowExecutor, DetailRequest, DetailResponse},
+        discovery::{DiscoveryFlowExecutor, DiscoveryRequest, DiscoveryResponse},
+        pager::{SearchPager, SearchPagerState},
         search::{SearchFlowExecutor, SearchRequest, SearchResponse},
     },
+    util::concurrent,
     webview::{SharedWebViewProvider, noop_provider},
 };
-use crawler_schema::core::CrawlerRule;
-use std::sync::Arc;
+use crawler_schema::{
+    core::CrawlerRule,
+    flow::{DiscoveryFlow, FilterGroup, OptionItem},
+};
+use std::{collections::HashMap, sync::Arc};
 
 /// 爬虫运行时
 ///
@@ -40,27 +49,160 @@ impl CrawlerRuntime {
         Ok(Self { runtime_context })
     }
 
+    /// 创建构建器，用于注入 WebView、HTTP 客户端、脚本引擎、缓存等依赖
+    pub fn builder(rule: CrawlerRule) -> CrawlerRuntimeBuilder {
+        CrawlerRuntimeBuilder::new(rule)
+    }
+
+    /// 从已构建的运行时上下文创建
+    pub(crate) fn from_context(runtime_context: Arc<RuntimeContext>) -> Self {
+        Self { runtime_context }
+    }
+
     /// 搜索
+    ///
+    /// 每次调用相互独立，分页配置的 `dedup_by` 只在页内去重；
+    /// 需要跨页去重时使用 [`Self::search_pager`] 与 [`Self::search_page`]
     pub async fn search(&self, keyword: &str, page: u32) -> Result<SearchResponse> {
         let request = SearchRequest {
             keyword: keyword.to_string(),
             page,
         };
         let flow = &self.runtime_context.rule().search;
-        let mut flow_context = FlowContext::new(self.runtime_context.clone());
+        let mut flow_context = self.flow_context().await;
         SearchFlowExecutor::execute(request, flow, &self.runtime_context, &mut flow_context).await
     }
 
+    /// 创建从第一页开始的搜索分页器
+    pub fn search_pager(&self, keyword: &str) -> SearchPager {
+        let pagination = self.runtime_context.rule().search.pagination.clone();
+        let first_page = pagination.as_ref().map_or(1, |p| p.first_page());
+        SearchPager::new(
+            self.runtime_context.clone(),
+            pagination,
+            SearchPagerState::new(keyword, first_page),
+        )
+    }
+
+    /// 搜索分页器当前页
+    ///
+    /// 分页器在各页之间保留已出现条目的去重键，配置了 `dedup_by` 时
+    /// 丢弃之前页面已返回过的条目，并记录首次提取到的总页数。
+    /// 通过 [`SearchPager::next_page_pager`] 获取下一页的分页器
+    pub async fn search_page(&self, pager: &mut SearchPager) -> Result<SearchResponse> {
+        let state = pager.state();
+        let mut response = self.search(&state.keyword, state.page).await?;
+        pager.set_total_pages(response.total_pages);
+
+        let items = std::mem::take(&mut response.items);
+        response.items = pager.dedup_items(items);
+        response.raw_items = response.items.iter().map(|item| item.raw.clone()).collect();
+        Ok(response)
+    }
+
     /// 获取详情
     pub async fn detail(&self, url: &str) -> Result<DetailResponse> {
+        let flow_context = self.flow_context().await;
+        self.detail_in(url, flow_context).await
+    }
+
+    async fn detail_in(&self, url: &str, mut flow_context: FlowContext) -> Result<DetailResponse> {
         let request = DetailRequest {
             url: url.to_string(),
         };
         let flow = &self.runtime_context.rule().detail;
-        let mut flow_context = FlowContext::new(self.runtime_context.clone());
         DetailFlowExecutor::execute(request, flow, &self.runtime_context, &mut flow_context).await
     }
 
+    /// 并发获取多个详情
+    ///
+    /// 同时进行的详情流程不超过 `concurrency` 个，请求仍受 HTTP 客户端的域名级限流约束；
+    /// 结果与 `urls` 顺序一致，单个详情失败不影响其他详情。
+    /// 整批详情共享 `limits.max_requests` 的请求计数
+    pub async fn details(
+        &self,
+        urls: Vec<String>,
+        concurrency: usize,
+    ) -> Vec<Result<DetailResponse>> {
+        let session = self.flow_context().await;
+        concurrent::map_bounded(urls, concurrency, |url| {
+            let runtime = self.clone();
+            let flow_context = session.fork();
+            async move { runtime.detail_in(&url, flow_context).await }
+        })
+        .await
+    }
+
+    /// 发现页
+    ///
+    /// `filters` 为筛选器 key 到选中值的映射；规则未定义 discovery 时返回错误
+    pub async fn discover(
+        &self,
+        filters: HashMap<String, String>,
+        page: u32,
+    ) -> Result<DiscoveryResponse> {
+        let flow = self.discovery_flow()?;
+        let request = DiscoveryRequest { filters, page };
+        let mut flow_context = self.flow_context().await;
+        DiscoveryFlowExecutor::execute(request, flow, &self.runtime_context, &mut flow_context)
+            .await
+    }
+
+    /// 获取发现页的分类列表，动态分类会请求数据源
+    ///
+    /// 规则未定义 discovery 时返回错误
+    pub async fn discovery_categories(&self) -> Result<Vec<OptionItem>> {
+        let flow = self.discovery_flow()?;
+        let mut flow_context = self.flow_context().await;
+        DiscoveryFlowExecutor::categories(flow, &self.runtime_context, &mut flow_context).await
+    }
+
+    /// 获取发现页的筛选器组，动态筛选器会请求数据源
+    ///
+    /// 规则未定义 discovery 时返回错误
+    pub async fn discovery_filters(&self) -> Result<Vec<FilterGroup>> {
+        let flow = self.discovery_flow()?;
+        let mut flow_context = self.flow_context().await;
+        DiscoveryFlowExecutor::filters(flow, &self.runtime_context, &mut flow_context).await
+    }
+
+    fn discovery_flow(&self) -> Result<&DiscoveryFlow> {
+        self.runtime_context
+            .rule()
+            .discovery
+            .as_ref()
+            .ok_or_else(|| RuntimeError::UndefinedFlow {
+                flow: "discovery".to_string(),
+            })
+    }
+
+    /// 获取内容
+    ///
+    /// 规则未定义 content 时返回错误
+    pub async fn content(&self, url: &str) -> Result<ContentResponse> {
+        let flow = self
+            .runtime_context
+            .rule()
+            .content
+            .as_ref()
+            .ok_or_else(|| RuntimeError::UndefinedFlow {
+                flow: "content".to_string(),
+            })?;
+        let request = ContentRequest {
+            url: url.to_string(),
+        };
+        let mut flow_context = self.flow_context().await;
+        ContentFlowExecutor::execute(request, flow, &self.runtime_context, &mut flow_context).await
+    }
+
+    /// 创建流程上下文
+    ///
+    /// 脚本引擎同步执行，规则引用的远程脚本在此之前异步加载
+    async fn flow_context(&self) -> FlowContext {
+        self.runtime_context.load_remote_scripts().await;
+        FlowContext::new(self.runtime_context.clone())
+    }
+
     /// 获取运行时上下文
     pub fn runtime_ctx(&self) -> &Arc<RuntimeContext> {
         &self.runtime_context
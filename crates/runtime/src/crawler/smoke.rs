@@ -0,0 +1,172 @@
+//! # 规则冒烟测试
+//!
+//! 一键验证规则的 search → detail 链路能跑通并产出非空结果，
+//! 供规则作者本地调试或 CI 使用
+
+use crate::{
+    crawler::CrawlerRuntime,
+    flow::{detail::DetailResponse, search::SearchResponse},
+    model::SearchItem,
+};
+use crawler_schema::core::CrawlerRule;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// 流程冒烟状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SmokeStatus {
+    /// 执行成功且有结果
+    Passed,
+    /// 执行成功但结果为空
+    Empty,
+    /// 执行失败
+    Failed,
+    /// 因前置流程未通过而跳过
+    Skipped,
+}
+
+/// 单个流程的冒烟结果
+#[derive(Debug, Clone, Serialize)]
+pub struct FlowSmokeReport {
+    /// 状态
+    pub status: SmokeStatus,
+    /// 错误信息
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// 各字段是否提取到非空值
+    pub fields: BTreeMap<&'static str, bool>,
+}
+
+impl FlowSmokeReport {
+    fn failed(error: impl ToString) -> Self {
+        Self {
+            status: SmokeStatus::Failed,
+            error: Some(error.to_string()),
+            fields: BTreeMap::new(),
+        }
+    }
+
+    fn skipped() -> Self {
+        Self {
+            status: SmokeStatus::Skipped,
+            error: None,
+            fields: BTreeMap::new(),
+        }
+    }
+}
+
+/// 规则冒烟测试报告
+#[derive(Debug, Clone, Serialize)]
+pub struct SmokeReport {
+    /// 搜索流程结果
+    pub search: FlowSmokeReport,
+    /// 详情流程结果（使用搜索首项的 URL）
+    pub detail: FlowSmokeReport,
+}
+
+impl SmokeReport {
+    /// 所有流程是否均通过
+    pub fn is_passed(&self) -> bool {
+        self.search.status == SmokeStatus::Passed && self.detail.status == SmokeStatus::Passed
+    }
+}
+
+/// 对规则执行冒烟测试
+///
+/// 使用 `sample_keyword` 执行搜索并取首项，再对首项执行详情流程
+pub async fn smoke_test(rule: CrawlerRule, sample_keyword: &str) -> SmokeReport {
+    match CrawlerRuntime::new(rule, None) {
+        Ok(runtime) => runtime.smoke_test(sample_keyword).await,
+        Err(e) => SmokeReport {
+            search: FlowSmokeReport::failed(e),
+            detail: FlowSmokeReport::skipped(),
+        },
+    }
+}
+
+impl CrawlerRuntime {
+    /// 对当前规则执行冒烟测试，见 [`smoke_test`]
+    pub async fn smoke_test(&self, sample_keyword: &str) -> SmokeReport {
+        let first = match self.search(sample_keyword, 1).await {
+            Ok(response) => search_report(response),
+            Err(e) => Err(FlowSmokeReport::failed(e)),
+        };
+
+        match first {
+            Ok((search, item)) => {
+                let detail = match self.detail(&item.url).await {
+                    Ok(response) => detail_report(&response),
+                    Err(e) => FlowSmokeReport::failed(e),
+                };
+                SmokeReport { search, detail }
+            }
+            Err(search) => SmokeReport {
+                search,
+                detail: FlowSmokeReport::skipped(),
+            },
+        }
+    }
+}
+
+/// 生成搜索报告，有结果时同时返回首项
+fn search_report(
+    response: SearchResponse,
+) -> std::result::Result<(FlowSmokeReport, SearchItem), FlowSmokeReport> {
+    let Some(item) = response.items.into_iter().next() else {
+        return Err(FlowSmokeReport {
+            status: SmokeStatus::Empty,
+            error: None,
+            fields: BTreeMap::new(),
+        });
+    };
+
+    let fields = BTreeMap::from([
+        ("title", !item.title.is_empty()),
+        ("url", !item.url.is_empty()),
+        ("cover", item.cover.is_some()),
+        ("summary", item.summary.is_some()),
+        ("author", item.author.is_some()),
+        ("latest", item.latest.is_some()),
+        ("score", item.score.is_some()),
+        ("status", item.status.is_some()),
+        ("category", item.category.is_some()),
+    ]);
+
+    let report = FlowSmokeReport {
+        status: SmokeStatus::Passed,
+        error: None,
+        fields,
+    };
+    Ok((report, item))
+}
+
+/// 生成详情报告
+fn detail_report(response: &DetailResponse) -> FlowSmokeReport {
+    let fields = match response {
+        DetailResponse::Book(book) => BTreeMap::from([
+            ("title", !book.title.is_empty()),
+            ("author", !book.author.is_empty()),
+            ("cover", book.cover.is_some()),
+            ("intro", book.intro.is_some()),
+            ("category", book.category.is_some()),
+            ("status", book.status.is_some()),
+            ("last_chapter", book.last_chapter.is_some()),
+            ("word_count", book.word_count.is_some()),
+            ("chapters", !book.chapters.is_empty()),
+        ]),
+        _ => BTreeMap::from([("title", !response.title().is_empty())]),
+    };
+
+    let status = if response.title().is_empty() {
+        SmokeStatus::Empty
+    } else {
+        SmokeStatus::Passed
+    };
+
+    FlowSmokeReport {
+        status,
+        error: None,
+        fields,
+    }
+}
@@ -17,6 +17,10 @@ pub enum RuntimeError {
     #[error("组件 '{component}' 未定义")]
     UndefinedComponent { component: String },
 
+    /// 组件缺少必填参数
+    #[error("组件 '{component}' 缺少必填参数 '{arg}'")]
+    MissingComponentArg { component: String, arg: String },
+
     /// 流程未定义
     #[error("流程 '{flow}' 未定义")]
     UndefinedFlow { flow: String },
@@ -25,6 +29,10 @@ pub enum RuntimeError {
     #[error("检测到循环引用: {path}")]
     CircularReference { path: String },
 
+    /// 字段映射无效
+    #[error("字段映射 '{field}' 无效: {reason}")]
+    InvalidFieldMapping { field: String, reason: String },
+
     /// 脚本模块未定义
     #[error("脚本模块 '{module}' 未定义")]
     UndefinedScriptModule { module: String },
@@ -51,6 +59,10 @@ pub enum RuntimeError {
         limit_ms: u64,
     },
 
+    /// 超过规则 `limits` 配置的资源上限
+    #[error("超过{limit}上限 {max}")]
+    LimitExceeded { limit: String, max: u32 },
+
     // --- HTTP 相关错误 ---
     /// HTTP 配置错误
     #[error("HTTP 配置错误: {0}")]
@@ -60,16 +72,60 @@ pub enum RuntimeError {
     #[error("HTTP 请求错误: {0}")]
     HttpRequest(String),
 
+    /// HTTP 响应状态码表示失败
+    #[error("HTTP 状态码 {status}: {url}")]
+    HttpStatus { status: u16, url: String },
+
+    /// HTTP 请求超时
+    #[error("HTTP 请求超时: {url}")]
+    HttpTimeout { url: String },
+
+    /// 域名解析失败
+    #[error("域名解析失败 {url}: {message}")]
+    HttpDns { url: String, message: String },
+
+    /// 连接失败（连接被拒绝、重置、网络不可达等）
+    #[error("连接失败 {url}: {message}")]
+    HttpConnect { url: String, message: String },
+
+    /// TLS 握手或证书校验失败
+    #[error("TLS 错误 {url}: {message}")]
+    HttpTls { url: String, message: String },
+
     // --- 数据提取错误 ---
     /// 数据提取错误
     #[error("数据提取错误: {0}")]
     Extraction(String),
 
+    /// 必填字段提取结果为空
+    #[error("字段 '{field}' 提取结果为空")]
+    FieldEmpty { field: String },
+
+    /// 步骤执行失败
+    #[error("第 {step} 个步骤 ({kind}) 执行失败: {reason}")]
+    StepFailed {
+        step: usize,
+        kind: String,
+        reason: String,
+    },
+
+    /// 断言失败
+    #[error("断言失败: {message}")]
+    AssertionFailed { message: String },
+
     // --- 配置文件错误 ---
     /// 配置文件错误
     #[error("配置文件错误: {0}")]
     Config(String),
 
+    /// 规则解析错误
+    #[error("规则解析错误 ({format}): {error}")]
+    RuleParse { format: String, error: String },
+
+    /// 规则的规范版本不在运行时支持范围内
+    #[error("规则要求规范版本 {required}，当前运行时支持 {supported}")]
+    IncompatibleSpecVersion { required: String, supported: String },
+
     /// 模板验证错误
     #[error("模板验证错误 '{template}': {error}")]
     TemplateValidation { template: String, error: String },
@@ -87,6 +143,14 @@ pub enum RuntimeError {
     #[error("脚本执行超时")]
     ScriptTimeout,
 
+    /// 脚本输入或返回值超过大小上限
+    #[error("脚本{target}大小 {size} 字节超过上限 {limit} 字节")]
+    ScriptDataTooLarge {
+        target: String,
+        size: usize,
+        limit: usize,
+    },
+
     // --- WebView 相关错误 ---
     /// WebView 不可用
     #[error("WebView 不可用: {0}")]
@@ -129,5 +193,107 @@ pub enum RuntimeError {
     TemplateRender { message: String },
 }
 
+impl RuntimeError {
+    /// 稳定的错误码，供调用方分类处理错误
+    ///
+    /// 错误码与错误信息的措辞无关，新增变体时只追加新码，已有码不再变更
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            Self::TemplateError { .. } => "TEMPLATE_ERROR",
+            Self::UndefinedComponent { .. } => "UNDEFINED_COMPONENT",
+            Self::MissingComponentArg { .. } => "MISSING_COMPONENT_ARG",
+            Self::UndefinedFlow { .. } => "UNDEFINED_FLOW",
+            Self::CircularReference { .. } => "CIRCULAR_REFERENCE",
+            Self::InvalidFieldMapping { .. } => "INVALID_FIELD_MAPPING",
+            Self::UndefinedScriptModule { .. } => "UNDEFINED_SCRIPT_MODULE",
+            Self::UndefinedScriptFunction { .. } => "UNDEFINED_SCRIPT_FUNCTION",
+            Self::MissingConfig { .. } => "CONFIG_MISSING",
+            Self::InvalidConfigValue { .. } => "CONFIG_INVALID",
+            Self::ExecutionTimeout { .. } => "EXECUTION_TIMEOUT",
+            Self::LimitExceeded { .. } => "LIMIT_EXCEEDED",
+            Self::HttpConfig(_) => "HTTP_CONFIG",
+            Self::HttpRequest(_) => "HTTP_REQUEST",
+            Self::HttpStatus { .. } => "HTTP_STATUS",
+            Self::HttpTimeout { .. } => "HTTP_TIMEOUT",
+            Self::HttpDns { .. } => "HTTP_DNS",
+            Self::HttpConnect { .. } => "HTTP_CONNECT",
+            Self::HttpTls { .. } => "HTTP_TLS",
+            Self::Extraction(_) => "EXTRACT_FAILED",
+            Self::FieldEmpty { .. } => "EXTRACT_EMPTY",
+            Self::StepFailed { .. } => "EXTRACT_STEP_FAILED",
+            Self::AssertionFailed { .. } => "ASSERTION_FAILED",
+            Self::Config(_) => "CONFIG_ERROR",
+            Self::RuleParse { .. } => "RULE_PARSE",
+            Self::IncompatibleSpecVersion { .. } => "RULE_INCOMPATIBLE_SPEC",
+            Self::TemplateValidation { .. } => "TEMPLATE_VALIDATION",
+            Self::ScriptSyntax(_) => "SCRIPT_SYNTAX",
+            Self::ScriptRuntime(_) => "SCRIPT_RUNTIME",
+            Self::ScriptTimeout => "SCRIPT_TIMEOUT",
+            Self::ScriptDataTooLarge { .. } => "SCRIPT_DATA_TOO_LARGE",
+            Self::WebViewUnavailable(_) => "WEBVIEW_UNAVAILABLE",
+            Self::WebViewTimeout => "WEBVIEW_TIMEOUT",
+            Self::WebViewUserClosed => "WEBVIEW_USER_CLOSED",
+            Self::WebViewError(_) => "WEBVIEW_ERROR",
+            Self::ChallengeFailed(_) => "CHALLENGE_FAILED",
+            Self::ChallengeMaxAttempts { .. } => "CHALLENGE_MAX_ATTEMPTS",
+            Self::Pagination(_) => "PAGINATION",
+            Self::VariableNotFound(_) => "VARIABLE_NOT_FOUND",
+            Self::TemplateRender { .. } => "TEMPLATE_RENDER",
+        }
+    }
+
+    /// 是否为超时或中断类错误
+    ///
+    /// 这类错误表示执行被外部条件终止，而非数据本身有误，容错步骤默认不捕获
+    pub fn is_interruption(&self) -> bool {
+        matches!(
+            self,
+            Self::ExecutionTimeout { .. }
+                | Self::LimitExceeded { .. }
+                | Self::HttpTimeout { .. }
+                | Self::ScriptTimeout
+                | Self::WebViewTimeout
+                | Self::WebViewUserClosed
+        )
+    }
+
+    /// 是否值得重试
+    ///
+    /// 超时、连接失败、域名解析失败、连接中途断开等请求错误可能是暂时性的，重试可能成功；
+    /// 状态码中只有 408/429/5xx 值得重试。
+    /// TLS/证书错误与无效 URL 等永久性问题重试也无法恢复
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::HttpTimeout { .. }
+            | Self::HttpConnect { .. }
+            | Self::HttpDns { .. }
+            | Self::HttpRequest(_) => true,
+            Self::HttpStatus { status, .. } => matches!(status, 408 | 429 | 500..=599),
+            _ => false,
+        }
+    }
+}
+
 /// 运行时结果类型
 pub type Result<T> = std::result::Result<T, RuntimeError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_permanent_errors_are_not_retryable() {
+        let url = String::from("https://example.com");
+        let message = String::new();
+        assert!(RuntimeError::HttpRequest("connection reset".into()).is_retryable());
+        assert!(
+            RuntimeError::HttpDns {
+                url: url.clone(),
+                message: message.clone()
+            }
+            .is_retryable()
+        );
+        assert!(!RuntimeError::HttpTls { url, message }.is_retryable());
+        assert!(!RuntimeError::HttpConfig("invalid url".into()).is_retryable());
+    }
+}
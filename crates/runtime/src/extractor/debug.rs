@@ -0,0 +1,147 @@
+//! # 单步调试
+//!
+//! 基于 [`StepHook`] 的单步执行器：每个步骤执行前暂停，
+//! 通过通道向调试端发送步骤快照，收到继续信号后再执行
+
+use crate::extractor::trace::{self, StepEvent, StepHook, StepTrace};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::sync::mpsc::{self, Receiver, RecvError, Sender};
+
+/// 快照所处阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StepPhase {
+    /// 步骤执行前（此时提取已暂停）
+    Before,
+    /// 步骤执行后
+    After,
+}
+
+/// 步骤快照
+#[derive(Debug, Clone, Serialize)]
+pub struct StepSnapshot {
+    /// 所处阶段
+    pub phase: StepPhase,
+    /// 步骤在所属步骤链中的序号
+    pub index: usize,
+    /// 步骤类型
+    pub step: &'static str,
+    /// 所属回退链序号（主步骤链为 None）
+    pub fallback: Option<usize>,
+    /// 输入摘要
+    pub input: String,
+    /// 输出摘要（仅 `After` 阶段且步骤成功时存在）
+    pub output: Option<String>,
+    /// 错误信息（仅 `After` 阶段且步骤失败时存在）
+    pub error: Option<String>,
+    /// 流程变量快照
+    pub variables: Map<String, Value>,
+}
+
+impl StepSnapshot {
+    fn new(phase: StepPhase, event: &StepEvent<'_>) -> Self {
+        Self {
+            phase,
+            index: event.index,
+            step: event.step,
+            fallback: event.fallback,
+            input: trace::summarize(event.input),
+            output: None,
+            error: None,
+            variables: event.variables.clone(),
+        }
+    }
+}
+
+/// 单步调试钩子
+///
+/// 传给 [`ExtractEngine::extract_field_with_hook`](crate::extractor::ExtractEngine::extract_field_with_hook)
+/// 使用。步骤执行前发送 `Before` 快照并阻塞等待继续信号，执行后发送 `After` 快照；
+/// [`StepController`] 被丢弃后不再暂停。
+///
+/// 提取过程会被阻塞，应在独立线程（如 `spawn_blocking`）中执行。
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// let (mut debugger, controller) = StepDebugger::new();
+/// let handle = std::thread::spawn(move || {
+///     ExtractEngine::extract_field_with_hook(&extractor, &input, &rt, &flow, &mut debugger)
+/// });
+/// while let Some(snapshot) = controller.next() {
+///     println!("{:?} {} {}", snapshot.phase, snapshot.step, snapshot.input);
+///     if snapshot.phase == StepPhase::Before {
+///         controller.resume();
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct StepDebugger {
+    snapshots: Sender<StepSnapshot>,
+    resume: Receiver<()>,
+    /// 调试端已断开
+    detached: bool,
+}
+
+/// 单步调试控制端
+#[derive(Debug)]
+pub struct StepController {
+    snapshots: Receiver<StepSnapshot>,
+    resume: Sender<()>,
+}
+
+impl StepDebugger {
+    /// 创建调试钩子及其控制端
+    pub fn new() -> (Self, StepController) {
+        let (snapshot_tx, snapshot_rx) = mpsc::channel();
+        let (resume_tx, resume_rx) = mpsc::channel();
+        (
+            Self {
+                snapshots: snapshot_tx,
+                resume: resume_rx,
+                detached: false,
+            },
+            StepController {
+                snapshots: snapshot_rx,
+                resume: resume_tx,
+            },
+        )
+    }
+
+    /// 发送快照，控制端断开时标记为已分离
+    fn send(&mut self, snapshot: StepSnapshot) {
+        if !self.detached && self.snapshots.send(snapshot).is_err() {
+            self.detached = true;
+        }
+    }
+}
+
+impl StepHook for StepDebugger {
+    fn before_step(&mut self, event: &StepEvent<'_>) {
+        self.send(StepSnapshot::new(StepPhase::Before, event));
+        if !self.detached && self.resume.recv() == Err(RecvError) {
+            self.detached = true;
+        }
+    }
+
+    fn after_step(&mut self, event: &StepEvent<'_>, trace: StepTrace) {
+        let mut snapshot = StepSnapshot::new(StepPhase::After, event);
+        snapshot.input = trace.input;
+        snapshot.output = trace.output;
+        snapshot.error = trace.error;
+        self.send(snapshot);
+    }
+}
+
+impl StepController {
+    /// 等待下一个快照，提取结束后返回 None
+    pub fn next(&self) -> Option<StepSnapshot> {
+        self.snapshots.recv().ok()
+    }
+
+    /// 继续执行当前暂停的步骤
+    pub fn resume(&self) {
+        let _ = self.resume.send(());
+    }
+}
@@ -8,11 +8,24 @@ use crate::{
     error::RuntimeError,
     extractor::{
         StepExecutorFactory,
+        selector::{condition::ConditionExecutor, try_catch::TryExecutor},
+        trace::{self, StepEvent, StepHook, StepTrace},
         value::{ExtractValueData, SharedValue},
     },
 };
-use crawler_schema::extract::{ExtractStep, FieldExtractor};
-use std::sync::Arc;
+use crawler_schema::extract::{ExpectType, ExtractStep, FieldExtractor, VarContext};
+use serde_json::Value;
+use std::{borrow::Cow, sync::Arc};
+
+/// 期望类型在错误信息中的名称
+fn expect_type_name(expect: ExpectType) -> &'static str {
+    match expect {
+        ExpectType::String => "string",
+        ExpectType::Number => "number",
+        ExpectType::Array => "array",
+        ExpectType::Bool => "bool",
+    }
+}
 
 /// 提取引擎
 ///
@@ -23,71 +36,235 @@ pub struct ExtractEngine;
 impl ExtractEngine {
     /// 提取字段（关键改动：仅接收引用）
     ///
-    /// 执行 FieldExtractor 定义的提取流程
+    /// 执行 FieldExtractor 定义的提取流程：
+    /// 1. 执行主步骤链，结果非空则直接返回（`auto_trim` 开启时先去除首尾空白）
+    /// 2. 主步骤出错或结果为空时，依次尝试 fallback（中断类错误直接返回，见
+    ///    [`RuntimeError::is_interruption`]）
+    /// 3. 仍无结果时使用 default（若有）
+    /// 4. 最后由 nullable 决定返回 null 还是错误
+    ///
     /// 所有回退尝试都使用同一个 input 引用，避免多次克隆
     pub fn extract_field(
         extractor: &FieldExtractor,
         input: &ExtractValueData,
         runtime_context: &RuntimeContext,
         flow_context: &FlowContext,
+    ) -> Result<SharedValue> {
+        Self::extract_field_inner(extractor, input, runtime_context, flow_context, None)
+    }
+
+    /// 提取字段并记录调试轨迹
+    ///
+    /// 与 [`Self::extract_field`] 流程一致，额外返回每个步骤的执行轨迹；
+    /// 提取失败时同样返回已记录的轨迹，便于定位断在哪一步
+    pub fn extract_field_traced(
+        extractor: &FieldExtractor,
+        input: &ExtractValueData,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> (Result<SharedValue>, Vec<StepTrace>) {
+        let mut traces = Vec::new();
+        let result = Self::extract_field_with_hook(
+            extractor,
+            input,
+            runtime_context,
+            flow_context,
+            &mut traces,
+        );
+        (result, traces)
+    }
+
+    /// 提取字段，并在每个步骤前后调用钩子
+    ///
+    /// 用于编辑器单步执行等场景，配合 [`StepDebugger`](crate::extractor::StepDebugger)
+    /// 可在每个步骤前暂停
+    pub fn extract_field_with_hook(
+        extractor: &FieldExtractor,
+        input: &ExtractValueData,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+        hook: &mut dyn StepHook,
+    ) -> Result<SharedValue> {
+        Self::extract_field_inner(extractor, input, runtime_context, flow_context, Some(hook))
+    }
+
+    /// 字段提取主流程，`hook` 为 None 时不做任何记录
+    fn extract_field_inner(
+        extractor: &FieldExtractor,
+        input: &ExtractValueData,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+        hook: Option<&mut (dyn StepHook + '_)>,
+    ) -> Result<SharedValue> {
+        let value = Self::extract_value(extractor, input, runtime_context, flow_context, hook)?;
+        match extractor.expect_type {
+            Some(expect) => Self::apply_expect_type(expect, value),
+            None => Ok(value),
+        }
+    }
+
+    /// 依次尝试主步骤、回退与默认值
+    fn extract_value(
+        extractor: &FieldExtractor,
+        input: &ExtractValueData,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+        mut hook: Option<&mut (dyn StepHook + '_)>,
     ) -> Result<SharedValue> {
         // 执行主步骤链
-        match Self::execute_steps(&extractor.steps, input, runtime_context, flow_context) {
-            Ok(value) => {
-                // 检查是否为空
-                if value.is_empty() && !extractor.nullable {
-                    // 尝试回退（仍然使用 input 的引用，无克隆）
-                    if let Some(fallback) = &extractor.fallback {
-                        for fallback_steps in fallback {
-                            if let Ok(fallback_value) = Self::execute_steps(
-                                fallback_steps,
-                                input,
-                                runtime_context,
-                                flow_context,
-                            ) && !fallback_value.is_empty()
-                            {
-                                return Ok(fallback_value);
-                            }
-                        }
-                    }
-
-                    // 使用默认值
-                    if let Some(default) = &extractor.default {
-                        return Ok(Arc::new(ExtractValueData::from_json(default)));
-                    }
-
-                    // 如果不允许空值，返回错误
-                    return Err(RuntimeError::Extraction(
-                        "Field extraction returned empty value".to_string(),
-                    ));
-                }
+        let mut last_error = match Self::run_steps(
+            &extractor.steps,
+            input,
+            runtime_context,
+            flow_context,
+            None,
+            hook.as_deref_mut(),
+        )
+        .map(|value| Self::apply_trim(extractor, value))
+        {
+            Ok(value) if !value.is_empty() => return Ok(value),
+            Ok(_) => None,
+            // 超时、上限、用户取消等中断不走回退与默认值
+            Err(e) if e.is_interruption() => return Err(e),
+            Err(e) => Some(e),
+        };
 
-                Ok(value)
+        // 尝试回退（仍然使用 input 的引用，无克隆）
+        for (index, fallback_steps) in extractor.fallback.iter().flatten().enumerate() {
+            match Self::run_steps(
+                fallback_steps,
+                input,
+                runtime_context,
+                flow_context,
+                Some(index),
+                hook.as_deref_mut(),
+            )
+            .map(|value| Self::apply_trim(extractor, value))
+            {
+                Ok(value) if !value.is_empty() => return Ok(value),
+                Ok(_) => {}
+                Err(e) if e.is_interruption() => return Err(e),
+                Err(e) => last_error = Some(e),
             }
-            Err(e) => {
-                // 尝试回退
-                if let Some(fallback) = &extractor.fallback {
-                    for fallback_steps in fallback {
-                        if let Ok(fallback_value) = Self::execute_steps(
-                            fallback_steps,
-                            input,
-                            runtime_context,
-                            flow_context,
-                        ) && !fallback_value.is_empty()
-                        {
-                            return Ok(fallback_value);
-                        }
-                    }
-                }
+        }
 
-                // 使用默认值
-                if let Some(default) = &extractor.default {
-                    return Ok(Arc::new(ExtractValueData::from_json(default)));
-                }
+        // 使用默认值
+        if let Some(default) = &extractor.default {
+            let value = Arc::new(ExtractValueData::from_json(default));
+            if let Some(hook) = hook {
+                let event = StepEvent {
+                    index: 0,
+                    step: "default",
+                    fallback: None,
+                    input,
+                    variables: flow_context.data(),
+                };
+                hook.before_step(&event);
+                hook.after_step(
+                    &event,
+                    StepTrace {
+                        step: "default",
+                        input: trace::summarize(input),
+                        output: Some(trace::summarize(&value)),
+                        error: None,
+                        fallback: None,
+                    },
+                );
+            }
+            return Ok(value);
+        }
+
+        if extractor.nullable {
+            return Ok(Arc::new(ExtractValueData::Null));
+        }
+
+        // 不允许空值，返回最后一次错误
+        Err(last_error.unwrap_or_else(|| {
+            RuntimeError::Extraction("Field extraction returned empty value".to_string())
+        }))
+    }
+
+    /// 按 `expect_type` 校验并转换提取结果，null 原样返回
+    fn apply_expect_type(expect: ExpectType, value: SharedValue) -> Result<SharedValue> {
+        if value.is_null() {
+            return Ok(value);
+        }
 
-                Err(e)
+        // 标量类型：单元素数组取其元素
+        let scalar = match value.as_array() {
+            Some(items) if items.len() == 1 => items[0].clone(),
+            _ => value.clone(),
+        };
+        let converted = match expect {
+            ExpectType::Array => {
+                return Ok(match value.as_array() {
+                    Some(_) if value.is_array() => value,
+                    Some(items) => Arc::new(ExtractValueData::Array(Arc::new(items))),
+                    None => Arc::new(ExtractValueData::Array(Arc::new(vec![value]))),
+                });
             }
+            ExpectType::String => match scalar.as_ref() {
+                ExtractValueData::String(_)
+                | ExtractValueData::Html(_)
+                | ExtractValueData::Xml(_) => Some(scalar.clone()),
+                other => other
+                    .to_str_lossy()
+                    .map(|s| Arc::new(ExtractValueData::String(Arc::from(s.as_ref())))),
+            },
+            ExpectType::Number => scalar
+                .as_i64()
+                .map(Value::from)
+                .or_else(|| {
+                    scalar
+                        .as_f64()
+                        .and_then(serde_json::Number::from_f64)
+                        .map(Value::Number)
+                })
+                .map(|n| Arc::new(ExtractValueData::Json(Arc::new(n)))),
+            ExpectType::Bool => scalar
+                .as_bool()
+                .map(|b| Arc::new(ExtractValueData::Json(Arc::new(Value::Bool(b))))),
+        };
+
+        converted.ok_or_else(|| {
+            RuntimeError::Extraction(format!(
+                "提取结果无法转换为 {}: {}",
+                expect_type_name(expect),
+                trace::summarize(&scalar)
+            ))
+        })
+    }
+
+    /// 按 `auto_trim` 去除字符串结果的首尾空白
+    ///
+    /// 仅处理字符串（含 JSON 字符串），JSON 空字符串转为空字符串以触发回退；
+    /// 未发生变化时直接复用原值
+    fn apply_trim(extractor: &FieldExtractor, value: SharedValue) -> SharedValue {
+        if !extractor.auto_trim {
+            return value;
         }
+        let trimmed = match value.as_ref() {
+            ExtractValueData::String(s) if s.trim().len() != s.len() => s.trim(),
+            ExtractValueData::Json(v) => match v.as_str() {
+                Some(s) if s.is_empty() || s.trim().len() != s.len() => s.trim(),
+                _ => return value,
+            },
+            _ => return value,
+        };
+        Arc::new(ExtractValueData::String(Arc::from(trimmed)))
+    }
+
+    /// 执行字段的单条步骤链（主链或回退链）
+    fn run_steps(
+        steps: &[ExtractStep],
+        input: &ExtractValueData,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+        fallback: Option<usize>,
+        hook: Option<&mut (dyn StepHook + '_)>,
+    ) -> Result<SharedValue> {
+        Self::execute_chain(steps, input, runtime_context, flow_context, fallback, hook)
+            .map(ChainOutcome::into_value)
     }
 
     /// 执行步骤链
@@ -97,13 +274,160 @@ impl ExtractEngine {
         runtime_context: &RuntimeContext,
         flow_context: &FlowContext,
     ) -> Result<SharedValue> {
+        Self::execute_chain(steps, input, runtime_context, flow_context, None, None)
+            .map(ChainOutcome::into_value)
+    }
+
+    /// 执行步骤链并处理控制步骤（`return`、`goto`）
+    ///
+    /// `condition` 分支与 `try`/`catch` 中的 `return` 会向外传播，结束当前步骤链；
+    /// `hook` 不为 None 时在每个步骤前后调用钩子。
+    /// `set_var` 写入的流程变量仅对本步骤链可见，不会修改 `flow_context`
+    pub(crate) fn execute_chain(
+        steps: &[ExtractStep],
+        input: &ExtractValueData,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+        fallback: Option<usize>,
+        hook: Option<&mut (dyn StepHook + '_)>,
+    ) -> Result<ChainOutcome> {
+        let mut scope = Cow::Borrowed(flow_context);
+        Self::execute_chain_in(steps, input, runtime_context, &mut scope, fallback, hook)
+    }
+
+    /// 在给定的变量作用域中执行步骤链
+    ///
+    /// `set_var`（`context = "flow"`）写入 `scope`，对后续步骤及外层步骤链可见；
+    /// `condition`、`try` 的分支共用同一作用域，`try` 块失败时回滚其写入的变量
+    pub(crate) fn execute_chain_in(
+        steps: &[ExtractStep],
+        input: &ExtractValueData,
+        runtime_context: &RuntimeContext,
+        scope: &mut Cow<'_, FlowContext>,
+        fallback: Option<usize>,
+        mut hook: Option<&mut (dyn StepHook + '_)>,
+    ) -> Result<ChainOutcome> {
         let mut current = Arc::new(input.clone());
+        let mut index = 0;
+
+        while let Some(step) = steps.get(index) {
+            let step_input = current.clone();
+            let kind = trace::step_kind(step);
+            if let Some(hook) = hook.as_deref_mut() {
+                hook.before_step(&StepEvent {
+                    index,
+                    step: kind,
+                    fallback,
+                    input: &step_input,
+                    variables: scope.data(),
+                });
+            }
+            let mut next = index + 1;
+
+            let result = match step {
+                ExtractStep::Return => Ok(ChainOutcome::Returned(current.clone())),
+                ExtractStep::Goto(target) => {
+                    Self::goto_target(index, *target, steps.len()).map(|target| {
+                        next = target;
+                        ChainOutcome::Completed(current.clone())
+                    })
+                }
+                ExtractStep::SetVar(set_var) if matches!(set_var.context, VarContext::Flow) => {
+                    scope
+                        .to_mut()
+                        .set(set_var.name.clone(), current.to_owned_json());
+                    Ok(ChainOutcome::Completed(current.clone()))
+                }
+                ExtractStep::Condition(condition) => {
+                    ConditionExecutor::execute_branch(condition, &current, runtime_context, scope)
+                }
+                ExtractStep::Try(try_step) => {
+                    TryExecutor::execute_branch(try_step, &current, runtime_context, scope)
+                }
+                // 直接调用工厂的静态方法，避免创建执行器实例
+                _ => StepExecutorFactory::execute(step, &current, runtime_context, scope)
+                    .map(ChainOutcome::Completed),
+            };
+            // 为自由文本的提取错误补充步骤位置，已带位置的内层错误保持不变
+            let result = result.map_err(|e| match e {
+                RuntimeError::Extraction(reason) => RuntimeError::StepFailed {
+                    step: index,
+                    kind: kind.to_string(),
+                    reason,
+                },
+                other => other,
+            });
+
+            if let Some(hook) = hook.as_deref_mut() {
+                hook.after_step(
+                    &StepEvent {
+                        index,
+                        step: kind,
+                        fallback,
+                        input: &step_input,
+                        variables: scope.data(),
+                    },
+                    StepTrace {
+                        step: kind,
+                        input: trace::summarize(&step_input),
+                        output: result
+                            .as_ref()
+                            .ok()
+                            .map(|outcome| trace::summarize(outcome.value())),
+                        error: result.as_ref().err().map(|e| e.to_string()),
+                        fallback,
+                    },
+                );
+            }
 
-        for step in steps {
-            // 直接调用工厂的静态方法，避免创建执行器实例
-            current = StepExecutorFactory::execute(step, &current, runtime_context, flow_context)?;
+            match result? {
+                ChainOutcome::Completed(value) => current = value,
+                returned => return Ok(returned),
+            }
+            index = next;
         }
 
-        Ok(current)
+        Ok(ChainOutcome::Completed(current))
+    }
+
+    /// 校验跳转目标，只允许向前跳转
+    fn goto_target(index: usize, target: usize, len: usize) -> Result<usize> {
+        if target <= index || target > len {
+            return Err(RuntimeError::InvalidConfigValue {
+                field: "goto".to_string(),
+                reason: format!(
+                    "第 {} 个步骤只能向前跳转到 {}..={}，实际为 {}",
+                    index,
+                    index + 1,
+                    len,
+                    target
+                ),
+            });
+        }
+        Ok(target)
+    }
+}
+
+/// 步骤链执行结果
+pub(crate) enum ChainOutcome {
+    /// 步骤链执行完毕
+    Completed(SharedValue),
+    /// 遇到 `return` 步骤提前结束
+    Returned(SharedValue),
+}
+
+impl ChainOutcome {
+    /// 获取结果值
+    pub(crate) fn value(&self) -> &SharedValue {
+        match self {
+            Self::Completed(value) | Self::Returned(value) => value,
+        }
+    }
+
+    /// 取出结果值
+    pub(crate) fn into_value(self) -> SharedValue {
+        match self {
+            Self::Completed(value) | Self::Returned(value) => value,
+        }
     }
 }
@@ -8,6 +8,7 @@ use crate::{
     extractor::value::{ExtractValueData, SharedValue},
 };
 use crawler_schema::extract::ExtractStep;
+use std::sync::Arc;
 
 /// 步骤执行器工厂
 ///
@@ -67,6 +68,9 @@ impl StepExecutorFactory {
                 runtime_context,
                 flow_context,
             ),
+            ExtractStep::Enumerate => {
+                crate::extractor::selector::enumerate::EnumerateExecutor::execute(input)
+            }
             ExtractStep::SetVar(set_var) => {
                 crate::extractor::selector::set_var::SetVarExecutor::execute(
                     set_var,
@@ -75,6 +79,14 @@ impl StepExecutorFactory {
                     flow_context,
                 )
             }
+            ExtractStep::Template(template) => {
+                crate::extractor::selector::template::TemplateExecutor::execute(
+                    template,
+                    input,
+                    runtime_context,
+                    flow_context,
+                )
+            }
             ExtractStep::Script(script) => {
                 crate::script::ScriptExecutor::execute(script, input, runtime_context, flow_context)
             }
@@ -86,14 +98,16 @@ impl StepExecutorFactory {
                     flow_context,
                 )
             }
-            ExtractStep::Xpath(_selector) => {
-                // XPath 需要 JS 环境，暂不支持
-                Err(crate::error::RuntimeError::Extraction(
-                    "XPath not supported in this context".into(),
-                ))
+            ExtractStep::Xpath(selector) => {
+                crate::extractor::selector::xpath::XPathExecutor::execute(
+                    selector,
+                    input,
+                    runtime_context,
+                    flow_context,
+                )
             }
-            ExtractStep::Map(steps) => crate::extractor::selector::map::MapExecutor::execute(
-                steps,
+            ExtractStep::Map(map) => crate::extractor::selector::map::MapExecutor::execute(
+                map,
                 input,
                 runtime_context,
                 flow_context,
@@ -106,6 +120,30 @@ impl StepExecutorFactory {
                     flow_context,
                 )
             }
+            ExtractStep::Try(try_step) => {
+                crate::extractor::selector::try_catch::TryExecutor::execute(
+                    try_step,
+                    input,
+                    runtime_context,
+                    flow_context,
+                )
+            }
+            ExtractStep::Assert(assert) => {
+                crate::extractor::selector::assert::AssertExecutor::execute(
+                    assert,
+                    input,
+                    runtime_context,
+                    flow_context,
+                )
+            }
+            ExtractStep::Log(log) => crate::extractor::selector::log::LogExecutor::execute(
+                log,
+                input,
+                runtime_context,
+                flow_context,
+            ),
+            // 控制步骤由步骤链执行器处理，单独执行时原样返回输入
+            ExtractStep::Return | ExtractStep::Goto(_) => Ok(Arc::new(input.clone())),
         }
     }
 }
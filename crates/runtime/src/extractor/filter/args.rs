@@ -0,0 +1,83 @@
+//! # 过滤器参数
+//!
+//! 统一解析位置参数与命名参数：
+//! - 管道字符串：`sort(desc, number)`、`sort(by="title", order=desc)`
+//! - 结构化形式：`args = ["desc"]`、`args = [{ by = "title", order = "desc" }]`
+
+use serde_json::{Map, Value};
+
+/// 解析后的过滤器参数
+#[derive(Debug, Default)]
+pub struct FilterArgs {
+    positional: Vec<Value>,
+    named: Map<String, Value>,
+}
+
+impl FilterArgs {
+    /// 解析参数
+    ///
+    /// 对象参数展开为命名参数；`key=value` 形式的字符串（键为标识符）视为命名参数，
+    /// 值两侧的引号会被去除；其余按顺序作为位置参数
+    pub fn parse(args: &[Value]) -> Self {
+        let mut parsed = Self::default();
+        for arg in args {
+            match arg {
+                Value::Object(map) => {
+                    parsed
+                        .named
+                        .extend(map.iter().map(|(k, v)| (k.clone(), v.clone())));
+                }
+                Value::String(s) => match split_named(s) {
+                    Some((key, value)) => {
+                        parsed
+                            .named
+                            .insert(key.to_string(), Value::String(unquote(value).to_string()));
+                    }
+                    None => parsed
+                        .positional
+                        .push(Value::String(unquote(s).to_string())),
+                },
+                other => parsed.positional.push(other.clone()),
+            }
+        }
+        parsed
+    }
+
+    /// 按名称或位置获取参数，命名参数优先
+    pub fn get(&self, name: &str, position: usize) -> Option<&Value> {
+        self.named
+            .get(name)
+            .or_else(|| self.positional.get(position))
+    }
+
+    /// 按名称获取参数
+    pub fn named(&self, name: &str) -> Option<&Value> {
+        self.named.get(name)
+    }
+
+    /// 位置参数
+    pub fn positional(&self) -> &[Value] {
+        &self.positional
+    }
+}
+
+/// 拆分 `key=value`，键必须是标识符
+fn split_named(s: &str) -> Option<(&str, &str)> {
+    let (key, value) = s.split_once('=')?;
+    let key = key.trim();
+    let mut chars = key.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    valid.then_some((key, value.trim()))
+}
+
+/// 去除两侧成对的引号
+fn unquote(s: &str) -> &str {
+    let s = s.trim();
+    ['"', '\'']
+        .iter()
+        .find_map(|q| s.strip_prefix(*q).and_then(|s| s.strip_suffix(*q)))
+        .unwrap_or(s)
+}
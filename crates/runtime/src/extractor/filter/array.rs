@@ -6,6 +6,185 @@
 // - nth
 // - slice
 // - unique
-// - sort
 // - flatten
 // - length
+
+use crate::{
+    Result,
+    error::RuntimeError,
+    extractor::{
+        SharedValue,
+        filter::{Filter, args::FilterArgs},
+        value::ExtractValueData,
+    },
+};
+use serde_json::Value;
+use std::{cmp::Ordering, sync::Arc};
+
+/// 排序方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SortKind {
+    /// 全部可解析为数字时按数字，否则按字符串
+    Auto,
+    /// 按字符串（Unicode 码点）
+    String,
+    /// 按数字
+    Number,
+    /// 按拼音（需启用 `collation` feature）
+    Pinyin,
+}
+
+/// 排序键
+enum SortKey {
+    Number(f64),
+    Text(String),
+    Missing,
+}
+
+/// Sort 过滤器
+///
+/// 参数（位置或命名均可）：
+/// - `order`：`asc`（默认）/ `desc`
+/// - `type`：`string` / `number` / `pinyin`，默认自动判断
+/// - `by`：对象数组按该字段排序
+///
+/// 无法取得排序键的元素（缺少字段、非数字）始终排在末尾，排序是稳定的。
+///
+/// ```toml
+/// steps = [{ filter = "sort(desc, number)" }]
+/// steps = [{ filter = 'sort(by="title", type=pinyin)' }]
+/// ```
+pub struct SortFilter;
+
+impl Filter for SortFilter {
+    fn apply(&self, input: &SharedValue, args: &[Value]) -> Result<SharedValue> {
+        let items = input.as_array().ok_or_else(|| {
+            RuntimeError::Extraction("sort filter requires array input".to_string())
+        })?;
+        let args = FilterArgs::parse(args);
+
+        let mut descending = false;
+        let mut kind = SortKind::Auto;
+        let mut by = None;
+        for value in args.positional() {
+            let Some(s) = value.as_str() else {
+                continue;
+            };
+            match s {
+                "asc" | "desc" => descending = s == "desc",
+                "string" | "number" | "pinyin" => kind = parse_kind(s)?,
+                _ => by = Some(s),
+            }
+        }
+        if let Some(order) = args.named("order") {
+            descending = match order.as_str() {
+                Some("asc") => false,
+                Some("desc") => true,
+                _ => return Err(invalid_arg("order", order)),
+            };
+        }
+        if let Some(value) = args.named("type") {
+            kind = parse_kind(value.as_str().unwrap_or_default())?;
+        }
+        if let Some(value) = args.named("by") {
+            by = Some(value.as_str().ok_or_else(|| invalid_arg("by", value))?);
+        }
+
+        let keyed: Vec<(SharedValue, Option<SharedValue>)> = items
+            .into_iter()
+            .map(|item| {
+                let key = match by {
+                    Some(field) => item.get(field),
+                    None => Some(item.clone()),
+                };
+                (item, key)
+            })
+            .collect();
+
+        if kind == SortKind::Auto {
+            let numeric = keyed
+                .iter()
+                .filter_map(|(_, key)| key.as_ref())
+                .all(|key| key.as_f64().is_some());
+            kind = if numeric {
+                SortKind::Number
+            } else {
+                SortKind::String
+            };
+        }
+
+        let mut keyed: Vec<(SharedValue, SortKey)> = keyed
+            .into_iter()
+            .map(|(item, key)| {
+                let key = match (kind, key) {
+                    (_, None) => SortKey::Missing,
+                    (SortKind::Number, Some(key)) => {
+                        key.as_f64().map_or(SortKey::Missing, SortKey::Number)
+                    }
+                    (_, Some(key)) => key.as_text().map_or(SortKey::Missing, SortKey::Text),
+                };
+                (item, key)
+            })
+            .collect();
+
+        let compare_text = text_comparator(kind)?;
+        keyed.sort_by(|(_, a), (_, b)| {
+            let ordering = match (a, b) {
+                (SortKey::Missing, SortKey::Missing) => return Ordering::Equal,
+                (SortKey::Missing, _) => return Ordering::Greater,
+                (_, SortKey::Missing) => return Ordering::Less,
+                (SortKey::Number(a), SortKey::Number(b)) => a.total_cmp(b),
+                (SortKey::Text(a), SortKey::Text(b)) => compare_text(a, b),
+                // 同一次排序只会产生一种键
+                _ => Ordering::Equal,
+            };
+            if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+
+        Ok(Arc::new(ExtractValueData::Array(Arc::new(
+            keyed.into_iter().map(|(item, _)| item).collect(),
+        ))))
+    }
+}
+
+/// 解析排序方式
+fn parse_kind(s: &str) -> Result<SortKind> {
+    match s {
+        "string" => Ok(SortKind::String),
+        "number" => Ok(SortKind::Number),
+        "pinyin" => Ok(SortKind::Pinyin),
+        _ => Err(invalid_arg("type", &Value::String(s.to_string()))),
+    }
+}
+
+fn invalid_arg(name: &str, value: &Value) -> RuntimeError {
+    RuntimeError::Extraction(format!("sort: invalid '{}' argument {}", name, value))
+}
+
+/// 文本比较函数
+type TextComparator = Box<dyn Fn(&str, &str) -> Ordering>;
+
+/// 按排序方式选择文本比较函数
+fn text_comparator(kind: SortKind) -> Result<TextComparator> {
+    if kind != SortKind::Pinyin {
+        return Ok(Box::new(|a: &str, b: &str| a.cmp(b)));
+    }
+
+    #[cfg(feature = "collation")]
+    {
+        use icu_collator::{Collator, CollatorOptions};
+
+        let collator = Collator::try_new(&icu_locid::locale!("zh").into(), CollatorOptions::new())
+            .map_err(|e| RuntimeError::Extraction(format!("sort: 创建拼音排序器失败: {}", e)))?;
+        Ok(Box::new(move |a: &str, b: &str| collator.compare(a, b)))
+    }
+
+    #[cfg(not(feature = "collation"))]
+    Err(RuntimeError::Extraction(
+        "sort: 按拼音排序需要启用 `collation` feature".to_string(),
+    ))
+}
@@ -4,6 +4,7 @@ use crate::{
     Result,
     error::RuntimeError,
     extractor::{SharedValue, filter::Filter, value::ExtractValueData},
+    script::builtin::core,
 };
 use serde_json::Value;
 use std::sync::Arc;
@@ -35,7 +36,7 @@ impl Filter for ToStringFilter {
         let s = match input.as_ref() {
             ExtractValueData::String(s) => s.to_string(),
             ExtractValueData::Json(v) => v.to_string(),
-            ExtractValueData::Html(h) => h.to_string(),
+            ExtractValueData::Html(h) | ExtractValueData::Xml(h) => h.to_string(),
             ExtractValueData::Array(_) => {
                 return Err(RuntimeError::Extraction(
                     "Cannot convert array to string".to_string(),
@@ -50,6 +51,24 @@ impl Filter for ToStringFilter {
     }
 }
 
+/// StripJsonp 过滤器
+///
+/// 去除 `callback(...)` 包裹后解析为 JSON
+pub struct StripJsonpFilter;
+
+impl Filter for StripJsonpFilter {
+    fn apply(&self, input: &SharedValue, _args: &[Value]) -> Result<SharedValue> {
+        let s = input.as_str().ok_or_else(|| {
+            RuntimeError::Extraction("strip_jsonp filter requires string input".to_string())
+        })?;
+
+        let json: Value = serde_json::from_str(&core::strip_jsonp(s))
+            .map_err(|e| RuntimeError::Extraction(format!("Failed to parse JSONP: {}", e)))?;
+
+        Ok(Arc::new(ExtractValueData::Json(Arc::new(json))))
+    }
+}
+
 // TODO: 实现更多转换过滤器
 // - to_float
 // - to_bool
@@ -6,3 +6,28 @@
 // - html_encode
 // - html_decode
 // - md5
+
+use crate::{
+    Result,
+    error::RuntimeError,
+    extractor::{SharedValue, filter::Filter, value::ExtractValueData},
+    script::builtin::core,
+};
+use serde_json::Value;
+use std::sync::Arc;
+
+/// DecodeAll 过滤器
+///
+/// HTML 实体解码 + Unicode 转义解码 + 去首尾空白，见 [`core::decode_all`]
+pub struct DecodeAllFilter;
+
+impl Filter for DecodeAllFilter {
+    fn apply(&self, input: &SharedValue, _args: &[Value]) -> Result<SharedValue> {
+        let s = input.as_str().ok_or_else(|| {
+            RuntimeError::Extraction("decode_all filter requires string input".to_string())
+        })?;
+        Ok(Arc::new(ExtractValueData::String(Arc::from(
+            core::decode_all(s).into_boxed_str(),
+        ))))
+    }
+}
@@ -2,13 +2,17 @@
 //!
 //! 实现各种数据过滤和转换功能
 
+pub mod args;
 pub mod array;
 pub mod convert;
 pub mod encoding;
 pub mod executor;
+pub mod pipeline;
 pub mod registry;
 pub mod string;
 pub mod url;
 
+pub use args::FilterArgs;
 pub use executor::FilterExecutor;
-pub use registry::{Filter, FilterRegistry};
+pub use pipeline::{FilterCall, parse_pipeline};
+pub use registry::{Filter, FilterContext, FilterRegistry};
@@ -0,0 +1,154 @@
+//! # 过滤器管道解析
+//!
+//! 解析 `"trim | lower | replace(a, b)"` 形式的管道字符串：
+//!
+//! - `|` 分隔过滤器，`,` 分隔参数，二者在引号与括号内不生效
+//! - 参数可用双引号或单引号包裹（引号只在词首生效）：`replace("a, b", "c")`，引号内支持
+//!   `\"`、`\'`、`\\` 转义， 其他反斜杠原样保留（如正则中的 `\d`）
+//! - 未加引号的参数去除首尾空白，可包含成对的括号：`regex_replace((\d+)年, $1)`
+//! - 未加引号的参数原样传递，`key=value` 形式由 [`FilterArgs`](super::FilterArgs) 解析为命名参数
+
+use crate::{Result, error::RuntimeError};
+use serde_json::Value;
+
+/// 管道中的单个过滤器调用
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilterCall {
+    /// 过滤器名称
+    pub name: String,
+    /// 参数
+    pub args: Vec<Value>,
+}
+
+/// 解析过滤器管道字符串
+pub fn parse_pipeline(pipeline: &str) -> Result<Vec<FilterCall>> {
+    split_top_level(pipeline, '|')
+        .and_then(|parts| {
+            parts
+                .into_iter()
+                .map(|part| parse_call(part.raw.trim()))
+                .collect()
+        })
+        .map_err(|reason| {
+            RuntimeError::Extraction(format!("过滤器管道 '{}' 解析失败: {}", pipeline, reason))
+        })
+}
+
+/// 解析单个过滤器调用：`name` 或 `name(args...)`
+fn parse_call(part: &str) -> std::result::Result<FilterCall, String> {
+    let (name, args) = match part.find('(') {
+        Some(open) => {
+            let inner = part[open + 1..]
+                .strip_suffix(')')
+                .ok_or_else(|| format!("过滤器 '{}' 缺少右括号", part))?;
+            (part[..open].trim(), Some(inner))
+        }
+        None => (part, None),
+    };
+    if name.is_empty() {
+        return Err("过滤器名称为空".to_string());
+    }
+
+    let args = match args {
+        Some(inner) if !inner.trim().is_empty() => split_top_level(inner, ',')?
+            .into_iter()
+            .map(Segment::into_arg)
+            .collect(),
+        _ => Vec::new(),
+    };
+    Ok(FilterCall {
+        name: name.to_string(),
+        args,
+    })
+}
+
+/// 按分隔符拆分出的片段
+struct Segment<'a> {
+    /// 原始文本
+    raw: &'a str,
+    /// 片段整体为一个引号字符串时，去除引号并处理转义后的内容
+    quoted: Option<String>,
+}
+
+impl<'a> Segment<'a> {
+    fn new(raw: &'a str) -> Self {
+        Self {
+            raw,
+            quoted: unquote_escaped(raw.trim()),
+        }
+    }
+
+    /// 转换为过滤器参数
+    fn into_arg(self) -> Value {
+        Value::String(self.quoted.unwrap_or_else(|| self.raw.trim().to_string()))
+    }
+}
+
+/// 在引号与括号之外按分隔符拆分
+fn split_top_level(s: &str, separator: char) -> std::result::Result<Vec<Segment<'_>>, String> {
+    let mut segments = Vec::new();
+    let mut start = 0;
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    let mut chars = s.char_indices();
+
+    while let Some((i, c)) = chars.next() {
+        match quote {
+            Some(q) => match c {
+                '\\' => {
+                    chars.next();
+                }
+                _ if c == q => quote = None,
+                _ => {}
+            },
+            None => match c {
+                // 引号只在词首生效，`it's` 这类词中的引号按普通字符处理
+                '"' | '\'' if opens_quote(&s[start..i]) => quote = Some(c),
+                '(' => depth += 1,
+                ')' => depth = depth.saturating_sub(1),
+                _ if c == separator && depth == 0 => {
+                    segments.push(Segment::new(&s[start..i]));
+                    start = i + c.len_utf8();
+                }
+                _ => {}
+            },
+        }
+    }
+    if let Some(q) = quote {
+        return Err(format!("引号 {} 未闭合", q));
+    }
+    segments.push(Segment::new(&s[start..]));
+    Ok(segments)
+}
+
+/// 引号前的内容为空或以 `=`、`(`、`,` 结尾时，引号开启字符串
+fn opens_quote(before: &str) -> bool {
+    before
+        .trim_end()
+        .chars()
+        .last()
+        .is_none_or(|c| matches!(c, '=' | '(' | ','))
+}
+
+/// 文本整体为一个引号字符串时，去除引号并处理转义
+fn unquote_escaped(s: &str) -> Option<String> {
+    let quote = s.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s[1..].chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(e @ ('"' | '\'' | '\\')) => out.push(e),
+                Some(other) => {
+                    out.push('\\');
+                    out.push(other);
+                }
+                None => out.push('\\'),
+            },
+            // 闭合引号之后不能再有内容
+            _ if c == quote => return chars.as_str().trim().is_empty().then_some(out),
+            _ => out.push(c),
+        }
+    }
+    None
+}
@@ -6,10 +6,31 @@ use crate::{Result, error::RuntimeError, extractor::SharedValue};
 use serde_json::Value;
 use std::{collections::HashMap, sync::Arc};
 
+/// 过滤器执行上下文
+///
+/// 由过滤器执行器根据当前流程上下文构造
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FilterContext<'a> {
+    /// 当前页面 URL（见 [`FlowContext::page_url`](crate::context::FlowContext::page_url)）
+    pub base_url: Option<&'a str>,
+}
+
 /// 过滤器 trait
 pub trait Filter: Send + Sync {
     /// 应用过滤器
     fn apply(&self, input: &SharedValue, args: &[Value]) -> Result<SharedValue>;
+
+    /// 结合执行上下文应用过滤器
+    ///
+    /// 默认忽略上下文，需要页面信息的过滤器（如 `absolute_url`）重写此方法
+    fn apply_with_context(
+        &self,
+        input: &SharedValue,
+        args: &[Value],
+        _context: &FilterContext<'_>,
+    ) -> Result<SharedValue> {
+        self.apply(input, args)
+    }
 }
 
 /// 过滤器注册表（全局单例）
@@ -44,23 +65,39 @@ impl FilterRegistry {
     ///
     /// 接受输入值的所有权，内部使用引用传递给过滤器
     pub fn apply(&self, name: &str, input: SharedValue, args: &[Value]) -> Result<SharedValue> {
+        self.apply_with_context(name, input, args, &FilterContext::default())
+    }
+
+    /// 结合执行上下文应用过滤器
+    pub fn apply_with_context(
+        &self,
+        name: &str,
+        input: SharedValue,
+        args: &[Value],
+        context: &FilterContext<'_>,
+    ) -> Result<SharedValue> {
         let filter = self
             .get(name)
             .ok_or_else(|| RuntimeError::Extraction(format!("Filter not found: {}", name)))?;
 
-        filter.apply(&input, args)
+        filter.apply_with_context(&input, args, context)
     }
 
     /// 注册所有内置过滤器
     fn register_builtin_filters(&mut self) {
-        use crate::extractor::filter::{convert, string, url};
+        use crate::extractor::filter::{array, convert, encoding, string, url};
 
         // 字符串过滤器
         self.register("trim", string::TrimFilter);
         self.register("lower", string::LowerFilter);
         self.register("upper", string::UpperFilter);
+        self.register("capitalize", string::CapitalizeFilter);
+        self.register("collapse_whitespace", string::CollapseWhitespaceFilter);
         self.register("replace", string::ReplaceFilter);
         self.register("regex_replace", string::RegexReplaceFilter);
+        self.register("regex_extract", string::RegexExtractFilter);
+        self.register("regex_match", string::RegexMatchFilter);
+        self.register("regex_find_all", string::RegexFindAllFilter);
         self.register("split", string::SplitFilter);
         self.register("join", string::JoinFilter);
         self.register("strip_html", string::StripHtmlFilter);
@@ -69,11 +106,18 @@ impl FilterRegistry {
         // 类型转换过滤器
         self.register("to_int", convert::ToIntFilter);
         self.register("to_string", convert::ToStringFilter);
+        self.register("strip_jsonp", convert::StripJsonpFilter);
+
+        // 数组过滤器
+        self.register("sort", array::SortFilter);
 
         // URL 过滤器
         self.register("absolute_url", url::AbsoluteUrlFilter);
         self.register("url_encode", url::UrlEncodeFilter);
         self.register("url_decode", url::UrlDecodeFilter);
+
+        // 编码过滤器
+        self.register("decode_all", encoding::DecodeAllFilter);
     }
 }
 
@@ -3,7 +3,11 @@
 use crate::{
     Result,
     error::RuntimeError,
-    extractor::{SharedValue, filter::Filter, value::ExtractValueData},
+    extractor::{
+        SharedValue,
+        filter::{Filter, args::FilterArgs},
+        value::ExtractValueData,
+    },
 };
 use serde_json::Value;
 use std::sync::Arc;
@@ -50,6 +54,81 @@ impl Filter for UpperFilter {
     }
 }
 
+/// Capitalize 过滤器
+/// 参数: [mode?]，默认仅首字母大写、其余小写；`capitalize(title)` 时每个单词首字母大写
+pub struct CapitalizeFilter;
+
+impl Filter for CapitalizeFilter {
+    fn apply(&self, input: &SharedValue, args: &[Value]) -> Result<SharedValue> {
+        let s = input.as_str().ok_or_else(|| {
+            RuntimeError::Extraction("capitalize filter requires string input".to_string())
+        })?;
+
+        let args = FilterArgs::parse(args);
+        let result = match args.get("mode", 0).and_then(|v| v.as_str()) {
+            None => capitalize(s),
+            Some("title") => {
+                // 以空白分隔单词，保留原有空白
+                let mut result = String::with_capacity(s.len());
+                let mut word_start = true;
+                for c in s.chars() {
+                    if c.is_whitespace() {
+                        result.push(c);
+                        word_start = true;
+                    } else if word_start {
+                        result.extend(c.to_uppercase());
+                        word_start = false;
+                    } else {
+                        result.extend(c.to_lowercase());
+                    }
+                }
+                result
+            }
+            Some(mode) => {
+                return Err(RuntimeError::Extraction(format!(
+                    "capitalize: unknown mode '{}', expected 'title'",
+                    mode
+                )));
+            }
+        };
+
+        Ok(Arc::new(ExtractValueData::String(Arc::from(
+            result.into_boxed_str(),
+        ))))
+    }
+}
+
+/// 首字母大写，其余小写
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first
+            .to_uppercase()
+            .chain(chars.flat_map(char::to_lowercase))
+            .collect(),
+        None => String::new(),
+    }
+}
+
+/// CollapseWhitespace 过滤器
+/// 连续空白（含制表符、换行、全角空格）折叠为单个空格，并去除首尾空白
+pub struct CollapseWhitespaceFilter;
+
+impl Filter for CollapseWhitespaceFilter {
+    fn apply(&self, input: &SharedValue, _args: &[Value]) -> Result<SharedValue> {
+        let s = input.as_str().ok_or_else(|| {
+            RuntimeError::Extraction("collapse_whitespace filter requires string input".to_string())
+        })?;
+
+        // split_whitespace 按 Unicode 空白拆分，涵盖 U+3000 全角空格与 U+00A0
+        let result = s.split_whitespace().collect::<Vec<_>>().join(" ");
+
+        Ok(Arc::new(ExtractValueData::String(Arc::from(
+            result.into_boxed_str(),
+        ))))
+    }
+}
+
 /// Replace 过滤器
 /// 参数: [from, to]
 pub struct ReplaceFilter;
@@ -111,6 +190,93 @@ impl Filter for RegexReplaceFilter {
     }
 }
 
+/// 取出输入字符串，并以第一个参数编译正则
+fn regex_input<'a>(
+    filter: &str,
+    input: &'a SharedValue,
+    args: &[Value],
+) -> Result<(&'a str, regex::Regex)> {
+    let s = input.as_str().ok_or_else(|| {
+        RuntimeError::Extraction(format!("{} filter requires string input", filter))
+    })?;
+    let pattern = args.first().and_then(|v| v.as_str()).ok_or_else(|| {
+        RuntimeError::Extraction(format!(
+            "{} filter requires a string argument: pattern",
+            filter
+        ))
+    })?;
+    let re = regex::Regex::new(pattern)
+        .map_err(|e| RuntimeError::Extraction(format!("Invalid regex pattern: {}", e)))?;
+    Ok((s, re))
+}
+
+/// 从参数中读取捕获组序号（数字或数字字符串）
+fn group_arg(filter: &str, args: &[Value], index: usize, default: usize) -> Result<usize> {
+    match args.get(index) {
+        None => Ok(default),
+        Some(v) => v
+            .as_u64()
+            .map(|n| n as usize)
+            .or_else(|| v.as_str().and_then(|s| s.trim().parse().ok()))
+            .ok_or_else(|| {
+                RuntimeError::Extraction(format!(
+                    "{}: 'group' must be a non-negative integer",
+                    filter
+                ))
+            }),
+    }
+}
+
+/// RegexExtract 过滤器
+/// 参数: [pattern, group?]，group 默认为 1，无匹配时返回 null
+pub struct RegexExtractFilter;
+
+impl Filter for RegexExtractFilter {
+    fn apply(&self, input: &SharedValue, args: &[Value]) -> Result<SharedValue> {
+        let (s, re) = regex_input("regex_extract", input, args)?;
+        let group = group_arg("regex_extract", args, 1, 1)?;
+
+        Ok(Arc::new(
+            re.captures(s)
+                .and_then(|caps| caps.get(group))
+                .map(|m| ExtractValueData::String(Arc::from(m.as_str())))
+                .unwrap_or(ExtractValueData::Null),
+        ))
+    }
+}
+
+/// RegexMatch 过滤器
+/// 参数: [pattern]，返回是否匹配
+pub struct RegexMatchFilter;
+
+impl Filter for RegexMatchFilter {
+    fn apply(&self, input: &SharedValue, args: &[Value]) -> Result<SharedValue> {
+        let (s, re) = regex_input("regex_match", input, args)?;
+        let matched = re.is_match(s);
+        Ok(Arc::new(ExtractValueData::Json(Arc::new(Value::Bool(
+            matched,
+        )))))
+    }
+}
+
+/// RegexFindAll 过滤器
+/// 参数: [pattern, group?]，group 默认为 0（整个匹配），无匹配时返回空数组
+pub struct RegexFindAllFilter;
+
+impl Filter for RegexFindAllFilter {
+    fn apply(&self, input: &SharedValue, args: &[Value]) -> Result<SharedValue> {
+        let (s, re) = regex_input("regex_find_all", input, args)?;
+        let group = group_arg("regex_find_all", args, 1, 0)?;
+
+        let matches: Vec<SharedValue> = re
+            .captures_iter(s)
+            .filter_map(|caps| caps.get(group))
+            .map(|m| Arc::new(ExtractValueData::String(Arc::from(m.as_str()))))
+            .collect();
+        Ok(Arc::new(ExtractValueData::Array(Arc::new(matches))))
+    }
+}
+
 /// Split 过滤器
 /// 参数: [separator]
 pub struct SplitFilter;
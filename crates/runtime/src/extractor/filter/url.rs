@@ -3,52 +3,60 @@
 use crate::{
     Result,
     error::RuntimeError,
-    extractor::{SharedValue, filter::Filter, value::ExtractValueData},
+    extractor::{
+        SharedValue,
+        filter::{Filter, FilterContext},
+        value::ExtractValueData,
+    },
+    script::builtin,
 };
 use serde_json::Value;
 use std::sync::Arc;
 
 /// AbsoluteUrl 过滤器
 /// 将相对 URL 转换为绝对 URL
-/// 参数: [base_url]
+/// 参数: [base_url]，省略时使用当前页面 URL（见 [`FilterContext`]）
+///
+/// 支持相对路径（`a.html`）、绝对路径（`/a.html`）与协议相对 URL（`//host/a.html`）
 pub struct AbsoluteUrlFilter;
 
 impl Filter for AbsoluteUrlFilter {
     fn apply(&self, input: &SharedValue, args: &[Value]) -> Result<SharedValue> {
+        self.apply_with_context(input, args, &FilterContext::default())
+    }
+
+    fn apply_with_context(
+        &self,
+        input: &SharedValue,
+        args: &[Value],
+        context: &FilterContext<'_>,
+    ) -> Result<SharedValue> {
         let url = input.as_str().ok_or_else(|| {
             RuntimeError::Extraction("absolute_url filter requires string input".to_string())
         })?;
+        let url = url.trim();
 
-        // 如果已经是绝对 URL，直接返回
-        if url.starts_with("http://") || url.starts_with("https://") {
-            return Ok(Arc::new(ExtractValueData::String(Arc::from(
-                url.to_string().into_boxed_str(),
-            ))));
-        }
-
-        // 需要 base_url 参数
-        let base_url = args.first().and_then(|v| v.as_str()).ok_or_else(|| {
-            RuntimeError::Extraction("absolute_url filter requires base_url argument".to_string())
-        })?;
-
-        // 拼接 URL
-        let absolute = if url.starts_with('/') {
-            // 绝对路径
-            let base = base_url.trim_end_matches('/');
-            // 提取 base 的 origin (scheme + host)
-            if let Some(idx) = base.find("://") {
-                if let Some(path_start) = base[idx + 3..].find('/') {
-                    format!("{}{}", &base[..idx + 3 + path_start], url)
-                } else {
-                    format!("{}{}", base, url)
+        // 已经是绝对 URL 时无需 base_url
+        let absolute = if url.starts_with("http://") || url.starts_with("https://") {
+            url.to_string()
+        } else {
+            // 显式参数优先，其次为当前页面 URL
+            let base_url = args
+                .first()
+                .and_then(|v| v.as_str())
+                .filter(|s| !s.is_empty())
+                .or(context.base_url);
+            match base_url {
+                Some(base_url) => builtin::join_url(base_url, url),
+                // 协议相对 URL 缺少 base_url 时默认 https
+                None if url.starts_with("//") => builtin::join_url("", url),
+                None => {
+                    return Err(RuntimeError::Extraction(
+                        "absolute_url filter requires base_url argument or page URL in context"
+                            .to_string(),
+                    ));
                 }
-            } else {
-                format!("{}{}", base, url)
             }
-        } else {
-            // 相对路径
-            let base = base_url.trim_end_matches('/');
-            format!("{}/{}", base, url)
         };
 
         Ok(Arc::new(ExtractValueData::String(Arc::from(
@@ -2,12 +2,16 @@
 //!
 //! 提供从 HTML/JSON/XML 中提取数据的功能
 
+pub mod debug;
 pub mod engine;
 pub mod executor;
 pub mod filter;
 pub mod selector;
+pub mod trace;
 pub mod value;
 
+pub use debug::{StepController, StepDebugger, StepPhase, StepSnapshot};
 pub use engine::ExtractEngine;
 pub use executor::StepExecutorFactory;
+pub use trace::{StepEvent, StepHook, StepTrace};
 pub use value::{ExtractValueData, SharedValue};
@@ -0,0 +1,72 @@
+//! # 断言执行器
+//!
+//! 条件不成立时中断步骤链
+
+use crate::{
+    Result,
+    context::{FlowContext, RuntimeContext},
+    error::RuntimeError,
+    extractor::value::{ExtractValueData, SharedValue},
+    template::{TemplateExt, referenced_variables},
+};
+use crawler_schema::extract::AssertStep;
+use std::sync::Arc;
+
+/// 条件模板中表示当前值的变量名
+const VALUE_VAR: &str = "value";
+
+/// 断言执行器
+pub struct AssertExecutor;
+
+impl AssertExecutor {
+    /// 执行断言，通过时原样返回输入
+    pub fn execute(
+        assert: &AssertStep,
+        input: &ExtractValueData,
+        _runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> Result<SharedValue> {
+        // 仅在模板引用当前值时才复制上下文
+        let rendered = if referenced_variables(assert.condition.as_str())
+            .iter()
+            .any(|v| v == VALUE_VAR)
+        {
+            let mut context = flow_context.clone();
+            context.set(
+                VALUE_VAR,
+                serde_json::to_value(input).unwrap_or(serde_json::Value::Null),
+            );
+            assert.condition.render(&context)
+        } else {
+            assert.condition.render(flow_context)
+        };
+        // 条件无法渲染（如引用了未定义的变量）同样视为断言失败
+        let rendered = rendered.map_err(|e| RuntimeError::AssertionFailed {
+            message: format!("{}（{}）", Self::message(assert), e),
+        })?;
+
+        if !is_truthy(&rendered) {
+            return Err(RuntimeError::AssertionFailed {
+                message: Self::message(assert),
+            });
+        }
+        Ok(Arc::new(input.clone()))
+    }
+
+    /// 断言失败时的提示，未配置 `message` 时使用条件模板
+    fn message(assert: &AssertStep) -> String {
+        assert
+            .message
+            .clone()
+            .unwrap_or_else(|| assert.condition.to_string())
+    }
+}
+
+/// 判断渲染结果是否为真
+fn is_truthy(rendered: &str) -> bool {
+    let rendered = rendered.trim();
+    !(rendered.is_empty()
+        || ["false", "0", "null", "none"]
+            .iter()
+            .any(|f| rendered.eq_ignore_ascii_case(f)))
+}
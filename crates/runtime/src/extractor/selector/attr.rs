@@ -28,17 +28,17 @@ impl AttrExecutor {
         _flow_context: &FlowContext,
     ) -> Result<SharedValue> {
         match input {
-            ExtractValueData::Html(html) | ExtractValueData::String(html) => {
-                Self::extract_from_html(html, attr_name)
-            }
+            ExtractValueData::Html(html)
+            | ExtractValueData::String(html)
+            | ExtractValueData::Xml(html) => Self::extract_from_html(html, attr_name),
             ExtractValueData::Array(arr) => {
                 // 对数组中的每个元素提取属性
                 let results: Vec<SharedValue> = arr
                     .iter()
                     .filter_map(|item| match item.as_ref() {
-                        ExtractValueData::Html(h) | ExtractValueData::String(h) => {
-                            Self::extract_from_html(h, attr_name).ok()
-                        }
+                        ExtractValueData::Html(h)
+                        | ExtractValueData::String(h)
+                        | ExtractValueData::Xml(h) => Self::extract_from_html(h, attr_name).ok(),
                         _ => None,
                     })
                     .filter(|v| !v.is_empty())
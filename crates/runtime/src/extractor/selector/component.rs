@@ -2,16 +2,53 @@
 //!
 //! 处理 `use_component` 步骤，引用预定义的可复用组件。
 //!
-//! 组件执行需要在运行时解析组件定义并执行其提取逻辑。
-//! 当前实现为占位符，完整实现需要访问全局组件注册表。
+//! 组件按名称从规则的 `components` 中查找，以当前输入执行组件的提取逻辑。
+//! 调用参数 `args` 覆盖组件 `inputs` 中的默认值后作为流程变量传入组件，
+//! 缺少 `required` 中的参数时报错，未声明的参数仅记录警告。
+//! 组件内部可以再引用其他组件，嵌套深度受 `limits.max_depth` 限制，
+//! 防止组件互相引用导致无限递归。
 
 use crate::{
     Result,
-    context::{FlowContext, RuntimeContext},
-    extractor::value::{ExtractValueData, SharedValue},
+    context::{FlowContext, LimitsExt, RuntimeContext},
+    error::RuntimeError,
+    extractor::{
+        ExtractEngine,
+        value::{ExtractValueData, SharedValue},
+    },
+    template::TemplateExt,
 };
-use crawler_schema::flow::ComponentRef;
-use std::sync::Arc;
+use crawler_schema::{
+    flow::{ComponentDefinition, ComponentRef},
+    template::Template,
+};
+use serde_json::{Map, Value};
+use std::cell::Cell;
+
+thread_local! {
+    /// 当前线程上组件调用的嵌套深度
+    static DEPTH: Cell<u32> = const { Cell::new(0) };
+}
+
+/// 组件嵌套深度守卫，离开作用域时恢复深度
+struct DepthGuard;
+
+impl DepthGuard {
+    /// 进入一层组件调用，返回进入后的深度
+    fn enter() -> (Self, u32) {
+        let depth = DEPTH.with(|d| {
+            d.set(d.get() + 1);
+            d.get()
+        });
+        (Self, depth)
+    }
+}
+
+impl Drop for DepthGuard {
+    fn drop(&mut self) {
+        DEPTH.with(|d| d.set(d.get().saturating_sub(1)));
+    }
+}
 
 /// 组件引用执行器
 pub struct ComponentExecutor;
@@ -25,21 +62,87 @@ impl ComponentExecutor {
         }
     }
 
+    /// 合并组件参数
+    ///
+    /// 以调用参数覆盖默认输入，字符串参数按模板渲染；
+    /// 缺少必填参数时返回错误，未声明的参数记录警告后仍传入组件
+    fn resolve_params(
+        name: &str,
+        component: &ComponentDefinition,
+        component_ref: &ComponentRef,
+        flow_context: &FlowContext,
+    ) -> Result<Map<String, Value>> {
+        let mut params: Map<String, Value> = component
+            .inputs
+            .iter()
+            .flatten()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        let required = component.required.as_deref().unwrap_or_default();
+
+        if let ComponentRef::WithArgs {
+            args: Some(args), ..
+        } = component_ref
+        {
+            for (key, value) in args {
+                if !params.contains_key(key) && !required.contains(key) {
+                    tracing::warn!("组件 '{}' 未声明参数 '{}'", name, key);
+                }
+                let value = match value {
+                    Value::String(s) => {
+                        Value::String(Template::new(s.clone()).render(flow_context)?)
+                    }
+                    other => other.clone(),
+                };
+                params.insert(key.clone(), value);
+            }
+        }
+
+        if let Some(missing) = required.iter().find(|key| !params.contains_key(*key)) {
+            return Err(RuntimeError::MissingComponentArg {
+                component: name.to_string(),
+                arg: missing.clone(),
+            });
+        }
+        Ok(params)
+    }
+
     /// 执行组件引用
+    ///
+    /// 参数作为流程变量在组件内部可见，不影响调用方的流程上下文
     pub fn execute(
         component_ref: &ComponentRef,
         input: &ExtractValueData,
-        _runtime_context: &RuntimeContext,
-        _flow_context: &FlowContext,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
     ) -> Result<SharedValue> {
-        // TODO: 完整实现需要：
-        // 1. 从上下文获取全局组件注册表
-        // 2. 根据名称查找组件定义
-        // 3. 合并参数（组件默认 inputs + 调用时的 args）
-        // 4. 执行组件的 extractor 步骤
-        //
-        // 当前返回输入值作为占位
-        let _ = Self::component_name(component_ref); // 避免 dead_code 警告
-        Ok(Arc::new(input.clone()))
+        let name = Self::component_name(component_ref);
+        let component = runtime_context
+            .rule()
+            .components
+            .as_ref()
+            .and_then(|components| components.get(name))
+            .ok_or_else(|| RuntimeError::UndefinedComponent {
+                component: name.to_string(),
+            })?;
+
+        let (_guard, depth) = DepthGuard::enter();
+        runtime_context.limits().check_depth(depth)?;
+
+        let params = Self::resolve_params(name, component, component_ref, flow_context)?;
+        if params.is_empty() {
+            return ExtractEngine::extract_field(
+                &component.extractor,
+                input,
+                runtime_context,
+                flow_context,
+            );
+        }
+
+        let mut scoped = flow_context.clone();
+        for (key, value) in params {
+            scoped.set(key, value);
+        }
+        ExtractEngine::extract_field(&component.extractor, input, runtime_context, &scoped)
     }
 }
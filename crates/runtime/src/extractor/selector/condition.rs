@@ -6,12 +6,13 @@ use crate::{
     Result,
     context::{FlowContext, RuntimeContext},
     extractor::{
-        StepExecutorFactory,
+        ExtractEngine,
+        engine::ChainOutcome,
         value::{ExtractValueData, SharedValue},
     },
 };
 use crawler_schema::extract::{ConditionStep, ExtractStep};
-use std::sync::Arc;
+use std::{borrow::Cow, sync::Arc};
 
 /// 条件执行器
 pub struct ConditionExecutor;
@@ -24,32 +25,36 @@ impl ConditionExecutor {
         runtime_context: &RuntimeContext,
         flow_context: &FlowContext,
     ) -> Result<SharedValue> {
-        if Self::evaluate_condition(&condition.when, input, runtime_context, flow_context) {
+        Self::execute_branch(
+            condition,
+            input,
+            runtime_context,
+            &mut Cow::Borrowed(flow_context),
+        )
+        .map(ChainOutcome::into_value)
+    }
+
+    /// 执行条件分支，保留分支内 `return` 的提前结束信号
+    ///
+    /// 分支在外层步骤链的作用域 `scope` 中执行，分支内设置的变量对外层可见
+    pub(crate) fn execute_branch(
+        condition: &ConditionStep,
+        input: &ExtractValueData,
+        runtime_context: &RuntimeContext,
+        scope: &mut Cow<'_, FlowContext>,
+    ) -> Result<ChainOutcome> {
+        let branch = if Self::evaluate_condition(&condition.when, input, runtime_context, scope) {
             // 条件为真，执行 then 步骤
-            Self::execute_steps(&condition.then, input, runtime_context, flow_context)
+            &condition.then
         } else if let Some(otherwise) = &condition.otherwise {
             // 条件为假，执行 otherwise 步骤
-            Self::execute_steps(otherwise, input, runtime_context, flow_context)
+            otherwise
         } else {
             // 没有 otherwise，返回原输入
-            Ok(Arc::new(input.clone()))
-        }
-    }
-
-    /// 执行一系列步骤
-    fn execute_steps(
-        steps: &[ExtractStep],
-        input: &ExtractValueData,
-        runtime_context: &RuntimeContext,
-        flow_context: &FlowContext,
-    ) -> Result<SharedValue> {
-        let mut current = Arc::new(input.clone());
-
-        for step in steps {
-            current = StepExecutorFactory::execute(step, &current, runtime_context, flow_context)?;
-        }
+            return Ok(ChainOutcome::Completed(Arc::new(input.clone())));
+        };
 
-        Ok(current)
+        ExtractEngine::execute_chain_in(branch, input, runtime_context, scope, None, None)
     }
 
     /// 判断条件是否为真
@@ -61,7 +66,7 @@ impl ConditionExecutor {
         runtime_context: &RuntimeContext,
         flow_context: &FlowContext,
     ) -> bool {
-        match Self::execute_steps(steps, input, runtime_context, flow_context) {
+        match ExtractEngine::execute_steps(steps, input, runtime_context, flow_context) {
             Ok(result) => result.is_truthy(),
             Err(_) => false,
         }
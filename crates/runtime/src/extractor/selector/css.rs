@@ -7,12 +7,152 @@ use crate::{
     extractor::value::{ExtractValueData, SharedValue},
 };
 use crawler_schema::extract::SelectorStep;
-use scraper::{Html, Selector};
-use std::sync::Arc;
+use regex::Regex;
+use scraper::{ElementRef, Html, Selector};
+use std::sync::{Arc, OnceLock};
 
 /// CSS 选择器执行器
 pub struct CssSelectorExecutor;
 
+/// 降级为运行时筛选的伪类
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PseudoKind {
+    /// `:has(...)`：存在匹配的后代元素
+    Has,
+    /// `:not(...)`：自身不匹配
+    Not,
+}
+
+/// 编译后的选择器
+///
+/// 逗号分隔的选择器组逐组编译：含 `:contains` 的组各自携带筛选条件，
+/// 匹配结果按文档顺序合并
+struct CompiledSelector {
+    /// 各选择器组
+    groups: Vec<CompiledGroup>,
+}
+
+/// 编译后的单个选择器组
+///
+/// scraper 无法处理的部分（`:contains` 及包含它的 `:has`/`:not`）
+/// 从选择器中移出，对匹配元素逐个筛选
+struct CompiledGroup {
+    /// 交给 scraper 的选择器
+    selector: Selector,
+    /// 文本需包含的内容
+    contains: Vec<String>,
+    /// 运行时筛选的伪类
+    pseudos: Vec<(PseudoKind, CompiledSelector)>,
+}
+
+impl CompiledSelector {
+    /// 编译选择器
+    fn compile(selector: &str) -> Result<Self> {
+        // 不含 `:contains` 时整体交给 scraper
+        if !selector.contains(":contains(") {
+            return Ok(Self {
+                groups: vec![CompiledGroup {
+                    selector: CssSelectorExecutor::parse_selector(selector, selector)?,
+                    contains: Vec::new(),
+                    pseudos: Vec::new(),
+                }],
+            });
+        }
+
+        let groups = CssSelectorExecutor::split_groups(selector)
+            .into_iter()
+            .map(|group| CompiledGroup::compile(group.trim(), selector))
+            .collect::<Result<_>>()?;
+        Ok(Self { groups })
+    }
+
+    /// 在文档中按文档顺序选择匹配的元素
+    fn select<'a>(&'a self, document: &'a Html) -> Box<dyn Iterator<Item = ElementRef<'a>> + 'a> {
+        match self.groups.as_slice() {
+            [group] => Box::new(
+                document
+                    .select(&group.selector)
+                    .filter(|el| group.accepts(el)),
+            ),
+            _ => Box::new(
+                document
+                    .tree
+                    .nodes()
+                    .filter_map(ElementRef::wrap)
+                    .filter(|el| self.matches(el)),
+            ),
+        }
+    }
+
+    /// 元素是否有匹配的后代元素
+    fn matches_descendant(&self, el: &ElementRef) -> bool {
+        match self.groups.as_slice() {
+            [group] => el
+                .select(&group.selector)
+                .any(|child| group.accepts(&child)),
+            _ => el
+                .descendants()
+                .skip(1)
+                .filter_map(ElementRef::wrap)
+                .any(|child| self.matches(&child)),
+        }
+    }
+
+    /// 元素自身是否匹配
+    fn matches(&self, el: &ElementRef) -> bool {
+        self.groups
+            .iter()
+            .any(|group| group.selector.matches(el) && group.accepts(el))
+    }
+}
+
+impl CompiledGroup {
+    /// 编译单个选择器组，`original` 为完整选择器，用于错误信息
+    fn compile(selector: &str, original: &str) -> Result<Self> {
+        let (remaining, pseudos) = CssSelectorExecutor::split_functional(selector)?;
+        let (mut css_str, contains) = CssSelectorExecutor::split_contains(&remaining)?;
+        // `.list :has(...)` 去掉伪类后需补全为 `.list *`
+        if css_str.trim().is_empty()
+            || css_str.ends_with(|c: char| c.is_whitespace() || "+>~".contains(c))
+        {
+            css_str.push('*');
+        }
+
+        let pseudos = pseudos
+            .into_iter()
+            .map(|(kind, inner)| {
+                if kind == PseudoKind::Has && inner.starts_with(['>', '+', '~']) {
+                    return Err(RuntimeError::Extraction(format!(
+                        "':has({})' with ':contains' does not support relative combinators: '{}'",
+                        inner, original
+                    )));
+                }
+                Ok((kind, CompiledSelector::compile(&inner)?))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            selector: CssSelectorExecutor::parse_selector(&css_str, original)?,
+            contains,
+            pseudos,
+        })
+    }
+
+    /// 元素是否通过 scraper 之外的筛选条件
+    fn accepts(&self, el: &ElementRef) -> bool {
+        if !self.contains.is_empty() {
+            let text: String = el.text().collect();
+            if !self.contains.iter().all(|c| text.contains(c.as_str())) {
+                return false;
+            }
+        }
+        self.pseudos.iter().all(|(kind, inner)| match kind {
+            PseudoKind::Has => inner.matches_descendant(el),
+            PseudoKind::Not => !inner.matches(el),
+        })
+    }
+}
+
 impl CssSelectorExecutor {
     /// 执行 CSS 选择器
     pub fn execute(
@@ -23,15 +163,17 @@ impl CssSelectorExecutor {
     ) -> Result<SharedValue> {
         // 获取 HTML 字符串
         let html = match input {
-            ExtractValueData::String(s) | ExtractValueData::Html(s) => s.as_ref(),
+            ExtractValueData::String(s) | ExtractValueData::Html(s) | ExtractValueData::Xml(s) => {
+                s.as_ref()
+            }
             ExtractValueData::Array(arr) => {
                 // 如果是数组，对每个元素应用选择器
                 let results: Vec<SharedValue> = arr
                     .iter()
                     .filter_map(|item| match item.as_ref() {
-                        ExtractValueData::Html(h) | ExtractValueData::String(h) => {
-                            Self::execute_on_html(h, selector).ok()
-                        }
+                        ExtractValueData::Html(h)
+                        | ExtractValueData::String(h)
+                        | ExtractValueData::Xml(h) => Self::execute_on_html(h, selector).ok(),
                         _ => None,
                     })
                     .flatten()
@@ -64,11 +206,8 @@ impl CssSelectorExecutor {
             SelectorStep::WithOptions { expr, all } => (expr.as_str(), *all),
         };
 
-        let css_selector = Selector::parse(selector_str).map_err(|e| {
-            RuntimeError::Extraction(format!("Invalid CSS selector '{}': {:?}", selector_str, e))
-        })?;
-
-        let elements = document.select(&css_selector);
+        let compiled = CompiledSelector::compile(selector_str)?;
+        let elements = compiled.select(&document);
 
         let results: Vec<SharedValue> = if select_all {
             elements
@@ -93,6 +232,173 @@ impl CssSelectorExecutor {
         Ok(results)
     }
 
+    /// 拆出参数中含 `:contains` 的 `:has(...)` / `:not(...)`
+    ///
+    /// scraper 本身支持 `:has`、`:not`，但参数中的非标准 `:contains` 无法交给 scraper，
+    /// 此时将整个伪类移出选择器，降级为对匹配元素的运行时筛选；
+    /// 与 `:contains` 一样只允许出现在最后一个复合选择器上
+    fn split_functional(selector: &str) -> Result<(String, Vec<(PseudoKind, String)>)> {
+        let mut remaining = selector.to_string();
+        let mut pseudos = Vec::new();
+        let mut search_from = 0;
+
+        while let Some((start, kind)) = ["has", "not"]
+            .iter()
+            .filter_map(|name| {
+                remaining[search_from..]
+                    .find(&format!(":{}(", name))
+                    .map(|pos| (search_from + pos, *name))
+            })
+            .min()
+        {
+            let open = start + kind.len() + 1;
+            let close = Self::matching_paren(&remaining, open).ok_or_else(|| {
+                RuntimeError::Extraction(format!(
+                    "Unbalanced parentheses in selector '{}'",
+                    selector
+                ))
+            })?;
+            let inner = &remaining[open + 1..close];
+            if !inner.contains(":contains(") {
+                search_from = close + 1;
+                continue;
+            }
+
+            let rest = &remaining[close + 1..];
+            if rest.contains(|c: char| c.is_whitespace() || matches!(c, '>' | '+' | '~' | ',')) {
+                return Err(RuntimeError::Extraction(format!(
+                    "':{}(...)' containing ':contains' is only supported on the last compound selector: '{}'",
+                    kind, selector
+                )));
+            }
+            let kind = if kind == "has" {
+                PseudoKind::Has
+            } else {
+                PseudoKind::Not
+            };
+            pseudos.push((kind, inner.trim().to_string()));
+            remaining.replace_range(start..=close, "");
+            search_from = start;
+        }
+
+        Ok((remaining, pseudos))
+    }
+
+    /// 查找与 `open` 处左括号匹配的右括号位置
+    fn matching_paren(s: &str, open: usize) -> Option<usize> {
+        let mut depth = 0;
+        for (i, c) in s[open..].char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(open + i);
+                    }
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// 按顶层逗号拆分选择器组，忽略括号与引号内的逗号
+    fn split_groups(selector: &str) -> Vec<&str> {
+        let mut groups = Vec::new();
+        let (mut depth, mut quote, mut start) = (0, None, 0);
+        for (i, c) in selector.char_indices() {
+            match (quote, c) {
+                (Some(q), _) if c == q => quote = None,
+                (Some(_), _) => {}
+                (None, '\'' | '"') => quote = Some(c),
+                (None, '(') => depth += 1,
+                (None, ')') => depth -= 1,
+                (None, ',') if depth == 0 => {
+                    groups.push(&selector[start..i]);
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        groups.push(&selector[start..]);
+        groups
+    }
+
+    /// 解析选择器，失败时针对常见的不支持写法给出建议
+    fn parse_selector(css: &str, original: &str) -> Result<Selector> {
+        static JQUERY_RE: OnceLock<Regex> = OnceLock::new();
+        let re = JQUERY_RE.get_or_init(|| {
+            Regex::new(r":(eq|gt|lt|first|last|even|odd|visible|hidden|header)\b").unwrap()
+        });
+
+        Selector::parse(css).map_err(|e| {
+            let hint = match re.captures(css).and_then(|caps| caps.get(1)) {
+                Some(m) if matches!(m.as_str(), "eq" | "gt" | "lt" | "first" | "last") => format!(
+                    "; jQuery pseudo-class ':{}' is not supported, remove it and use an index step instead (e.g. {{ index = 0 }} or {{ index = \"1:\" }})",
+                    m.as_str()
+                ),
+                Some(m) if matches!(m.as_str(), "even" | "odd") => format!(
+                    "; jQuery pseudo-class ':{}' is not supported, use ':nth-child({})' instead",
+                    m.as_str(),
+                    // jQuery 的 :even/:odd 按从 0 开始的下标计数，与 nth-child 相反
+                    if m.as_str() == "even" { "odd" } else { "even" }
+                ),
+                Some(m) => format!("; jQuery pseudo-class ':{}' is not supported", m.as_str()),
+                None => String::new(),
+            };
+            RuntimeError::Extraction(format!(
+                "Invalid CSS selector '{}': {:?}{}",
+                original, e, hint
+            ))
+        })
+    }
+
+    /// 拆出 `:contains(text)` 伪类
+    ///
+    /// scraper 不支持非标准的 `:contains`，这里将其从选择器中移除，
+    /// 改为对最终匹配元素按文本过滤，因此只允许出现在最后一个复合选择器上
+    fn split_contains(selector: &str) -> Result<(String, Vec<String>)> {
+        static CONTAINS_RE: OnceLock<Regex> = OnceLock::new();
+        let re = CONTAINS_RE.get_or_init(|| {
+            Regex::new(r#":contains\(\s*(?:'([^']*)'|"([^"]*)"|([^)]*?))\s*\)"#).unwrap()
+        });
+
+        let mut texts = Vec::new();
+        let mut first_start = None;
+        for caps in re.captures_iter(selector) {
+            let text = caps
+                .get(1)
+                .or_else(|| caps.get(2))
+                .or_else(|| caps.get(3))
+                .map(|m| m.as_str().to_string())
+                .unwrap_or_default();
+            texts.push(text);
+            first_start.get_or_insert(caps.get(0).map(|m| m.start()).unwrap_or(0));
+        }
+
+        let Some(first_start) = first_start else {
+            return Ok((selector.to_string(), texts));
+        };
+
+        // 第一个 `:contains` 之后（去掉各 `:contains` 本身）不能再有组合符
+        let rest = re.replace_all(&selector[first_start..], "");
+        if rest.contains(|c: char| c.is_whitespace() || matches!(c, '>' | '+' | '~' | ',')) {
+            return Err(RuntimeError::Extraction(format!(
+                "':contains' is only supported on the last compound selector: '{}'",
+                selector
+            )));
+        }
+
+        // `.list :contains(x)` 去掉伪类后需补全为 `.list *`
+        let mut stripped = re.replace_all(selector, "").trim_start().to_string();
+        if stripped.is_empty()
+            || stripped.ends_with(|c: char| c.is_whitespace() || "+>~".contains(c))
+        {
+            stripped.push('*');
+        }
+        Ok((stripped, texts))
+    }
+
     /// 是否选择所有匹配
     fn is_select_all(selector: &SelectorStep) -> bool {
         match selector {
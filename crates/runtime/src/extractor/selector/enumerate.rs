@@ -0,0 +1,40 @@
+//! # 枚举执行器
+
+use crate::{
+    Result,
+    error::RuntimeError,
+    extractor::value::{ExtractValueData, SharedValue},
+};
+use serde_json::json;
+use std::sync::Arc;
+
+/// 枚举执行器
+pub struct EnumerateExecutor;
+
+impl EnumerateExecutor {
+    /// 将数组元素转换为 `{ index, value }` 对象，索引从 0 开始
+    pub fn execute(input: &ExtractValueData) -> Result<SharedValue> {
+        let arr = match input {
+            ExtractValueData::Array(arr) => arr.to_vec(),
+            ExtractValueData::Json(v) if v.is_array() => input.as_array().unwrap_or_default(),
+            ExtractValueData::Null => Vec::new(),
+            _ => {
+                return Err(RuntimeError::Extraction(
+                    "enumerate 步骤需要数组输入".to_string(),
+                ));
+            }
+        };
+
+        let items = arr
+            .iter()
+            .enumerate()
+            .map(|(index, value)| {
+                Arc::new(ExtractValueData::Json(Arc::new(json!({
+                    "index": index,
+                    "value": value.to_owned_json(),
+                }))))
+            })
+            .collect();
+        Ok(Arc::new(ExtractValueData::Array(Arc::new(items))))
+    }
+}
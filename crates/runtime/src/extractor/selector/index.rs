@@ -14,51 +14,119 @@ pub struct IndexExecutor;
 
 impl IndexExecutor {
     /// 执行索引/切片
+    ///
+    /// 语义与 Python 一致：负索引从末尾计数，切片支持省略端点与步长，
+    /// 如 `-1`、`1:`、`:-1`、`::2`、`::-1`
     pub fn execute(
         index: &IndexStep,
         input: &ExtractValueData,
         _runtime_context: &RuntimeContext,
         _flow_context: &FlowContext,
     ) -> Result<SharedValue> {
-        match input {
-            ExtractValueData::Array(arr) => match index {
-                IndexStep::Single(idx) => {
-                    let index_pos = if *idx < 0 {
-                        (arr.len() as i32 + idx) as usize
-                    } else {
-                        *idx as usize
-                    };
+        let arr = match input {
+            ExtractValueData::Array(arr) => arr.to_vec(),
+            ExtractValueData::Json(v) if v.is_array() => input.as_array().unwrap_or_default(),
+            other => {
+                return Err(RuntimeError::Extraction(format!(
+                    "index 步骤需要数组输入，实际为 {}",
+                    type_name(other)
+                )));
+            }
+        };
 
-                    if index_pos < arr.len() {
-                        Ok(arr[index_pos].clone())
-                    } else {
-                        Err(RuntimeError::Extraction("Index out of bounds".to_string()))
-                    }
-                }
-                IndexStep::Slice(slice_str) => {
-                    // 解析切片：start:end 或 start:end:step
-                    let parts: Vec<&str> = slice_str.split(':').collect();
-                    let start = parts
-                        .first()
-                        .and_then(|s| s.parse::<usize>().ok())
-                        .unwrap_or(0);
-                    let end = parts
-                        .get(1)
-                        .and_then(|s| s.parse::<usize>().ok())
-                        .unwrap_or(arr.len());
-
-                    let sliced: Vec<SharedValue> = arr
-                        .iter()
-                        .skip(start)
-                        .take(end.saturating_sub(start))
-                        .cloned()
+        match index {
+            IndexStep::Single(idx) => Self::single(&arr, *idx as i64),
+            IndexStep::Slice(expr) => match expr.trim().parse::<i64>() {
+                // 不含冒号的字符串按单个索引处理
+                Ok(idx) => Self::single(&arr, idx),
+                Err(_) => {
+                    let (start, end, step) = parse_slice(expr)?;
+                    let sliced = slice_indices(arr.len(), start, end, step)
+                        .map(|i| arr[i].clone())
                         .collect();
                     Ok(Arc::new(ExtractValueData::Array(Arc::new(sliced))))
                 }
             },
-            _ => Err(RuntimeError::Extraction(
-                "Index operation requires array input".to_string(),
-            )),
         }
     }
+
+    /// 取单个元素
+    fn single(arr: &[SharedValue], idx: i64) -> Result<SharedValue> {
+        let len = arr.len() as i64;
+        let pos = if idx < 0 { len + idx } else { idx };
+        if (0..len).contains(&pos) {
+            Ok(arr[pos as usize].clone())
+        } else {
+            Err(RuntimeError::Extraction(format!(
+                "索引 {} 越界（数组长度 {}）",
+                idx, len
+            )))
+        }
+    }
+}
+
+/// 值类型名称（用于错误信息）
+fn type_name(value: &ExtractValueData) -> &'static str {
+    match value {
+        ExtractValueData::String(_) => "string",
+        ExtractValueData::Html(_) => "html",
+        ExtractValueData::Xml(_) => "xml",
+        ExtractValueData::Json(_) => "json",
+        ExtractValueData::Array(_) => "array",
+        ExtractValueData::Null => "null",
+    }
+}
+
+/// 解析切片表达式 `start:end` 或 `start:end:step`，省略的部分为 None
+fn parse_slice(expr: &str) -> Result<(Option<i64>, Option<i64>, i64)> {
+    let invalid = |reason: &str| RuntimeError::InvalidConfigValue {
+        field: "index".to_string(),
+        reason: format!("切片 '{}' {}", expr, reason),
+    };
+
+    let parts: Vec<&str> = expr.split(':').map(str::trim).collect();
+    if parts.len() > 3 {
+        return Err(invalid("最多包含 start:end:step 三部分"));
+    }
+    let parse = |part: Option<&&str>| -> Result<Option<i64>> {
+        match part {
+            None | Some(&"") => Ok(None),
+            Some(s) => s.parse().map(Some).map_err(|_| invalid("包含非整数部分")),
+        }
+    };
+
+    let start = parse(parts.first())?;
+    let end = parse(parts.get(1))?;
+    let step = parse(parts.get(2))?.unwrap_or(1);
+    if step == 0 {
+        return Err(invalid("步长不能为 0"));
+    }
+    Ok((start, end, step))
+}
+
+/// 按 Python 切片规则计算下标序列
+fn slice_indices(
+    len: usize,
+    start: Option<i64>,
+    end: Option<i64>,
+    step: i64,
+) -> impl Iterator<Item = usize> {
+    let len = len as i64;
+    let normalize = |i: i64| if i < 0 { i + len } else { i };
+
+    let (start, end) = if step > 0 {
+        (
+            start.map(normalize).unwrap_or(0).clamp(0, len),
+            end.map(normalize).unwrap_or(len).clamp(0, len),
+        )
+    } else {
+        (
+            start.map(normalize).unwrap_or(len - 1).clamp(-1, len - 1),
+            end.map(normalize).unwrap_or(-1).clamp(-1, len - 1),
+        )
+    };
+
+    std::iter::successors(Some(start), move |i| Some(i + step))
+        .take_while(move |i| if step > 0 { *i < end } else { *i > end })
+        .map(|i| i as usize)
 }
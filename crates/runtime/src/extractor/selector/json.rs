@@ -8,14 +8,21 @@ use crate::{
 };
 use crawler_schema::extract::SelectorStep;
 use jsonpath_rust::JsonPath;
+use regex::Regex;
 use serde_json::Value;
-use std::sync::Arc;
+use std::{
+    borrow::Cow,
+    sync::{Arc, OnceLock},
+};
 
 /// JSON 选择器执行器
 pub struct JsonSelectorExecutor;
 
 impl JsonSelectorExecutor {
     /// 执行 JSON 选择器
+    ///
+    /// 输入为字符串或 HTML（如直接传入的 API 响应体）时按 JSON 解析后查询；
+    /// 查询结果保留原始类型，对象与数组不会被字符串化
     pub fn execute(
         selector: &SelectorStep,
         input: &ExtractValueData,
@@ -25,7 +32,7 @@ impl JsonSelectorExecutor {
         // 获取 JSON 值
         let json: Value = match input {
             ExtractValueData::Json(v) => (**v).clone(),
-            ExtractValueData::String(s) => serde_json::from_str(s)
+            ExtractValueData::String(s) | ExtractValueData::Html(s) => serde_json::from_str(s)
                 .map_err(|e| RuntimeError::Extraction(format!("Failed to parse JSON: {}", e)))?,
             ExtractValueData::Array(arr) => {
                 // 如果是数组，对每个元素应用选择器
@@ -50,7 +57,7 @@ impl JsonSelectorExecutor {
         };
 
         // 使用 JsonPath trait 的 query 方法
-        let results = json.query(jsonpath_str).map_err(|e| {
+        let results = json.query(&expand_contains(jsonpath_str)).map_err(|e| {
             RuntimeError::Extraction(format!("Invalid JSONPath '{}': {}", jsonpath_str, e))
         })?;
 
@@ -68,3 +75,46 @@ impl JsonSelectorExecutor {
         }
     }
 }
+
+/// 将过滤表达式中的 `contains` 谓词改写为 jsonpath_rust 支持的 RFC 9535 表达式
+///
+/// jsonpath_rust 已支持 `==`、`!=`、`>`、`<` 等比较，但不支持 `contains`：
+/// - `@.title contains '完结'` 改写为 `(search(@.title, '完结') || @.title[?@ == '完结'])`，
+///   同时匹配字符串子串与数组元素
+/// - 数字等非字符串字面量只匹配数组元素：`@.ids contains 3` 改写为 `@.ids[?@ == 3]`
+pub(crate) fn expand_contains(path: &str) -> Cow<'_, str> {
+    static CONTAINS_RE: OnceLock<Regex> = OnceLock::new();
+    let re = CONTAINS_RE.get_or_init(|| {
+        Regex::new(
+            r#"(@(?:\.[\w$]+|\[[^\]]*\])*)\s+contains\s+('(?:[^'\\]|\\.)*'|"(?:[^"\\]|\\.)*"|-?\d+(?:\.\d+)?|true|false|null)"#,
+        )
+        .unwrap()
+    });
+
+    re.replace_all(path, |caps: &regex::Captures| {
+        let (target, literal) = (&caps[1], &caps[2]);
+        let member = format!("{}[?@ == {}]", target, literal);
+        if !literal.starts_with(['\'', '"']) {
+            return member;
+        }
+        let text = unescape(&literal[1..literal.len() - 1]);
+        // search 的第二个参数是正则，需转义后再作为单引号字符串写入
+        let pattern = regex::escape(&text)
+            .replace('\\', "\\\\")
+            .replace('\'', "\\'");
+        format!("(search({}, '{}') || {})", target, pattern, member)
+    })
+}
+
+/// 还原字符串字面量中的转义
+fn unescape(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => result.extend(chars.next()),
+            c => result.push(c),
+        }
+    }
+    result
+}
@@ -0,0 +1,75 @@
+//! # 日志执行器
+//!
+//! 按级别输出渲染后的消息与上下文字段，原样返回输入；
+//! 模板渲染失败时输出模板原文，不会使步骤链失败
+
+use crate::{
+    Result,
+    context::{FlowContext, RuntimeContext},
+    extractor::value::{ExtractValueData, SharedValue},
+    template::{TemplateExt, referenced_variables},
+};
+use crawler_schema::{
+    extract::{LogLevel, LogStep},
+    template::Template,
+};
+use serde_json::{Map, Value};
+use std::{borrow::Cow, sync::Arc};
+
+/// 模板中表示当前值的变量名
+const VALUE_VAR: &str = "value";
+
+/// 日志执行器
+pub struct LogExecutor;
+
+impl LogExecutor {
+    /// 输出日志，原样返回输入
+    pub fn execute(
+        log: &LogStep,
+        input: &ExtractValueData,
+        _runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> Result<SharedValue> {
+        // 仅在模板引用当前值时才复制上下文
+        let references_value = std::iter::once(&log.message)
+            .chain(log.context.iter().flat_map(|c| c.values()))
+            .any(|t| {
+                referenced_variables(t.as_str())
+                    .iter()
+                    .any(|v| v == VALUE_VAR)
+            });
+        let context = if references_value {
+            let mut context = flow_context.clone();
+            context.set(
+                VALUE_VAR,
+                serde_json::to_value(input).unwrap_or(Value::Null),
+            );
+            Cow::Owned(context)
+        } else {
+            Cow::Borrowed(flow_context)
+        };
+
+        let message = Self::render(&log.message, &context);
+        let mut fields = Map::new();
+        for (key, template) in log.context.iter().flatten() {
+            fields.insert(key.clone(), Value::String(Self::render(template, &context)));
+        }
+        let fields = Value::Object(fields);
+
+        match log.level {
+            LogLevel::Debug => tracing::debug!(context = %fields, "{}", message),
+            LogLevel::Info => tracing::info!(context = %fields, "{}", message),
+            LogLevel::Warn => tracing::warn!(context = %fields, "{}", message),
+            LogLevel::Error => tracing::error!(context = %fields, "{}", message),
+        }
+        Ok(Arc::new(input.clone()))
+    }
+
+    /// 渲染模板，失败时记录警告并返回模板原文，日志步骤不会中断步骤链
+    fn render(template: &Template, context: &FlowContext) -> String {
+        template.render(context).unwrap_or_else(|e| {
+            tracing::warn!("日志模板渲染失败: {}", e);
+            template.as_str().to_string()
+        })
+    }
+}
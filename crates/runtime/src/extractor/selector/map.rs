@@ -1,40 +1,69 @@
 //! # 映射执行器
 //!
-//! 对数组每个元素应用步骤
+//! 对数组每个元素应用步骤，可按 `concurrency` 并发处理
 
 use crate::{
     Result,
     context::{FlowContext, RuntimeContext},
     error::RuntimeError,
     extractor::{
-        StepExecutorFactory,
+        ExtractEngine,
         value::{ExtractValueData, SharedValue},
     },
 };
-use crawler_schema::extract::ExtractStep;
-use std::sync::Arc;
+use crawler_schema::extract::{ExtractStep, MapStep};
+use std::sync::{
+    Arc,
+    Mutex,
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+};
 
 /// 映射执行器
 pub struct MapExecutor;
 
 impl MapExecutor {
     /// 执行映射
+    ///
+    /// 处理失败的元素会被丢弃，其余结果保持输入顺序；
+    /// 中断类错误（超时、超出限制等）不丢弃，直接向上返回
     pub fn execute(
-        steps: &[ExtractStep],
+        map: &MapStep,
         input: &ExtractValueData,
         runtime_context: &RuntimeContext,
         flow_context: &FlowContext,
     ) -> Result<SharedValue> {
         match input {
             ExtractValueData::Array(arr) => {
-                let results: Vec<SharedValue> = arr
-                    .iter()
-                    .filter_map(|item| {
-                        Self::execute_steps(steps, item, runtime_context, flow_context).ok()
-                    })
-                    .collect();
+                let steps = map.steps();
+                let concurrency = map.concurrency().min(arr.len());
+                let results = if concurrency > 1 {
+                    Self::execute_concurrent(
+                        steps,
+                        arr,
+                        concurrency,
+                        runtime_context,
+                        flow_context,
+                    )?
+                } else {
+                    let mut results = Vec::with_capacity(arr.len());
+                    for item in arr.iter() {
+                        match ExtractEngine::execute_steps(
+                            steps,
+                            item,
+                            runtime_context,
+                            flow_context,
+                        ) {
+                            Ok(value) => results.push(Some(value)),
+                            Err(e) if e.is_interruption() => return Err(e),
+                            Err(_) => results.push(None),
+                        }
+                    }
+                    results
+                };
 
-                Ok(Arc::new(ExtractValueData::Array(Arc::new(results))))
+                Ok(Arc::new(ExtractValueData::Array(Arc::new(
+                    results.into_iter().flatten().collect(),
+                ))))
             }
             _ => {
                 // 非数组输入，直接应用步骤
@@ -45,19 +74,67 @@ impl MapExecutor {
         }
     }
 
-    /// 对单个值执行所有步骤
-    fn execute_steps(
+    /// 以有界并发处理元素
+    ///
+    /// 启动 `concurrency` 个工作线程依次领取元素下标，结果按下标写回以保持顺序。
+    /// 任一元素遇到中断类错误后不再领取新元素，等待进行中的元素结束后返回该错误
+    fn execute_concurrent(
         steps: &[ExtractStep],
-        input: &ExtractValueData,
+        items: &[SharedValue],
+        concurrency: usize,
         runtime_context: &RuntimeContext,
         flow_context: &FlowContext,
-    ) -> Result<SharedValue> {
-        let mut current = Arc::new(input.clone());
+    ) -> Result<Vec<Option<SharedValue>>> {
+        let next = AtomicUsize::new(0);
+        let interrupted: Mutex<Option<RuntimeError>> = Mutex::new(None);
+        let stop = AtomicBool::new(false);
+        let mut results = vec![None; items.len()];
 
-        for step in steps {
-            current = StepExecutorFactory::execute(step, &current, runtime_context, flow_context)?;
-        }
+        std::thread::scope(|scope| {
+            let workers: Vec<_> = (0..concurrency)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut done = Vec::new();
+                        while !stop.load(Ordering::Acquire) {
+                            let index = next.fetch_add(1, Ordering::Relaxed);
+                            let Some(item) = items.get(index) else {
+                                break;
+                            };
+                            let value = ExtractEngine::execute_steps(
+                                steps,
+                                item,
+                                runtime_context,
+                                flow_context,
+                            );
+                            match value {
+                                Ok(value) => done.push((index, Some(value))),
+                                Err(e) if e.is_interruption() => {
+                                    stop.store(true, Ordering::Release);
+                                    interrupted.lock().unwrap().get_or_insert(e);
+                                    break;
+                                }
+                                Err(_) => {}
+                            }
+                        }
+                        done
+                    })
+                })
+                .collect();
 
-        Ok(current)
+            for worker in workers {
+                // 工作线程 panic 时向上传播
+                let done = worker
+                    .join()
+                    .unwrap_or_else(|e| std::panic::resume_unwind(e));
+                for (index, value) in done {
+                    results[index] = value;
+                }
+            }
+        });
+
+        match interrupted.into_inner().unwrap() {
+            Some(e) => Err(e),
+            None => Ok(results),
+        }
     }
 }
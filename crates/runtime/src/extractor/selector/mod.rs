@@ -2,21 +2,32 @@
 //!
 //! 实现各种选择器：CSS, JSON, XPath, Regex
 
+pub mod assert;
 pub mod attr;
 pub mod component;
 pub mod condition;
 pub mod const_value;
 pub mod css;
+pub mod enumerate;
 pub mod index;
 pub mod json;
+pub mod log;
 pub mod map;
 pub mod noop;
 pub mod regex;
 pub mod set_var;
+pub mod template;
+pub mod try_catch;
+pub mod xpath;
 
+pub use assert::AssertExecutor;
 pub use component::ComponentExecutor;
 pub use condition::ConditionExecutor;
 pub use css::CssSelectorExecutor;
 pub use json::JsonSelectorExecutor;
+pub use log::LogExecutor;
 pub use map::MapExecutor;
 pub use regex::RegexSelectorExecutor;
+pub use template::TemplateExecutor;
+pub use try_catch::TryExecutor;
+pub use xpath::XPathExecutor;
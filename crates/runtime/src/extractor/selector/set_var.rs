@@ -1,7 +1,10 @@
 //! # 变量执行器
 //!
-//! 注意: SetVar 步骤目前仅返回输入值，变量设置逻辑需要在调用方处理
-//! 因为 RuntimeContext 和 FlowContext 的 set 方法需要可变引用
+//! 流程变量由步骤链在其作用域中写入，见 [`ExtractEngine::execute_chain_in`]；
+//! 此执行器只处理单独执行的步骤，仅返回输入值。
+//! 运行时变量需要 RuntimeContext 的可变引用，目前同样仅返回输入值
+//!
+//! [`ExtractEngine::execute_chain_in`]: crate::extractor::ExtractEngine
 
 use crawler_schema::extract::SetVarStep;
 use std::sync::Arc;
@@ -0,0 +1,47 @@
+//! # 字符串模板执行器
+//!
+//! 以流程上下文渲染模板，当前值以 `value` 变量提供
+
+use crate::{
+    Result,
+    context::{FlowContext, RuntimeContext},
+    extractor::value::{ExtractValueData, SharedValue},
+    template::{TemplateExt, referenced_variables},
+};
+use crawler_schema::template::Template;
+use serde_json::Value;
+use std::{borrow::Cow, sync::Arc};
+
+/// 模板中表示当前值的变量名
+const VALUE_VAR: &str = "value";
+
+/// 字符串模板执行器
+pub struct TemplateExecutor;
+
+impl TemplateExecutor {
+    /// 渲染模板，返回字符串
+    pub fn execute(
+        template: &Template,
+        input: &ExtractValueData,
+        _runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> Result<SharedValue> {
+        // 仅在模板引用当前值时才复制上下文
+        let references_value = referenced_variables(template.as_str())
+            .iter()
+            .any(|v| v == VALUE_VAR);
+        let context = if references_value {
+            let mut context = flow_context.clone();
+            context.set(
+                VALUE_VAR,
+                serde_json::to_value(input).unwrap_or(Value::Null),
+            );
+            Cow::Owned(context)
+        } else {
+            Cow::Borrowed(flow_context)
+        };
+
+        let rendered = template.render(&context)?;
+        Ok(Arc::new(ExtractValueData::String(Arc::from(rendered))))
+    }
+}
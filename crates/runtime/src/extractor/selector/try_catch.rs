@@ -0,0 +1,91 @@
+//! # 容错执行器
+//!
+//! `try` 步骤失败时转入 `catch` 步骤
+
+use crate::{
+    Result,
+    context::{FlowContext, RuntimeContext},
+    extractor::{
+        ExtractEngine,
+        engine::ChainOutcome,
+        value::{ExtractValueData, SharedValue},
+    },
+};
+use crawler_schema::extract::TryStep;
+use serde_json::Value;
+use std::{borrow::Cow, sync::Arc};
+
+/// `catch` 步骤中表示错误信息的变量名
+const ERROR_VAR: &str = "error";
+
+/// 容错执行器
+pub struct TryExecutor;
+
+impl TryExecutor {
+    /// 执行容错步骤
+    pub fn execute(
+        try_step: &TryStep,
+        input: &ExtractValueData,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> Result<SharedValue> {
+        Self::execute_branch(
+            try_step,
+            input,
+            runtime_context,
+            &mut Cow::Borrowed(flow_context),
+        )
+        .map(ChainOutcome::into_value)
+    }
+
+    /// 执行容错步骤，保留 `try`/`catch` 内 `return` 的提前结束信号
+    ///
+    /// `try` 块在外层步骤链的作用域 `scope` 中执行，成功时其设置的变量对外层可见；
+    /// 失败时先回滚到执行前的变量，再执行 `catch`，`catch` 中的 `error` 变量不会泄露到外层
+    pub(crate) fn execute_branch(
+        try_step: &TryStep,
+        input: &ExtractValueData,
+        runtime_context: &RuntimeContext,
+        scope: &mut Cow<'_, FlowContext>,
+    ) -> Result<ChainOutcome> {
+        let snapshot = scope.snapshot();
+        let error = match ExtractEngine::execute_chain_in(
+            &try_step.steps,
+            input,
+            runtime_context,
+            scope,
+            None,
+            None,
+        ) {
+            Ok(outcome) => return Ok(outcome),
+            Err(e) => {
+                // 借用的作用域未被写入，无需回滚
+                if let Cow::Owned(context) = scope {
+                    context.restore(snapshot);
+                }
+                if e.is_interruption() && !try_step.catch_timeout {
+                    return Err(e);
+                }
+                e
+            }
+        };
+
+        let Some(catch) = &try_step.catch else {
+            return Ok(ChainOutcome::Completed(Arc::new(input.clone())));
+        };
+        let context = scope.to_mut();
+        let previous = context.get(ERROR_VAR).cloned();
+        context.set(ERROR_VAR, Value::String(error.to_string()));
+        let result =
+            ExtractEngine::execute_chain_in(catch, input, runtime_context, scope, None, None);
+
+        let context = scope.to_mut();
+        match previous {
+            Some(value) => context.set(ERROR_VAR, value),
+            None => {
+                context.remove(ERROR_VAR);
+            }
+        }
+        result
+    }
+}
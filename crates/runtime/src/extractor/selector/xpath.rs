@@ -0,0 +1,499 @@
+//! # XPath 选择器执行器
+//!
+//! 基于 roxmltree 在 XML 上执行 XPath 子集：
+//!
+//! - 路径：`/`、`//`、相对路径、`.`、`..`
+//! - 节点测试：元素名（按本地名匹配，`p:name` 额外校验命名空间）、`*`、`text()`、`@attr`、`@*`
+//! - 谓词：`[n]`、`[last()]`、`[@a]`、`[@a='v']`、`[name]`、`[name='v']`、`[text()='v']`
+//!
+//! 元素结果为源码片段（[`ExtractValueData::Xml`]，补全继承的命名空间声明），
+//! 文本与属性结果为字符串
+
+use crate::{
+    Result,
+    context::{FlowContext, RuntimeContext},
+    error::RuntimeError,
+    extractor::value::{ExtractValueData, SharedValue},
+};
+use crawler_schema::extract::SelectorStep;
+use roxmltree::{Document, Node};
+use std::sync::Arc;
+
+/// XPath 选择器执行器
+pub struct XPathExecutor;
+
+/// 轴
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Axis {
+    /// `/`：子节点
+    Child,
+    /// `//`：任意层级的后代
+    Descendant,
+}
+
+/// 带前缀的名称
+#[derive(Debug, Clone)]
+struct QName {
+    prefix: Option<String>,
+    local: String,
+}
+
+/// 节点测试
+#[derive(Debug, Clone)]
+enum NodeTest {
+    /// 元素名
+    Element(QName),
+    /// `*`
+    AnyElement,
+    /// `text()`
+    Text,
+    /// `@name`，`@*` 时为 None
+    Attribute(Option<QName>),
+    /// `.`
+    SelfNode,
+    /// `..`
+    Parent,
+}
+
+/// 谓词
+#[derive(Debug, Clone)]
+enum Predicate {
+    /// `[n]`，从 1 开始
+    Position(usize),
+    /// `[last()]`
+    Last,
+    /// `[@a]`
+    HasAttr(QName),
+    /// `[@a='v']`
+    AttrEq(QName, String),
+    /// `[name]`
+    HasChild(QName),
+    /// `[name='v']`
+    ChildEq(QName, String),
+    /// `[text()='v']`
+    TextEq(String),
+}
+
+/// 路径中的一步
+#[derive(Debug, Clone)]
+struct Step {
+    axis: Axis,
+    test: NodeTest,
+    predicates: Vec<Predicate>,
+}
+
+/// 解析后的 XPath 表达式
+#[derive(Debug, Clone)]
+struct XPath {
+    /// 是否从文档根开始（否则从根元素开始）
+    absolute: bool,
+    steps: Vec<Step>,
+}
+
+/// 选择结果
+enum Item<'a, 'input> {
+    Node(Node<'a, 'input>),
+    Attr(&'a str),
+}
+
+impl XPathExecutor {
+    /// 执行 XPath 选择器
+    pub fn execute(
+        selector: &SelectorStep,
+        input: &ExtractValueData,
+        _runtime_context: &RuntimeContext,
+        _flow_context: &FlowContext,
+    ) -> Result<SharedValue> {
+        let (expr, select_all) = match selector {
+            SelectorStep::Simple(s) => (s.as_str(), false),
+            SelectorStep::WithOptions { expr, all } => (expr.as_str(), *all),
+        };
+        let xpath = XPath::parse(expr)?;
+
+        let xml = match input {
+            ExtractValueData::Xml(s) | ExtractValueData::String(s) | ExtractValueData::Html(s) => {
+                s.as_ref()
+            }
+            ExtractValueData::Array(arr) => {
+                // 如果是数组，对每个元素应用选择器
+                let results: Vec<SharedValue> = arr
+                    .iter()
+                    .filter_map(|item| {
+                        let xml = item.as_str()?;
+                        Self::execute_on_xml(xml, &xpath, select_all).ok()
+                    })
+                    .flatten()
+                    .collect();
+                return Ok(Arc::new(ExtractValueData::Array(Arc::new(results))));
+            }
+            _ => {
+                return Err(RuntimeError::Extraction(
+                    "XPath selector requires XML input".to_string(),
+                ));
+            }
+        };
+
+        let results = Self::execute_on_xml(xml, &xpath, select_all)?;
+        if results.is_empty() {
+            Ok(Arc::new(ExtractValueData::Null))
+        } else if results.len() == 1 && !select_all {
+            Ok(results.into_iter().next().unwrap())
+        } else {
+            Ok(Arc::new(ExtractValueData::Array(Arc::new(results))))
+        }
+    }
+
+    /// 在 XML 上执行选择器
+    fn execute_on_xml(xml: &str, xpath: &XPath, select_all: bool) -> Result<Vec<SharedValue>> {
+        let document = Document::parse(xml).map_err(|e| {
+            RuntimeError::Extraction(format!("XPath selector requires well-formed XML: {}", e))
+        })?;
+
+        let items = xpath.evaluate(&document);
+        let take = if select_all { items.len() } else { 1 };
+        Ok(items
+            .into_iter()
+            .take(take)
+            .map(|item| Arc::new(Self::to_value(item, xml)))
+            .collect())
+    }
+
+    fn to_value(item: Item, xml: &str) -> ExtractValueData {
+        match item {
+            Item::Attr(value) => value.into(),
+            Item::Node(node) if node.is_text() => node.text().unwrap_or_default().into(),
+            Item::Node(node) if node.is_element() => {
+                ExtractValueData::Xml(Arc::from(element_xml(node, xml).into_boxed_str()))
+            }
+            Item::Node(_) => ExtractValueData::Xml(Arc::from(xml)),
+        }
+    }
+}
+
+impl XPath {
+    /// 解析表达式
+    fn parse(expr: &str) -> Result<Self> {
+        let invalid = |reason: &str| {
+            RuntimeError::Extraction(format!("Invalid XPath '{}': {}", expr, reason))
+        };
+
+        let segments = split_top_level(expr.trim(), '/');
+        let absolute = segments.len() > 1 && segments[0].is_empty();
+        let mut steps = Vec::new();
+        let mut axis = Axis::Child;
+        for (i, segment) in segments.iter().enumerate() {
+            let segment = segment.trim();
+            if segment.is_empty() {
+                // 开头的 `/` 表示绝对路径，其余位置的空段来自 `//`
+                if i > 0 {
+                    if axis == Axis::Descendant {
+                        return Err(invalid("unexpected '///'"));
+                    }
+                    axis = Axis::Descendant;
+                }
+                continue;
+            }
+            if let Some(Step {
+                test: NodeTest::Text | NodeTest::Attribute(_),
+                ..
+            }) = steps.last()
+            {
+                return Err(invalid("text() and @attr must be the last step"));
+            }
+            steps.push(Step::parse(segment, axis).map_err(|e| invalid(&e))?);
+            axis = Axis::Child;
+        }
+
+        if steps.is_empty() && !absolute {
+            return Err(invalid("empty expression"));
+        }
+        if axis == Axis::Descendant {
+            return Err(invalid("expression must not end with '/'"));
+        }
+        Ok(Self { absolute, steps })
+    }
+
+    /// 求值，结果按文档顺序排列
+    fn evaluate<'a, 'input>(&self, document: &'a Document<'input>) -> Vec<Item<'a, 'input>> {
+        let mut context = vec![if self.absolute {
+            document.root()
+        } else {
+            document.root_element()
+        }];
+
+        for step in &self.steps {
+            if let NodeTest::Attribute(name) = &step.test {
+                return context
+                    .iter()
+                    .flat_map(|node| step.axis_nodes(*node))
+                    .filter(|node| node.is_element())
+                    .flat_map(|node| {
+                        node.attributes()
+                            .filter(|attr| {
+                                name.as_ref().is_none_or(|name| {
+                                    name.matches(attr.name(), attr.namespace(), &node)
+                                })
+                            })
+                            .map(|attr| Item::Attr(attr.value()))
+                            .collect::<Vec<_>>()
+                    })
+                    .collect();
+            }
+
+            let mut next: Vec<Node> = context.iter().flat_map(|node| step.select(*node)).collect();
+            next.sort_by_key(|node| node.id().get());
+            next.dedup_by_key(|node| node.id().get());
+            context = next;
+        }
+
+        context.into_iter().map(Item::Node).collect()
+    }
+}
+
+impl Step {
+    /// 解析单步，如 `item[@type='a'][1]`
+    fn parse(segment: &str, axis: Axis) -> std::result::Result<Self, String> {
+        let (base, mut rest) = match find_top_level(segment, '[') {
+            Some(pos) => (segment[..pos].trim(), &segment[pos..]),
+            None => (segment, ""),
+        };
+
+        let test = match base {
+            "." => NodeTest::SelfNode,
+            ".." => NodeTest::Parent,
+            "*" => NodeTest::AnyElement,
+            "text()" => NodeTest::Text,
+            "@*" => NodeTest::Attribute(None),
+            _ => match base.strip_prefix('@') {
+                Some(name) => NodeTest::Attribute(Some(QName::parse(name)?)),
+                None => NodeTest::Element(QName::parse(base)?),
+            },
+        };
+
+        let mut predicates = Vec::new();
+        while !rest.is_empty() {
+            let close = find_top_level(rest, ']')
+                .filter(|_| rest.starts_with('['))
+                .ok_or_else(|| format!("unbalanced predicate in '{}'", segment))?;
+            predicates.push(Predicate::parse(rest[1..close].trim())?);
+            rest = rest[close + 1..].trim_start();
+        }
+        if !predicates.is_empty() && matches!(test, NodeTest::Attribute(_)) {
+            return Err("predicates on @attr are not supported".to_string());
+        }
+
+        Ok(Self {
+            axis,
+            test,
+            predicates,
+        })
+    }
+
+    /// 按轴展开的候选上下文：`//` 时为自身及全部后代
+    fn axis_nodes<'a, 'input>(&self, node: Node<'a, 'input>) -> Vec<Node<'a, 'input>> {
+        match self.axis {
+            Axis::Child => vec![node],
+            Axis::Descendant => node.descendants().collect(),
+        }
+    }
+
+    /// 对上下文节点执行本步，谓词中的位置相对每个父节点计算
+    fn select<'a, 'input>(&self, node: Node<'a, 'input>) -> Vec<Node<'a, 'input>> {
+        self.axis_nodes(node)
+            .into_iter()
+            .flat_map(|context| {
+                let candidates: Vec<Node> = match &self.test {
+                    NodeTest::SelfNode => vec![context],
+                    NodeTest::Parent => context.parent().into_iter().collect(),
+                    NodeTest::Text => context.children().filter(Node::is_text).collect(),
+                    NodeTest::AnyElement => context.children().filter(Node::is_element).collect(),
+                    NodeTest::Element(name) => context
+                        .children()
+                        .filter(|child| name.matches_element(child))
+                        .collect(),
+                    NodeTest::Attribute(_) => Vec::new(),
+                };
+                self.predicates.iter().fold(candidates, |nodes, predicate| {
+                    let size = nodes.len();
+                    nodes
+                        .into_iter()
+                        .enumerate()
+                        .filter(|(i, node)| predicate.matches(node, i + 1, size))
+                        .map(|(_, node)| node)
+                        .collect()
+                })
+            })
+            .collect()
+    }
+}
+
+impl Predicate {
+    fn parse(inner: &str) -> std::result::Result<Self, String> {
+        if inner == "last()" {
+            return Ok(Self::Last);
+        }
+        if let Ok(position) = inner.parse::<usize>() {
+            return match position {
+                0 => Err("positions start at 1".to_string()),
+                n => Ok(Self::Position(n)),
+            };
+        }
+
+        let (left, value) = match find_top_level(inner, '=') {
+            Some(pos) => (inner[..pos].trim(), Some(parse_literal(&inner[pos + 1..])?)),
+            None => (inner, None),
+        };
+        Ok(match (left, value) {
+            ("text()", Some(value)) => Self::TextEq(value),
+            (left, value) => match (left.strip_prefix('@'), value) {
+                (Some(name), Some(value)) => Self::AttrEq(QName::parse(name)?, value),
+                (Some(name), None) => Self::HasAttr(QName::parse(name)?),
+                (None, Some(value)) => Self::ChildEq(QName::parse(left)?, value),
+                (None, None) => Self::HasChild(QName::parse(left)?),
+            },
+        })
+    }
+
+    fn matches(&self, node: &Node, position: usize, size: usize) -> bool {
+        let attr = |name: &QName| {
+            node.attributes()
+                .find(|attr| name.matches(attr.name(), attr.namespace(), node))
+                .map(|attr| attr.value())
+        };
+        let children = |name: &QName| {
+            node.children()
+                .filter(|child| name.matches_element(child))
+                .collect::<Vec<_>>()
+        };
+
+        match self {
+            Self::Position(n) => position == *n,
+            Self::Last => position == size,
+            Self::HasAttr(name) => attr(name).is_some(),
+            Self::AttrEq(name, value) => attr(name) == Some(value.as_str()),
+            Self::HasChild(name) => !children(name).is_empty(),
+            Self::ChildEq(name, value) => children(name)
+                .iter()
+                .any(|child| string_value(child) == *value),
+            Self::TextEq(value) => string_value(node) == *value,
+        }
+    }
+}
+
+impl QName {
+    fn parse(name: &str) -> std::result::Result<Self, String> {
+        let valid = |s: &str| {
+            !s.is_empty()
+                && s.chars()
+                    .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.'))
+        };
+        let (prefix, local) = match name.split_once(':') {
+            Some((prefix, local)) => (Some(prefix), local),
+            None => (None, name),
+        };
+        if !valid(local) || prefix.is_some_and(|p| !valid(p)) {
+            return Err(format!("unsupported node test '{}'", name));
+        }
+        Ok(Self {
+            prefix: prefix.map(str::to_string),
+            local: local.to_string(),
+        })
+    }
+
+    /// 按本地名匹配；带前缀时还需命名空间与前缀在 `scope` 中绑定的 URI 一致
+    fn matches(&self, local: &str, namespace: Option<&str>, scope: &Node) -> bool {
+        local == self.local
+            && self.prefix.as_deref().is_none_or(|prefix| {
+                namespace.is_some() && scope.lookup_namespace_uri(Some(prefix)) == namespace
+            })
+    }
+
+    fn matches_element(&self, node: &Node) -> bool {
+        node.is_element() && self.matches(node.tag_name().name(), node.tag_name().namespace(), node)
+    }
+}
+
+/// 节点的字符串值：全部后代文本拼接
+fn string_value(node: &Node) -> String {
+    node.descendants()
+        .filter(Node::is_text)
+        .filter_map(|n| n.text())
+        .collect()
+}
+
+/// 元素的源码片段，补全从祖先继承的命名空间声明，使片段可独立解析
+fn element_xml(node: Node, xml: &str) -> String {
+    let mut fragment = xml[node.range()].to_string();
+    let start_tag_end = fragment.find('>').unwrap_or(fragment.len());
+    let name_end = fragment[..start_tag_end]
+        .find(|c: char| c.is_whitespace() || c == '/' || c == '>')
+        .unwrap_or(start_tag_end);
+
+    let declared: Vec<&str> = fragment[..start_tag_end]
+        .split_whitespace()
+        .filter_map(|token| token.split('=').next())
+        .collect();
+    let missing: String = node
+        .namespaces()
+        .filter(|ns| ns.name() != Some("xml"))
+        .filter_map(|ns| {
+            let attr = match ns.name() {
+                Some(prefix) => format!("xmlns:{}", prefix),
+                None => "xmlns".to_string(),
+            };
+            (!declared.contains(&attr.as_str())).then(|| {
+                format!(
+                    " {}=\"{}\"",
+                    attr,
+                    ns.uri().replace('&', "&amp;").replace('"', "&quot;")
+                )
+            })
+        })
+        .collect();
+
+    fragment.insert_str(name_end, &missing);
+    fragment
+}
+
+/// 解析带引号的字面量
+fn parse_literal(s: &str) -> std::result::Result<String, String> {
+    let s = s.trim();
+    ['\'', '"']
+        .into_iter()
+        .find_map(|quote| s.strip_prefix(quote)?.strip_suffix(quote))
+        .filter(|inner| s.len() >= 2 && !inner.contains(&s[..1]))
+        .map(str::to_string)
+        .ok_or_else(|| format!("expected a quoted string, got '{}'", s))
+}
+
+/// 在引号与方括号之外查找字符
+fn find_top_level(s: &str, target: char) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut quote = None;
+    for (i, c) in s.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, c) if c == target && (depth == 0 || (c == ']' && depth == 1)) => {
+                return Some(i);
+            }
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '[') => depth += 1,
+            (None, ']') => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// 在引号与方括号之外按分隔符拆分
+fn split_top_level(s: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut rest = s;
+    while let Some(pos) = find_top_level(rest, separator) {
+        parts.push(&rest[..pos]);
+        rest = &rest[pos + separator.len_utf8()..];
+    }
+    parts.push(rest);
+    parts
+}
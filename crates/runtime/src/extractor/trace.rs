@@ -0,0 +1,118 @@
+//! # 提取调试轨迹
+//!
+//! 记录字段提取过程中每个步骤的输入/输出摘要，供规则调试与编辑器高亮使用
+
+use crate::extractor::value::ExtractValueData;
+use crawler_schema::extract::ExtractStep;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// 摘要最大字符数
+const SUMMARY_MAX_CHARS: usize = 80;
+
+/// 单个步骤的执行轨迹
+#[derive(Debug, Clone, Serialize)]
+pub struct StepTrace {
+    /// 步骤类型（如 `css`、`filter`；使用默认值时为 `default`）
+    pub step: &'static str,
+    /// 输入摘要
+    pub input: String,
+    /// 输出摘要（步骤出错时为 None）
+    pub output: Option<String>,
+    /// 错误信息
+    pub error: Option<String>,
+    /// 所属回退链序号（主步骤链为 None）
+    pub fallback: Option<usize>,
+}
+
+impl StepTrace {
+    /// 是否执行失败
+    pub fn is_failed(&self) -> bool {
+        self.error.is_some()
+    }
+}
+
+/// 步骤事件
+///
+/// 在步骤执行前后传给 [`StepHook`]，`variables` 为当前流程变量快照
+#[derive(Debug, Clone, Copy)]
+pub struct StepEvent<'a> {
+    /// 步骤在所属步骤链中的序号（使用默认值时为 0）
+    pub index: usize,
+    /// 步骤类型
+    pub step: &'static str,
+    /// 所属回退链序号（主步骤链为 None）
+    pub fallback: Option<usize>,
+    /// 步骤输入
+    pub input: &'a ExtractValueData,
+    /// 流程变量
+    pub variables: &'a Map<String, Value>,
+}
+
+/// 步骤钩子
+///
+/// 在步骤链的每个步骤前后调用，可用于记录轨迹或单步调试；
+/// 钩子在提取线程上同步执行，阻塞钩子即可暂停提取
+pub trait StepHook {
+    /// 步骤执行前调用
+    fn before_step(&mut self, _event: &StepEvent<'_>) {}
+
+    /// 步骤执行后调用，`trace` 为该步骤的执行轨迹
+    fn after_step(&mut self, event: &StepEvent<'_>, trace: StepTrace);
+}
+
+/// 收集全部步骤轨迹
+impl StepHook for Vec<StepTrace> {
+    fn after_step(&mut self, _event: &StepEvent<'_>, trace: StepTrace) {
+        self.push(trace);
+    }
+}
+
+/// 获取步骤类型名称
+pub(crate) fn step_kind(step: &ExtractStep) -> &'static str {
+    match step {
+        ExtractStep::Css(_) => "css",
+        ExtractStep::Json(_) => "json",
+        ExtractStep::Xpath(_) => "xpath",
+        ExtractStep::Regex(_) => "regex",
+        ExtractStep::Filter(_) => "filter",
+        ExtractStep::Attr(_) => "attr",
+        ExtractStep::Index(_) => "index",
+        ExtractStep::Enumerate => "enumerate",
+        ExtractStep::SetVar(_) => "set_var",
+        ExtractStep::Template(_) => "template",
+        ExtractStep::Script(_) => "script",
+        ExtractStep::UseComponent(_) => "use_component",
+        ExtractStep::Map(_) => "map",
+        ExtractStep::Condition(_) => "condition",
+        ExtractStep::Try(_) => "try",
+        ExtractStep::Return => "return",
+        ExtractStep::Goto(_) => "goto",
+        ExtractStep::Assert(_) => "assert",
+        ExtractStep::Log(_) => "log",
+    }
+}
+
+/// 生成值的简短摘要，如 `html(1024): <div class="item">...`
+///
+/// 字符串与 HTML 直接在共享数据上截取，避免为整页 HTML 生成完整副本
+pub(crate) fn summarize(value: &ExtractValueData) -> String {
+    let json;
+    let (kind, text): (&str, &str) = match value {
+        ExtractValueData::String(s) => ("string", s),
+        ExtractValueData::Html(h) => ("html", h),
+        ExtractValueData::Xml(x) => ("xml", x),
+        ExtractValueData::Json(v) => {
+            json = v.to_string();
+            ("json", &json)
+        }
+        ExtractValueData::Array(arr) => return format!("array({})", arr.len()),
+        ExtractValueData::Null => return "null".to_string(),
+    };
+
+    let len = text.chars().count();
+    match text.char_indices().nth(SUMMARY_MAX_CHARS) {
+        Some((end, _)) => format!("{}({}): {}...", kind, len, &text[..end]),
+        None => format!("{}({}): {}", kind, len, text),
+    }
+}
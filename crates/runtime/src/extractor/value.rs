@@ -2,9 +2,10 @@
 //!
 //! 中间值表示，使用 Arc 实现零拷贝处理
 
+use scraper::Html;
 use serde::{Deserialize, Serialize, ser::SerializeSeq};
 use serde_json::Value;
-use std::sync::Arc;
+use std::{borrow::Cow, sync::Arc};
 
 /// 共享的提取值（使用 Arc 实现廉价克隆）
 pub type SharedValue = Arc<ExtractValueData>;
@@ -20,6 +21,8 @@ pub enum ExtractValueData {
     Json(Arc<Value>),
     /// HTML 字符串（使用 Arc<str> 零拷贝）
     Html(Arc<str>),
+    /// XML 字符串（使用 Arc<str> 零拷贝）
+    Xml(Arc<str>),
     /// 数组（包含共享值）
     Array(Arc<Vec<SharedValue>>),
     /// 空值
@@ -32,11 +35,29 @@ impl ExtractValueData {
     pub fn as_str(&self) -> Option<&str> {
         match self {
             Self::String(s) => Some(s),
-            Self::Html(h) => Some(h),
+            Self::Html(h) | Self::Xml(h) => Some(h),
             _ => None,
         }
     }
 
+    /// 按需转换为字符串
+    ///
+    /// 提取链中 JSON 数字、布尔保持原类型，只在最终需要字符串时调用本方法：
+    /// 字符串/HTML/XML 零拷贝返回原文，JSON 字符串/数字/布尔转为对应的字符串形式，
+    /// 数组、JSON 数组/对象、null 返回 None
+    pub fn to_str_lossy(&self) -> Option<Cow<'_, str>> {
+        match self {
+            Self::String(s) | Self::Html(s) | Self::Xml(s) => Some(Cow::Borrowed(s)),
+            Self::Json(v) => match v.as_ref() {
+                Value::String(s) => Some(Cow::Borrowed(s)),
+                Value::Number(n) => Some(Cow::Owned(n.to_string())),
+                Value::Bool(b) => Some(Cow::Owned(b.to_string())),
+                _ => None,
+            },
+            Self::Array(_) | Self::Null => None,
+        }
+    }
+
     /// 转换为 JSON 引用（零拷贝）
     pub fn as_json_ref(&self) -> Option<&Value> {
         match self {
@@ -58,7 +79,7 @@ impl ExtractValueData {
         match self {
             Self::String(s) => Value::String(s.to_string()),
             Self::Json(v) => (**v).clone(),
-            Self::Html(h) => Value::String(h.to_string()),
+            Self::Html(h) | Self::Xml(h) => Value::String(h.to_string()),
             Self::Array(arr) => Value::Array(arr.iter().map(|v| v.to_owned_json()).collect()),
             Self::Null => Value::Null,
         }
@@ -82,7 +103,7 @@ impl ExtractValueData {
         match self {
             Self::String(s) => s.is_empty(),
             Self::Json(v) => v.is_null(),
-            Self::Html(h) => h.is_empty(),
+            Self::Html(h) | Self::Xml(h) => h.is_empty(),
             Self::Array(arr) => arr.is_empty(),
             Self::Null => true,
         }
@@ -93,27 +114,152 @@ impl ExtractValueData {
         matches!(self, Self::Array(_))
     }
 
+    /// 是否为 null（包括 JSON null）
+    pub fn is_null(&self) -> bool {
+        match self {
+            Self::Null => true,
+            Self::Json(v) => v.is_null(),
+            _ => false,
+        }
+    }
+
+    /// 获取长度
+    ///
+    /// - 字符串/HTML/XML：字符数
+    /// - 数组：元素个数
+    /// - JSON 数组/对象：元素/字段个数，JSON 字符串：字符数
+    /// - 其他：0
+    pub fn len(&self) -> usize {
+        match self {
+            Self::String(s) | Self::Html(s) | Self::Xml(s) => s.chars().count(),
+            Self::Array(arr) => arr.len(),
+            Self::Json(v) => match v.as_ref() {
+                Value::Array(arr) => arr.len(),
+                Value::Object(obj) => obj.len(),
+                Value::String(s) => s.chars().count(),
+                _ => 0,
+            },
+            Self::Null => 0,
+        }
+    }
+
+    /// 获取文本内容（去除首尾空白）
+    ///
+    /// - 字符串：原文
+    /// - HTML：元素的文本内容
+    /// - XML：节点的文本内容（无法解析时为原文）
+    /// - JSON 字符串/数字/布尔：对应的字符串形式
+    /// - 数组、JSON 数组/对象、null：None
+    pub fn as_text(&self) -> Option<String> {
+        let text = match self {
+            Self::String(s) => s.trim().to_string(),
+            Self::Html(h) => Html::parse_fragment(h)
+                .root_element()
+                .text()
+                .collect::<String>()
+                .trim()
+                .to_string(),
+            Self::Xml(x) => match roxmltree::Document::parse(x) {
+                Ok(doc) => doc
+                    .root()
+                    .descendants()
+                    .filter(|n| n.is_text())
+                    .filter_map(|n| n.text())
+                    .collect::<String>()
+                    .trim()
+                    .to_string(),
+                Err(_) => x.trim().to_string(),
+            },
+            Self::Json(v) => match v.as_ref() {
+                Value::String(s) => s.trim().to_string(),
+                Value::Number(n) => n.to_string(),
+                Value::Bool(b) => b.to_string(),
+                _ => return None,
+            },
+            Self::Array(_) | Self::Null => return None,
+        };
+        Some(text)
+    }
+
+    /// 转换为整数
+    ///
+    /// JSON 数字直接取值（整数值的浮点数也可转换），文本按去除空白后解析
+    pub fn as_i64(&self) -> Option<i64> {
+        if let Self::Json(v) = self
+            && let Value::Number(n) = v.as_ref()
+        {
+            return n
+                .as_i64()
+                .or_else(|| n.as_f64().filter(|f| f.fract() == 0.0).map(|f| f as i64));
+        }
+        self.as_text()?.parse().ok()
+    }
+
+    /// 转换为浮点数
+    pub fn as_f64(&self) -> Option<f64> {
+        if let Self::Json(v) = self
+            && let Value::Number(n) = v.as_ref()
+        {
+            return n.as_f64();
+        }
+        self.as_text()?.parse().ok()
+    }
+
+    /// 转换为布尔值
+    ///
+    /// 文本支持 `true`/`false`/`1`/`0`/`yes`/`no`（不区分大小写）
+    pub fn as_bool(&self) -> Option<bool> {
+        if let Self::Json(v) = self
+            && let Value::Bool(b) = v.as_ref()
+        {
+            return Some(*b);
+        }
+        match self.as_text()?.to_lowercase().as_str() {
+            "true" | "1" | "yes" => Some(true),
+            "false" | "0" | "no" => Some(false),
+            _ => None,
+        }
+    }
+
+    /// 转换为数组
+    ///
+    /// 数组直接共享元素，JSON 数组逐个转换，其他类型返回 None
+    pub fn as_array(&self) -> Option<Vec<SharedValue>> {
+        match self {
+            Self::Array(arr) => Some(arr.to_vec()),
+            Self::Json(v) => v
+                .as_array()
+                .map(|arr| arr.iter().map(|v| Arc::new(Self::from_json(v))).collect()),
+            _ => None,
+        }
+    }
+
+    /// 获取 JSON 对象的字段
+    ///
+    /// 支持链式访问，如 `value.get("data")?.get("title")`
+    pub fn get(&self, key: &str) -> Option<SharedValue> {
+        match self {
+            Self::Json(v) => v.get(key).map(|v| Arc::new(Self::from_json(v))),
+            _ => None,
+        }
+    }
+
     /// 是否为真值
     ///
     /// 用于条件判断，以下情况返回 false：
     /// - Null
     /// - 空字符串
     /// - 空数组
-    /// - JSON 的 false、null、空字符串、空数组
+    /// - JSON 的 false、0、null、空字符串、空数组、空对象
+    ///
+    /// 与脚本内置函数 `truthy` 的规则一致
     pub fn is_truthy(&self) -> bool {
         match self {
             Self::Null => false,
             Self::String(s) => !s.is_empty(),
-            Self::Html(h) => !h.is_empty(),
+            Self::Html(h) | Self::Xml(h) => !h.is_empty(),
             Self::Array(arr) => !arr.is_empty(),
-            Self::Json(v) => match v.as_ref() {
-                Value::Null => false,
-                Value::Bool(b) => *b,
-                Value::String(s) => !s.is_empty(),
-                Value::Array(arr) => !arr.is_empty(),
-                Value::Number(_) => true,
-                Value::Object(_) => true,
-            },
+            Self::Json(v) => crate::script::builtin::core::truthy(v),
         }
     }
 }
@@ -145,7 +291,7 @@ impl Serialize for ExtractValueData {
         match self {
             Self::String(s) => serializer.serialize_str(s),
             Self::Json(v) => v.serialize(serializer),
-            Self::Html(h) => serializer.serialize_str(h),
+            Self::Html(h) | Self::Xml(h) => serializer.serialize_str(h),
             Self::Array(arr) => {
                 let mut seq = serializer.serialize_seq(Some(arr.len()))?;
                 for item in arr.iter() {
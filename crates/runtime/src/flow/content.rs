@@ -3,8 +3,19 @@
 use crate::{
     Result,
     context::{FlowContext, RuntimeContext},
+    error::RuntimeError,
+    extractor::{ExtractEngine, SharedValue},
+    http::RESPONSE_VAR,
+    script::builtin::core,
+    template::TemplateExt,
 };
-use crawler_schema::flow::ContentFlow;
+use crawler_schema::{
+    extract::FieldExtractor,
+    fields::{BookContentFields, ContentFields},
+    flow::ContentFlow,
+};
+use serde::Serialize;
+use serde_json::{Map, Value};
 
 /// 内容请求
 #[derive(Debug, Clone)]
@@ -14,31 +25,129 @@ pub struct ContentRequest {
 }
 
 /// 内容响应
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ContentResponse {
     /// 内容数据
+    ///
+    /// 书籍正文包含 `content_html`（原始排版）与 `content_text`（去标签、规范化行后的纯文本），
+    /// 以及可选的 `title`、`prev_url`、`next_url`
     pub data: serde_json::Value,
 }
 
+impl ContentResponse {
+    /// 转换为 JSON，供宿主 App 或 FFI 传出
+    pub fn to_json(&self) -> Value {
+        serde_json::to_value(self).unwrap_or(Value::Null)
+    }
+}
+
 /// 内容流程执行器
 pub struct ContentFlowExecutor;
 
 impl ContentFlowExecutor {
+    /// 提取字符串字段
+    fn extract_string(
+        extractor: &FieldExtractor,
+        input: &SharedValue,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> Result<Option<String>> {
+        let value = match ExtractEngine::extract_field(
+            extractor,
+            input.as_ref(),
+            runtime_context,
+            flow_context,
+        ) {
+            Ok(value) => value,
+            Err(e) if e.is_interruption() => return Err(e),
+            Err(_) => return Ok(None),
+        };
+        Ok(value
+            .to_str_lossy()
+            .map(|text| text.trim().to_string())
+            .filter(|text| !text.is_empty()))
+    }
+
+    /// 提取书籍正文
+    ///
+    /// 正文同时输出 HTML 与纯文本两个版本：纯文本经 `strip_html` 与 `normalize_lines` 处理
+    fn extract_book_content(
+        fields: &BookContentFields,
+        html: &SharedValue,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> Result<Value> {
+        let content_html = Self::extract_string(
+            &fields.content.extractor,
+            html,
+            runtime_context,
+            flow_context,
+        )?
+        .ok_or_else(|| RuntimeError::FieldEmpty {
+            field: "content".to_string(),
+        })?;
+        let content_text = core::normalize_lines(&core::strip_html(&content_html));
+
+        let mut data = Map::new();
+        data.insert("content_html".to_string(), Value::String(content_html));
+        data.insert("content_text".to_string(), Value::String(content_text));
+        for (name, rule) in [
+            ("title", &fields.title),
+            ("prev_url", &fields.prev_url),
+            ("next_url", &fields.next_url),
+        ] {
+            if let Some(value) = rule
+                .as_ref()
+                .map(|f| Self::extract_string(&f.extractor, html, runtime_context, flow_context))
+                .transpose()?
+                .flatten()
+            {
+                data.insert(name.to_string(), Value::String(value));
+            }
+        }
+        Ok(Value::Object(data))
+    }
+
     /// 执行内容流程
     pub async fn execute(
         input: ContentRequest,
         flow: &ContentFlow,
-        _runtime_context: &RuntimeContext,
+        runtime_context: &RuntimeContext,
         flow_context: &mut FlowContext,
     ) -> Result<ContentResponse> {
-        // 设置上下文变量
-        flow_context.set("content_url", serde_json::json!(input.url));
+        // 1. 设置上下文变量
+        flow_context.set("content_url", serde_json::json!(&input.url));
+
+        // 2. 渲染 URL
+        let url = flow.url.render(flow_context)?;
+        flow_context.set("request_url", serde_json::json!(&url));
+        flow_context.record_request()?;
+        if let Some(listener) = runtime_context.progress_listener() {
+            listener.on_request(&url);
+        }
+
+        // 3. 发起 HTTP 请求
+        let client = runtime_context.flow_http_client("content", flow.http.as_ref())?;
+        let response = client.fetch(&url, flow_context).await?;
+        let response = client.read_response(response).await?;
+        flow_context.set(RESPONSE_VAR, response.to_value());
+        let html = response.into_document(
+            client
+                .config()
+                .response
+                .as_ref()
+                .and_then(|r| r.content_type.as_ref()),
+        );
 
-        // TODO: 实现内容流程
-        let _ = flow;
+        // 4. 根据媒体类型提取字段
+        let data = match &flow.fields {
+            ContentFields::Book(fields) => {
+                Self::extract_book_content(fields, &html, runtime_context, flow_context)?
+            }
+            // TODO: 实现视频、音频、漫画内容提取
+            _ => serde_json::json!({}),
+        };
 
-        Ok(ContentResponse {
-            data: serde_json::json!({}),
-        })
+        Ok(ContentResponse { data })
     }
 }
@@ -5,14 +5,41 @@ use crate::{
     context::{FlowContext, RuntimeContext},
     error::RuntimeError,
     extractor::{ExtractEngine, SharedValue, value::ExtractValueData},
-    model::{BookDetail, ChapterItem},
+    http::RESPONSE_VAR,
+    model::{
+        AudioDetail,
+        BookDetail,
+        ChapterItem,
+        EpisodeItem,
+        ItemDetail,
+        MangaDetail,
+        PlayLine,
+        TrackItem,
+        VideoDetail,
+    },
     template::TemplateExt,
 };
 use crawler_schema::{
-    fields::{BookDetailFields, ChapterListRule, DetailFields},
+    config::MediaType,
+    fields::{
+        AudioDetailFields,
+        BookDetailFields,
+        ChapterListRule,
+        DetailFields,
+        FieldRule,
+        MangaDetailFields,
+        OptionalFieldRule,
+        PlayLineListRule,
+        TrackListRule,
+        VideoDetailFields,
+    },
     flow::DetailFlow,
 };
-use std::sync::Arc;
+use serde::Serialize;
+use serde_json::Value;
+
+/// 漫画章节列表作为单条线路时的线路名
+const MANGA_LINE_NAME: &str = "默认";
 
 /// 详情请求
 #[derive(Debug, Clone)]
@@ -22,19 +49,36 @@ pub struct DetailRequest {
 }
 
 /// 详情响应（通用）
-#[derive(Debug, Clone)]
+///
+/// 序列化为以媒体类型为键的对象，如 `{ "book": { "title": ..., "chapters": [...] } }`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum DetailResponse {
     /// 书籍详情
     Book(Box<BookDetail>),
+    /// 视频详情
+    Video(Box<VideoDetail>),
+    /// 音频详情
+    Audio(Box<AudioDetail>),
+    /// 漫画详情
+    Manga(Box<MangaDetail>),
     /// 其他类型（暂用 JSON）
     Other(serde_json::Value),
 }
 
 impl DetailResponse {
+    /// 转换为 JSON，供宿主 App 或 FFI 传出
+    pub fn to_json(&self) -> Value {
+        serde_json::to_value(self).unwrap_or(Value::Null)
+    }
+
     /// 获取标题
     pub fn title(&self) -> &str {
         match self {
             Self::Book(b) => &b.title,
+            Self::Video(v) => &v.title,
+            Self::Audio(a) => &a.title,
+            Self::Manga(m) => &m.title,
             Self::Other(v) => v.get("title").and_then(|t| t.as_str()).unwrap_or(""),
         }
     }
@@ -43,6 +87,9 @@ impl DetailResponse {
     pub fn author(&self) -> &str {
         match self {
             Self::Book(b) => &b.author,
+            Self::Video(v) => v.director.as_deref().unwrap_or(""),
+            Self::Audio(a) => a.artist.as_deref().unwrap_or(""),
+            Self::Manga(m) => m.author.as_deref().unwrap_or(""),
             Self::Other(v) => v.get("author").and_then(|t| t.as_str()).unwrap_or(""),
         }
     }
@@ -51,9 +98,47 @@ impl DetailResponse {
     pub fn intro(&self) -> Option<&str> {
         match self {
             Self::Book(b) => b.intro.as_deref(),
+            Self::Video(v) => v.intro.as_deref(),
+            Self::Audio(a) => a.intro.as_deref(),
+            Self::Manga(m) => m.intro.as_deref(),
             Self::Other(v) => v.get("intro").and_then(|t| t.as_str()),
         }
     }
+
+    /// 转换为标准详情模型
+    ///
+    /// `url` 为详情页地址，用于生成 id
+    pub fn into_item_detail(self, url: impl Into<String>) -> ItemDetail {
+        match self {
+            Self::Book(b) => b.into_item_detail(url),
+            Self::Video(v) => v.into_item_detail(url),
+            Self::Audio(a) => a.into_item_detail(url),
+            Self::Manga(m) => m.into_item_detail(url),
+            Self::Other(v) => {
+                let url = url.into();
+                let get = |key: &str| v.get(key).and_then(|t| t.as_str()).map(str::to_string);
+                let media_type = v
+                    .get("type")
+                    .and_then(|t| serde_json::from_value::<MediaType>(t.clone()).ok())
+                    .unwrap_or_default();
+                ItemDetail {
+                    id: crate::model::item_id(&v, &url),
+                    media_type,
+                    url,
+                    title: get("title").unwrap_or_default(),
+                    author: get("author"),
+                    cover: get("cover"),
+                    intro: get("intro"),
+                    status: get("status"),
+                    latest: get("latest"),
+                    tags: get("category")
+                        .map(|c| crate::model::split_tags(&c))
+                        .unwrap_or_default(),
+                    extra: v,
+                }
+            }
+        }
+    }
 }
 
 /// 详情流程执行器
@@ -61,17 +146,29 @@ pub struct DetailFlowExecutor;
 
 impl DetailFlowExecutor {
     /// 提取字符串字段
+    ///
+    /// 页面以共享引用传入，各字段复用同一份 HTML，仅复制提取结果；
+    /// 提取失败视为字段缺失，超时、超限等中断类错误原样返回
     fn extract_string(
         extractor: &crawler_schema::extract::FieldExtractor,
         input: &SharedValue,
         runtime_context: &RuntimeContext,
         flow_context: &FlowContext,
-    ) -> Option<String> {
-        ExtractEngine::extract_field(extractor, input.as_ref(), runtime_context, flow_context)
-            .ok()
-            .and_then(|v| v.as_str().map(|s| s.to_string()))
-            .map(|s| s.trim().to_string())
-            .filter(|s| !s.is_empty())
+    ) -> Result<Option<String>> {
+        let value = match ExtractEngine::extract_field(
+            extractor,
+            input.as_ref(),
+            runtime_context,
+            flow_context,
+        ) {
+            Ok(value) => value,
+            Err(e) if e.is_interruption() => return Err(e),
+            Err(_) => return Ok(None),
+        };
+        Ok(value
+            .to_str_lossy()
+            .map(|text| text.trim().to_string())
+            .filter(|text| !text.is_empty()))
     }
 
     /// 提取书籍详情
@@ -83,47 +180,63 @@ impl DetailFlowExecutor {
     ) -> Result<BookDetail> {
         // 提取必需字段
         let title =
-            Self::extract_string(&fields.title.extractor, html, runtime_context, flow_context)
-                .ok_or_else(|| RuntimeError::Extraction("无法提取标题".to_string()))?;
+            Self::extract_string(&fields.title.extractor, html, runtime_context, flow_context)?
+                .ok_or_else(|| RuntimeError::FieldEmpty {
+                    field: "title".to_string(),
+                })?;
 
         let author = Self::extract_string(
             &fields.author.extractor,
             html,
             runtime_context,
             flow_context,
-        )
-        .ok_or_else(|| RuntimeError::Extraction("无法提取作者".to_string()))?;
+        )?
+        .ok_or_else(|| RuntimeError::FieldEmpty {
+            field: "author".to_string(),
+        })?;
 
         // 提取可选字段
         let cover = fields
             .cover
             .as_ref()
-            .and_then(|f| Self::extract_string(&f.extractor, html, runtime_context, flow_context));
+            .map(|f| Self::extract_string(&f.extractor, html, runtime_context, flow_context))
+            .transpose()?
+            .flatten();
 
         let intro = fields
             .intro
             .as_ref()
-            .and_then(|f| Self::extract_string(&f.extractor, html, runtime_context, flow_context));
+            .map(|f| Self::extract_string(&f.extractor, html, runtime_context, flow_context))
+            .transpose()?
+            .flatten();
 
         let category = fields
             .category
             .as_ref()
-            .and_then(|f| Self::extract_string(&f.extractor, html, runtime_context, flow_context));
+            .map(|f| Self::extract_string(&f.extractor, html, runtime_context, flow_context))
+            .transpose()?
+            .flatten();
 
         let status = fields
             .status
             .as_ref()
-            .and_then(|f| Self::extract_string(&f.extractor, html, runtime_context, flow_context));
+            .map(|f| Self::extract_string(&f.extractor, html, runtime_context, flow_context))
+            .transpose()?
+            .flatten();
 
         let last_chapter = fields
             .last_chapter
             .as_ref()
-            .and_then(|f| Self::extract_string(&f.extractor, html, runtime_context, flow_context));
+            .map(|f| Self::extract_string(&f.extractor, html, runtime_context, flow_context))
+            .transpose()?
+            .flatten();
 
         let word_count = fields
             .word_count
             .as_ref()
-            .and_then(|f| Self::extract_string(&f.extractor, html, runtime_context, flow_context));
+            .map(|f| Self::extract_string(&f.extractor, html, runtime_context, flow_context))
+            .transpose()?
+            .flatten();
 
         // 提取章节列表
         let chapters = if let Some(chapter_rule) = &fields.chapters {
@@ -172,9 +285,9 @@ impl DetailFlowExecutor {
         let mut chapters = Vec::new();
         for item in items.iter() {
             let title =
-                Self::extract_string(&rule.title.extractor, item, runtime_context, flow_context);
+                Self::extract_string(&rule.title.extractor, item, runtime_context, flow_context)?;
             let url =
-                Self::extract_string(&rule.url.extractor, item, runtime_context, flow_context);
+                Self::extract_string(&rule.url.extractor, item, runtime_context, flow_context)?;
 
             if let (Some(title), Some(url)) = (title, url) {
                 chapters.push(ChapterItem { title, url });
@@ -184,6 +297,235 @@ impl DetailFlowExecutor {
         Ok(chapters)
     }
 
+    /// 提取可选字符串字段，未配置时为 None
+    fn extract_optional(
+        field: &OptionalFieldRule,
+        html: &SharedValue,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> Result<Option<String>> {
+        field
+            .as_ref()
+            .map(|f| Self::extract_string(&f.extractor, html, runtime_context, flow_context))
+            .transpose()
+            .map(Option::flatten)
+    }
+
+    /// 提取必需字符串字段，缺失时报 `FieldEmpty`
+    fn extract_required(
+        field: &FieldRule,
+        name: &str,
+        html: &SharedValue,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> Result<String> {
+        Self::extract_string(&field.extractor, html, runtime_context, flow_context)?.ok_or_else(
+            || RuntimeError::FieldEmpty {
+                field: name.to_string(),
+            },
+        )
+    }
+
+    /// 提取列表容器，非数组结果视为空列表
+    fn extract_items(
+        list: &FieldRule,
+        html: &SharedValue,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> Result<Vec<SharedValue>> {
+        let list_result = ExtractEngine::extract_field(
+            &list.extractor,
+            html.as_ref(),
+            runtime_context,
+            flow_context,
+        )?;
+        Ok(match list_result.as_ref() {
+            ExtractValueData::Array(arr) => arr.iter().cloned().collect(),
+            _ => Vec::new(),
+        })
+    }
+
+    /// 提取视频详情
+    fn extract_video_detail(
+        fields: &VideoDetailFields,
+        html: &SharedValue,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> Result<VideoDetail> {
+        let optional = |field| Self::extract_optional(field, html, runtime_context, flow_context);
+
+        let mut detail = VideoDetail::new(Self::extract_required(
+            &fields.title,
+            "title",
+            html,
+            runtime_context,
+            flow_context,
+        )?);
+        detail.cover = optional(&fields.cover)?;
+        detail.intro = optional(&fields.intro)?;
+        detail.director = optional(&fields.director)?;
+        detail.actors = optional(&fields.actors)?;
+        detail.category = optional(&fields.category)?;
+        detail.tags = optional(&fields.tags)?;
+        detail.region = optional(&fields.region)?;
+        detail.year = optional(&fields.year)?;
+        detail.score = optional(&fields.score)?;
+        detail.language = optional(&fields.language)?;
+        detail.update_info = optional(&fields.update_info)?;
+        detail.duration = optional(&fields.duration)?;
+        if let Some(rule) = &fields.play_lines {
+            detail.play_lines =
+                Self::extract_play_lines(rule, html, runtime_context, flow_context)?;
+        }
+        detail.raw = serde_json::json!({});
+        Ok(detail)
+    }
+
+    /// 提取音频详情
+    fn extract_audio_detail(
+        fields: &AudioDetailFields,
+        html: &SharedValue,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> Result<AudioDetail> {
+        let optional = |field| Self::extract_optional(field, html, runtime_context, flow_context);
+
+        let mut detail = AudioDetail::new(Self::extract_required(
+            &fields.title,
+            "title",
+            html,
+            runtime_context,
+            flow_context,
+        )?);
+        detail.artist = optional(&fields.artist)?;
+        detail.cover = optional(&fields.cover)?;
+        detail.intro = optional(&fields.intro)?;
+        detail.album = optional(&fields.album)?;
+        detail.category = optional(&fields.category)?;
+        detail.tags = optional(&fields.tags)?;
+        detail.update_time = optional(&fields.update_time)?;
+        detail.play_count = optional(&fields.play_count)?;
+        if let Some(rule) = &fields.tracks {
+            detail.tracks = Self::extract_tracks(rule, html, runtime_context, flow_context)?;
+        }
+        detail.raw = serde_json::json!({});
+        Ok(detail)
+    }
+
+    /// 提取漫画详情
+    ///
+    /// 章节列表作为唯一一条线路；`last_chapter` 没有对应字段，保留在原始数据中
+    fn extract_manga_detail(
+        fields: &MangaDetailFields,
+        html: &SharedValue,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> Result<MangaDetail> {
+        let optional = |field| Self::extract_optional(field, html, runtime_context, flow_context);
+
+        let mut detail = MangaDetail::new(Self::extract_required(
+            &fields.title,
+            "title",
+            html,
+            runtime_context,
+            flow_context,
+        )?);
+        detail.author = optional(&fields.author)?;
+        detail.cover = optional(&fields.cover)?;
+        detail.intro = optional(&fields.intro)?;
+        detail.category = optional(&fields.category)?;
+        detail.tags = optional(&fields.tags)?;
+        detail.status = optional(&fields.status)?;
+        detail.update_time = optional(&fields.update_time)?;
+        if let Some(rule) = &fields.chapters {
+            let episodes = Self::extract_chapters(rule, html, runtime_context, flow_context)?
+                .into_iter()
+                .map(|c| EpisodeItem {
+                    name: c.title,
+                    url: c.url,
+                })
+                .collect();
+            detail.add_play_line(MANGA_LINE_NAME, episodes);
+        }
+        let mut raw = serde_json::Map::new();
+        if let Some(last_chapter) = optional(&fields.last_chapter)? {
+            raw.insert("last_chapter".to_string(), Value::String(last_chapter));
+        }
+        detail.raw = Value::Object(raw);
+        Ok(detail)
+    }
+
+    /// 提取播放线路，名称缺失的线路与剧集被跳过
+    fn extract_play_lines(
+        rule: &PlayLineListRule,
+        html: &SharedValue,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> Result<Vec<PlayLine>> {
+        let mut lines = Vec::new();
+        for line in Self::extract_items(&rule.lines, html, runtime_context, flow_context)? {
+            let Some(name) = Self::extract_string(
+                &rule.line_name.extractor,
+                &line,
+                runtime_context,
+                flow_context,
+            )?
+            else {
+                continue;
+            };
+
+            let episode_rule = &rule.episodes;
+            let mut episodes = Vec::new();
+            for item in
+                Self::extract_items(&episode_rule.list, &line, runtime_context, flow_context)?
+            {
+                let name = Self::extract_string(
+                    &episode_rule.name.extractor,
+                    &item,
+                    runtime_context,
+                    flow_context,
+                )?;
+                let url = Self::extract_string(
+                    &episode_rule.url.extractor,
+                    &item,
+                    runtime_context,
+                    flow_context,
+                )?;
+                if let (Some(name), Some(url)) = (name, url) {
+                    episodes.push(EpisodeItem { name, url });
+                }
+            }
+            lines.push(PlayLine { name, episodes });
+        }
+        Ok(lines)
+    }
+
+    /// 提取音轨列表，名称或 URL 缺失的音轨被跳过
+    fn extract_tracks(
+        rule: &TrackListRule,
+        html: &SharedValue,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> Result<Vec<TrackItem>> {
+        let mut tracks = Vec::new();
+        for item in Self::extract_items(&rule.list, html, runtime_context, flow_context)? {
+            let name =
+                Self::extract_string(&rule.name.extractor, &item, runtime_context, flow_context)?;
+            let url =
+                Self::extract_string(&rule.url.extractor, &item, runtime_context, flow_context)?;
+            let duration =
+                Self::extract_optional(&rule.duration, &item, runtime_context, flow_context)?;
+            if let (Some(name), Some(url)) = (name, url) {
+                tracks.push(TrackItem {
+                    name,
+                    url,
+                    duration,
+                });
+            }
+        }
+        Ok(tracks)
+    }
+
     /// 执行详情流程
     pub async fn execute(
         input: DetailRequest,
@@ -196,16 +538,24 @@ impl DetailFlowExecutor {
 
         // 2. 渲染 URL
         let url = flow.url.render(flow_context)?;
+        flow_context.set("request_url", serde_json::json!(&url));
+        flow_context.record_request()?;
+        if let Some(listener) = runtime_context.progress_listener() {
+            listener.on_request(&url);
+        }
 
         // 3. 发起 HTTP 请求
-        let response = runtime_context.http_client().get(&url).await?;
-        let html_text = response
-            .text()
-            .await
-            .map_err(|e| RuntimeError::HttpRequest(format!("读取响应失败: {}", e)))?;
-        let html = Arc::new(ExtractValueData::Html(Arc::from(
-            html_text.into_boxed_str(),
-        )));
+        let client = runtime_context.flow_http_client("detail", flow.http.as_ref())?;
+        let response = client.fetch(&url, flow_context).await?;
+        let response = client.read_response(response).await?;
+        flow_context.set(RESPONSE_VAR, response.to_value());
+        let html = response.into_document(
+            client
+                .config()
+                .response
+                .as_ref()
+                .and_then(|r| r.content_type.as_ref()),
+        );
 
         // 4. 根据媒体类型提取字段
         match &flow.fields {
@@ -214,17 +564,20 @@ impl DetailFlowExecutor {
                     Self::extract_book_detail(fields, &html, runtime_context, flow_context)?;
                 Ok(DetailResponse::Book(Box::new(detail)))
             }
-            DetailFields::Video(_) => {
-                // TODO: 实现视频详情提取
-                Ok(DetailResponse::Other(serde_json::json!({"type": "video"})))
+            DetailFields::Video(fields) => {
+                let detail =
+                    Self::extract_video_detail(fields, &html, runtime_context, flow_context)?;
+                Ok(DetailResponse::Video(Box::new(detail)))
             }
-            DetailFields::Audio(_) => {
-                // TODO: 实现音频详情提取
-                Ok(DetailResponse::Other(serde_json::json!({"type": "audio"})))
+            DetailFields::Audio(fields) => {
+                let detail =
+                    Self::extract_audio_detail(fields, &html, runtime_context, flow_context)?;
+                Ok(DetailResponse::Audio(Box::new(detail)))
             }
-            DetailFields::Manga(_) => {
-                // TODO: 实现漫画详情提取
-                Ok(DetailResponse::Other(serde_json::json!({"type": "manga"})))
+            DetailFields::Manga(fields) => {
+                let detail =
+                    Self::extract_manga_detail(fields, &html, runtime_context, flow_context)?;
+                Ok(DetailResponse::Manga(Box::new(detail)))
             }
         }
     }
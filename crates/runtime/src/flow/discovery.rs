@@ -2,51 +2,337 @@
 
 use crate::{
     Result,
-    context::{FlowContext, RuntimeContext},
+    context::{FlowContext, LimitsExt, RuntimeContext},
+    extractor::SharedValue,
+    flow::list::{self, ListPage, ListRule},
+    model::SearchItem,
+    script::builtin::core,
 };
-use crawler_schema::flow::DiscoveryFlow;
+use crawler_schema::{
+    extract::FieldExtractor,
+    flow::{
+        DiscoveryFlow,
+        DynamicFilterList,
+        DynamicOptionList,
+        FilterGroup,
+        FilterList,
+        FilterOption,
+        OptionItem,
+        OptionList,
+    },
+};
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use std::{collections::HashMap, time::Duration};
+
+/// 动态分类/筛选器的默认缓存时间（秒）
+const DEFAULT_OPTIONS_CACHE_DURATION: u32 = 3600;
 
 /// 发现请求
 #[derive(Debug, Clone)]
 pub struct DiscoveryRequest {
     /// 筛选条件
-    pub filters: std::collections::HashMap<String, String>,
+    pub filters: HashMap<String, String>,
     /// 页码
     pub page: u32,
 }
 
 /// 发现响应
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct DiscoveryResponse {
     /// 结果列表
-    pub items: Vec<serde_json::Value>,
+    pub items: Vec<SearchItem>,
     /// 是否有下一页
     pub has_next: bool,
+    /// 总页数（配置了总数提取规则且提取成功时）
+    pub total_pages: Option<u32>,
+    /// 原始数据
+    pub raw_items: Vec<Value>,
+    /// 因必填字段缺失而被跳过的项数
+    pub skipped: usize,
+}
+
+impl DiscoveryResponse {
+    /// 转换为 JSON，供宿主 App 或 FFI 传出
+    pub fn to_json(&self) -> Value {
+        serde_json::to_value(self).unwrap_or(Value::Null)
+    }
 }
 
 /// 发现流程执行器
 pub struct DiscoveryFlowExecutor;
 
 impl DiscoveryFlowExecutor {
+    /// 提取字段值为字符串，提取失败或为空视为缺失
+    fn extract_string(
+        extractor: &FieldExtractor,
+        input: &SharedValue,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> Result<Option<String>> {
+        Ok(
+            list::extract_string(extractor, input, runtime_context, flow_context)?
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty()),
+        )
+    }
+
+    /// 请求 URL，返回解析后的文档
+    async fn fetch_document(
+        full_url: &str,
+        flow: &DiscoveryFlow,
+        runtime_context: &RuntimeContext,
+        flow_context: &mut FlowContext,
+    ) -> Result<SharedValue> {
+        list::fetch_document(
+            "discovery",
+            flow.http.as_ref(),
+            full_url,
+            runtime_context,
+            flow_context,
+        )
+        .await
+    }
+
+    /// 读取动态选项的缓存，不存在或无法解析时返回 None
+    fn cached<T: DeserializeOwned>(runtime_context: &RuntimeContext, key: &str) -> Option<T> {
+        let cached = runtime_context.cache_store().get(key)?;
+        serde_json::from_str(&cached)
+            .inspect_err(|e| tracing::debug!("动态选项缓存 '{}' 无法解析: {}", key, e))
+            .ok()
+    }
+
+    /// 写入动态选项的缓存，`cache_duration` 为 0 时不缓存
+    fn store<T: Serialize>(
+        runtime_context: &RuntimeContext,
+        key: &str,
+        value: &T,
+        cache_duration: Option<u32>,
+    ) {
+        let duration = cache_duration.unwrap_or(DEFAULT_OPTIONS_CACHE_DURATION);
+        if duration == 0 {
+            return;
+        }
+        if let Ok(json) = serde_json::to_string(value) {
+            runtime_context.cache_store().set(
+                key,
+                json,
+                Some(Duration::from_secs(u64::from(duration))),
+            );
+        }
+    }
+
+    /// 获取分类列表
+    ///
+    /// 静态列表原样返回；动态列表请求数据源并按 `fields` 提取，缺少 key 或 label 的项被跳过。
+    /// 动态结果按 URL 缓存 `cache_duration` 秒
+    pub async fn categories(
+        flow: &DiscoveryFlow,
+        runtime_context: &RuntimeContext,
+        flow_context: &mut FlowContext,
+    ) -> Result<Vec<OptionItem>> {
+        match &flow.categories {
+            None => Ok(Vec::new()),
+            Some(OptionList::Static(items)) => Ok(items.clone()),
+            Some(OptionList::Dynamic(dynamic)) => {
+                Self::fetch_options(dynamic, flow, runtime_context, flow_context).await
+            }
+        }
+    }
+
+    async fn fetch_options(
+        dynamic: &DynamicOptionList,
+        flow: &DiscoveryFlow,
+        runtime_context: &RuntimeContext,
+        flow_context: &mut FlowContext,
+    ) -> Result<Vec<OptionItem>> {
+        let url = list::render_url(&dynamic.url, flow_context)?;
+        let cache_key = format!("discovery:categories:{}", url);
+        if let Some(options) = Self::cached(runtime_context, &cache_key) {
+            return Ok(options);
+        }
+
+        let document = Self::fetch_document(&url, flow, runtime_context, flow_context).await?;
+        let fields = &dynamic.fields;
+
+        let mut options = Vec::new();
+        for item in list::extract_list(&dynamic.list, &document, runtime_context, flow_context)? {
+            let key = Self::extract_string(&fields.key, &item, runtime_context, flow_context)?;
+            let label = Self::extract_string(&fields.label, &item, runtime_context, flow_context)?;
+            let (Some(key), Some(label)) = (key, label) else {
+                tracing::warn!("跳过缺少 key 或 label 的动态分类项");
+                continue;
+            };
+            let value = fields
+                .value
+                .as_ref()
+                .map(|f| Self::extract_string(f, &item, runtime_context, flow_context))
+                .transpose()?
+                .flatten();
+            options.push(OptionItem { key, label, value });
+        }
+
+        Self::store(
+            runtime_context,
+            &cache_key,
+            &options,
+            dynamic.cache_duration,
+        );
+        Ok(options)
+    }
+
+    /// 获取筛选器组列表
+    ///
+    /// 静态列表原样返回；动态列表请求数据源并按 `fields` 提取，
+    /// 缺少 key 或 name 的筛选组与选项被跳过。动态结果按 URL 缓存 `cache_duration` 秒
+    pub async fn filters(
+        flow: &DiscoveryFlow,
+        runtime_context: &RuntimeContext,
+        flow_context: &mut FlowContext,
+    ) -> Result<Vec<FilterGroup>> {
+        match &flow.filters {
+            None => Ok(Vec::new()),
+            Some(FilterList::Static(groups)) => Ok(groups.clone()),
+            Some(FilterList::Dynamic(dynamic)) => {
+                Self::fetch_filters(dynamic, flow, runtime_context, flow_context).await
+            }
+        }
+    }
+
+    async fn fetch_filters(
+        dynamic: &DynamicFilterList,
+        flow: &DiscoveryFlow,
+        runtime_context: &RuntimeContext,
+        flow_context: &mut FlowContext,
+    ) -> Result<Vec<FilterGroup>> {
+        let url = list::render_url(&dynamic.url, flow_context)?;
+        let cache_key = format!("discovery:filters:{}", url);
+        if let Some(groups) = Self::cached(runtime_context, &cache_key) {
+            return Ok(groups);
+        }
+
+        let document = Self::fetch_document(&url, flow, runtime_context, flow_context).await?;
+        let fields = &dynamic.fields;
+
+        let mut groups = Vec::new();
+        for group in list::extract_list(&dynamic.list, &document, runtime_context, flow_context)? {
+            let key = Self::extract_string(&fields.key, &group, runtime_context, flow_context)?;
+            let name = Self::extract_string(&fields.name, &group, runtime_context, flow_context)?;
+            let (Some(key), Some(name)) = (key, name) else {
+                tracing::warn!("跳过缺少 key 或 name 的动态筛选组");
+                continue;
+            };
+            let multiselect = fields
+                .multiselect
+                .as_ref()
+                .map(|f| Self::extract_string(f, &group, runtime_context, flow_context))
+                .transpose()?
+                .flatten()
+                .is_some_and(|s| core::to_bool(&s));
+
+            let option_fields = &fields.options.fields;
+            let mut options = Vec::new();
+            for option in
+                list::extract_list(&fields.options.list, &group, runtime_context, flow_context)?
+            {
+                let value = Self::extract_string(
+                    &option_fields.key,
+                    &option,
+                    runtime_context,
+                    flow_context,
+                )?;
+                let name = Self::extract_string(
+                    &option_fields.name,
+                    &option,
+                    runtime_context,
+                    flow_context,
+                )?;
+                // 选项值允许为空（常用于"全部"），名称缺失时跳过
+                let Some(name) = name else {
+                    continue;
+                };
+                options.push(FilterOption {
+                    name,
+                    value: value.unwrap_or_default(),
+                });
+            }
+
+            groups.push(FilterGroup {
+                name,
+                key,
+                multiselect,
+                options,
+            });
+        }
+
+        Self::store(runtime_context, &cache_key, &groups, dynamic.cache_duration);
+        Ok(groups)
+    }
+
     /// 执行发现流程
+    ///
+    /// 选中的筛选值以筛选器 key 为变量名注入上下文；
+    /// 静态筛选器未选择时使用其第一个选项的值
     pub async fn execute(
         input: DiscoveryRequest,
         flow: &DiscoveryFlow,
-        _runtime_context: &RuntimeContext,
+        runtime_context: &RuntimeContext,
         flow_context: &mut FlowContext,
     ) -> Result<DiscoveryResponse> {
+        // 获取 base_url
+        let base_url = runtime_context
+            .globals()
+            .get("base_url")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string();
+
+        // 检查页数限制
+        let pagination = flow.pagination.as_ref();
+        runtime_context
+            .limits()
+            .check_page(list::page_index(input.page, pagination), pagination)?;
+
         // 设置上下文变量
+        if let Some(FilterList::Static(groups)) = &flow.filters {
+            for group in groups {
+                if !input.filters.contains_key(&group.key) {
+                    let default = group.options.first().map(|o| o.value.as_str());
+                    flow_context.set(&group.key, serde_json::json!(default.unwrap_or("")));
+                }
+            }
+        }
         for (key, value) in &input.filters {
             flow_context.set(key, serde_json::json!(value));
         }
         flow_context.set("page", serde_json::json!(input.page));
+        flow_context.set("base_url", serde_json::json!(&base_url));
+
+        // 1. 请求列表页
+        let url = list::render_url(&flow.url, flow_context)?;
+        let document = Self::fetch_document(&url, flow, runtime_context, flow_context).await?;
 
-        // TODO: 实现发现流程
-        let _ = flow;
+        // 2. 提取列表项并判断是否有下一页，规则同搜索流程
+        let rule = ListRule {
+            list: &flow.list,
+            fields: &flow.fields,
+            pagination,
+            skip_invalid_items: flow.skip_invalid_items,
+        };
+        let ListPage {
+            items,
+            raw_items,
+            skipped,
+            total_pages,
+            has_next,
+        } = ListPage::extract(&rule, &document, input.page, runtime_context, flow_context)?;
 
         Ok(DiscoveryResponse {
-            items: vec![],
-            has_next: false,
+            items,
+            has_next,
+            total_pages,
+            raw_items,
+            skipped,
         })
     }
 }
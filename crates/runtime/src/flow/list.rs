@@ -0,0 +1,320 @@
+//! # 列表页处理
+//!
+//! 搜索与发现流程共用的列表页逻辑：请求列表页、提取列表项字段、补全相对 URL、
+//! 页内去重、触发进度事件，并判断是否有下一页
+
+use crate::{
+    Result,
+    context::{FlowContext, LimitsExt, RuntimeContext},
+    error::RuntimeError,
+    extractor::{ExtractEngine, SharedValue, value::ExtractValueData},
+    flow::pager,
+    http::RESPONSE_VAR,
+    model::SearchItem,
+    template::TemplateExt,
+};
+use crawler_schema::{
+    config::HttpConfig,
+    extract::FieldExtractor,
+    fields::ItemFields,
+    flow::Pagination,
+    template::Template,
+};
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+
+/// 列表页规则
+pub(crate) struct ListRule<'a> {
+    /// 列表提取规则
+    pub list: &'a FieldExtractor,
+    /// 列表项字段
+    pub fields: &'a ItemFields,
+    /// 分页配置
+    pub pagination: Option<&'a Pagination>,
+    /// 是否跳过缺失必填字段的列表项
+    pub skip_invalid_items: bool,
+}
+
+/// 处理后的列表页
+pub(crate) struct ListPage {
+    /// 列表项
+    pub items: Vec<SearchItem>,
+    /// 列表项原始数据
+    pub raw_items: Vec<Value>,
+    /// 因必填字段缺失而被跳过的项数
+    pub skipped: usize,
+    /// 总页数
+    pub total_pages: Option<u32>,
+    /// 是否有下一页
+    pub has_next: bool,
+}
+
+impl ListPage {
+    /// 从列表页文档中提取第 `page` 页的列表项
+    ///
+    /// 到达页数上限时没有下一页；否则优先按总页数判断，总页数未知时有结果就认为可能有下一页
+    pub(crate) fn extract(
+        rule: &ListRule<'_>,
+        document: &SharedValue,
+        page: u32,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> Result<Self> {
+        let base_url = flow_context
+            .resolve("base_url")
+            .and_then(|v| v.as_str())
+            .unwrap_or("");
+        let item_values = extract_list(rule.list, document, runtime_context, flow_context)?;
+
+        let mut items = Vec::new();
+        let mut raw_items = Vec::new();
+        let mut skipped = 0;
+        for item_value in &item_values {
+            match extract_item(
+                rule.fields,
+                item_value,
+                runtime_context,
+                flow_context,
+                base_url,
+            ) {
+                Ok(item) => {
+                    raw_items.push(item.raw.clone());
+                    items.push(item);
+                }
+                Err(e) if rule.skip_invalid_items && !e.is_interruption() => {
+                    tracing::warn!("跳过缺失必填字段的列表项: {}", e);
+                    skipped += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        // 页内去重，跨页去重由分页器完成
+        if let Some(field) = rule.pagination.and_then(|p| p.dedup_by()) {
+            let mut seen = HashSet::new();
+            let (kept, kept_raw) = items
+                .into_iter()
+                .zip(raw_items)
+                .filter(|(item, _)| item.dedup_key(field).is_none_or(|key| seen.insert(key)))
+                .unzip();
+            items = kept;
+            raw_items = kept_raw;
+        }
+
+        if let Some(listener) = runtime_context.progress_listener() {
+            for raw in &raw_items {
+                listener.on_item(raw);
+            }
+            listener.on_page(page, items.len());
+        }
+
+        let page_index = page_index(page, rule.pagination);
+        let total_pages = rule.pagination.and_then(|p| {
+            pager::extract_total_pages(
+                p,
+                document.as_ref(),
+                (page_index == 0).then_some(item_values.len()),
+                runtime_context,
+                flow_context,
+            )
+        });
+        let page_limit_reached = runtime_context
+            .limits()
+            .check_page(page_index + 1, rule.pagination)
+            .is_err();
+        let has_next = match (total_pages, rule.pagination) {
+            _ if page_limit_reached => false,
+            (Some(total), Some(p)) => !pager::is_last_page(page, p.first_page(), total),
+            _ => !items.is_empty(),
+        };
+
+        Ok(Self {
+            items,
+            raw_items,
+            skipped,
+            total_pages,
+            has_next,
+        })
+    }
+}
+
+/// 第 `page` 页相对首页的序号（从 0 开始）
+pub(crate) fn page_index(page: u32, pagination: Option<&Pagination>) -> u32 {
+    page.saturating_sub(pagination.map_or(1, |p| p.first_page()))
+}
+
+/// 相对 URL 按 `base_url` 补全为绝对 URL
+pub(crate) fn resolve_url(base_url: &str, url: String) -> String {
+    if url.starts_with("http") || base_url.is_empty() {
+        url
+    } else if url.starts_with('/') {
+        format!("{}{}", base_url.trim_end_matches('/'), url)
+    } else {
+        format!("{}/{}", base_url.trim_end_matches('/'), url)
+    }
+}
+
+/// 渲染 URL 模板，相对路径按上下文中的 `base_url` 补全
+pub(crate) fn render_url(url: &Template, flow_context: &FlowContext) -> Result<String> {
+    let url = url.render(flow_context)?;
+    let base_url = flow_context
+        .resolve("base_url")
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    Ok(resolve_url(base_url, url))
+}
+
+/// 请求列表页，返回解析后的文档
+///
+/// 记录请求次数并触发 `on_request`，响应写入 `$response` 变量
+pub(crate) async fn fetch_document(
+    flow_name: &str,
+    http: Option<&HttpConfig>,
+    full_url: &str,
+    runtime_context: &RuntimeContext,
+    flow_context: &mut FlowContext,
+) -> Result<SharedValue> {
+    flow_context.set("request_url", serde_json::json!(full_url));
+    flow_context.record_request()?;
+    if let Some(listener) = runtime_context.progress_listener() {
+        listener.on_request(full_url);
+    }
+
+    let client = runtime_context.flow_http_client(flow_name, http)?;
+    let response = client.fetch(full_url, flow_context).await?;
+    let response = client.read_response(response).await?;
+    flow_context.set(RESPONSE_VAR, response.to_value());
+    Ok(response.into_document(
+        client
+            .config()
+            .response
+            .as_ref()
+            .and_then(|r| r.content_type.as_ref()),
+    ))
+}
+
+/// 提取字段值为字符串
+///
+/// 提取失败视为字段缺失，超时、超限等中断类错误原样返回
+pub(crate) fn extract_string(
+    extractor: &FieldExtractor,
+    input: &SharedValue,
+    runtime_context: &RuntimeContext,
+    flow_context: &FlowContext,
+) -> Result<Option<String>> {
+    match ExtractEngine::extract_field(extractor, input.as_ref(), runtime_context, flow_context) {
+        Ok(value) => Ok(value.to_str_lossy().map(|s| s.into_owned())),
+        Err(e) if e.is_interruption() => Err(e),
+        Err(_) => Ok(None),
+    }
+}
+
+/// 提取列表，单个节点或 JSON 对象视为只有一项，JSON 数组按元素展开
+pub(crate) fn extract_list(
+    extractor: &FieldExtractor,
+    input: &SharedValue,
+    runtime_context: &RuntimeContext,
+    flow_context: &FlowContext,
+) -> Result<Vec<SharedValue>> {
+    let list =
+        ExtractEngine::extract_field(extractor, input.as_ref(), runtime_context, flow_context)?;
+    Ok(match list.as_ref() {
+        ExtractValueData::Array(arr) => arr.iter().cloned().collect(),
+        ExtractValueData::Json(v) if v.is_array() => list.as_array().unwrap_or_default(),
+        ExtractValueData::Html(_) | ExtractValueData::Xml(_) => vec![list.clone()],
+        ExtractValueData::Json(v) if v.is_object() => vec![list.clone()],
+        _ => Vec::new(),
+    })
+}
+
+/// 从列表项提取搜索结果
+pub(crate) fn extract_item(
+    fields: &ItemFields,
+    item_html: &SharedValue,
+    runtime_context: &RuntimeContext,
+    flow_context: &FlowContext,
+    base_url: &str,
+) -> Result<SearchItem> {
+    // 提取必需字段
+    let title = extract_string(
+        &fields.title.extractor,
+        item_html,
+        runtime_context,
+        flow_context,
+    )?
+    .ok_or_else(|| RuntimeError::FieldEmpty {
+        field: "title".to_string(),
+    })?;
+
+    let url = extract_string(
+        &fields.url.extractor,
+        item_html,
+        runtime_context,
+        flow_context,
+    )?
+    .ok_or_else(|| RuntimeError::FieldEmpty {
+        field: "url".to_string(),
+    })?;
+
+    // 处理相对 URL
+    let url = resolve_url(base_url, url);
+
+    // 提取可选字段
+    let cover = fields
+        .cover
+        .as_ref()
+        .map(|f| extract_string(&f.extractor, item_html, runtime_context, flow_context))
+        .transpose()?
+        .flatten();
+
+    let summary = fields
+        .summary
+        .as_ref()
+        .map(|f| extract_string(&f.extractor, item_html, runtime_context, flow_context))
+        .transpose()?
+        .flatten();
+
+    let author = fields
+        .author
+        .as_ref()
+        .map(|f| extract_string(&f.extractor, item_html, runtime_context, flow_context))
+        .transpose()?
+        .flatten();
+
+    let latest = fields
+        .latest
+        .as_ref()
+        .map(|f| extract_string(&f.extractor, item_html, runtime_context, flow_context))
+        .transpose()?
+        .flatten();
+
+    // 构建原始数据
+    let mut raw: Map<String, Value> = Map::new();
+    raw.insert("title".to_string(), Value::String(title.clone()));
+    raw.insert("url".to_string(), Value::String(url.clone()));
+    if let Some(ref c) = cover {
+        raw.insert("cover".to_string(), Value::String(c.clone()));
+    }
+    if let Some(ref s) = summary {
+        raw.insert("summary".to_string(), Value::String(s.clone()));
+    }
+    if let Some(ref a) = author {
+        raw.insert("author".to_string(), Value::String(a.clone()));
+    }
+    if let Some(ref l) = latest {
+        raw.insert("latest".to_string(), Value::String(l.clone()));
+    }
+
+    Ok(SearchItem {
+        title,
+        url,
+        cover,
+        summary,
+        author,
+        latest,
+        score: None,
+        status: None,
+        category: None,
+        raw: Value::Object(raw),
+    })
+}
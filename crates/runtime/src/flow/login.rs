@@ -2,9 +2,17 @@
 
 use crate::{
     Result,
+    RuntimeError,
+    challenge::webview_script,
     context::{FlowContext, RuntimeContext},
+    template::TemplateExt,
+    webview::{WebViewCloseReason, WebViewRequest},
 };
-use crawler_schema::flow::LoginFlow;
+use crawler_schema::flow::{LoginFlow, WebViewLoginFlow};
+use std::time::Duration;
+
+/// 网页登录默认超时（秒）
+const DEFAULT_WEBVIEW_LOGIN_TIMEOUT: u32 = 300;
 
 /// 登录请求
 #[derive(Debug, Clone)]
@@ -32,19 +40,84 @@ impl LoginFlowExecutor {
     pub async fn execute(
         input: LoginRequest,
         flow: &LoginFlow,
-        _runtime_context: &RuntimeContext,
+        runtime_context: &RuntimeContext,
         flow_context: &mut FlowContext,
     ) -> Result<LoginResponse> {
         // 设置上下文变量
         flow_context.set("username", serde_json::json!(input.username));
         flow_context.set("password", serde_json::json!(input.password));
 
-        // TODO: 实现登录流程
-        let _ = flow;
+        match flow {
+            LoginFlow::Webview(flow) => {
+                Self::execute_webview(flow, runtime_context, flow_context).await
+            }
+            // TODO: 实现脚本与凭证登录流程
+            _ => Ok(LoginResponse {
+                success: false,
+                session: None,
+            }),
+        }
+    }
+
+    /// 执行网页登录
+    ///
+    /// 登录成功后的会话包含 `cookies` 与 `extra`：
+    /// `extra` 由 localStorage 数据与 finish_script 返回的对象合并而成
+    async fn execute_webview(
+        flow: &WebViewLoginFlow,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> Result<LoginResponse> {
+        let url = flow.start_url.render(flow_context)?;
+        let timeout = flow
+            .timeout_seconds
+            .unwrap_or(DEFAULT_WEBVIEW_LOGIN_TIMEOUT);
+
+        let mut request =
+            WebViewRequest::new(url).with_timeout(Duration::from_secs(timeout as u64));
+        request.allow_redirects = flow.allow_redirects;
+        if let Some(ua) = &flow.user_agent {
+            request = request.with_user_agent(ua);
+        }
+        if let Some(script) = &flow.inject_script {
+            request = request.with_inject_script(script);
+        }
+        if let Some(check) = &flow.check_script {
+            request = request.with_success_check(check);
+        }
+        if let Some(interval) = flow.check_interval_ms {
+            request = request.with_check_interval(Duration::from_millis(interval as u64));
+        }
+        if let Some(keys) = &flow.extract_local_storage {
+            request = request.with_extract_local_storage(keys.clone());
+        }
+        if let Some(script) = flow.finish_script.as_ref().and_then(webview_script) {
+            request = request.with_finish_script(script);
+        }
+
+        let response = runtime_context.webview_provider().open(request).await?;
+        if !response.success {
+            return match response.close_reason {
+                WebViewCloseReason::Timeout => Err(RuntimeError::WebViewTimeout),
+                WebViewCloseReason::UserClosed => Err(RuntimeError::WebViewUserClosed),
+                _ => Ok(LoginResponse {
+                    success: false,
+                    session: None,
+                }),
+            };
+        }
+
+        // 完成脚本返回的同名键覆盖 localStorage
+        let script_result = response.script_result_map();
+        let mut extra = response.local_storage;
+        extra.extend(script_result);
 
         Ok(LoginResponse {
-            success: false,
-            session: None,
+            success: true,
+            session: Some(serde_json::json!({
+                "cookies": response.cookies,
+                "extra": extra,
+            })),
         })
     }
 }
@@ -14,6 +14,7 @@ pub mod content;
 pub mod detail;
 pub mod discovery;
 pub mod executor;
+mod list;
 pub mod login;
 pub mod pager;
 pub mod search;
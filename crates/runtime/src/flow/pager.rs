@@ -2,10 +2,18 @@
 //!
 //! 为流程结果提供链式分页能力
 
-use crate::{Result, context::RuntimeContext};
+use crate::{
+    Result,
+    context::{FlowContext, LimitsExt, RuntimeContext},
+    extractor::{ExtractEngine, value::ExtractValueData},
+    model::SearchItem,
+};
 use crawler_schema::flow::common::Pagination;
 use serde_json::Value;
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 /// 分页状态 trait
 ///
@@ -30,6 +38,8 @@ pub struct SearchPagerState {
     pub page: u32,
     /// 游标（用于游标分页）
     pub cursor: Option<String>,
+    /// 已出现过的条目去重键（跨页保留）
+    pub seen: HashSet<String>,
 }
 
 impl SearchPagerState {
@@ -39,9 +49,23 @@ impl SearchPagerState {
             keyword: keyword.into(),
             page,
             cursor: None,
+            seen: HashSet::new(),
         }
     }
 
+    /// 按字段去重，丢弃已出现过的条目并记录新条目
+    ///
+    /// 缺少去重字段的条目原样保留
+    pub fn dedup(&mut self, items: Vec<SearchItem>, field: &str) -> Vec<SearchItem> {
+        items
+            .into_iter()
+            .filter(|item| match item.dedup_key(field) {
+                Some(key) => self.seen.insert(key),
+                None => true,
+            })
+            .collect()
+    }
+
     /// 设置游标
     pub fn with_cursor(mut self, cursor: impl Into<String>) -> Self {
         self.cursor = Some(cursor.into());
@@ -59,6 +83,7 @@ impl PagerState for SearchPagerState {
             keyword: self.keyword.clone(),
             page,
             cursor: None, // 切换页码时清除游标
+            seen: self.seen.clone(),
         }
     }
 
@@ -143,6 +168,8 @@ pub struct Pager<S: PagerState> {
     state: S,
     /// 下一页游标（从响应中提取）
     next_cursor: Option<String>,
+    /// 总页数（首次请求后从响应中提取）
+    total_pages: Option<u32>,
 }
 
 impl<S: PagerState> Pager<S> {
@@ -153,6 +180,7 @@ impl<S: PagerState> Pager<S> {
             pagination,
             state,
             next_cursor: None,
+            total_pages: None,
         }
     }
 
@@ -197,8 +225,46 @@ impl<S: PagerState> Pager<S> {
         self.next_cursor.as_deref()
     }
 
+    /// 设置总页数
+    ///
+    /// 已记录的总页数不会被覆盖，只有首次提取到的值生效
+    pub fn set_total_pages(&mut self, total_pages: Option<u32>) {
+        if self.total_pages.is_none() {
+            self.total_pages = total_pages;
+        }
+    }
+
+    /// 获取总页数
+    #[inline]
+    pub fn total_pages(&self) -> Option<u32> {
+        self.total_pages
+    }
+
+    /// 当前页是否已到达总页数（总页数未知时为 false）
+    pub fn is_last_page(&self) -> bool {
+        let first_page = self.pagination.as_ref().map_or(1, |p| p.first_page());
+        self.total_pages
+            .is_some_and(|total| is_last_page(self.current_page(), first_page, total))
+    }
+
+    /// 是否已到达最大页数（`limits.max_pages` 与分页配置的限制取较小值）
+    pub fn is_page_limit_reached(&self) -> bool {
+        let first_page = self.pagination.as_ref().map_or(1, |p| p.first_page());
+        let next_index = self.current_page().saturating_sub(first_page) + 1;
+        self.runtime
+            .limits()
+            .check_page(next_index, self.pagination.as_ref())
+            .is_err()
+    }
+
     /// 创建下一页的分页器
+    ///
+    /// 已知总页数且当前页为最后一页，或已到达最大页数时返回 None
     pub fn next_page_pager(&self) -> Option<Self> {
+        if self.is_last_page() || self.is_page_limit_reached() {
+            return None;
+        }
+
         // 如果是游标分页，需要有游标才能翻页
         if let Some(Pagination::Cursor(_)) = &self.pagination {
             let cursor = self.next_cursor.clone()?;
@@ -209,6 +275,7 @@ impl<S: PagerState> Pager<S> {
                 pagination: self.pagination.clone(),
                 state: new_state,
                 next_cursor: Some(cursor),
+                total_pages: self.total_pages,
             });
         }
 
@@ -218,6 +285,7 @@ impl<S: PagerState> Pager<S> {
             pagination: self.pagination.clone(),
             state: self.state.with_page(self.state.current_page() + 1),
             next_cursor: None,
+            total_pages: self.total_pages,
         })
     }
 
@@ -233,6 +301,7 @@ impl<S: PagerState> Pager<S> {
             pagination: self.pagination.clone(),
             state: self.state.with_page(current - 1),
             next_cursor: None,
+            total_pages: self.total_pages,
         })
     }
 
@@ -250,6 +319,7 @@ impl<S: PagerState> Pager<S> {
             pagination: self.pagination.clone(),
             state: self.state.with_page(page),
             next_cursor: None,
+            total_pages: self.total_pages,
         })
     }
 
@@ -266,8 +336,68 @@ impl<S: PagerState> Pager<S> {
     }
 }
 
+/// 从响应中提取总页数
+///
+/// 优先使用 `total_pages` 规则；否则按 `total_items` 与每页数量换算，
+/// 未配置每页数量时以首页条目数估算：`first_page_len` 为首页条目数，
+/// 非首页时传 None（末页条目数偏少，不能用于估算）。
+/// 未配置、提取失败或结果不是正整数时返回 None，由调用方回退到默认判断
+pub fn extract_total_pages(
+    pagination: &Pagination,
+    input: &ExtractValueData,
+    first_page_len: Option<usize>,
+    runtime_context: &RuntimeContext,
+    flow_context: &FlowContext,
+) -> Option<u32> {
+    let extract_count = |extractor| match ExtractEngine::extract_field(
+        extractor,
+        input,
+        runtime_context,
+        flow_context,
+    ) {
+        Ok(value) => value
+            .as_i64()
+            .and_then(|n| u32::try_from(n).ok())
+            .filter(|&n| n > 0),
+        Err(e) => {
+            tracing::debug!("提取分页总数失败，回退到默认判断: {}", e);
+            None
+        }
+    };
+
+    if let Some(extractor) = pagination.total_pages()
+        && let Some(total) = extract_count(extractor)
+    {
+        return Some(total);
+    }
+
+    let total_items = extract_count(pagination.total_items()?)?;
+    let page_size = pagination
+        .page_size()
+        .or_else(|| first_page_len.and_then(|len| u32::try_from(len).ok()))
+        .filter(|&n| n > 0)?;
+    Some(total_items.div_ceil(page_size))
+}
+
+/// 页码 `page` 是否为共 `total` 页中的最后一页（或已超出）
+pub fn is_last_page(page: u32, first_page: u32, total: u32) -> bool {
+    page.saturating_sub(first_page) + 1 >= total
+}
+
 /// 搜索分页器类型别名
 pub type SearchPager = Pager<SearchPagerState>;
 
+impl SearchPager {
+    /// 按分页配置的 `dedup_by` 去重
+    ///
+    /// 丢弃之前页面已出现过的条目，未配置时原样返回
+    pub fn dedup_items(&mut self, items: Vec<SearchItem>) -> Vec<SearchItem> {
+        match self.pagination.as_ref().and_then(|p| p.dedup_by()) {
+            Some(field) => self.state.dedup(items, field),
+            None => items,
+        }
+    }
+}
+
 /// 发现分页器类型别名
 pub type DiscoveryPager = Pager<DiscoveryPagerState>;
@@ -0,0 +1,99 @@
+//! # 响应编码
+//!
+//! 按配置的 `ResponseEncoding` 解码响应体。`auto` 时依次尝试：
+//! 1. `Content-Type` 响应头中的 `charset`
+//! 2. BOM
+//! 3. HTML 开头的 `<meta charset>` / `<meta http-equiv="Content-Type">` 声明
+//! 4. chardetng 按内容猜测
+
+use crawler_schema::config::ResponseEncoding;
+use encoding_rs::{Encoding, UTF_8};
+use regex::bytes::Regex;
+use std::sync::OnceLock;
+
+/// 扫描 meta 声明的字节数
+const META_SCAN_LIMIT: usize = 4096;
+
+/// 按配置解码响应体
+///
+/// `content_type` 为 `Content-Type` 响应头，`encoding` 为 None 时按 `auto` 处理
+pub fn decode_body(
+    bytes: &[u8],
+    content_type: Option<&str>,
+    encoding: Option<&ResponseEncoding>,
+) -> String {
+    let encoding = match encoding {
+        Some(ResponseEncoding::Auto) | None => detect_encoding(bytes, content_type),
+        Some(declared) => configured_encoding(declared),
+    };
+    // decode 会识别 BOM 并优先使用 BOM 指示的编码
+    let (text, used, had_errors) = encoding.decode(bytes);
+    if had_errors {
+        tracing::debug!("响应体按 {} 解码时存在无效字节", used.name());
+    }
+    text.into_owned()
+}
+
+/// 自动检测响应体编码
+pub fn detect_encoding(bytes: &[u8], content_type: Option<&str>) -> &'static Encoding {
+    if let Some(encoding) = content_type.and_then(header_charset) {
+        return encoding;
+    }
+    if let Some((encoding, _)) = Encoding::for_bom(bytes) {
+        return encoding;
+    }
+    if let Some(encoding) = meta_charset(bytes) {
+        return encoding;
+    }
+
+    let mut detector = chardetng::EncodingDetector::new();
+    detector.feed(bytes, true);
+    detector.guess(None, true)
+}
+
+/// 从 `Content-Type` 响应头中读取 charset
+fn header_charset(content_type: &str) -> Option<&'static Encoding> {
+    content_type.split(';').skip(1).find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        if !name.trim().eq_ignore_ascii_case("charset") {
+            return None;
+        }
+        Encoding::for_label(value.trim().trim_matches(['"', '\'']).as_bytes())
+    })
+}
+
+/// 扫描 HTML 开头的 meta 编码声明
+///
+/// 同时匹配 `<meta charset="gbk">` 与
+/// `<meta http-equiv="Content-Type" content="text/html; charset=gbk">`
+fn meta_charset(bytes: &[u8]) -> Option<&'static Encoding> {
+    static META_RE: OnceLock<Regex> = OnceLock::new();
+    let re = META_RE.get_or_init(|| {
+        Regex::new(r#"(?i-u)<meta\s[^>]*?charset\s*=\s*["']?\s*([a-z0-9_:.\-]+)"#).unwrap()
+    });
+
+    let head = &bytes[..bytes.len().min(META_SCAN_LIMIT)];
+    let label = re.captures(head)?.get(1)?.as_bytes();
+    let encoding = Encoding::for_label(label)?;
+    // 按 HTML 规范，meta 声明的 UTF-16 视为 UTF-8（能读到 ASCII 的 meta 就不可能是 UTF-16）
+    if encoding == encoding_rs::UTF_16LE || encoding == encoding_rs::UTF_16BE {
+        return Some(UTF_8);
+    }
+    Some(encoding)
+}
+
+/// 配置中显式指定的编码
+fn configured_encoding(encoding: &ResponseEncoding) -> &'static Encoding {
+    match encoding {
+        ResponseEncoding::Auto | ResponseEncoding::Utf8 => UTF_8,
+        // encoding_rs 的 GBK 解码器兼容 GB2312 与 GB18030 的双字节部分
+        ResponseEncoding::Gbk | ResponseEncoding::Gb2312 => encoding_rs::GBK,
+        ResponseEncoding::Gb18030 => encoding_rs::GB18030,
+        ResponseEncoding::Big5 => encoding_rs::BIG5,
+        ResponseEncoding::ShiftJis => encoding_rs::SHIFT_JIS,
+        ResponseEncoding::EucJp => encoding_rs::EUC_JP,
+        ResponseEncoding::EucKr => encoding_rs::EUC_KR,
+        // WHATWG 编码标准中 ISO-8859-1 即 windows-1252
+        ResponseEncoding::Iso8859_1 | ResponseEncoding::Windows1252 => encoding_rs::WINDOWS_1252,
+    }
+}
@@ -1,18 +1,73 @@
 //! # HTTP 客户端
 //!
-//! 封装 reqwest，提供连接池和重试机制
+//! 封装 reqwest，提供连接池、域名级限流和重试机制；
+//! 可选开启按 ETag/Last-Modified 协商的响应缓存，见 [`HttpClient::with_response_cache`]
 
-use crate::{Result, error::RuntimeError};
-use crawler_schema::config::HttpConfig;
-use std::time::Duration;
+use crate::{
+    Result,
+    context::FlowContext,
+    error::RuntimeError,
+    http::{
+        HostRateLimiter,
+        HttpConfigExt,
+        HttpResponse,
+        SharedCredentialsProvider,
+        credentials,
+        request::{PreparedRequest, RequestBody, RequestBuilder},
+    },
+    util::SharedCacheStore,
+};
+use crawler_schema::{
+    config::{HttpConfig, RequestConfig},
+    template::Template,
+};
+use dashmap::DashMap;
+use reqwest::{
+    StatusCode,
+    header::{HeaderMap, HeaderName, HeaderValue, IF_MODIFIED_SINCE, IF_NONE_MATCH, REFERER},
+};
+use std::{
+    fmt,
+    net::{IpAddr, SocketAddr},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+use tokio::io::AsyncWriteExt;
 
 /// HTTP 客户端
 ///
-/// 封装 reqwest::Client，提供连接池复用
-#[derive(Debug, Clone)]
+/// 封装 reqwest::Client，提供连接池复用；
+/// 通过 [`Self::for_flow`] 派生的客户端共享同一个域名级限流器、凭证提供者与 Referer 记录
+#[derive(Clone)]
 pub struct HttpClient {
     client: reqwest::Client,
     config: HttpConfig,
+    limiter: Arc<HostRateLimiter>,
+    credentials: Option<SharedCredentialsProvider>,
+    /// 各域名上一次请求的最终 URL，用于自动填充 Referer
+    referers: Arc<DashMap<String, String>>,
+    /// 协商缓存的响应存储
+    response_cache: Option<SharedCacheStore>,
+    /// Client 是否由集成方通过 [`Self::with_client`] 注入
+    injected: bool,
+}
+
+/// 响应缓存键前缀
+const RESPONSE_CACHE_PREFIX: &str = "http:response:";
+
+impl fmt::Debug for HttpClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HttpClient")
+            .field("client", &self.client)
+            .field("config", &self.config)
+            .field("limiter", &self.limiter)
+            .field("credentials", &self.credentials.is_some())
+            .field("referers", &self.referers.len())
+            .field("response_cache", &self.response_cache.is_some())
+            .field("injected", &self.injected)
+            .finish()
+    }
 }
 
 impl HttpClient {
@@ -46,11 +101,18 @@ impl HttpClient {
             client_builder = client_builder.danger_accept_invalid_certs(!verify);
         }
 
-        // 配置代理
-        if let Some(proxy) = &config.proxy {
-            let proxy = reqwest::Proxy::all(proxy)
-                .map_err(|e| RuntimeError::HttpConfig(format!("Invalid proxy: {}", e)))?;
-            client_builder = client_builder.proxy(proxy);
+        // 配置代理，空字符串表示不使用代理（包括系统代理）
+        match config.proxy.as_deref().map(str::trim) {
+            Some("") => client_builder = client_builder.no_proxy(),
+            Some(proxy) => client_builder = client_builder.proxy(parse_proxy(proxy)?),
+            None => {}
+        }
+
+        // 配置域名解析覆盖
+        if let Some(overrides) = &config.dns_overrides {
+            for (host, target) in overrides {
+                client_builder = client_builder.resolve(host, parse_dns_target(host, target)?);
+            }
         }
 
         // 配置连接池
@@ -60,7 +122,82 @@ impl HttpClient {
             .build()
             .map_err(|e| RuntimeError::HttpConfig(format!("Failed to build client: {}", e)))?;
 
-        Ok(Self { client, config })
+        Ok(Self {
+            injected: false,
+            ..Self::with_client(client, config)
+        })
+    }
+
+    /// 使用外部构造好的 reqwest::Client 创建客户端
+    ///
+    /// 适用于集成方复用自带代理池、证书等配置的 Client；
+    /// `config` 中的连接参数（超时、代理等）不会再应用到 Client 上，仅请求级配置生效；
+    /// 流程级配置覆盖连接参数时同样沿用该 Client，见 [`Self::for_flow`]
+    pub fn with_client(client: reqwest::Client, config: HttpConfig) -> Self {
+        let limiter = Arc::new(HostRateLimiter::new(&config));
+        Self {
+            client,
+            config,
+            limiter,
+            credentials: None,
+            referers: Arc::default(),
+            response_cache: None,
+            injected: true,
+        }
+    }
+
+    /// 注册凭证提供者
+    ///
+    /// 每次请求前按域名查询未过期的验证凭证，自动合并到 Cookie 与请求头
+    pub fn with_credentials_provider(mut self, provider: SharedCredentialsProvider) -> Self {
+        self.credentials = Some(provider);
+        self
+    }
+
+    /// 开启协商缓存
+    ///
+    /// 响应带 `ETag` 或 `Last-Modified` 时，[`Self::read_response`] 将其按 URL 存入 `store`；
+    /// 之后经 [`Self::fetch`] 对同一 URL 的 GET 请求自动附加 `If-None-Match`/`If-Modified-Since`，
+    /// 服务端返回 304 时 [`Self::read_response`] 返回缓存的响应
+    pub fn with_response_cache(mut self, store: SharedCacheStore) -> Self {
+        self.response_cache = Some(store);
+        self
+    }
+
+    /// 派生流程级客户端
+    ///
+    /// 流程级配置中非 None 的字段覆盖当前配置；
+    /// 仅当覆盖了连接参数（连接超时、代理、SSL、重定向、域名解析）时才创建新的连接池，
+    /// 否则复用当前 Client，请求超时、请求头等按请求应用。
+    /// Client 由集成方注入时始终复用，以保留其代理池、证书等配置，流程级连接参数被忽略并输出警告。
+    ///
+    /// 派生客户端沿用当前的域名级限流器，流程级的 `request_delay`、`max_concurrent` 不生效
+    pub fn for_flow(&self, flow: &HttpConfig) -> Result<Self> {
+        let merged = self.config.merge(flow);
+        let needs_new_client = flow.connect_timeout.is_some()
+            || flow.proxy.is_some()
+            || flow.verify_ssl.is_some()
+            || flow.follow_redirects.is_some()
+            || flow.max_redirects.is_some()
+            || flow.dns_overrides.is_some();
+
+        if needs_new_client && self.injected {
+            tracing::warn!("注入的 HTTP Client 不应用流程级连接参数，已忽略");
+        }
+
+        let mut client = if needs_new_client && !self.injected {
+            Self::new(merged)?
+        } else {
+            Self {
+                injected: self.injected,
+                ..Self::with_client(self.client.clone(), merged)
+            }
+        };
+        client.limiter = self.limiter.clone();
+        client.credentials = self.credentials.clone();
+        client.referers = self.referers.clone();
+        client.response_cache = self.response_cache.clone();
+        Ok(client)
     }
 
     /// 获取底层 reqwest::Client
@@ -73,16 +210,30 @@ impl HttpClient {
         &self.config
     }
 
-    /// 发起 GET 请求
-    pub async fn get(&self, url: &str) -> Result<reqwest::Response> {
-        let mut request = self.client.get(url);
+    /// 获取域名级限流器
+    pub fn rate_limiter(&self) -> &Arc<HostRateLimiter> {
+        &self.limiter
+    }
+
+    /// 指定域名上一次请求的最终 URL（仅在开启 `auto_referer` 时记录）
+    pub fn last_url(&self, host: &str) -> Option<String> {
+        self.referers.get(host).map(|url| url.clone())
+    }
+
+    /// 创建请求并应用全局请求头与 User-Agent
+    ///
+    /// 含模板语法的请求头需要流程上下文，只由 [`Self::fetch`] 渲染后发送
+    fn base_request(&self, method: reqwest::Method, url: &str) -> reqwest::RequestBuilder {
+        let mut request = self.client.request(method, url);
 
         // 应用全局请求头
         if let Some(req_config) = &self.config.request
             && let Some(headers) = &req_config.headers
         {
             for (key, value) in headers {
-                request = request.header(key, value.as_str());
+                if !is_template(value) {
+                    request = request.header(key, value.as_str());
+                }
             }
         }
 
@@ -91,28 +242,19 @@ impl HttpClient {
             request = request.header("User-Agent", ua);
         }
 
-        self.execute_with_retry(request).await
+        request
+    }
+
+    /// 发起 GET 请求
+    pub async fn get(&self, url: &str) -> Result<reqwest::Response> {
+        let request = self.base_request(reqwest::Method::GET, url);
+        self.execute_with_retry(request, false).await
     }
 
     /// 发起 POST 请求
     pub async fn post(&self, url: &str, body: String) -> Result<reqwest::Response> {
-        let mut request = self.client.post(url).body(body);
-
-        // 应用全局请求头
-        if let Some(req_config) = &self.config.request
-            && let Some(headers) = &req_config.headers
-        {
-            for (key, value) in headers {
-                request = request.header(key, value.as_str());
-            }
-        }
-
-        // 应用 User-Agent
-        if let Some(ua) = &self.config.user_agent {
-            request = request.header("User-Agent", ua);
-        }
-
-        self.execute_with_retry(request).await
+        let request = self.base_request(reqwest::Method::POST, url).body(body);
+        self.execute_with_retry(request, false).await
     }
 
     /// 发起 POST 表单请求
@@ -121,33 +263,216 @@ impl HttpClient {
         url: &str,
         form: &[(String, String)],
     ) -> Result<reqwest::Response> {
-        let mut request = self.client.post(url).form(form);
+        let request = self.base_request(reqwest::Method::POST, url).form(form);
+        self.execute_with_retry(request, false).await
+    }
 
-        // 应用全局请求头
-        if let Some(req_config) = &self.config.request
-            && let Some(headers) = &req_config.headers
-        {
-            for (key, value) in headers {
-                request = request.header(key, value.as_str());
+    /// 下载响应体为字节，用于图片、封面等二进制内容
+    ///
+    /// 状态码不是 2xx 时返回 [`RuntimeError::HttpStatus`]
+    pub async fn get_bytes(&self, url: &str) -> Result<Vec<u8>> {
+        let response = error_for_status(self.get(url).await?)?;
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| RuntimeError::HttpRequest(format!("读取响应失败: {}", e)))?;
+        Ok(bytes.to_vec())
+    }
+
+    /// 流式下载到文件，返回写入的字节数
+    ///
+    /// 响应体按块写入，不会整体载入内存；每写入一块调用一次 `on_progress(已下载字节数, 总字节数)`，
+    /// 总字节数取自 `Content-Length`，未知时为 None。
+    /// 先写入同目录下的 `.part` 临时文件，完成后再替换目标文件，下载失败不会留下不完整的文件
+    pub async fn download_to(
+        &self,
+        url: &str,
+        path: impl AsRef<Path>,
+        mut on_progress: impl FnMut(u64, Option<u64>),
+    ) -> Result<u64> {
+        let path = path.as_ref();
+        let mut response = error_for_status(self.get(url).await?)?;
+        let total = response.content_length();
+
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".part");
+        let tmp = PathBuf::from(tmp);
+        let io_error = |e: std::io::Error| {
+            RuntimeError::HttpRequest(format!("写入文件 '{}' 失败: {}", path.display(), e))
+        };
+
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            tokio::fs::create_dir_all(parent).await.map_err(io_error)?;
+        }
+        let mut file = tokio::fs::File::create(&tmp).await.map_err(io_error)?;
+
+        let written = async {
+            let mut written = 0u64;
+            while let Some(chunk) = response
+                .chunk()
+                .await
+                .map_err(|e| RuntimeError::HttpRequest(format!("读取响应失败: {}", e)))?
+            {
+                file.write_all(&chunk).await.map_err(io_error)?;
+                written += chunk.len() as u64;
+                on_progress(written, total);
             }
+            file.flush().await.map_err(io_error)?;
+            Ok(written)
         }
+        .await;
 
-        // 应用 User-Agent
-        if let Some(ua) = &self.config.user_agent {
-            request = request.header("User-Agent", ua);
+        drop(file);
+        match written {
+            Ok(written) => {
+                tokio::fs::rename(&tmp, path).await.map_err(io_error)?;
+                Ok(written)
+            }
+            Err(e) => {
+                let _ = tokio::fs::remove_file(&tmp).await;
+                Err(e)
+            }
+        }
+    }
+
+    /// 按配置中的默认请求发起请求
+    ///
+    /// 应用合并后配置中 `request` 的方法、内容类型、请求头与请求体（模板使用流程上下文渲染），
+    /// 未配置 `request` 时发起 GET 请求。
+    ///
+    /// 开启协商缓存时 GET 请求附加缓存的验证器，响应需经 [`Self::read_response`] 读取以处理 304
+    pub async fn fetch(&self, url: &str, context: &FlowContext) -> Result<reqwest::Response> {
+        let Some(config) = &self.config.request else {
+            let request = self.base_request(reqwest::Method::GET, url);
+            return self.execute_with_retry(request, true).await;
+        };
+        // 静态请求头已由 base_request 统一应用，这里只渲染含模板语法的请求头
+        let headers = config.headers.as_ref().map(|headers| {
+            headers
+                .iter()
+                .filter(|(_, value)| is_template(value))
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect()
+        });
+        let config = RequestConfig {
+            headers,
+            ..config.clone()
+        };
+        // URL 已由调用方渲染，不再作为模板处理
+        let mut request = RequestBuilder::new(self, Template::new(String::new()))
+            .with_config(&config)
+            .prepare(context)?;
+        request.url = url.to_string();
+        self.execute_with_retry(self.build_request(request)?, true)
+            .await
+    }
+
+    /// 读取响应，按配置中 `response.encoding` 解码响应体，未配置时自动检测
+    ///
+    /// 开启协商缓存时，304 响应返回缓存的响应，带验证器的 2xx 响应写入缓存
+    pub async fn read_response(&self, response: reqwest::Response) -> Result<HttpResponse> {
+        if response.status() == StatusCode::NOT_MODIFIED
+            && let Some(cached) = self.cached_response(response.url().as_str())
+        {
+            tracing::debug!("响应未修改，使用缓存: {}", cached.url);
+            return Ok(cached);
+        }
+
+        let encoding = self
+            .config
+            .response
+            .as_ref()
+            .and_then(|r| r.encoding.as_ref());
+        let response = HttpResponse::read_with_encoding(response, encoding).await?;
+
+        if let Some(store) = &self.response_cache
+            && response.is_success()
+            && (response.header("ETag").is_some() || response.header("Last-Modified").is_some())
+            && let Ok(value) = serde_json::to_string(&response)
+        {
+            store.set(&response_cache_key(&response.url), value, None);
+        }
+        Ok(response)
+    }
+
+    /// 读取协商缓存中的响应
+    fn cached_response(&self, url: &str) -> Option<HttpResponse> {
+        let store = self.response_cache.as_ref()?;
+        let value = store.get(&response_cache_key(url))?;
+        serde_json::from_str(&value).ok()
+    }
+
+    /// 为 GET 请求附加缓存响应的验证器，请求中已显式设置的条件请求头不覆盖
+    fn apply_validators(&self, request: &mut reqwest::Request) {
+        if request.method() != reqwest::Method::GET {
+            return;
         }
+        let Some(cached) = self.cached_response(request.url().as_str()) else {
+            return;
+        };
+        let headers = request.headers_mut();
+        for (name, value) in [
+            (IF_NONE_MATCH, cached.header("ETag")),
+            (IF_MODIFIED_SINCE, cached.header("Last-Modified")),
+        ] {
+            if let Some(value) = value.and_then(|v| HeaderValue::from_str(v).ok()) {
+                headers.entry(name).or_insert(value);
+            }
+        }
+    }
 
-        self.execute_with_retry(request).await
+    /// 发送已构建的请求
+    ///
+    /// 请求头在全局请求头之后应用，同名时覆盖全局配置
+    pub async fn send(&self, request: PreparedRequest) -> Result<reqwest::Response> {
+        self.execute_with_retry(self.build_request(request)?, false)
+            .await
+    }
+
+    /// 将已构建的请求转换为 reqwest 请求
+    fn build_request(&self, request: PreparedRequest) -> Result<reqwest::RequestBuilder> {
+        let method = reqwest::Method::from_bytes(request.method.as_str().as_bytes())
+            .map_err(|e| RuntimeError::HttpRequest(e.to_string()))?;
+        // 逐个 insert 后整体应用，同名请求头替换全局请求头而不是追加
+        let mut headers = HeaderMap::new();
+        for (key, value) in &request.headers {
+            let name = HeaderName::from_bytes(key.as_bytes()).map_err(|e| {
+                RuntimeError::HttpConfig(format!("无效的请求头名称 '{}': {}", key, e))
+            })?;
+            let value = HeaderValue::from_str(value).map_err(|e| {
+                RuntimeError::HttpConfig(format!("请求头 '{}' 的值无效: {}", key, e))
+            })?;
+            headers.insert(name, value);
+        }
+        let mut builder = self.base_request(method, &request.url).headers(headers);
+        builder = match request.body {
+            Some(RequestBody::Json(value)) => builder.json(&value),
+            Some(RequestBody::Form(form)) => builder.form(&form),
+            Some(RequestBody::Text(text)) => builder.body(text),
+            None => builder,
+        };
+        Ok(builder)
     }
 
     /// 执行请求（带重试）
+    ///
+    /// `conditional` 为 true 时附加协商缓存的验证器，仅用于响应经 [`Self::read_response`]
+    /// 读取的请求
     async fn execute_with_retry(
         &self,
         request: reqwest::RequestBuilder,
+        conditional: bool,
     ) -> Result<reqwest::Response> {
         let retry_count = self.config.retry_count.unwrap_or(0);
         let retry_delay = self.config.retry_delay.unwrap_or(1000);
 
+        // 按请求应用超时，使复用同一 Client 的流程级配置也能生效
+        let request = match self.config.timeout {
+            Some(timeout) => request.timeout(Duration::from_secs(timeout as u64)),
+            None => request,
+        };
+
+        let auto_referer = self.config.auto_referer.unwrap_or(false);
         let mut last_error = None;
 
         for attempt in 0..=retry_count {
@@ -155,29 +480,162 @@ impl HttpClient {
                 tokio::time::sleep(Duration::from_millis(retry_delay as u64)).await;
             }
 
-            match request.try_clone() {
-                Some(req) => match req.send().await {
-                    Ok(response) => return Ok(response),
-                    Err(e) => {
-                        last_error = Some(e);
+            let Some(req) = request.try_clone() else {
+                return Err(RuntimeError::HttpRequest(
+                    "Failed to clone request".to_string(),
+                ));
+            };
+            let mut req = match req.build() {
+                Ok(req) => req,
+                Err(e) => return Err(RuntimeError::HttpRequest(e.to_string())),
+            };
+
+            let url = req.url().to_string();
+            let host = req.url().host_str().unwrap_or_default().to_string();
+
+            // 每次尝试重新查询，重试期间刷新的凭证也能生效
+            if let Some(provider) = &self.credentials
+                && let Some(creds) = provider.credentials(&host).await
+            {
+                credentials::apply_credentials(req.headers_mut(), &creds);
+            }
+
+            if conditional {
+                self.apply_validators(&mut req);
+            }
+
+            // 未显式设置 Referer 时使用同域名上一次请求的 URL
+            if auto_referer
+                && !req.headers().contains_key(REFERER)
+                && let Some(referer) = self.referers.get(&host)
+                && let Ok(value) = HeaderValue::from_str(&referer)
+            {
+                req.headers_mut().insert(REFERER, value);
+            }
+
+            // 按域名排队，许可持有到响应头返回
+            let _permit = self.limiter.acquire(&host).await;
+            match self.client.execute(req).await {
+                Ok(response) => {
+                    if auto_referer {
+                        self.referers.insert(host, response.url().to_string());
                     }
-                },
-                None => {
-                    return Err(RuntimeError::HttpRequest(
-                        "Failed to clone request".to_string(),
-                    ));
+                    return Ok(response);
+                }
+                Err(e) => {
+                    let error = classify_error(&e, &url);
+                    // TLS、无效 URL 等错误重试也无法恢复，直接返回
+                    if !error.is_retryable() {
+                        return Err(error);
+                    }
+                    last_error = Some(error);
                 }
             }
         }
 
-        Err(RuntimeError::HttpRequest(format!(
-            "Request failed after {} retries: {}",
-            retry_count,
-            last_error.unwrap()
-        )))
+        Err(last_error.unwrap_or_else(|| {
+            RuntimeError::HttpRequest(format!("Request failed after {} retries", retry_count))
+        }))
     }
 }
 
+/// 值是否含模板语法，含有时需要用流程上下文渲染
+fn is_template(value: &Template) -> bool {
+    let value = value.as_str();
+    value.contains("{{") || value.contains("{%")
+}
+
+/// 协商缓存的缓存键
+fn response_cache_key(url: &str) -> String {
+    format!("{}{}", RESPONSE_CACHE_PREFIX, url)
+}
+
+/// 状态码不是 2xx 时返回 [`RuntimeError::HttpStatus`]
+fn error_for_status(response: reqwest::Response) -> Result<reqwest::Response> {
+    let status = response.status();
+    if status.is_success() {
+        Ok(response)
+    } else {
+        Err(RuntimeError::HttpStatus {
+            status: status.as_u16(),
+            url: response.url().to_string(),
+        })
+    }
+}
+
+/// 按错误来源将 reqwest 错误归类为超时、域名解析、TLS、连接失败、无效请求等错误
+///
+/// reqwest 不直接暴露底层错误类型，这里沿错误链按描述识别。
+/// 只检查底层错误的描述，避免 URL 中的字样干扰判断
+fn classify_error(error: &reqwest::Error, url: &str) -> RuntimeError {
+    let url = url.to_string();
+    if error.is_timeout() {
+        return RuntimeError::HttpTimeout { url };
+    }
+
+    let mut chain = Vec::new();
+    let mut source = std::error::Error::source(error);
+    while let Some(e) = source {
+        chain.push(e.to_string());
+        source = e.source();
+    }
+    let lower = chain.join(": ").to_ascii_lowercase();
+    let message = if chain.is_empty() {
+        error.to_string()
+    } else {
+        chain.join(": ")
+    };
+
+    if error.is_builder() {
+        RuntimeError::HttpConfig(format!("无效的请求 {}: {}", url, message))
+    } else if lower.contains("dns error") || lower.contains("failed to lookup address") {
+        RuntimeError::HttpDns { url, message }
+    } else if ["tls", "ssl", "certificate", "handshake"]
+        .iter()
+        .any(|keyword| lower.contains(keyword))
+    {
+        RuntimeError::HttpTls { url, message }
+    } else if error.is_connect() {
+        RuntimeError::HttpConnect { url, message }
+    } else {
+        RuntimeError::HttpRequest(message)
+    }
+}
+
+/// 代理支持的协议
+const PROXY_SCHEMES: [&str; 4] = ["http", "https", "socks5", "socks5h"];
+
+/// 解析代理地址
+///
+/// 校验协议为 [`PROXY_SCHEMES`] 之一，URL 中的认证信息由 reqwest 提取
+fn parse_proxy(proxy: &str) -> Result<reqwest::Proxy> {
+    let url = url::Url::parse(proxy)
+        .map_err(|e| RuntimeError::HttpConfig(format!("Invalid proxy '{}': {}", proxy, e)))?;
+    if !PROXY_SCHEMES.contains(&url.scheme()) {
+        return Err(RuntimeError::HttpConfig(format!(
+            "Unsupported proxy scheme '{}', expected one of: {}",
+            url.scheme(),
+            PROXY_SCHEMES.join(", ")
+        )));
+    }
+    reqwest::Proxy::all(url)
+        .map_err(|e| RuntimeError::HttpConfig(format!("Invalid proxy '{}': {}", proxy, e)))
+}
+
+/// 解析域名解析覆盖的目标地址
+///
+/// 支持 `IP` 与 `IP:端口`，以逗号分隔多个地址时取第一个；
+/// 未指定端口时使用 0，即按请求协议的默认端口连接
+fn parse_dns_target(host: &str, target: &str) -> Result<SocketAddr> {
+    let first = target.split(',').next().unwrap_or_default().trim();
+    first
+        .parse::<SocketAddr>()
+        .or_else(|_| first.parse::<IpAddr>().map(|ip| SocketAddr::new(ip, 0)))
+        .map_err(|_| {
+            RuntimeError::HttpConfig(format!("Invalid DNS override for {}: {}", host, target))
+        })
+}
+
 impl Default for HttpClient {
     fn default() -> Self {
         Self::new(HttpConfig::default()).expect("Failed to create default HttpClient")
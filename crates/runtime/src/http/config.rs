@@ -3,6 +3,7 @@
 //! 为 HttpConfig 提供合并和转换功能
 
 use crawler_schema::config::{HttpConfig, RequestConfig, ResponseConfig};
+use std::collections::HashMap;
 
 /// HTTP 配置扩展 trait
 pub trait HttpConfigExt {
@@ -21,12 +22,14 @@ impl HttpConfigExt for HttpConfig {
             proxy: other.proxy.clone().or_else(|| self.proxy.clone()),
             follow_redirects: other.follow_redirects.or(self.follow_redirects),
             max_redirects: other.max_redirects.or(self.max_redirects),
+            dns_overrides: merge_dns_overrides(&self.dns_overrides, &other.dns_overrides),
             connect_timeout: other.connect_timeout.or(self.connect_timeout),
             verify_ssl: other.verify_ssl.or(self.verify_ssl),
             request_delay: other.request_delay.or(self.request_delay),
             max_concurrent: other.max_concurrent.or(self.max_concurrent),
             retry_count: other.retry_count.or(self.retry_count),
             retry_delay: other.retry_delay.or(self.retry_delay),
+            auto_referer: other.auto_referer.or(self.auto_referer),
             request: merge_request_config(&self.request, &other.request),
             response: merge_response_config(&self.response, &other.response),
         }
@@ -39,6 +42,21 @@ impl HttpConfigExt for HttpConfig {
     }
 }
 
+/// 合并域名解析覆盖，同一域名以 override 为准
+fn merge_dns_overrides(
+    base: &Option<HashMap<String, String>>,
+    override_map: &Option<HashMap<String, String>>,
+) -> Option<HashMap<String, String>> {
+    match (base, override_map) {
+        (Some(b), Some(o)) => {
+            let mut merged = b.clone();
+            merged.extend(o.clone());
+            Some(merged)
+        }
+        _ => override_map.clone().or_else(|| base.clone()),
+    }
+}
+
 /// 合并请求配置
 fn merge_request_config(
     base: &Option<RequestConfig>,
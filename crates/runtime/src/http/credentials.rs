@@ -0,0 +1,67 @@
+//! # 凭证注入
+//!
+//! 为 [`HttpClient`](super::HttpClient) 注册凭证提供者后，每次请求前按域名查询
+//! 未过期的验证凭证，自动合并到 `Cookie` 与请求头中
+
+use crate::challenge::{ChallengeCredentials, CredentialsCache};
+use async_trait::async_trait;
+use reqwest::header::{COOKIE, HeaderMap, HeaderName, HeaderValue};
+use std::sync::Arc;
+
+/// 凭证提供者
+#[async_trait]
+pub trait CredentialsProvider: Send + Sync {
+    /// 获取域名当前有效的凭证，不存在或已过期时返回 None
+    async fn credentials(&self, host: &str) -> Option<ChallengeCredentials>;
+}
+
+/// 共享的凭证提供者
+pub type SharedCredentialsProvider = Arc<dyn CredentialsProvider>;
+
+#[async_trait]
+impl CredentialsProvider for CredentialsCache {
+    async fn credentials(&self, host: &str) -> Option<ChallengeCredentials> {
+        self.get(host).await
+    }
+}
+
+/// 将凭证合并到请求头
+///
+/// - Cookie 与请求中已有的 Cookie 合并，同名时以凭证为准
+/// - 请求头直接覆盖（如 Cloudflare 凭证需与获取时的 User-Agent 一致）
+pub(crate) fn apply_credentials(headers: &mut HeaderMap, credentials: &ChallengeCredentials) {
+    if !credentials.cookies.is_empty() {
+        let mut cookies: Vec<(String, String)> = headers
+            .get_all(COOKIE)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(';'))
+            .filter_map(|pair| {
+                let (name, value) = pair.trim().split_once('=')?;
+                Some((name.to_string(), value.to_string()))
+            })
+            .filter(|(name, _)| !credentials.cookies.contains_key(name))
+            .collect();
+        let mut added: Vec<_> = credentials.cookies.iter().collect();
+        added.sort();
+        cookies.extend(added.into_iter().map(|(k, v)| (k.clone(), v.clone())));
+
+        let cookie = cookies
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect::<Vec<_>>()
+            .join("; ");
+        if let Ok(value) = HeaderValue::from_str(&cookie) {
+            headers.insert(COOKIE, value);
+        }
+    }
+
+    for (name, value) in &credentials.headers {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(value),
+        ) {
+            headers.insert(name, value);
+        }
+    }
+}
@@ -0,0 +1,97 @@
+//! # 域名级限流
+//!
+//! 按请求域名协调请求间隔与并发数。同一运行时内派生的所有客户端共享同一个限流器，
+//! 多个流程并发访问同一域名时共同受 `request_delay` 与 `max_concurrent` 约束
+
+use crawler_schema::config::HttpConfig;
+use dashmap::DashMap;
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::{
+    sync::{OwnedSemaphorePermit, Semaphore},
+    time::Instant,
+};
+
+/// 单个域名的限流状态
+#[derive(Debug)]
+struct HostSlot {
+    /// 并发许可（未配置 `max_concurrent` 时为 None）
+    semaphore: Option<Arc<Semaphore>>,
+    /// 下一个请求最早可发出的时间
+    next_start: Mutex<Instant>,
+}
+
+/// 限流许可
+///
+/// 持有期间占用一个并发名额，丢弃后释放
+#[derive(Debug)]
+pub struct RatePermit {
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
+/// 域名级限流器
+#[derive(Debug)]
+pub struct HostRateLimiter {
+    /// 同一域名两次请求的最小间隔
+    delay: Duration,
+    /// 同一域名的最大并发数
+    max_concurrent: Option<usize>,
+    /// 各域名状态
+    hosts: DashMap<String, Arc<HostSlot>>,
+}
+
+impl HostRateLimiter {
+    /// 按 HTTP 配置中的 `request_delay`、`max_concurrent` 创建限流器
+    pub fn new(config: &HttpConfig) -> Self {
+        Self {
+            delay: Duration::from_millis(config.request_delay.unwrap_or(0) as u64),
+            max_concurrent: config.max_concurrent.map(|n| n.max(1) as usize),
+            hosts: DashMap::new(),
+        }
+    }
+
+    /// 是否配置了任何限制
+    pub fn is_enabled(&self) -> bool {
+        !self.delay.is_zero() || self.max_concurrent.is_some()
+    }
+
+    /// 等待指定域名的请求名额
+    ///
+    /// 先占用并发名额，再按请求间隔排队，返回的许可需持有到请求完成
+    pub async fn acquire(&self, host: &str) -> RatePermit {
+        if !self.is_enabled() {
+            return RatePermit { _permit: None };
+        }
+
+        let slot = self
+            .hosts
+            .entry(host.to_string())
+            .or_insert_with(|| {
+                Arc::new(HostSlot {
+                    semaphore: self.max_concurrent.map(|n| Arc::new(Semaphore::new(n))),
+                    next_start: Mutex::new(Instant::now()),
+                })
+            })
+            .clone();
+
+        let permit = match &slot.semaphore {
+            // 信号量不会被关闭，获取失败时退化为不限并发
+            Some(semaphore) => semaphore.clone().acquire_owned().await.ok(),
+            None => None,
+        };
+
+        if !self.delay.is_zero() {
+            let start = {
+                let mut next_start = slot.next_start.lock().unwrap();
+                let start = (*next_start).max(Instant::now());
+                *next_start = start + self.delay;
+                start
+            };
+            tokio::time::sleep_until(start).await;
+        }
+
+        RatePermit { _permit: permit }
+    }
+}
@@ -2,10 +2,19 @@
 //!
 //! 提供 HTTP 请求功能和配置管理
 
+pub mod charset;
 pub mod client;
 pub mod config;
+pub mod credentials;
+pub mod limiter;
 pub mod request;
+pub mod response;
+pub mod stream;
 
 pub use client::HttpClient;
 pub use config::HttpConfigExt;
-pub use request::RequestBuilder;
+pub use credentials::{CredentialsProvider, SharedCredentialsProvider};
+pub use limiter::{HostRateLimiter, RatePermit};
+pub use request::{PreparedRequest, RequestBody, RequestBuilder};
+pub use response::{HttpResponse, RESPONSE_VAR, parse_set_cookie};
+pub use stream::{JsonItemStream, for_each_json_item, stream_json_items};
@@ -7,6 +7,73 @@ use crawler_schema::{
     config::{HttpMethod, RequestConfig},
     template::Template,
 };
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// JSON 内容类型
+const CONTENT_TYPE_JSON: &str = "application/json";
+/// 表单内容类型
+const CONTENT_TYPE_FORM: &str = "application/x-www-form-urlencoded";
+
+/// 编码后的请求体
+#[derive(Debug, Clone, PartialEq)]
+pub enum RequestBody {
+    /// JSON 请求体（发送时自动设置 `Content-Type: application/json`）
+    Json(Value),
+    /// 表单请求体（发送时自动设置 `Content-Type: application/x-www-form-urlencoded`）
+    Form(Vec<(String, String)>),
+    /// 原样发送的文本
+    Text(String),
+}
+
+impl RequestBody {
+    /// 按内容类型编码渲染后的请求体
+    ///
+    /// - `application/json`：内容可解析为 JSON 时按 JSON 发送，否则原样发送
+    /// - `application/x-www-form-urlencoded`：内容为 JSON 对象时转换为表单，
+    ///   否则视为已编码的表单字符串原样发送
+    /// - 其他或未指定：原样发送
+    pub fn encode(body: String, content_type: Option<&str>) -> Self {
+        let essence = content_type
+            .and_then(|ct| ct.split(';').next())
+            .map(|ct| ct.trim().to_ascii_lowercase());
+        match essence.as_deref() {
+            Some(CONTENT_TYPE_JSON) => match serde_json::from_str(&body) {
+                Ok(value) => Self::Json(value),
+                Err(_) => Self::Text(body),
+            },
+            Some(CONTENT_TYPE_FORM) => match serde_json::from_str::<Value>(&body) {
+                Ok(Value::Object(map)) => Self::Form(
+                    map.into_iter()
+                        .map(|(k, v)| {
+                            let v = match v {
+                                Value::String(s) => s,
+                                Value::Null => String::new(),
+                                other => other.to_string(),
+                            };
+                            (k, v)
+                        })
+                        .collect(),
+                ),
+                _ => Self::Text(body),
+            },
+            _ => Self::Text(body),
+        }
+    }
+}
+
+/// 渲染完成、待发送的请求
+#[derive(Debug, Clone)]
+pub struct PreparedRequest {
+    /// HTTP 方法
+    pub method: HttpMethod,
+    /// 请求 URL
+    pub url: String,
+    /// 请求头
+    pub headers: HashMap<String, String>,
+    /// 请求体（方法不支持请求体时为 None）
+    pub body: Option<RequestBody>,
+}
 
 /// 请求构建器
 pub struct RequestBuilder<'a> {
@@ -14,7 +81,8 @@ pub struct RequestBuilder<'a> {
     url: Template,
     method: HttpMethod,
     body: Option<Template>,
-    headers: std::collections::HashMap<String, Template>,
+    headers: HashMap<String, Template>,
+    content_type: Option<String>,
 }
 
 impl<'a> RequestBuilder<'a> {
@@ -25,7 +93,8 @@ impl<'a> RequestBuilder<'a> {
             url,
             method: HttpMethod::Get,
             body: None,
-            headers: std::collections::HashMap::new(),
+            headers: HashMap::new(),
+            content_type: None,
         }
     }
 
@@ -47,6 +116,12 @@ impl<'a> RequestBuilder<'a> {
         self
     }
 
+    /// 设置内容类型，决定请求体的编码方式
+    pub fn content_type(mut self, content_type: impl Into<String>) -> Self {
+        self.content_type = Some(content_type.into());
+        self
+    }
+
     /// 应用请求配置
     pub fn with_config(mut self, config: &RequestConfig) -> Self {
         if let Some(method) = &config.method {
@@ -58,25 +133,55 @@ impl<'a> RequestBuilder<'a> {
         if let Some(headers) = &config.headers {
             self.headers.extend(headers.clone());
         }
+        if let Some(content_type) = &config.content_type {
+            self.content_type = Some(content_type.clone());
+        }
         self
     }
 
-    /// 执行请求
-    pub async fn execute(self, context: &FlowContext) -> Result<reqwest::Response> {
-        // 渲染 URL
+    /// 渲染模板并编码请求体
+    ///
+    /// 方法不支持请求体（[`HttpMethod::has_body`] 为 false）时忽略 body 与 `content_type`；
+    /// 请求体按 `content_type` 编码（见 [`RequestBody::encode`]），
+    /// 显式的 `content_type` 会覆盖请求头中的 `Content-Type`
+    pub fn prepare(&self, context: &FlowContext) -> Result<PreparedRequest> {
         let url = self.url.render(context)?;
 
-        match self.method {
-            HttpMethod::Get => self.client.get(&url).await,
-            HttpMethod::Post => {
-                let body = if let Some(body_template) = self.body {
-                    body_template.render(context)?
-                } else {
-                    String::new()
-                };
-                self.client.post(&url, body).await
-            }
-            _ => todo!("Implement other HTTP methods"),
+        let mut headers = HashMap::with_capacity(self.headers.len() + 1);
+        for (key, value) in &self.headers {
+            headers.insert(key.clone(), value.render(context)?);
         }
+        if let Some(content_type) = &self.content_type
+            && self.method.has_body()
+        {
+            headers.retain(|k: &String, _| !k.eq_ignore_ascii_case("content-type"));
+            headers.insert("Content-Type".to_string(), content_type.clone());
+        }
+
+        let body = match &self.body {
+            Some(body) if self.method.has_body() => {
+                let content_type = self.content_type.as_deref().or_else(|| {
+                    headers
+                        .iter()
+                        .find(|(k, _)| k.eq_ignore_ascii_case("content-type"))
+                        .map(|(_, v)| v.as_str())
+                });
+                Some(RequestBody::encode(body.render(context)?, content_type))
+            }
+            _ => None,
+        };
+
+        Ok(PreparedRequest {
+            method: self.method,
+            url,
+            headers,
+            body,
+        })
+    }
+
+    /// 执行请求
+    pub async fn execute(self, context: &FlowContext) -> Result<reqwest::Response> {
+        let request = self.prepare(context)?;
+        self.client.send(request).await
     }
 }
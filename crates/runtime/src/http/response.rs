@@ -0,0 +1,190 @@
+//! # 响应对象
+//!
+//! 将 HTTP 响应读取为结构化对象 `{ body, status, headers, url }`，
+//! 存入流程变量后，后续模板与脚本可访问状态码与响应头：
+//!
+//! ```text
+//! {{ response.status }}
+//! {{ response.headers.Location }}
+//! {{ response.url }}
+//! ```
+
+use crate::{
+    Result,
+    error::RuntimeError,
+    extractor::{ExtractValueData, SharedValue},
+    http::charset,
+};
+use crawler_schema::config::{ResponseContentType, ResponseEncoding};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{collections::BTreeMap, sync::Arc};
+
+/// 流程变量中存放响应对象的变量名
+pub const RESPONSE_VAR: &str = "response";
+
+/// 已读取的 HTTP 响应
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpResponse {
+    /// 响应体文本
+    pub body: String,
+    /// 状态码
+    pub status: u16,
+    /// 响应头
+    ///
+    /// 名称统一为规范大小写（如 `Content-Type`、`Location`），
+    /// 同名响应头的多个值以 `, ` 连接
+    pub headers: BTreeMap<String, String>,
+    /// 最终 URL（跟随重定向后）
+    pub url: String,
+}
+
+impl HttpResponse {
+    /// 读取响应，自动检测响应体编码
+    pub async fn read(response: reqwest::Response) -> Result<Self> {
+        Self::read_with_encoding(response, None).await
+    }
+
+    /// 按配置的编码读取响应，None 或 `auto` 时自动检测，见 [`charset::decode_body`]
+    pub async fn read_with_encoding(
+        response: reqwest::Response,
+        encoding: Option<&ResponseEncoding>,
+    ) -> Result<Self> {
+        let status = response.status().as_u16();
+        let url = response.url().to_string();
+
+        let mut headers: BTreeMap<String, String> = BTreeMap::new();
+        for (name, value) in response.headers() {
+            let value = String::from_utf8_lossy(value.as_bytes());
+            headers
+                .entry(canonical_header_name(name.as_str()))
+                .and_modify(|existing| {
+                    existing.push_str(", ");
+                    existing.push_str(&value);
+                })
+                .or_insert_with(|| value.into_owned());
+        }
+
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| RuntimeError::HttpRequest(format!("读取响应失败: {}", e)))?;
+        let content_type = headers.get("Content-Type").map(String::as_str);
+        let body = charset::decode_body(&bytes, content_type, encoding);
+
+        Ok(Self {
+            body,
+            status,
+            headers,
+            url,
+        })
+    }
+
+    /// 按名称获取响应头（不区分大小写）
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .get(&canonical_header_name(name))
+            .map(|v| v.as_str())
+    }
+
+    /// 解析 `Set-Cookie` 响应头得到的 Cookie（名称 → 值）
+    pub fn cookies(&self) -> BTreeMap<String, String> {
+        self.header("Set-Cookie")
+            .map(parse_set_cookie)
+            .unwrap_or_default()
+    }
+
+    /// 状态码是否为 2xx
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// 状态码不是 2xx 时返回 [`RuntimeError::HttpStatus`]
+    pub fn error_for_status(&self) -> Result<()> {
+        if self.is_success() {
+            Ok(())
+        } else {
+            Err(RuntimeError::HttpStatus {
+                status: self.status,
+                url: self.url.clone(),
+            })
+        }
+    }
+
+    /// 是否按 XML 解析
+    ///
+    /// 规则声明了 `response.content_type` 时以声明为准，
+    /// 否则根据 `Content-Type` 响应头判断（如 `application/rss+xml`、`text/xml`，排除 `xhtml`）
+    pub fn is_xml(&self, declared: Option<&ResponseContentType>) -> bool {
+        match declared {
+            Some(content_type) => matches!(content_type, ResponseContentType::Xml),
+            None => self.header("Content-Type").is_some_and(|ct| {
+                let ct = ct.to_ascii_lowercase();
+                ct.contains("xml") && !ct.contains("html")
+            }),
+        }
+    }
+
+    /// 将响应体转换为提取输入：XML 响应为 [`ExtractValueData::Xml`]，其余为 HTML
+    pub fn into_document(self, declared: Option<&ResponseContentType>) -> SharedValue {
+        let xml = self.is_xml(declared);
+        let body = Arc::from(self.body.into_boxed_str());
+        Arc::new(if xml {
+            ExtractValueData::Xml(body)
+        } else {
+            ExtractValueData::Html(body)
+        })
+    }
+
+    /// 转换为 JSON 对象，用于存入流程变量
+    pub fn to_value(&self) -> Value {
+        serde_json::to_value(self).unwrap_or(Value::Null)
+    }
+}
+
+/// 解析以 `, ` 连接的多个 `Set-Cookie` 值，只保留名称与值，忽略 `Path`、`Expires` 等属性
+///
+/// `Expires` 日期中的逗号（如 `Expires=Wed, 21 Oct 2026 07:28:00 GMT`）不作为分隔符
+pub fn parse_set_cookie(header: &str) -> BTreeMap<String, String> {
+    let mut cookies = BTreeMap::new();
+    let mut current = String::new();
+    let mut flush = |segment: &str| {
+        let pair = segment.split(';').next().unwrap_or("");
+        if let Some((name, value)) = pair.split_once('=') {
+            let name = name.trim();
+            if !name.is_empty() {
+                cookies.insert(name.to_string(), value.trim().to_string());
+            }
+        }
+    };
+    for part in header.split(',') {
+        // 逗号后第一段不含 `=` 时说明仍属于上一个 Cookie 的属性值
+        let continues = part
+            .split(';')
+            .next()
+            .is_some_and(|head| !head.contains('='));
+        if continues && !current.is_empty() {
+            current.push(',');
+            current.push_str(part);
+        } else {
+            flush(&current);
+            current = part.to_string();
+        }
+    }
+    flush(&current);
+    cookies
+}
+
+/// 响应头名称规范化：以 `-` 分隔的每段首字母大写，其余小写
+fn canonical_header_name(name: &str) -> String {
+    name.split('-')
+        .map(|part| {
+            let mut part = part.to_ascii_lowercase();
+            if let Some(first) = part.get_mut(..1) {
+                first.make_ascii_uppercase();
+            }
+            part
+        })
+        .collect::<Vec<_>>()
+        .join("-")
+}
@@ -0,0 +1,317 @@
+//! # 流式 JSON 提取
+//!
+//! 对超大 JSON 响应按 `$.items[*]` 形式的路径边解析边产出数组元素，
+//! 只在内存中保留当前元素，避免整体载入响应体
+
+use crate::{Result, RuntimeError};
+use serde::de::{self, DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde_json::Value;
+use std::{fmt, io::Read};
+use tokio::sync::mpsc;
+
+/// 下载缓冲的响应块数量
+const CHUNK_BUFFER: usize = 8;
+/// 已解析但未消费的元素数量
+const ITEM_BUFFER: usize = 64;
+/// 调用方提前结束时的内部标记
+const STOP_MARKER: &str = "__json_stream_stopped__";
+
+/// 流式路径片段
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    /// 对象键
+    Key(String),
+    /// 数组下标
+    Index(usize),
+    /// 数组全部元素（必须位于末尾）
+    Wildcard,
+}
+
+/// 解析流式路径
+///
+/// 支持 `$.a.b[*]`、`$['a'][0].b[*]`、`$[*]`，路径必须以 `[*]` 结尾
+fn parse_path(path: &str) -> Result<Vec<Segment>> {
+    let invalid = || {
+        RuntimeError::Extraction(format!(
+            "流式 JSON 路径 '{}' 无效，仅支持键名、下标并以 [*] 结尾",
+            path
+        ))
+    };
+
+    let mut rest = path.trim().strip_prefix('$').ok_or_else(invalid)?;
+    let mut segments = Vec::new();
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('.') {
+            let end = after.find(['.', '[']).unwrap_or(after.len());
+            if end == 0 {
+                return Err(invalid());
+            }
+            segments.push(Segment::Key(after[..end].to_string()));
+            rest = &after[end..];
+        } else if let Some(after) = rest.strip_prefix('[') {
+            let end = after.find(']').ok_or_else(invalid)?;
+            let inner = after[..end].trim();
+            let segment = if inner == "*" {
+                Segment::Wildcard
+            } else if let Some(key) = inner
+                .strip_prefix('\'')
+                .and_then(|s| s.strip_suffix('\''))
+                .or_else(|| inner.strip_prefix('"').and_then(|s| s.strip_suffix('"')))
+            {
+                Segment::Key(key.to_string())
+            } else {
+                Segment::Index(inner.parse().map_err(|_| invalid())?)
+            };
+            segments.push(segment);
+            rest = &after[end + 1..];
+        } else {
+            return Err(invalid());
+        }
+    }
+
+    match segments.iter().position(|s| *s == Segment::Wildcard) {
+        Some(pos) if pos == segments.len() - 1 => Ok(segments),
+        _ => Err(invalid()),
+    }
+}
+
+/// 解析状态
+struct StreamState<F> {
+    on_item: F,
+    count: usize,
+    stopped: bool,
+}
+
+/// 沿路径定位的反序列化种子
+struct PathSeed<'a, F> {
+    path: &'a [Segment],
+    state: &'a mut StreamState<F>,
+}
+
+impl<'de, F> DeserializeSeed<'de> for PathSeed<'_, F>
+where
+    F: FnMut(Value) -> bool,
+{
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> std::result::Result<(), D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de, F> Visitor<'de> for PathSeed<'_, F>
+where
+    F: FnMut(Value) -> bool,
+{
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("JSON 值")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> std::result::Result<(), A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let (target, rest) = match self.path.split_first() {
+            Some((Segment::Key(key), rest)) => (Some(key.as_str()), rest),
+            _ => (None, self.path),
+        };
+
+        while let Some(key) = map.next_key::<String>()? {
+            if Some(key.as_str()) == target {
+                map.next_value_seed(PathSeed {
+                    path: rest,
+                    state: &mut *self.state,
+                })?;
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> std::result::Result<(), A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        match self.path.split_first() {
+            Some((Segment::Wildcard, _)) => {
+                while let Some(item) = seq.next_element::<Value>()? {
+                    self.state.count += 1;
+                    if !(self.state.on_item)(item) {
+                        self.state.stopped = true;
+                        return Err(de::Error::custom(STOP_MARKER));
+                    }
+                }
+            }
+            Some((Segment::Index(index), rest)) => {
+                let mut i = 0;
+                loop {
+                    let found = if i == *index {
+                        seq.next_element_seed(PathSeed {
+                            path: rest,
+                            state: &mut *self.state,
+                        })?
+                    } else {
+                        seq.next_element::<IgnoredAny>()?.map(|_| ())
+                    };
+                    if found.is_none() {
+                        break;
+                    }
+                    i += 1;
+                }
+            }
+            _ => while seq.next_element::<IgnoredAny>()?.is_some() {},
+        }
+        Ok(())
+    }
+
+    // 路径未命中的标量值直接忽略
+    fn visit_bool<E>(self, _: bool) -> std::result::Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_i64<E>(self, _: i64) -> std::result::Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_u64<E>(self, _: u64) -> std::result::Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_f64<E>(self, _: f64) -> std::result::Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_str<E>(self, _: &str) -> std::result::Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_unit<E>(self) -> std::result::Result<(), E> {
+        Ok(())
+    }
+}
+
+/// 从读取器中流式提取数组元素
+///
+/// 每解析出一个元素调用一次 `on_item`，返回 false 时提前结束；
+/// 返回已产出的元素数量
+pub fn for_each_json_item<R, F>(reader: R, path: &str, on_item: F) -> Result<usize>
+where
+    R: Read,
+    F: FnMut(Value) -> bool,
+{
+    let segments = parse_path(path)?;
+    let mut state = StreamState {
+        on_item,
+        count: 0,
+        stopped: false,
+    };
+
+    let mut deserializer = serde_json::Deserializer::from_reader(reader);
+    let result = PathSeed {
+        path: &segments,
+        state: &mut state,
+    }
+    .deserialize(&mut deserializer)
+    .and_then(|_| deserializer.end());
+
+    match result {
+        Ok(()) => Ok(state.count),
+        Err(_) if state.stopped => Ok(state.count),
+        Err(e) => Err(RuntimeError::Extraction(format!(
+            "流式 JSON 解析失败: {}",
+            e
+        ))),
+    }
+}
+
+/// 流式 JSON 元素接收端
+///
+/// 由 [`stream_json_items`] 创建，元素按解析顺序产出
+#[derive(Debug)]
+pub struct JsonItemStream {
+    receiver: mpsc::Receiver<Result<Value>>,
+}
+
+impl JsonItemStream {
+    /// 获取下一个元素，全部产出后返回 None
+    pub async fn next(&mut self) -> Option<Result<Value>> {
+        self.receiver.recv().await
+    }
+}
+
+/// 对 HTTP 响应做流式 JSON 提取
+///
+/// 响应体按块下载并交给后台解析线程，解析出的元素通过有界通道产出；
+/// 丢弃 [`JsonItemStream`] 会停止下载与解析
+pub fn stream_json_items(response: reqwest::Response, path: &str) -> Result<JsonItemStream> {
+    let path = path.to_string();
+    parse_path(&path)?;
+
+    let (chunk_tx, chunk_rx) = mpsc::channel(CHUNK_BUFFER);
+    let (item_tx, item_rx) = mpsc::channel(ITEM_BUFFER);
+
+    // 下载响应块
+    tokio::spawn(async move {
+        let mut response = response;
+        loop {
+            let chunk = match response.chunk().await {
+                Ok(Some(chunk)) => Ok(chunk.to_vec()),
+                Ok(None) => break,
+                Err(e) => Err(std::io::Error::other(e)),
+            };
+            let failed = chunk.is_err();
+            if chunk_tx.send(chunk).await.is_err() || failed {
+                break;
+            }
+        }
+    });
+
+    // 解析并产出元素
+    tokio::task::spawn_blocking(move || {
+        let reader = ChannelReader {
+            receiver: chunk_rx,
+            chunk: Vec::new(),
+            offset: 0,
+        };
+        let result = for_each_json_item(reader, &path, |item| {
+            item_tx.blocking_send(Ok(item)).is_ok()
+        });
+        if let Err(e) = result {
+            let _ = item_tx.blocking_send(Err(e));
+        }
+    });
+
+    Ok(JsonItemStream { receiver: item_rx })
+}
+
+/// 从通道读取响应块的同步读取器
+struct ChannelReader {
+    receiver: mpsc::Receiver<std::io::Result<Vec<u8>>>,
+    chunk: Vec<u8>,
+    offset: usize,
+}
+
+impl Read for ChannelReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        while self.offset >= self.chunk.len() {
+            match self.receiver.blocking_recv() {
+                Some(chunk) => {
+                    self.chunk = chunk?;
+                    self.offset = 0;
+                }
+                None => return Ok(0),
+            }
+        }
+
+        let n = buf.len().min(self.chunk.len() - self.offset);
+        buf[..n].copy_from_slice(&self.chunk[self.offset..self.offset + n]);
+        self.offset += n;
+        Ok(n)
+    }
+}
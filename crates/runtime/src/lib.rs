@@ -42,6 +42,9 @@ pub mod flow;
 // 脚本执行引擎
 pub mod script;
 
+// 规则文件加载与校验
+pub mod rule;
+
 // 爬虫运行时主入口
 pub mod crawler;
 
@@ -74,6 +74,21 @@ impl SearchItem {
         self.summary = Some(summary.into());
         self
     }
+
+    /// 获取去重键
+    ///
+    /// `url` 返回条目 URL，其他字段从原始数据中读取；字段不存在或为空时返回 None
+    pub fn dedup_key(&self, field: &str) -> Option<String> {
+        let key = match field {
+            "url" => self.url.clone(),
+            _ => match self.raw.get(field)? {
+                Value::String(s) => s.clone(),
+                Value::Null => return None,
+                other => other.to_string(),
+            },
+        };
+        (!key.is_empty()).then_some(key)
+    }
 }
 
 /// 章节项
@@ -0,0 +1,245 @@
+//! # 标准化输出模型
+//!
+//! `ItemSummary`/`ItemDetail` 是面向 App 端的统一结构，
+//! 由各流程的内部结果（`SearchItem`、`BookDetail` 等）转换而来
+
+use super::{AudioDetail, BookDetail, MangaDetail, SearchItem, VideoDetail};
+use crawler_schema::config::MediaType;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// 列表项标准模型
+///
+/// 对应 schema 中 `ItemFields` 的提取结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemSummary {
+    /// 唯一标识（显式 `id` 字段，否则为 URL 的 MD5）
+    pub id: String,
+    /// 媒体类型
+    pub media_type: MediaType,
+    /// 标题
+    pub title: String,
+    /// 详情页 URL
+    pub url: String,
+    /// 封面图 URL
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cover: Option<String>,
+    /// 简介/摘要
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// 作者/创作者
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// 最新章节/更新信息
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latest: Option<String>,
+    /// 评分
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<f64>,
+    /// 状态
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    /// 标签（由分类字符串拆分）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// 扩展数据
+    #[serde(default)]
+    pub extra: Value,
+}
+
+/// 详情标准模型
+///
+/// 对应 schema 中 `DetailFields` 的提取结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemDetail {
+    /// 唯一标识（显式 `id` 字段，否则为 URL 的 MD5）
+    pub id: String,
+    /// 媒体类型
+    pub media_type: MediaType,
+    /// 详情页 URL
+    pub url: String,
+    /// 标题
+    pub title: String,
+    /// 作者/创作者
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// 封面图 URL
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cover: Option<String>,
+    /// 简介
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub intro: Option<String>,
+    /// 状态
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    /// 最新章节/更新信息
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latest: Option<String>,
+    /// 标签（合并分类与标签字段后拆分去重）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// 扩展数据
+    #[serde(default)]
+    pub extra: Value,
+}
+
+impl SearchItem {
+    /// 转换为标准列表项模型
+    pub fn into_item_summary(self, media_type: MediaType) -> ItemSummary {
+        ItemSummary {
+            id: item_id(&self.raw, &self.url),
+            media_type,
+            tags: self.category.as_deref().map(split_tags).unwrap_or_default(),
+            score: self.score.as_deref().and_then(parse_score),
+            title: self.title,
+            url: self.url,
+            cover: self.cover,
+            summary: self.summary,
+            author: self.author,
+            latest: self.latest,
+            status: self.status,
+            extra: self.raw,
+        }
+    }
+}
+
+impl BookDetail {
+    /// 转换为标准详情模型
+    ///
+    /// `url` 为详情页地址，用于生成 id
+    pub fn into_item_detail(self, url: impl Into<String>) -> ItemDetail {
+        let url = url.into();
+        ItemDetail {
+            id: item_id(&self.raw, &url),
+            media_type: MediaType::Book,
+            url,
+            tags: merge_tags(&[self.category.as_deref(), self.tags.as_deref()]),
+            title: self.title,
+            author: Some(self.author).filter(|a| !a.is_empty()),
+            cover: self.cover,
+            intro: self.intro,
+            status: self.status,
+            latest: self.last_chapter,
+            extra: self.raw,
+        }
+    }
+}
+
+impl VideoDetail {
+    /// 转换为标准详情模型
+    ///
+    /// 导演作为创作者，更新信息作为最新进度，分类、标签、地区与年份合并为标签
+    pub fn into_item_detail(self, url: impl Into<String>) -> ItemDetail {
+        let url = url.into();
+        ItemDetail {
+            id: item_id(&self.raw, &url),
+            media_type: MediaType::Video,
+            url,
+            tags: merge_tags(&[
+                self.category.as_deref(),
+                self.tags.as_deref(),
+                self.region.as_deref(),
+                self.year.as_deref(),
+            ]),
+            title: self.title,
+            author: self.director,
+            cover: self.cover,
+            intro: self.intro,
+            status: None,
+            latest: self.update_info,
+            extra: self.raw,
+        }
+    }
+}
+
+impl AudioDetail {
+    /// 转换为标准详情模型
+    ///
+    /// 艺术家作为创作者，最后一条音轨作为最新进度
+    pub fn into_item_detail(self, url: impl Into<String>) -> ItemDetail {
+        let url = url.into();
+        ItemDetail {
+            id: item_id(&self.raw, &url),
+            media_type: MediaType::Audio,
+            url,
+            tags: merge_tags(&[self.category.as_deref(), self.tags.as_deref()]),
+            latest: self.tracks.last().map(|t| t.name.clone()),
+            title: self.title,
+            author: self.artist,
+            cover: self.cover,
+            intro: self.intro,
+            status: None,
+            extra: self.raw,
+        }
+    }
+}
+
+impl MangaDetail {
+    /// 转换为标准详情模型
+    ///
+    /// 首条线路的最后一话作为最新进度
+    pub fn into_item_detail(self, url: impl Into<String>) -> ItemDetail {
+        let url = url.into();
+        ItemDetail {
+            id: item_id(&self.raw, &url),
+            media_type: MediaType::Manga,
+            url,
+            tags: merge_tags(&[self.category.as_deref(), self.tags.as_deref()]),
+            latest: self
+                .play_lines
+                .first()
+                .and_then(|line| line.episodes.last())
+                .map(|e| e.name.clone()),
+            title: self.title,
+            author: self.author,
+            cover: self.cover,
+            intro: self.intro,
+            status: self.status,
+            extra: self.raw,
+        }
+    }
+}
+
+/// 合并多个标签字符串，拆分后按出现顺序去重
+fn merge_tags(sources: &[Option<&str>]) -> Vec<String> {
+    let mut tags = Vec::new();
+    for source in sources.iter().flatten() {
+        for tag in split_tags(source) {
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+    }
+    tags
+}
+
+/// 拆分标签字符串
+///
+/// 支持中英文逗号、顿号、斜杠、竖线和空白作为分隔符
+pub fn split_tags(s: &str) -> Vec<String> {
+    s.split(|c: char| matches!(c, ',' | '，' | '、' | '/' | '|') || c.is_whitespace())
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// 生成条目 id：优先使用原始数据中的 `id` 字段，否则使用 URL 的 MD5
+pub(crate) fn item_id(raw: &Value, url: &str) -> String {
+    match raw.get("id") {
+        Some(Value::String(id)) if !id.is_empty() => id.clone(),
+        Some(Value::Number(id)) => id.to_string(),
+        _ => format!("{:x}", md5::compute(url)),
+    }
+}
+
+/// 从评分文本中解析数值，如 "9.2分" -> 9.2
+fn parse_score(s: &str) -> Option<f64> {
+    let num: String = s
+        .trim()
+        .chars()
+        .skip_while(|c| !c.is_ascii_digit())
+        .take_while(|c| c.is_ascii_digit() || *c == '.')
+        .collect();
+    num.parse().ok()
+}
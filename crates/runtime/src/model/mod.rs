@@ -5,6 +5,7 @@
 //! ## 模块结构
 //!
 //! - `common`: 通用数据模型（搜索、列表项等）
+//! - `item`: 标准化输出模型（ItemSummary / ItemDetail）
 //! - `book`: 书籍相关模型
 //! - `video`: 视频相关模型
 //! - `audio`: 音频相关模型
@@ -13,11 +14,13 @@
 mod audio;
 mod book;
 mod common;
+mod item;
 mod manga;
 mod video;
 
 pub use audio::*;
 pub use book::*;
 pub use common::*;
+pub use item::*;
 pub use manga::*;
 pub use video::*;
@@ -0,0 +1,151 @@
+//! # 规则文件加载
+//!
+//! 提供统一的规则加载入口，支持 TOML 与 JSON 两种格式：
+//! - 按扩展名自动识别格式
+//! - 去除 UTF-8 BOM
+//! - 规范化 `meta.encoding`
+//! - 反序列化失败时按 JSON Schema 校验，报告带字段路径的错误
+//! - 解析后自动执行规则校验
+
+pub mod schema;
+pub mod validator;
+
+pub use schema::{SchemaViolation, ValidationResult, validate_against_schema};
+pub use validator::{ValidationReport, ValidationWarning};
+
+use crate::{Result, RuntimeError};
+use crawler_schema::{config::ResponseEncoding, core::CrawlerRule};
+use std::path::Path;
+
+/// 规则文件格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RuleFormat {
+    /// TOML
+    Toml,
+    /// JSON
+    Json,
+}
+
+impl RuleFormat {
+    /// 根据文件扩展名识别格式
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        let ext = path.as_ref().extension()?.to_str()?;
+        match ext.to_lowercase().as_str() {
+            "toml" => Some(Self::Toml),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    /// 转为字符串
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Toml => "toml",
+            Self::Json => "json",
+        }
+    }
+}
+
+/// 已加载并通过校验的规则文件
+#[derive(Debug, Clone)]
+pub struct RuleFile {
+    /// 规则内容
+    pub rule: CrawlerRule,
+    /// 源文件格式
+    pub format: RuleFormat,
+}
+
+impl RuleFile {
+    /// 从文件加载规则，按扩展名（`.toml` / `.json`）选择解析器
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let format = RuleFormat::from_path(path).ok_or_else(|| {
+            RuntimeError::Config(format!(
+                "无法识别规则文件格式 '{}'，仅支持 .toml / .json",
+                path.display()
+            ))
+        })?;
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            RuntimeError::Config(format!("读取规则文件 '{}' 失败: {}", path.display(), e))
+        })?;
+
+        Self::from_str(&content, format)
+    }
+
+    /// 从字符串加载规则
+    pub fn from_str(content: &str, format: RuleFormat) -> Result<Self> {
+        let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+
+        let parsed = match format {
+            RuleFormat::Toml => toml::from_str(content).map_err(|e| e.to_string()),
+            RuleFormat::Json => serde_json::from_str(content).map_err(|e| e.to_string()),
+        };
+        let mut rule: CrawlerRule = parsed.map_err(|error| RuntimeError::RuleParse {
+            format: format.as_str().to_string(),
+            error: Self::explain_parse_error(content, format).unwrap_or(error),
+        })?;
+        normalize_encoding(&mut rule)?;
+
+        let file = Self { rule, format };
+        for warning in file.validate()?.warnings {
+            tracing::warn!("规则校验警告: {}", warning);
+        }
+        Ok(file)
+    }
+
+    /// 反序列化失败时，按 JSON Schema 校验原始内容，给出带字段路径的错误说明
+    ///
+    /// 内容本身无法解析或 Schema 校验未发现问题时返回 None，沿用原始错误
+    fn explain_parse_error(content: &str, format: RuleFormat) -> Option<String> {
+        let value: serde_json::Value = match format {
+            RuleFormat::Toml => toml::from_str(content).ok()?,
+            RuleFormat::Json => serde_json::from_str(content).ok()?,
+        };
+        let result = validate_against_schema(&value);
+        (!result.is_valid()).then(|| result.to_string())
+    }
+
+    /// 校验规则，返回不影响执行的警告
+    pub fn validate(&self) -> Result<ValidationReport> {
+        validator::validate(&self.rule)
+    }
+
+    /// 取出规则
+    pub fn into_rule(self) -> CrawlerRule {
+        self.rule
+    }
+}
+
+/// 规范化 `meta.encoding`
+///
+/// 编码名不区分大小写；未单独配置响应编码时，以 `meta.encoding` 作为默认响应编码
+fn normalize_encoding(rule: &mut CrawlerRule) -> Result<()> {
+    let Some(encoding) = rule.meta.encoding.as_deref() else {
+        return Ok(());
+    };
+
+    let label = encoding.trim().to_lowercase().replace('_', "-");
+    let label = match label.as_str() {
+        "utf8" => "utf-8",
+        "shift-jis" | "sjis" => "shift_jis",
+        other => other,
+    };
+    let parsed: ResponseEncoding =
+        serde_json::from_value(serde_json::Value::from(label)).map_err(|_| {
+            RuntimeError::InvalidConfigValue {
+                field: "meta.encoding".to_string(),
+                reason: format!("不支持的编码 '{}'", encoding),
+            }
+        })?;
+    rule.meta.encoding = Some(label.to_string());
+
+    let response = rule
+        .http
+        .get_or_insert_with(Default::default)
+        .response
+        .get_or_insert_with(Default::default);
+    if response.encoding.is_none() {
+        response.encoding = Some(parsed);
+    }
+    Ok(())
+}
@@ -0,0 +1,225 @@
+//! # JSON Schema 校验
+//!
+//! 使用 [`jsonschema`] 按 [`rule_file_schema`] 生成的 Schema 校验规则的原始 JSON，
+//! 相比 serde 的反序列化错误，能一次报告全部问题并给出字段路径（如 `meta.name`）。
+//!
+//! 校验错误的 `instance_path`（JSON Pointer）转换为点号路径，缺少必填字段与未知字段
+//! 定位到字段本身；`oneOf` / `anyOf` 都不匹配时报告形状一致且错误最少的分支
+
+use crawler_schema::json_schema::rule_file_schema;
+use jsonschema::{
+    ValidationError,
+    Validator,
+    error::{TypeKind, ValidationErrorKind},
+    paths::{Location, LocationSegment},
+};
+use serde_json::Value;
+use std::{fmt, sync::OnceLock};
+
+/// 单条 Schema 校验错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaViolation {
+    /// 字段路径，如 `meta.name`、`search.steps[0]`，根为空字符串
+    pub path: String,
+    /// 错误说明
+    pub message: String,
+}
+
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "(根): {}", self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
+}
+
+/// Schema 校验结果
+#[derive(Debug, Clone, Default)]
+pub struct ValidationResult {
+    /// 全部校验错误
+    pub errors: Vec<SchemaViolation>,
+}
+
+impl ValidationResult {
+    /// 是否通过校验
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// 获取指定路径上的错误
+    pub fn errors_at<'a>(&'a self, path: &'a str) -> impl Iterator<Item = &'a SchemaViolation> {
+        self.errors.iter().filter(move |e| e.path == path)
+    }
+}
+
+impl fmt::Display for ValidationResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, error) in self.errors.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", error)?;
+        }
+        Ok(())
+    }
+}
+
+/// 按规则文件 Schema 校验原始 JSON
+///
+/// TOML 规则可先解析为 `serde_json::Value` 再校验
+pub fn validate_against_schema(value: &Value) -> ValidationResult {
+    static VALIDATOR: OnceLock<Validator> = OnceLock::new();
+    let validator = VALIDATOR.get_or_init(|| {
+        jsonschema::validator_for(&rule_file_schema().to_value()).expect("规则文件 Schema 无效")
+    });
+
+    let mut errors = Vec::new();
+    for error in validator.iter_errors(value) {
+        push_error(&error, &mut errors);
+    }
+    ValidationResult { errors }
+}
+
+/// 将校验错误转换为带字段路径的中文说明
+fn push_error(error: &ValidationError, errors: &mut Vec<SchemaViolation>) {
+    let path = dotted_path(error.instance_path());
+    match error.kind() {
+        ValidationErrorKind::Required { property } => {
+            let name = property.as_str().unwrap_or_default();
+            push(errors, &join_path(&path, name), "缺少必填字段");
+        }
+        ValidationErrorKind::AdditionalProperties { unexpected }
+        | ValidationErrorKind::UnevaluatedProperties { unexpected } => {
+            for name in unexpected {
+                push(errors, &join_path(&path, name), "未知字段");
+            }
+        }
+        ValidationErrorKind::OneOfNotValid { context } | ValidationErrorKind::AnyOf { context } => {
+            push_best_branch(context, &path, errors);
+        }
+        // 与 serde 的 untagged 枚举一致，任一分支通过即可，不检查唯一性
+        ValidationErrorKind::OneOfMultipleValid { .. } => {}
+        ValidationErrorKind::Type { kind } => push(
+            errors,
+            &path,
+            format!(
+                "类型应为 {}，实际为 {}",
+                describe_type(kind),
+                type_name(error.instance())
+            ),
+        ),
+        ValidationErrorKind::Enum { options } => {
+            let allowed: Vec<String> = options
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(Value::to_string)
+                .collect();
+            push(
+                errors,
+                &path,
+                format!(
+                    "取值 {} 不在允许范围内: {}",
+                    error.instance(),
+                    allowed.join(", ")
+                ),
+            );
+        }
+        ValidationErrorKind::Constant { expected_value } => {
+            push(errors, &path, format!("取值应为 {}", expected_value));
+        }
+        ValidationErrorKind::Minimum { limit } => push(
+            errors,
+            &path,
+            format!("取值 {} 小于最小值 {}", error.instance(), limit),
+        ),
+        ValidationErrorKind::Maximum { limit } => push(
+            errors,
+            &path,
+            format!("取值 {} 大于最大值 {}", error.instance(), limit),
+        ),
+        ValidationErrorKind::Pattern { pattern } => {
+            push(errors, &path, format!("取值不匹配模式 '{}'", pattern));
+        }
+        ValidationErrorKind::FalseSchema => push(errors, &path, "此处不允许出现值"),
+        _ => push(errors, &path, error.to_string()),
+    }
+}
+
+/// 报告 `oneOf` / `anyOf` 中最接近的分支
+///
+/// 错误全部位于更深路径的分支说明其形状与值一致，取其中错误最少的一个；
+/// 没有这样的分支时报告整体不匹配
+fn push_best_branch(
+    branches: &[Vec<ValidationError<'static>>],
+    path: &str,
+    errors: &mut Vec<SchemaViolation>,
+) {
+    let mut best: Option<Vec<SchemaViolation>> = None;
+    for branch in branches {
+        let mut branch_errors = Vec::new();
+        for error in branch {
+            push_error(error, &mut branch_errors);
+        }
+        let shape_matched = branch_errors.iter().all(|e| e.path != path);
+        if shape_matched && best.as_ref().is_none_or(|b| branch_errors.len() < b.len()) {
+            best = Some(branch_errors);
+        }
+    }
+
+    match best {
+        Some(branch_errors) => errors.extend(branch_errors),
+        None => push(errors, path, "取值不匹配任何允许的形式"),
+    }
+}
+
+fn push(errors: &mut Vec<SchemaViolation>, path: &str, message: impl Into<String>) {
+    errors.push(SchemaViolation {
+        path: path.to_string(),
+        message: message.into(),
+    });
+}
+
+/// 将 JSON Pointer 形式的位置转换为 `search.steps[0]` 形式的路径
+fn dotted_path(location: &Location) -> String {
+    let mut path = String::new();
+    for segment in location {
+        match segment {
+            LocationSegment::Property(name) => path = join_path(&path, &name),
+            LocationSegment::Index(index) => path.push_str(&format!("[{}]", index)),
+        }
+    }
+    path
+}
+
+fn join_path(parent: &str, name: &str) -> String {
+    if parent.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", parent, name)
+    }
+}
+
+fn describe_type(kind: &TypeKind) -> String {
+    match kind {
+        TypeKind::Single(ty) => ty.to_string(),
+        TypeKind::Multiple(types) => types
+            .iter()
+            .map(|ty| ty.to_string())
+            .collect::<Vec<_>>()
+            .join(" | "),
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
@@ -0,0 +1,643 @@
+//! # 规则校验
+//!
+//! 在执行前对规则做静态检查，尽早暴露配置错误
+
+use crate::{Result, RuntimeError, http::RESPONSE_VAR, template::required_variables};
+use crawler_schema::{
+    config::HttpConfig,
+    core::CrawlerRule,
+    extract::{ExtractStep, FieldExtractor, VarContext},
+    template::Template,
+};
+use serde_json::Value;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+/// 运行时支持的最低规范版本
+pub const MIN_SPEC_VERSION: &str = "1.0.0";
+
+/// 运行时支持的最高规范版本
+///
+/// 只比较主、次版本号，修订号不影响兼容性
+pub const MAX_SPEC_VERSION: &str = "1.0";
+
+/// 校验报告
+///
+/// 记录不阻止规则执行、但很可能是配置笔误的问题
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// 警告列表，按发现顺序排列
+    pub warnings: Vec<ValidationWarning>,
+}
+
+impl ValidationReport {
+    /// 是否没有任何警告
+    pub fn is_clean(&self) -> bool {
+        self.warnings.is_empty()
+    }
+}
+
+/// 校验警告
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationWarning {
+    /// 出现问题的步骤链路径，如 `search.fields.title`
+    pub path: String,
+    /// 警告信息
+    pub message: String,
+}
+
+impl fmt::Display for ValidationWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// 校验规则
+///
+/// 检查项：
+/// - `meta` 必填字段非空
+/// - `meta.spec_version` 在运行时支持范围内
+/// - `use_component` 引用的组件均已定义
+/// - 组件之间不存在循环引用
+/// - 同一步骤链中重复写入同名变量（仅警告，记录在返回的 [`ValidationReport`] 中）
+/// - 请求模板与步骤模板引用的变量均已定义
+/// - 详情/内容字段规则与 `meta.media_type` 一致
+pub fn validate(rule: &CrawlerRule) -> Result<ValidationReport> {
+    validate_meta(rule)?;
+    validate_spec_version(&rule.meta.spec_version)?;
+    validate_field_mapping(rule)?;
+    validate_components(rule)?;
+    let warnings = validate_variables(rule)?;
+    validate_template_variables(rule)?;
+    Ok(ValidationReport { warnings })
+}
+
+/// 校验元数据必填字段
+fn validate_meta(rule: &CrawlerRule) -> Result<()> {
+    let meta = &rule.meta;
+    for (field, value) in [
+        ("meta.name", &meta.name),
+        ("meta.version", &meta.version),
+        ("meta.spec_version", &meta.spec_version),
+        ("meta.domain", &meta.domain),
+    ] {
+        if value.trim().is_empty() {
+            return Err(RuntimeError::MissingConfig {
+                field: field.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// 校验规范版本是否在 [`MIN_SPEC_VERSION`] 与 [`MAX_SPEC_VERSION`] 之间
+///
+/// 版本号格式为 `主[.次[.修订]]`，缺省部分视为 0
+fn validate_spec_version(spec_version: &str) -> Result<()> {
+    let version = parse_version(spec_version).ok_or_else(|| RuntimeError::InvalidConfigValue {
+        field: "meta.spec_version".to_string(),
+        reason: format!("无效的版本号 '{}'", spec_version),
+    })?;
+    let min = parse_version(MIN_SPEC_VERSION).unwrap_or_default();
+    let max = parse_version(MAX_SPEC_VERSION).unwrap_or_default();
+
+    if version < min || (version[0], version[1]) > (max[0], max[1]) {
+        return Err(RuntimeError::IncompatibleSpecVersion {
+            required: spec_version.trim().to_string(),
+            supported: format!("{} ~ {}.x", MIN_SPEC_VERSION, MAX_SPEC_VERSION),
+        });
+    }
+    Ok(())
+}
+
+/// 解析 `主[.次[.修订]]` 形式的版本号，允许 `v` 前缀
+fn parse_version(version: &str) -> Option<[u64; 3]> {
+    let version = version.trim();
+    let version = version.strip_prefix(['v', 'V']).unwrap_or(version);
+    let mut parts = [0u64; 3];
+    for (index, part) in version.split('.').enumerate() {
+        *parts.get_mut(index)? = part.parse().ok()?;
+    }
+    Some(parts)
+}
+
+/// 校验字段映射
+///
+/// 详情/内容流程的字段规则决定输出模型（如书籍详情需要 `title`、`author`），
+/// 与 `meta.media_type` 不一致时运行期会产出错误的模型。
+///
+/// 模型的必填字段由字段规则的类型保证存在，但可能未配置任何提取步骤
+/// （如 `title.steps = []`），此时同样视为未映射
+fn validate_field_mapping(rule: &CrawlerRule) -> Result<()> {
+    let expected = rule.meta.media_type;
+    let mappings = [
+        ("detail.fields", Some(rule.detail.fields.media_type())),
+        (
+            "content.fields",
+            rule.content.as_ref().map(|c| c.fields.media_type()),
+        ),
+    ];
+
+    for (field, actual) in mappings {
+        if let Some(actual) = actual
+            && actual != expected
+        {
+            return Err(RuntimeError::InvalidFieldMapping {
+                field: field.to_string(),
+                reason: format!(
+                    "字段规则为{}类型，与 meta.media_type（{}）不一致",
+                    actual.display_name(),
+                    expected.display_name()
+                ),
+            });
+        }
+    }
+
+    let mut required = vec![
+        ("search.fields", rule.search.fields.required_fields()),
+        ("detail.fields", rule.detail.fields.required_fields()),
+    ];
+    if let Some(discovery) = &rule.discovery {
+        required.push(("discovery.fields", discovery.fields.required_fields()));
+    }
+    if let Some(content) = &rule.content {
+        required.push(("content.fields", content.fields.required_fields()));
+    }
+    for (path, fields) in required {
+        if let Some((name, _)) = fields.iter().find(|(_, field)| !field.is_mapped()) {
+            return Err(RuntimeError::InvalidFieldMapping {
+                field: format!("{}.{}", path, name),
+                reason: "必填字段未配置提取步骤、回退链或默认值".to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// 校验组件引用
+fn validate_components(rule: &CrawlerRule) -> Result<()> {
+    // 规则结构层级较深，统一转为 JSON 后遍历所有 `use_component` 步骤
+    let tree = serde_json::to_value(rule).map_err(|e| RuntimeError::Config(e.to_string()))?;
+    let components = rule.components.as_ref();
+
+    let mut refs = Vec::new();
+    collect_component_refs(&tree, &mut refs);
+    for (name, args) in refs {
+        let Some(definition) = components.and_then(|c| c.get(&name)) else {
+            return Err(RuntimeError::UndefinedComponent { component: name });
+        };
+        // 必填参数需在引用处传入
+        if let Some(missing) = definition
+            .required
+            .iter()
+            .flatten()
+            .find(|arg| !args.contains(arg))
+        {
+            return Err(RuntimeError::MissingComponentArg {
+                component: name,
+                arg: missing.clone(),
+            });
+        }
+    }
+
+    // 组件依赖图
+    let Some(components) = components else {
+        return Ok(());
+    };
+    let mut graph: HashMap<&str, Vec<String>> = HashMap::new();
+    for (name, definition) in components {
+        let tree = serde_json::to_value(&definition.extractor)
+            .map_err(|e| RuntimeError::Config(e.to_string()))?;
+        let mut deps = Vec::new();
+        collect_component_refs(&tree, &mut deps);
+        graph.insert(name, deps.into_iter().map(|(dep, _)| dep).collect());
+    }
+
+    let mut done = HashSet::new();
+    let mut names: Vec<&str> = graph.keys().copied().collect();
+    names.sort_unstable();
+    for name in names {
+        let mut path = Vec::new();
+        detect_cycle(name, &graph, &mut path, &mut done)?;
+    }
+    Ok(())
+}
+
+/// 深度优先检测组件循环引用
+fn detect_cycle<'a>(
+    name: &'a str,
+    graph: &'a HashMap<&str, Vec<String>>,
+    path: &mut Vec<&'a str>,
+    done: &mut HashSet<&'a str>,
+) -> Result<()> {
+    if done.contains(name) {
+        return Ok(());
+    }
+    if let Some(pos) = path.iter().position(|n| *n == name) {
+        let mut cycle = path[pos..].to_vec();
+        cycle.push(name);
+        return Err(RuntimeError::CircularReference {
+            path: cycle.join(" -> "),
+        });
+    }
+
+    path.push(name);
+    for dep in graph.get(name).into_iter().flatten() {
+        detect_cycle(dep, graph, path, done)?;
+    }
+    path.pop();
+    done.insert(name);
+    Ok(())
+}
+
+/// 收集 JSON 树中所有 `use_component` 引用的组件名及传入的参数名
+fn collect_component_refs(value: &Value, refs: &mut Vec<(String, Vec<String>)>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                if key == "use_component" {
+                    // 简单引用为字符串，带参数引用为 `{ name, args }`
+                    let name = child
+                        .as_str()
+                        .or_else(|| child.get("name").and_then(Value::as_str));
+                    let args = child
+                        .get("args")
+                        .and_then(Value::as_object)
+                        .map(|args| args.keys().cloned().collect())
+                        .unwrap_or_default();
+                    if let Some(name) = name {
+                        refs.push((name.to_string(), args));
+                    }
+                }
+                collect_component_refs(child, refs);
+            }
+        }
+        Value::Array(arr) => arr.iter().for_each(|v| collect_component_refs(v, refs)),
+        _ => {}
+    }
+}
+
+/// 校验变量写入，返回发现的警告
+fn validate_variables(rule: &CrawlerRule) -> Result<Vec<ValidationWarning>> {
+    let tree = serde_json::to_value(rule).map_err(|e| RuntimeError::Config(e.to_string()))?;
+    let mut extractors = Vec::new();
+    collect_extractors(&tree, "", &mut extractors);
+
+    let mut warnings = Vec::new();
+    for (path, extractor) in extractors {
+        check_variable_writes(&extractor.steps, &path, &mut HashSet::new(), &mut warnings);
+        for (index, steps) in extractor.fallback.iter().flatten().enumerate() {
+            let path = format!("{}.fallback[{}]", path, index);
+            check_variable_writes(steps, &path, &mut HashSet::new(), &mut warnings);
+        }
+    }
+    Ok(warnings)
+}
+
+/// 检查步骤链中的 `set_var` 是否重复写入同名变量，重复写入记录为警告
+///
+/// 后写入的值覆盖先写入的值，多为笔误但也可能是有意为之，因此不视为错误。
+/// - `map` 内的步骤逐元素执行，使用独立作用域
+/// - `condition` 的分支互斥，各自继承外层已写入的变量，分支结束后合并回外层
+/// - `try` 与 `catch` 同样按互斥分支处理
+fn check_variable_writes(
+    steps: &[ExtractStep],
+    path: &str,
+    written: &mut HashSet<String>,
+    warnings: &mut Vec<ValidationWarning>,
+) {
+    for step in steps {
+        match step {
+            ExtractStep::SetVar(set_var) if !written.insert(set_var.name.clone()) => {
+                warnings.push(ValidationWarning {
+                    path: path.to_string(),
+                    message: format!("变量 '{}' 被重复写入", set_var.name),
+                });
+            }
+            ExtractStep::Map(map) => {
+                check_variable_writes(
+                    map.steps(),
+                    &format!("{}.map", path),
+                    &mut HashSet::new(),
+                    warnings,
+                );
+            }
+            ExtractStep::Condition(condition) => {
+                check_variable_writes(
+                    &condition.when,
+                    &format!("{}.when", path),
+                    written,
+                    warnings,
+                );
+
+                let mut merged = written.clone();
+                for (name, branch) in [
+                    ("then", Some(&condition.then)),
+                    ("otherwise", condition.otherwise.as_ref()),
+                ] {
+                    let Some(branch) = branch else {
+                        continue;
+                    };
+                    let mut scope = written.clone();
+                    check_variable_writes(
+                        branch,
+                        &format!("{}.{}", path, name),
+                        &mut scope,
+                        warnings,
+                    );
+                    merged.extend(scope);
+                }
+                *written = merged;
+            }
+            // try 失败后才执行 catch，两者写入的变量视为互斥分支
+            ExtractStep::Try(try_step) => {
+                let mut merged = written.clone();
+                for (name, branch) in [
+                    ("try", Some(&try_step.steps)),
+                    ("catch", try_step.catch.as_ref()),
+                ] {
+                    let Some(branch) = branch else {
+                        continue;
+                    };
+                    let mut scope = written.clone();
+                    check_variable_writes(
+                        branch,
+                        &format!("{}.{}", path, name),
+                        &mut scope,
+                        warnings,
+                    );
+                    merged.extend(scope);
+                }
+                *written = merged;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// 内置全局变量
+const BUILTIN_GLOBALS: &[&str] = &["base_url", "domain"];
+
+/// 各流程入口注入的变量
+///
+/// discovery 的筛选变量由调用方传入，无法静态确定，不做检查
+const FLOW_VARIABLES: &[(&str, &[&str])] = &[
+    ("search", &["keyword", "page", "cursor", "base_url"]),
+    ("detail", &["detail_url"]),
+    ("content", &["content_url"]),
+];
+
+/// 步骤模板中由运行时提供的变量：请求地址、响应、当前值与 `try` 捕获的错误
+const STEP_VARIABLES: &[&str] = &["request_url", RESPONSE_VAR, "value", "error"];
+
+/// 校验模板引用的变量
+///
+/// 请求模板在字段提取之前渲染，因此可用变量为：
+/// - 流程入口注入的变量
+/// - 全局变量（内置变量与任意步骤以 `context = "runtime"` 写入的变量）
+///
+/// 步骤模板（`template`、`assert`、`log`）在字段提取时渲染，额外可用：
+/// - 运行时提供的变量（见 [`STEP_VARIABLES`]）
+/// - 同一步骤链中此前的 `set_var` 写入的流程变量（见 [`check_step_templates`]）
+///
+/// 组件中的模板可使用调用方传入的参数，无法静态确定，不做检查；
+/// 带 `default` 过滤器或经 `is defined` 判断的变量视为可选
+fn validate_template_variables(rule: &CrawlerRule) -> Result<()> {
+    let tree = serde_json::to_value(rule).map_err(|e| RuntimeError::Config(e.to_string()))?;
+    let mut extractors = Vec::new();
+    collect_extractors(&tree, "", &mut extractors);
+
+    let mut globals: HashSet<String> = BUILTIN_GLOBALS.iter().map(|v| v.to_string()).collect();
+    for (_, extractor) in &extractors {
+        collect_runtime_vars(&extractor.steps, &mut globals);
+        for steps in extractor.fallback.iter().flatten() {
+            collect_runtime_vars(steps, &mut globals);
+        }
+    }
+
+    let flows = [
+        (
+            "search",
+            Some((&rule.search.url, rule.search.http.as_ref())),
+        ),
+        (
+            "detail",
+            Some((&rule.detail.url, rule.detail.http.as_ref())),
+        ),
+        (
+            "content",
+            rule.content.as_ref().map(|f| (&f.url, f.http.as_ref())),
+        ),
+    ];
+    for (flow, templates) in flows {
+        let Some((url, http)) = templates else {
+            continue;
+        };
+        let entry = FLOW_VARIABLES
+            .iter()
+            .find(|(name, _)| *name == flow)
+            .map(|(_, vars)| *vars)
+            .unwrap_or_default();
+        let defined = |var: &str| entry.contains(&var) || globals.contains(var);
+
+        check_template(url, &format!("{}.url", flow), &globals, defined)?;
+        for (path, template) in request_templates(http, &format!("{}.http", flow)) {
+            check_template(template, &path, &globals, defined)?;
+        }
+
+        let mut flow_extractors = Vec::new();
+        collect_extractors(&tree[flow], flow, &mut flow_extractors);
+        let check = |template: &Template, path: &str, written: &HashSet<String>| {
+            check_template(template, path, &globals, |var| {
+                defined(var) || written.contains(var) || STEP_VARIABLES.contains(&var)
+            })
+        };
+        // 每条步骤链（主链与各回退链）以空的流程变量集合开始执行
+        for (path, extractor) in &flow_extractors {
+            check_step_templates(&extractor.steps, path, &mut HashSet::new(), &check)?;
+            for (index, steps) in extractor.fallback.iter().flatten().enumerate() {
+                let path = format!("{}.fallback[{}]", path, index);
+                check_step_templates(steps, &path, &mut HashSet::new(), &check)?;
+            }
+        }
+    }
+
+    // 全局请求配置会在任意流程中渲染，允许使用所有流程的入口变量
+    for (path, template) in request_templates(rule.http.as_ref(), "http") {
+        let defined = |var: &str| {
+            globals.contains(var) || FLOW_VARIABLES.iter().any(|(_, vars)| vars.contains(&var))
+        };
+        check_template(template, &path, &globals, defined)?;
+    }
+    Ok(())
+}
+
+/// 检查单个模板引用的变量
+fn check_template(
+    template: &Template,
+    path: &str,
+    globals: &HashSet<String>,
+    defined: impl Fn(&str) -> bool,
+) -> Result<()> {
+    for var in required_variables(template.as_str()) {
+        let ok = match var.strip_prefix("$.") {
+            Some(global) => globals.contains(global),
+            None => defined(&var),
+        };
+        if !ok {
+            return Err(RuntimeError::TemplateValidation {
+                template: template.to_string(),
+                error: format!("{} 引用了未定义的变量 '{}'", path, var),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// 列出请求配置中的模板
+fn request_templates<'a>(http: Option<&'a HttpConfig>, path: &str) -> Vec<(String, &'a Template)> {
+    let Some(request) = http.and_then(|h| h.request.as_ref()) else {
+        return Vec::new();
+    };
+
+    let mut templates = Vec::new();
+    if let Some(body) = &request.body {
+        templates.push((format!("{}.request.body", path), body));
+    }
+    for (name, value) in request.headers.iter().flatten() {
+        templates.push((format!("{}.request.headers.{}", path, name), value));
+    }
+    templates
+}
+
+/// 收集步骤链中 `set_var` 写入运行时上下文的变量
+fn collect_runtime_vars(steps: &[ExtractStep], vars: &mut HashSet<String>) {
+    for step in steps {
+        match step {
+            ExtractStep::SetVar(set_var) if matches!(set_var.context, VarContext::Runtime) => {
+                vars.insert(set_var.name.clone());
+            }
+            ExtractStep::Map(map) => collect_runtime_vars(map.steps(), vars),
+            ExtractStep::Try(try_step) => {
+                collect_runtime_vars(&try_step.steps, vars);
+                if let Some(catch) = &try_step.catch {
+                    collect_runtime_vars(catch, vars);
+                }
+            }
+            ExtractStep::Condition(condition) => {
+                collect_runtime_vars(&condition.when, vars);
+                collect_runtime_vars(&condition.then, vars);
+                if let Some(otherwise) = &condition.otherwise {
+                    collect_runtime_vars(otherwise, vars);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// 按执行顺序检查步骤链中 `template`、`assert`、`log` 步骤的模板
+///
+/// `written` 为执行到当前步骤前已写入的流程变量，作用域与运行时一致：
+/// - `set_var`（`context = "flow"`）写入的变量只对其后的步骤可见
+/// - `map` 与 `condition.when` 在独立作用域中执行，可读取外层变量，写入的变量不外泄
+/// - `condition` 的分支、`try` 与 `catch` 各自继承外层变量，结束后将写入的变量合并回外层
+fn check_step_templates<F>(
+    steps: &[ExtractStep],
+    path: &str,
+    written: &mut HashSet<String>,
+    check: &F,
+) -> Result<()>
+where
+    F: Fn(&Template, &str, &HashSet<String>) -> Result<()>,
+{
+    for (index, step) in steps.iter().enumerate() {
+        let path = format!("{}.steps[{}]", path, index);
+        match step {
+            ExtractStep::SetVar(set_var) if matches!(set_var.context, VarContext::Flow) => {
+                written.insert(set_var.name.clone());
+            }
+            ExtractStep::Template(template) => check(template, &path, written)?,
+            ExtractStep::Assert(assert) => check(
+                &assert.condition,
+                &format!("{}.assert.condition", path),
+                written,
+            )?,
+            ExtractStep::Log(log) => {
+                check(&log.message, &format!("{}.log.message", path), written)?;
+                for (key, template) in log.context.iter().flatten() {
+                    check(template, &format!("{}.log.context.{}", path, key), written)?;
+                }
+            }
+            ExtractStep::Map(map) => {
+                check_step_templates(map.steps(), &path, &mut written.clone(), check)?;
+            }
+            ExtractStep::Condition(condition) => {
+                let when = format!("{}.when", path);
+                check_step_templates(&condition.when, &when, &mut written.clone(), check)?;
+                let mut merged = written.clone();
+                for (name, branch) in [
+                    ("then", Some(&condition.then)),
+                    ("otherwise", condition.otherwise.as_ref()),
+                ] {
+                    let Some(branch) = branch else {
+                        continue;
+                    };
+                    let mut scope = written.clone();
+                    let path = format!("{}.{}", path, name);
+                    check_step_templates(branch, &path, &mut scope, check)?;
+                    merged.extend(scope);
+                }
+                *written = merged;
+            }
+            ExtractStep::Try(try_step) => {
+                let mut merged = written.clone();
+                for (name, branch) in [
+                    ("try", Some(&try_step.steps)),
+                    ("catch", try_step.catch.as_ref()),
+                ] {
+                    let Some(branch) = branch else {
+                        continue;
+                    };
+                    let mut scope = written.clone();
+                    let path = format!("{}.{}", path, name);
+                    check_step_templates(branch, &path, &mut scope, check)?;
+                    merged.extend(scope);
+                }
+                *written = merged;
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// 收集 JSON 树中所有字段提取器及其路径
+fn collect_extractors(value: &Value, path: &str, extractors: &mut Vec<(String, FieldExtractor)>) {
+    match value {
+        Value::Object(map) => {
+            if map.get("steps").is_some_and(Value::is_array)
+                && let Ok(extractor) = serde_json::from_value(value.clone())
+            {
+                extractors.push((path.to_string(), extractor));
+                return;
+            }
+            for (key, child) in map {
+                let path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                collect_extractors(child, &path, extractors);
+            }
+        }
+        Value::Array(arr) => {
+            for (index, child) in arr.iter().enumerate() {
+                collect_extractors(child, &format!("{}[{}]", path, index), extractors);
+            }
+        }
+        _ => {}
+    }
+}
@@ -60,6 +60,43 @@ pub fn substring(s: &str, start: usize, end: Option<usize>) -> String {
     chars[start..end].iter().collect()
 }
 
+/// 取第一对标记之间的文本
+///
+/// 如 `extract_between("<!--start-->abc<!--end-->", "<!--start-->", "<!--end-->")` 返回 `abc`；
+/// 标记不存在时返回空字符串，`end` 为空时取到字符串末尾
+pub fn extract_between(s: &str, start: &str, end: &str) -> String {
+    between(s, start, end)
+        .map(|(text, _)| text.to_string())
+        .unwrap_or_default()
+}
+
+/// 取所有标记对之间的文本，标记不存在时返回空数组
+pub fn extract_all_between(s: &str, start: &str, end: &str) -> Vec<String> {
+    let mut result = Vec::new();
+    let mut rest = s;
+    while let Some((text, next)) = between(rest, start, end) {
+        result.push(text.to_string());
+        // 空结束标记会一直取到末尾，避免死循环
+        if end.is_empty() || next.is_empty() {
+            break;
+        }
+        rest = next;
+    }
+    result
+}
+
+/// 查找第一对标记，返回标记间文本与结束标记之后的剩余部分
+fn between<'a>(s: &'a str, start: &str, end: &str) -> Option<(&'a str, &'a str)> {
+    let from = s.find(start)? + start.len();
+    let rest = &s[from..];
+    let to = if end.is_empty() {
+        rest.len()
+    } else {
+        rest.find(end)?
+    };
+    Some((&rest[..to], &rest[to + end.len()..]))
+}
+
 /// 检查字符串是否包含子串
 pub fn contains(s: &str, pattern: &str) -> bool {
     s.contains(pattern)
@@ -132,6 +169,168 @@ pub fn pad_end(s: &str, len: usize, pad: &str) -> String {
     result
 }
 
+/// 按显示宽度截断字符串
+///
+/// 中日韩等全角字符宽度为 2，超出 `width` 时截断并追加 `ellipsis`，
+/// 结果（含省略号）的显示宽度不超过 `width`
+pub fn truncate(s: &str, width: usize, ellipsis: &str) -> String {
+    use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+    if s.width() <= width {
+        return s.to_string();
+    }
+
+    let budget = width.saturating_sub(ellipsis.width());
+    let mut result = String::new();
+    let mut used = 0;
+    for c in s.chars() {
+        let w = c.width().unwrap_or(0);
+        if used + w > budget {
+            break;
+        }
+        used += w;
+        result.push(c);
+    }
+    result.push_str(ellipsis);
+    result
+}
+
+/// 编辑距离（Levenshtein）
+///
+/// 按字符（char）而非字节计算，中文每个字算一个单位
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.is_empty() {
+        return b.len();
+    }
+    if b.is_empty() {
+        return a.len();
+    }
+
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            curr[j + 1] = (prev[j + 1] + 1).min(curr[j] + 1).min(prev[j] + cost);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b.len()]
+}
+
+/// 字符串相似度（归一化 Levenshtein）
+///
+/// 返回 0.0-1.0，1.0 表示完全相同；两个空串视为完全相同
+pub fn similarity(a: &str, b: &str) -> f64 {
+    let max_len = a.chars().count().max(b.chars().count());
+    if max_len == 0 {
+        return 1.0;
+    }
+    1.0 - levenshtein(a, b) as f64 / max_len as f64
+}
+
+/// 去除连续重复行
+///
+/// 相邻两行去除首尾空白后相同时只保留第一行，常用于清洗正文中重复的段落或广告行
+pub fn dedup_lines(s: &str) -> String {
+    let mut lines: Vec<&str> = Vec::new();
+    for line in s.lines() {
+        if lines.last().is_some_and(|last| last.trim() == line.trim()) {
+            continue;
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+/// 去除 HTML 标签，保留段落结构
+///
+/// `<br>`、`<p>`、`<div>` 等块级标签转为换行，`<script>`/`<style>` 连同内容移除，
+/// 其余标签直接移除，最后解码 HTML 实体
+pub fn strip_html(s: &str) -> String {
+    static PATTERNS: std::sync::OnceLock<[Regex; 3]> = std::sync::OnceLock::new();
+    let [hidden, breaks, tags] = PATTERNS.get_or_init(|| {
+        [
+            Regex::new(r"(?is)<(script|style)\b[^>]*>.*?</(script|style)>").unwrap(),
+            Regex::new(r"(?i)<br\s*/?>|</?(p|div|li|h[1-6]|tr|section|article)\b[^>]*>").unwrap(),
+            Regex::new(r"<[^>]+>").unwrap(),
+        ]
+    });
+    let text = hidden.replace_all(s, "");
+    let text = breaks.replace_all(&text, "\n");
+    decode_all(&tags.replace_all(&text, ""))
+}
+
+/// 规范化行：每行去除首尾空白（含全角空格），丢弃空行
+pub fn normalize_lines(s: &str) -> String {
+    s.lines()
+        .map(|line| line.trim_matches(|c: char| c.is_whitespace() || c == '\u{3000}'))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 轻量占位替换
+///
+/// 将 `{name}` 替换为 `args` 中的同名字段，支持 `{a.b}` 访问嵌套字段、
+/// `args` 为数组时用 `{0}` 按下标访问。字符串原样插入，null 为空串，其他值按 JSON 文本插入。
+///
+/// `{{`、`}}` 原样保留，不会与页面中的模板语法冲突；找不到的占位符保持不变
+pub fn format_str(template: &str, args: &serde_json::Value) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find(['{', '}']) {
+        result.push_str(&rest[..start]);
+        let after = &rest[start..];
+        if after.starts_with("{{") || after.starts_with("}}") {
+            result.push_str(&after[..2]);
+            rest = &after[2..];
+            continue;
+        }
+        let placeholder = after.strip_prefix('{').and_then(|inner| {
+            let end = inner.find('}')?;
+            let name = &inner[..end];
+            let valid = !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '_' || c == '.');
+            valid.then_some(name)
+        });
+        let value = placeholder.and_then(|name| {
+            name.split('.').try_fold(args, |value, key| match value {
+                serde_json::Value::Array(arr) => arr.get(key.parse::<usize>().ok()?),
+                _ => value.get(key),
+            })
+        });
+        match (placeholder, value) {
+            (Some(name), Some(value)) => {
+                match value {
+                    serde_json::Value::String(s) => result.push_str(s),
+                    serde_json::Value::Null => {}
+                    // JS 引擎传入的整数为浮点数，整数值按整数输出
+                    serde_json::Value::Number(n) => match n.as_f64() {
+                        Some(f) if n.is_f64() && f.fract() == 0.0 && f.abs() < 1e15 => {
+                            result.push_str(&(f as i64).to_string())
+                        }
+                        _ => result.push_str(&n.to_string()),
+                    },
+                    other => result.push_str(&other.to_string()),
+                }
+                rest = &after[name.len() + 2..];
+            }
+            _ => {
+                result.push_str(&after[..1]);
+                rest = &after[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
 // ============================================
 // 正则表达式函数
 // ============================================
@@ -148,6 +347,73 @@ pub fn regex_replace(text: &str, pattern: &str, replacement: &str) -> String {
         .unwrap_or_else(|_| text.to_string())
 }
 
+/// 正则替换，对捕获组应用内置转换
+///
+/// 对每个匹配，将第 1 个捕获组（正则没有捕获组时为整个匹配）替换为转换结果，
+/// 匹配中的其余部分保持不变。支持的转换见 [`transform_text`]
+///
+/// 例如 `regex_replace_map("第一章 第二章", "第(.+?)章", "cn_to_num")` 得到 `"第1章 第2章"`
+pub fn regex_replace_map(text: &str, pattern: &str, transform: &str) -> Result<String, String> {
+    let re = Regex::new(pattern).map_err(|e| e.to_string())?;
+    transform_text(transform, "").ok_or_else(|| format!("未知的转换: {}", transform))?;
+    let group = usize::from(re.captures_len() > 1);
+
+    Ok(re
+        .replace_all(text, |caps: &regex::Captures| {
+            let whole = caps.get(0).map(|m| m.as_str()).unwrap_or_default();
+            let Some(target) = caps.get(group) else {
+                return whole.to_string();
+            };
+            let start = caps.get(0).map_or(0, |m| m.start());
+            let converted = transform_text(transform, target.as_str()).unwrap_or_default();
+            format!(
+                "{}{}{}",
+                &whole[..target.start() - start],
+                converted,
+                &whole[target.end() - start..]
+            )
+        })
+        .to_string())
+}
+
+/// [`transform_text`] 支持的转换名称
+pub const TRANSFORM_NAMES: &[&str] = &[
+    "cn_to_num",
+    "cn_to_float",
+    "num_to_cn",
+    "upper",
+    "lower",
+    "trim",
+    "t2s",
+    "s2t",
+    "url_encode",
+    "url_decode",
+    "html_decode",
+    "decode_all",
+];
+
+/// 按名称应用内置文本转换，名称未知时返回 None
+///
+/// 支持的名称见 [`TRANSFORM_NAMES`]
+pub fn transform_text(name: &str, s: &str) -> Option<String> {
+    let result = match name {
+        "cn_to_num" => cn_to_num(s).to_string(),
+        "cn_to_float" => cn_to_float(s).to_string(),
+        "num_to_cn" => decimal_to_cn(s).unwrap_or_else(|| s.to_string()),
+        "upper" => upper(s),
+        "lower" => lower(s),
+        "trim" => trim(s),
+        "t2s" => t2s(s),
+        "s2t" => s2t(s),
+        "url_encode" => url_encode(s),
+        "url_decode" => url_decode(s).unwrap_or_else(|_| s.to_string()),
+        "html_decode" => html_decode(s),
+        "decode_all" => decode_all(s),
+        _ => return None,
+    };
+    Some(result)
+}
+
 /// 正则提取（返回第一个匹配）
 pub fn regex_find(text: &str, pattern: &str) -> Option<String> {
     Regex::new(pattern)
@@ -176,6 +442,19 @@ pub fn regex_captures(text: &str, pattern: &str) -> Vec<String> {
         .unwrap_or_default()
 }
 
+/// 删除匹配正则的行
+///
+/// 正则无效时原样返回
+pub fn remove_lines_matching(s: &str, pattern: &str) -> String {
+    let Ok(re) = Regex::new(pattern) else {
+        return s.to_string();
+    };
+    s.lines()
+        .filter(|line| !re.is_match(line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 // ============================================
 // 编码/解码函数
 // ============================================
@@ -224,6 +503,84 @@ pub fn html_decode(s: &str) -> String {
         .replace("&nbsp;", " ")
 }
 
+/// 组合解码：HTML 实体解码 + Unicode 转义解码 + 去首尾空白
+///
+/// - HTML 实体：`&amp;` `&lt;` `&gt;` `&quot;` `&apos;` `&nbsp;` 及数字实体 `&#228;` / `&#xe4;`
+/// - Unicode 转义：`\uXXXX`（含代理对）与 `\u{XXXX}`
+///
+/// 单次扫描完成，`&amp;lt;` 只解码一层；无法识别的序列原样保留
+pub fn decode_all(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(pos) = rest.find(['&', '\\']) {
+        out.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+        let decoded = if rest.starts_with('&') {
+            decode_html_entity(rest)
+        } else {
+            decode_unicode_escape(rest)
+        };
+        match decoded {
+            Some((c, len)) => {
+                out.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                out.push_str(&rest[..1]);
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out.trim().to_string()
+}
+
+/// 解码 `s` 开头的 HTML 实体，返回字符与消耗的字节数
+fn decode_html_entity(s: &str) -> Option<(char, usize)> {
+    let end = s.bytes().take(12).position(|b| b == b';')?;
+    let name = &s[1..end];
+    let c = match name {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => ' ',
+        _ => {
+            let digits = name.strip_prefix('#')?;
+            let code = match digits.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => digits.parse().ok()?,
+            };
+            char::from_u32(code)?
+        }
+    };
+    Some((c, end + 1))
+}
+
+/// 解码 `s` 开头的 Unicode 转义，返回字符与消耗的字节数
+fn decode_unicode_escape(s: &str) -> Option<(char, usize)> {
+    let body = s.strip_prefix("\\u")?;
+    if let Some(braced) = body.strip_prefix('{') {
+        let end = braced.find('}')?;
+        let c = char::from_u32(u32::from_str_radix(&braced[..end], 16).ok()?)?;
+        return Some((c, 4 + end));
+    }
+
+    let high = u32::from_str_radix(body.get(..4)?, 16).ok()?;
+    if (0xD800..0xDC00).contains(&high) {
+        // 代理对：高位后必须紧跟 \uDC00..\uDFFF
+        let low = body
+            .get(4..10)
+            .and_then(|next| next.strip_prefix("\\u"))
+            .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+            .filter(|low| (0xDC00..0xE000).contains(low))?;
+        let c = char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00))?;
+        return Some((c, 12));
+    }
+    Some((char::from_u32(high)?, 6))
+}
+
 /// 十六进制编码
 pub fn hex_encode(s: &str) -> String {
     s.bytes().map(|b| format!("{:02x}", b)).collect()
@@ -265,6 +622,100 @@ pub fn sha1(s: &str) -> String {
     format!("{:x}", hasher.finalize())
 }
 
+/// RSA 公钥加密（PKCS#1 v1.5 填充），返回 base64
+///
+/// 公钥支持 `BEGIN PUBLIC KEY`（SPKI）与 `BEGIN RSA PUBLIC KEY`（PKCS#1）格式的 PEM，
+/// 也接受不带 PEM 头尾的 base64 公钥
+pub fn rsa_encrypt(data: &str, public_key_pem: &str) -> Result<String, String> {
+    use rsa::{
+        Pkcs1v15Encrypt,
+        RsaPublicKey,
+        pkcs1::DecodeRsaPublicKey,
+        pkcs8::DecodePublicKey,
+        rand_core::OsRng,
+    };
+
+    let pem = normalize_pem(public_key_pem, "PUBLIC KEY");
+    let key = RsaPublicKey::from_public_key_pem(&pem)
+        .or_else(|_| RsaPublicKey::from_pkcs1_pem(&pem))
+        .map_err(|e| format!("无效的 RSA 公钥: {}", e))?;
+    let encrypted = key
+        .encrypt(&mut OsRng, Pkcs1v15Encrypt, data.as_bytes())
+        .map_err(|e| format!("RSA 加密失败: {}", e))?;
+    Ok(general_purpose::STANDARD.encode(encrypted))
+}
+
+/// RSA 私钥解密（PKCS#1 v1.5 填充），`data` 为 base64 密文
+///
+/// 私钥支持 `BEGIN PRIVATE KEY`（PKCS#8）与 `BEGIN RSA PRIVATE KEY`（PKCS#1）格式的 PEM
+pub fn rsa_decrypt(data: &str, private_key_pem: &str) -> Result<String, String> {
+    use rsa::Pkcs1v15Encrypt;
+
+    let key = rsa_private_key(private_key_pem)?;
+    let encrypted = general_purpose::STANDARD
+        .decode(data.trim())
+        .map_err(|e| format!("无效的 base64 密文: {}", e))?;
+    let decrypted = key
+        .decrypt(Pkcs1v15Encrypt, &encrypted)
+        .map_err(|e| format!("RSA 解密失败: {}", e))?;
+    String::from_utf8(decrypted).map_err(|e| e.to_string())
+}
+
+/// RSA 私钥签名（PKCS#1 v1.5），返回 base64
+///
+/// `hash` 为摘要算法：`sha1`、`sha224`、`sha256`、`sha384`、`sha512`（不区分大小写，可含连字符）
+pub fn rsa_sign(data: &str, private_key_pem: &str, hash: &str) -> Result<String, String> {
+    use rsa::Pkcs1v15Sign;
+    use sha2::Digest;
+
+    fn sign_with<D: Digest + rsa::pkcs8::AssociatedOid>(
+        key: &rsa::RsaPrivateKey,
+        data: &str,
+    ) -> rsa::Result<Vec<u8>> {
+        key.sign(Pkcs1v15Sign::new::<D>(), &D::digest(data.as_bytes()))
+    }
+
+    let key = rsa_private_key(private_key_pem)?;
+    let signature = match hash.to_ascii_lowercase().replace('-', "").as_str() {
+        "sha1" => sign_with::<sha1::Sha1>(&key, data),
+        "sha224" => sign_with::<sha2::Sha224>(&key, data),
+        "sha256" => sign_with::<sha2::Sha256>(&key, data),
+        "sha384" => sign_with::<sha2::Sha384>(&key, data),
+        "sha512" => sign_with::<sha2::Sha512>(&key, data),
+        other => return Err(format!("不支持的摘要算法: {}", other)),
+    }
+    .map_err(|e| format!("RSA 签名失败: {}", e))?;
+    Ok(general_purpose::STANDARD.encode(signature))
+}
+
+/// 解析 PKCS#8 或 PKCS#1 格式的 RSA 私钥
+fn rsa_private_key(private_key_pem: &str) -> Result<rsa::RsaPrivateKey, String> {
+    use rsa::{RsaPrivateKey, pkcs1::DecodeRsaPrivateKey, pkcs8::DecodePrivateKey};
+
+    let pem = normalize_pem(private_key_pem, "PRIVATE KEY");
+    RsaPrivateKey::from_pkcs8_pem(&pem)
+        .or_else(|_| RsaPrivateKey::from_pkcs1_pem(&pem))
+        .map_err(|e| format!("无效的 RSA 私钥: {}", e))
+}
+
+/// 规范化 PEM：去除首尾空白；不带 PEM 头尾的 base64 按 `label` 补全并按 64 字符换行
+fn normalize_pem(key: &str, label: &str) -> String {
+    let key = key.trim();
+    if key.starts_with("-----BEGIN") {
+        return key.to_string();
+    }
+    let body: String = key.chars().filter(|c| !c.is_whitespace()).collect();
+    let lines: Vec<&str> = body
+        .as_bytes()
+        .chunks(64)
+        .filter_map(|chunk| std::str::from_utf8(chunk).ok())
+        .collect();
+    format!(
+        "-----BEGIN {label}-----\n{}\n-----END {label}-----\n",
+        lines.join("\n")
+    )
+}
+
 // ============================================
 // 中文处理函数 (使用 zhconv 库)
 // ============================================
@@ -309,36 +760,92 @@ pub fn is_hans(s: &str) -> bool {
     zhconv::is_hans(s)
 }
 
-/// 中文数字转阿拉伯数字章节
-/// 例如: "第一百二十三章" -> "第123章"
+/// 默认的章节序号模式，第 1 个捕获组为中文数字
+///
+/// 依次识别 "第X章/回/节/卷/集/部/篇/话"、"卷X" 与 "Chapter X"
+pub const CHAPTER_PATTERNS: &[&str] = &[
+    r"第\s*([零〇一二两三四五六七八九十百千万亿壹贰叁肆伍陆柒捌玖拾佰仟]+)\s*[章回节卷集部篇话]",
+    r"卷\s*([零〇一二两三四五六七八九十百千万亿壹贰叁肆伍陆柒捌玖拾佰仟]+)",
+    r"(?i)\bchapter\s*([零〇一二两三四五六七八九十百千万亿壹贰叁肆伍陆柒捌玖拾佰仟]+)",
+];
+
+/// 中文数字转阿拉伯数字章节，按 [`CHAPTER_PATTERNS`] 识别，其余文本保持不变
+/// 例如: "第一百二十三章" -> "第123章"，"卷二 第三回" -> "卷2 第3回"
 pub fn to_num_chapter(s: &str) -> String {
-    let re = Regex::new(r"第([零一二三四五六七八九十百千万]+)章").unwrap();
-    re.replace_all(s, |caps: &regex::Captures| {
-        let cn_num = &caps[1];
-        let num = cn_to_num(cn_num);
-        format!("第{}章", num)
+    static PATTERNS: std::sync::OnceLock<Vec<Regex>> = std::sync::OnceLock::new();
+    let patterns = PATTERNS.get_or_init(|| {
+        CHAPTER_PATTERNS
+            .iter()
+            .map(|p| Regex::new(p).unwrap())
+            .collect()
+    });
+    replace_chapter_numbers(s, patterns)
+}
+
+/// 使用自定义模式转换章节序号，每个模式的第 1 个捕获组为中文数字
+/// 例如: `to_num_chapter_with("番外三", &["番外(.+)"])` -> `"番外3"`
+pub fn to_num_chapter_with(s: &str, patterns: &[&str]) -> Result<String, String> {
+    let patterns = patterns
+        .iter()
+        .map(|p| Regex::new(p).map_err(|e| e.to_string()))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(replace_chapter_numbers(s, &patterns))
+}
+
+/// 依次应用各模式，将捕获组中的中文数字替换为阿拉伯数字
+fn replace_chapter_numbers(s: &str, patterns: &[Regex]) -> String {
+    patterns.iter().fold(s.to_string(), |text, re| {
+        re.replace_all(&text, |caps: &regex::Captures| {
+            let whole = &caps[0];
+            let Some(target) = caps.get(1) else {
+                return whole.to_string();
+            };
+            let start = caps.get(0).map_or(0, |m| m.start());
+            format!(
+                "{}{}{}",
+                &whole[..target.start() - start],
+                cn_to_num(target.as_str()),
+                &whole[target.end() - start..]
+            )
+        })
+        .into_owned()
     })
-    .to_string()
+}
+
+/// 中文数字字符对应的数值
+fn cn_digit(c: char) -> Option<i64> {
+    let digit = match c {
+        '零' | '〇' => 0,
+        '一' | '壹' => 1,
+        '二' | '贰' | '两' => 2,
+        '三' | '叁' => 3,
+        '四' | '肆' => 4,
+        '五' | '伍' => 5,
+        '六' | '陆' => 6,
+        '七' | '柒' => 7,
+        '八' | '捌' => 8,
+        '九' | '玖' => 9,
+        _ => return None,
+    };
+    Some(digit)
 }
 
 /// 中文数字转阿拉伯数字
+///
+/// 支持"负"前缀，"点"之后的小数部分被舍去；不含数字的文本（如"序章"）返回 0
 pub fn cn_to_num(s: &str) -> i64 {
+    let s = s.trim();
+    if let Some(rest) = s.strip_prefix('负') {
+        return -cn_to_num(rest);
+    }
+    let s = s.split('点').next().unwrap_or_default();
+
     let mut result: i64 = 0;
     let mut temp: i64 = 0;
     let mut section: i64 = 0;
 
     for c in s.chars() {
         match c {
-            '零' => {}
-            '一' | '壹' => temp = 1,
-            '二' | '贰' | '两' => temp = 2,
-            '三' | '叁' => temp = 3,
-            '四' | '肆' => temp = 4,
-            '五' | '伍' => temp = 5,
-            '六' | '陆' => temp = 6,
-            '七' | '柒' => temp = 7,
-            '八' | '捌' => temp = 8,
-            '九' | '玖' => temp = 9,
             '十' | '拾' => {
                 if temp == 0 {
                     temp = 1;
@@ -366,27 +873,106 @@ pub fn cn_to_num(s: &str) -> i64 {
                 section = 0;
                 temp = 0;
             }
-            _ => {}
+            c => {
+                if let Some(digit) = cn_digit(c) {
+                    temp = digit;
+                }
+            }
         }
     }
 
     result + section + temp
 }
 
-/// 阿拉伯数字转中文数字
+/// 中文数字转浮点数，支持"负"前缀与"点"后逐位读的小数部分
+/// 例如: "负三点五" -> -3.5，"零点零五" -> 0.05
+pub fn cn_to_float(s: &str) -> f64 {
+    let s = s.trim();
+    let (negative, s) = match s.strip_prefix('负') {
+        Some(rest) => (true, rest),
+        None => (false, s),
+    };
+    let (int_part, frac_part) = s.split_once('点').unwrap_or((s, ""));
+
+    // 拼成十进制字符串再解析，避免逐位累加的浮点误差
+    let frac: String = frac_part
+        .chars()
+        .filter_map(cn_digit)
+        .map(|d| char::from(b'0' + d as u8))
+        .collect();
+    let value: f64 = format!("{}.{}0", cn_to_num(int_part), frac)
+        .parse()
+        .unwrap_or_default();
+
+    if negative { -value } else { value }
+}
+
+/// 阿拉伯数字转中文数字，负数带"负"前缀
 pub fn num_to_cn(n: i64) -> String {
+    if n < 0 {
+        return format!("负{}", uint_to_cn(n.unsigned_abs()));
+    }
+    uint_to_cn(n as u64)
+}
+
+/// 十进制数字文本转中文数字，支持负号与逐位读的小数部分
+/// 例如: "-3.5" -> "负三点五"，"0.05" -> "零点零五"；无法解析时返回 None
+pub fn decimal_to_cn(s: &str) -> Option<String> {
+    let s = s.trim();
+    let (negative, s) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s),
+    };
+    let (int_part, frac_part) = s.split_once('.').unwrap_or((s, ""));
+    if !int_part.bytes().all(|b| b.is_ascii_digit())
+        || !frac_part.bytes().all(|b| b.is_ascii_digit())
+        || (int_part.is_empty() && frac_part.is_empty())
+    {
+        return None;
+    }
+
+    let int = if int_part.is_empty() {
+        0
+    } else {
+        int_part.parse().ok()?
+    };
+    let mut result = uint_to_cn(int);
+    if !frac_part.is_empty() {
+        result.push('点');
+        for b in frac_part.bytes() {
+            result.push_str(CN_DIGITS[(b - b'0') as usize]);
+        }
+    }
+
+    // "-0" 与 "-0.0" 不带负号
+    if negative && result.chars().any(|c| c != '零' && c != '点') {
+        result.insert(0, '负');
+    }
+    Some(result)
+}
+
+/// 浮点数转中文数字，小数部分逐位读
+/// 例如: -3.5 -> "负三点五"
+pub fn float_to_cn(n: f64) -> String {
+    decimal_to_cn(&n.to_string()).unwrap_or_else(|| n.to_string())
+}
+
+const CN_DIGITS: [&str; 10] = ["零", "一", "二", "三", "四", "五", "六", "七", "八", "九"];
+
+/// 非负整数转中文数字
+fn uint_to_cn(n: u64) -> String {
     if n == 0 {
         return "零".to_string();
     }
 
-    let digits = ["零", "一", "二", "三", "四", "五", "六", "七", "八", "九"];
     let units = ["", "十", "百", "千"];
-    let big_units = ["", "万", "亿"];
+    let big_units = ["", "万", "亿", "万亿", "亿亿"];
 
     let mut result = String::new();
     let mut n = n;
     let mut big_unit_idx = 0;
-    let mut need_zero = false;
+    // 上一个（更低的）四位节的值，不足千位时需要补"零"，如 10005 -> "一万零五"
+    let mut lower_section = 0;
 
     while n > 0 {
         let section = (n % 10000) as usize;
@@ -399,10 +985,10 @@ pub fn num_to_cn(n: i64) -> String {
             while s > 0 {
                 let digit = s % 10;
                 if digit > 0 {
-                    if section_need_zero {
+                    if section_need_zero && !section_str.is_empty() {
                         section_str = format!("零{}", section_str);
                     }
-                    section_str = format!("{}{}{}", digits[digit], units[unit_idx], section_str);
+                    section_str = format!("{}{}{}", CN_DIGITS[digit], units[unit_idx], section_str);
                     section_need_zero = false;
                 } else {
                     section_need_zero = true;
@@ -411,15 +997,13 @@ pub fn num_to_cn(n: i64) -> String {
                 unit_idx += 1;
             }
 
-            if need_zero && !result.is_empty() {
+            if !result.is_empty() && lower_section < 1000 {
                 result = format!("零{}", result);
             }
             result = format!("{}{}{}", section_str, big_units[big_unit_idx], result);
-            need_zero = false;
-        } else {
-            need_zero = true;
         }
 
+        lower_section = section;
         n /= 10000;
         big_unit_idx += 1;
     }
@@ -441,6 +1025,19 @@ pub fn json_parse(s: &str) -> Result<Value, String> {
     serde_json::from_str(s).map_err(|e| e.to_string())
 }
 
+/// 尝试按 JSON 解析字符串
+///
+/// 解析结果为对象或数组时返回该值，否则返回原字符串的 `Value::String`
+pub fn maybe_json(s: &str) -> Value {
+    let trimmed = s.trim();
+    if trimmed.starts_with(['{', '['])
+        && let Ok(value @ (Value::Object(_) | Value::Array(_))) = serde_json::from_str(trimmed)
+    {
+        return value;
+    }
+    Value::String(s.to_string())
+}
+
 /// 将值转换为 JSON 字符串
 pub fn json_stringify(value: &Value) -> String {
     serde_json::to_string(value).unwrap_or_default()
@@ -451,27 +1048,56 @@ pub fn json_stringify_pretty(value: &Value) -> String {
     serde_json::to_string_pretty(value).unwrap_or_default()
 }
 
+/// 去除 JSONP 包裹
+///
+/// 将 `callback({...})` / `cb({...});` 还原为内部 JSON 文本，
+/// 非 JSONP 格式时原样返回
+pub fn strip_jsonp(s: &str) -> String {
+    static JSONP_RE: std::sync::OnceLock<Regex> = std::sync::OnceLock::new();
+    let re = JSONP_RE.get_or_init(|| {
+        Regex::new(r"(?s)^\s*(?:/\*.*?\*/)?\s*[\w$.]+\s*\((.*)\)\s*;?\s*$").unwrap()
+    });
+    re.captures(s)
+        .and_then(|caps| caps.get(1))
+        .map(|m| m.as_str().trim().to_string())
+        .unwrap_or_else(|| s.to_string())
+}
+
 /// 获取 JSON 路径值
+///
+/// 零匹配返回 None，单个匹配返回该值，多个匹配返回数组；
+/// 无法区分"单个匹配的数组"与"多个匹配"，需要稳定结构时使用 [`json_path_all`] 或
+/// [`json_path_first`]
 pub fn json_path(value: &Value, path: &str) -> Option<Value> {
+    let mut results = json_path_all(value, path);
+    match results.len() {
+        0 => None,
+        1 => results.pop(),
+        _ => Some(Value::Array(results)),
+    }
+}
+
+/// 获取 JSON 路径的所有匹配，始终返回数组（零匹配或路径无效时为空数组）
+pub fn json_path_all(value: &Value, path: &str) -> Vec<Value> {
     use jsonpath_rust::JsonPath;
 
-    let path = if path.starts_with("$.") {
+    let path = if path.starts_with('$') {
         path.to_string()
-    } else if path.starts_with('.') {
+    } else if path.starts_with('.') || path.starts_with('[') {
         format!("${}", path)
     } else {
         format!("$.{}", path)
     };
 
-    value.query(&path).ok().and_then(|results| {
-        if results.is_empty() {
-            None
-        } else if results.len() == 1 {
-            Some(results[0].clone())
-        } else {
-            Some(Value::Array(results.into_iter().cloned().collect()))
-        }
-    })
+    value
+        .query(&crate::extractor::selector::json::expand_contains(&path))
+        .map(|results| results.into_iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// 获取 JSON 路径的第一个匹配，零匹配时返回 None
+pub fn json_path_first(value: &Value, path: &str) -> Option<Value> {
+    json_path_all(value, path).into_iter().next()
 }
 
 // ============================================
@@ -538,6 +1164,36 @@ pub fn flatten(arr: &[Value]) -> Vec<Value> {
         .collect()
 }
 
+/// 取出数组中的数字，非数字元素跳过
+fn numbers(arr: &[Value]) -> impl Iterator<Item = f64> + '_ {
+    arr.iter().filter_map(Value::as_f64)
+}
+
+/// 数字求和（非数字跳过，空数组为 0）
+pub fn sum(arr: &[Value]) -> f64 {
+    numbers(arr).fold(0.0, |total, n| total + n)
+}
+
+/// 数字平均值（非数字跳过，没有数字时为 0）
+pub fn avg(arr: &[Value]) -> f64 {
+    let (total, count) = numbers(arr).fold((0.0, 0usize), |(t, c), n| (t + n, c + 1));
+    if count == 0 {
+        0.0
+    } else {
+        total / count as f64
+    }
+}
+
+/// 数字最小值（非数字跳过，没有数字时为 None）
+pub fn min(arr: &[Value]) -> Option<f64> {
+    numbers(arr).reduce(f64::min)
+}
+
+/// 数字最大值（非数字跳过，没有数字时为 None）
+pub fn max(arr: &[Value]) -> Option<f64> {
+    numbers(arr).reduce(f64::max)
+}
+
 // ============================================
 // 类型转换函数
 // ============================================
@@ -588,6 +1244,76 @@ pub fn to_bool(s: &str) -> bool {
     matches!(s.to_lowercase().as_str(), "true" | "1" | "yes" | "on")
 }
 
+/// 是否为 null
+pub fn is_nil(value: &Value) -> bool {
+    value.is_null()
+}
+
+/// 是否为空值：null、空字符串、空数组、空对象
+pub fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(s) => s.is_empty(),
+        Value::Array(arr) => arr.is_empty(),
+        Value::Object(obj) => obj.is_empty(),
+        Value::Bool(_) | Value::Number(_) => false,
+    }
+}
+
+/// 是否为真值
+///
+/// 与 `condition` 步骤的判真规则一致：空值（见 [`is_empty`]）、`false`、`0` 为假，其余为真
+pub fn truthy(value: &Value) -> bool {
+    match value {
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+        other => !is_empty(other),
+    }
+}
+
+/// 将整数转换为指定进制（2-36）的小写字符串，进制无效时返回空字符串
+pub fn to_base(n: i64, base: u32) -> String {
+    if !(2..=36).contains(&base) {
+        return String::new();
+    }
+
+    let mut value = n.unsigned_abs();
+    let mut digits = Vec::new();
+    loop {
+        digits.push(std::char::from_digit((value % base as u64) as u32, base).unwrap_or('0'));
+        value /= base as u64;
+        if value == 0 {
+            break;
+        }
+    }
+    if n < 0 {
+        digits.push('-');
+    }
+    digits.iter().rev().collect()
+}
+
+/// 将指定进制（2-36）的字符串解析为整数，不区分大小写
+pub fn from_base(s: &str, base: u32) -> Option<i64> {
+    if !(2..=36).contains(&base) {
+        return None;
+    }
+    i64::from_str_radix(s.trim(), base).ok()
+}
+
+// ============================================
+// 网络请求函数
+// ============================================
+
+/// 发起 GET 请求，返回响应体（见 [`crate::script::http`]）
+pub fn http_get(url: &str) -> Result<String, String> {
+    crate::script::http::get(url).map_err(|e| e.to_string())
+}
+
+/// 发起 POST 请求，返回响应体（见 [`crate::script::http`]）
+pub fn http_post(url: &str, body: &str) -> Result<String, String> {
+    crate::script::http::post(url, body).map_err(|e| e.to_string())
+}
+
 // ============================================
 // 日期时间函数
 // ============================================
@@ -617,14 +1343,130 @@ pub fn format_timestamp(ts: i64, format: &str) -> String {
         .unwrap_or_default()
 }
 
+/// 按位数识别时间戳单位，归一化为秒
+///
+/// 不超过 10 位视为秒，11~13 位为毫秒，14~16 位为微秒，更长为纳秒
+pub fn normalize_timestamp(ts: i64) -> i64 {
+    let digits = ts.unsigned_abs().checked_ilog10().map_or(1, |d| d + 1);
+    let divisor = match digits {
+        0..=10 => 1,
+        11..=13 => 1_000,
+        14..=16 => 1_000_000,
+        _ => 1_000_000_000,
+    };
+    ts.div_euclid(divisor)
+}
+
+/// 格式化时间戳，自动识别秒/毫秒/微秒单位
+pub fn format_timestamp_auto(ts: i64, format: &str) -> String {
+    format_timestamp(normalize_timestamp(ts), format)
+}
+
+/// 解析相对时间，返回对应的时间戳（秒，相对于当前时间）
+///
+/// 支持的写法：
+/// - 中文：`刚刚`、`刚才`、`今天`、`昨天`、`前天`、`3天前`、`三小时前`、`半小时前`、`2个月前`
+/// - 英文：`just now`、`today`、`yesterday`、`5 minutes ago`、`an hour ago`
+///
+/// 月按 30 天、年按 365 天计算
+pub fn parse_relative_time(s: &str) -> Result<i64, String> {
+    parse_relative_time_from(s, timestamp())
+}
+
+/// 以指定时间戳为基准解析相对时间，见 [`parse_relative_time`]
+pub fn parse_relative_time_from(s: &str, now: i64) -> Result<i64, String> {
+    const MINUTE: i64 = 60;
+    const HOUR: i64 = 60 * MINUTE;
+    const DAY: i64 = 24 * HOUR;
+
+    static CN_RE: std::sync::OnceLock<Regex> = std::sync::OnceLock::new();
+    static EN_RE: std::sync::OnceLock<Regex> = std::sync::OnceLock::new();
+
+    let text = s.trim();
+    let keyword = match text.to_lowercase().as_str() {
+        "刚刚" | "刚才" | "今天" | "just now" | "now" | "today" => Some(0),
+        "昨天" | "yesterday" => Some(DAY),
+        "前天" => Some(2 * DAY),
+        _ => None,
+    };
+    if let Some(offset) = keyword {
+        return Ok(now - offset);
+    }
+
+    let unit_seconds = |unit: &str| match unit {
+        "秒" | "秒钟" | "second" | "sec" => 1,
+        "分" | "分钟" | "minute" | "min" => MINUTE,
+        "小时" | "个小时" | "钟头" | "个钟头" | "hour" | "hr" => HOUR,
+        "天" | "日" | "day" => DAY,
+        "周" | "星期" | "个星期" | "week" => 7 * DAY,
+        "月" | "个月" | "month" => 30 * DAY,
+        "年" | "year" => 365 * DAY,
+        _ => 0,
+    };
+
+    let cn = CN_RE.get_or_init(|| {
+        Regex::new(
+            r"^(\d+|[零一二两三四五六七八九十百]+|半)\s*(秒钟|秒|分钟|分|个小时|小时|个钟头|钟头|天|日|个星期|星期|周|个月|月|年)\s*(?:前|之前|以前)$",
+        )
+        .unwrap()
+    });
+    if let Some(caps) = cn.captures(text) {
+        let unit = unit_seconds(&caps[2]);
+        let offset = match &caps[1] {
+            "半" => unit / 2,
+            n => n.parse().unwrap_or_else(|_| cn_to_num(n)) * unit,
+        };
+        return Ok(now - offset);
+    }
+
+    let en = EN_RE.get_or_init(|| {
+        Regex::new(
+            r"(?i)^(\d+|an?|one)\s*(second|sec|minute|min|hour|hr|day|week|month|year)s?\s+ago$",
+        )
+        .unwrap()
+    });
+    if let Some(caps) = en.captures(text) {
+        let count = caps[1].parse().unwrap_or(1);
+        return Ok(now - count * unit_seconds(&caps[2].to_lowercase()));
+    }
+
+    Err(format!("无法识别的相对时间: {}", s))
+}
+
+/// 按指定时区格式化时间戳
+///
+/// `tz` 为 IANA 时区名（如 `Asia/Shanghai`、`UTC`），无法识别时返回 None
+pub fn format_timestamp_tz(ts: i64, format: &str, tz: &str) -> Option<String> {
+    use chrono::TimeZone;
+    let tz: chrono_tz::Tz = tz.trim().parse().ok()?;
+    tz.timestamp_opt(ts, 0)
+        .single()
+        .map(|dt| dt.format(format).to_string())
+}
+
 /// 解析日期字符串为时间戳
+///
+/// 格式只含日期（如 `%Y-%m-%d`）时按当天 00:00:00 计算
 pub fn parse_date(s: &str, format: &str) -> Option<i64> {
-    use chrono::NaiveDateTime;
+    use chrono::{NaiveDate, NaiveDateTime};
     NaiveDateTime::parse_from_str(s, format)
         .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(s, format)
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })
         .map(|dt| dt.and_utc().timestamp())
 }
 
+/// 依次尝试多种格式解析日期字符串，返回第一个成功的时间戳
+pub fn parse_date_any<S: AsRef<str>>(s: &str, formats: &[S]) -> Option<i64> {
+    let s = s.trim();
+    formats
+        .iter()
+        .find_map(|format| parse_date(s, format.as_ref()))
+}
+
 // ============================================
 // URL 处理函数
 // ============================================
@@ -675,6 +1517,37 @@ pub fn join_url(base: &str, path: &str) -> String {
         })
 }
 
+/// 规范化 URL，用于去重与缓存键
+///
+/// scheme 与 host 转为小写、去除默认端口、合并路径中的连续斜杠、
+/// 按参数名排序查询参数（同名参数保持原顺序）、去除 fragment；
+/// 无法解析时原样返回
+pub fn normalize_url(url_str: &str) -> String {
+    let Ok(mut url) = url::Url::parse(url_str.trim()) else {
+        return url_str.to_string();
+    };
+    url.set_fragment(None);
+
+    if url.path().contains("//") {
+        let mut path = String::with_capacity(url.path().len());
+        for c in url.path().chars() {
+            if !(c == '/' && path.ends_with('/')) {
+                path.push(c);
+            }
+        }
+        url.set_path(&path);
+    }
+
+    let mut pairs: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+    if pairs.is_empty() {
+        url.set_query(None);
+    } else {
+        pairs.sort_by(|a, b| a.0.cmp(&b.0));
+        url.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+    url.to_string()
+}
+
 /// 获取 URL 查询参数
 pub fn get_query_param(url_str: &str, key: &str) -> Option<String> {
     url::Url::parse(url_str).ok().and_then(|url| {
@@ -694,6 +1567,70 @@ pub fn set_query_param(url_str: &str, key: &str, value: &str) -> String {
     }
 }
 
+// ============================================
+// 播放线路函数
+// ============================================
+
+/// 清晰度关键字与对应分值（按从高到低匹配，先命中者生效）
+const QUALITY_RANKS: &[(&[&str], i32)] = &[
+    (&["8k", "4320"], 100),
+    (&["4k", "2160", "uhd"], 90),
+    (&["蓝光", "bluray", "blu-ray", "原画", "2k", "1440"], 80),
+    (&["1080", "fhd", "全高清"], 70),
+    (&["超清"], 65),
+    (&["720", "hd", "高清"], 60),
+    (&["540", "480", "标清", "sd"], 40),
+    (&["360", "流畅"], 20),
+    (&["240", "极速"], 10),
+];
+
+/// 计算清晰度标签的排序分值
+///
+/// 分值越高清晰度越高，无法识别时返回 0
+pub fn rank_quality(label: &str) -> i32 {
+    let label = label.to_lowercase();
+    QUALITY_RANKS
+        .iter()
+        .find(|(keywords, _)| keywords.iter().any(|k| label.contains(k)))
+        .map(|(_, rank)| *rank)
+        .unwrap_or(0)
+}
+
+/// 获取播放线路的标签
+///
+/// 线路可以是字符串，或包含 `name` / `label` / `title` / `quality` 字段的对象
+fn play_line_label(line: &Value) -> String {
+    match line {
+        Value::String(s) => s.clone(),
+        Value::Object(obj) => ["name", "label", "title", "quality"]
+            .iter()
+            .find_map(|key| obj.get(*key).and_then(Value::as_str))
+            .unwrap_or_default()
+            .to_string(),
+        other => to_string(other),
+    }
+}
+
+/// 按清晰度偏好排序播放线路
+///
+/// 标签包含 `prefer` 的线路优先，其余按清晰度降序；分值相同时保持原顺序
+pub fn sort_play_lines(lines: &[Value], prefer: &str) -> Vec<Value> {
+    let prefer = prefer.trim().to_lowercase();
+    let mut ranked: Vec<(bool, i32, &Value)> = lines
+        .iter()
+        .map(|line| {
+            let label = play_line_label(line);
+            let preferred = !prefer.is_empty() && label.to_lowercase().contains(&prefer);
+            (preferred, rank_quality(&label), line)
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.0.cmp(&a.0).then(b.1.cmp(&a.1)));
+    ranked
+        .into_iter()
+        .map(|(_, _, line)| line.clone())
+        .collect()
+}
+
 // ============================================
 // 工具函数
 // ============================================
@@ -24,6 +24,8 @@ pub fn register_builtin_functions(context: &mut Context) -> JsResult<()> {
     register_fn(context, "replace", 3, replace)?;
     register_fn(context, "split", 2, split)?;
     register_fn(context, "substring", 3, substring)?;
+    register_fn(context, "extract_between", 3, extract_between)?;
+    register_fn(context, "extract_all_between", 3, extract_all_between)?;
     register_fn(context, "contains", 2, contains)?;
     register_fn(context, "starts_with", 2, starts_with)?;
     register_fn(context, "ends_with", 2, ends_with)?;
@@ -31,10 +33,17 @@ pub fn register_builtin_functions(context: &mut Context) -> JsResult<()> {
     register_fn(context, "index_of", 2, index_of)?;
     register_fn(context, "repeat_str", 2, repeat_str)?;
     register_fn(context, "reverse", 1, reverse_str)?;
+    register_fn(context, "truncate", 3, truncate)?;
+    register_fn(context, "levenshtein", 2, levenshtein)?;
+    register_fn(context, "similarity", 2, similarity)?;
+    register_fn(context, "dedup_lines", 1, dedup_lines)?;
+    register_fn(context, "format_str", 2, format_str)?;
 
     // 正则表达式函数
     register_fn(context, "regex_match", 2, regex_match)?;
     register_fn(context, "regex_replace", 3, regex_replace)?;
+    register_fn(context, "regex_replace_map", 3, regex_replace_map)?;
+    register_fn(context, "remove_lines_matching", 2, remove_lines_matching)?;
     register_fn(context, "regex_find", 2, regex_find)?;
     register_fn(context, "regex_find_all", 2, regex_find_all)?;
 
@@ -45,6 +54,7 @@ pub fn register_builtin_functions(context: &mut Context) -> JsResult<()> {
     register_fn(context, "url_decode", 1, url_decode)?;
     register_fn(context, "html_encode", 1, html_encode)?;
     register_fn(context, "html_decode", 1, html_decode)?;
+    register_fn(context, "decode_all", 1, decode_all)?;
     register_fn(context, "hex_encode", 1, hex_encode)?;
     register_fn(context, "hex_decode", 1, hex_decode)?;
 
@@ -52,6 +62,9 @@ pub fn register_builtin_functions(context: &mut Context) -> JsResult<()> {
     register_fn(context, "md5", 1, md5)?;
     register_fn(context, "sha1", 1, sha1)?;
     register_fn(context, "sha256", 1, sha256)?;
+    register_fn(context, "rsa_encrypt", 2, rsa_encrypt)?;
+    register_fn(context, "rsa_decrypt", 2, rsa_decrypt)?;
+    register_fn(context, "rsa_sign", 3, rsa_sign)?;
 
     // 中文处理函数
     register_fn(context, "t2s", 1, t2s)?;
@@ -64,19 +77,54 @@ pub fn register_builtin_functions(context: &mut Context) -> JsResult<()> {
     register_fn(context, "is_hans", 1, is_hans)?;
     register_fn(context, "to_num_chapter", 1, to_num_chapter)?;
     register_fn(context, "cn_to_num", 1, cn_to_num)?;
+    register_fn(context, "cn_to_float", 1, cn_to_float)?;
+    register_fn(context, "num_to_cn", 1, num_to_cn)?;
+
+    // 数组处理函数
+    register_fn(context, "sum", 1, sum)?;
+    register_fn(context, "avg", 1, avg)?;
+    register_fn(context, "min", 1, min)?;
+    register_fn(context, "max", 1, max)?;
+
+    // 类型转换函数
+    register_fn(context, "to_base", 2, to_base)?;
+    register_fn(context, "from_base", 2, from_base)?;
+    register_fn(context, "is_nil", 1, is_nil)?;
+    register_fn(context, "is_empty", 1, is_empty)?;
+    register_fn(context, "truthy", 1, truthy)?;
 
     // JSON 处理函数
     register_fn(context, "json_parse", 1, json_parse)?;
     register_fn(context, "json_stringify", 1, json_stringify)?;
+    register_fn(context, "maybe_json", 1, maybe_json)?;
+    register_fn(context, "strip_jsonp", 1, strip_jsonp)?;
+    register_fn(context, "json_path_all", 2, json_path_all)?;
+    register_fn(context, "json_path_first", 2, json_path_first)?;
 
     // URL 处理函数
     register_fn(context, "join_url", 2, join_url)?;
+    register_fn(context, "normalize_url", 1, normalize_url)?;
     register_fn(context, "get_query_param", 2, get_query_param)?;
 
+    // 网络请求函数
+    register_fn(context, "http_get", 1, http_get)?;
+    register_fn(context, "http_post", 2, http_post)?;
+
+    // 播放线路函数
+    register_fn(context, "rank_quality", 1, rank_quality)?;
+    register_fn(context, "sort_play_lines", 2, sort_play_lines)?;
+
     // 工具函数
     register_fn(context, "uuid", 0, uuid)?;
     register_fn(context, "timestamp", 0, timestamp)?;
     register_fn(context, "timestamp_millis", 0, timestamp_millis)?;
+    register_fn(context, "format_timestamp", 2, format_timestamp)?;
+    register_fn(context, "normalize_timestamp", 1, normalize_timestamp)?;
+    register_fn(context, "format_timestamp_auto", 2, format_timestamp_auto)?;
+    register_fn(context, "format_timestamp_tz", 3, format_timestamp_tz)?;
+    register_fn(context, "parse_date", 2, parse_date)?;
+    register_fn(context, "parse_date_any", 2, parse_date_any)?;
+    register_fn(context, "parse_relative_time", 1, parse_relative_time)?;
     register_fn(context, "log", 1, log)?;
 
     Ok(())
@@ -128,6 +176,18 @@ fn get_int_arg(args: &[JsValue], index: usize, context: &mut Context) -> JsResul
         .map(|n| n as i64)
 }
 
+/// 辅助函数: 从参数获取 JSON 数组，非数组视为空数组
+fn get_array_arg(
+    args: &[JsValue],
+    index: usize,
+    context: &mut Context,
+) -> JsResult<Vec<serde_json::Value>> {
+    match js_to_json(args.get(index).unwrap_or(&JsValue::undefined()), context)? {
+        serde_json::Value::Array(arr) => Ok(arr),
+        _ => Ok(Vec::new()),
+    }
+}
+
 // ============================================
 // 字符串处理函数实现
 // ============================================
@@ -182,6 +242,26 @@ fn substring(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsVal
     Ok(JsValue::from(js_string!(core::substring(&s, start, end))))
 }
 
+fn extract_between(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let s = get_string_arg(args, 0, ctx)?;
+    let start = get_string_arg(args, 1, ctx)?;
+    let end = get_string_arg(args, 2, ctx)?;
+    Ok(JsValue::from(js_string!(core::extract_between(
+        &s, &start, &end
+    ))))
+}
+
+fn extract_all_between(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let s = get_string_arg(args, 0, ctx)?;
+    let start = get_string_arg(args, 1, ctx)?;
+    let end = get_string_arg(args, 2, ctx)?;
+    let arr = JsArray::new(ctx);
+    for part in core::extract_all_between(&s, &start, &end) {
+        arr.push(JsValue::from(js_string!(part)), ctx)?;
+    }
+    Ok(arr.into())
+}
+
 fn contains(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
     let s = get_string_arg(args, 0, ctx)?;
     let pattern = get_string_arg(args, 1, ctx)?;
@@ -222,6 +302,40 @@ fn reverse_str(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsV
     Ok(JsValue::from(js_string!(core::reverse(&s))))
 }
 
+fn truncate(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let s = get_string_arg(args, 0, ctx)?;
+    let width = get_int_arg(args, 1, ctx)?.max(0) as usize;
+    let ellipsis = get_optional_string_arg(args, 2, ctx)?.unwrap_or_else(|| "…".to_string());
+    Ok(JsValue::from(js_string!(core::truncate(
+        &s, width, &ellipsis
+    ))))
+}
+
+fn levenshtein(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let a = get_string_arg(args, 0, ctx)?;
+    let b = get_string_arg(args, 1, ctx)?;
+    Ok(JsValue::from(core::levenshtein(&a, &b) as i32))
+}
+
+fn similarity(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let a = get_string_arg(args, 0, ctx)?;
+    let b = get_string_arg(args, 1, ctx)?;
+    Ok(JsValue::from(core::similarity(&a, &b)))
+}
+
+fn dedup_lines(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let s = get_string_arg(args, 0, ctx)?;
+    Ok(JsValue::from(js_string!(core::dedup_lines(&s))))
+}
+
+fn format_str(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let template = get_string_arg(args, 0, ctx)?;
+    let values = js_to_json(args.get(1).unwrap_or(&JsValue::undefined()), ctx)?;
+    Ok(JsValue::from(js_string!(core::format_str(
+        &template, &values
+    ))))
+}
+
 // ============================================
 // 正则表达式函数实现
 // ============================================
@@ -243,6 +357,24 @@ fn regex_replace(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<J
     ))))
 }
 
+fn regex_replace_map(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let text = get_string_arg(args, 0, ctx)?;
+    let pattern = get_string_arg(args, 1, ctx)?;
+    let transform = get_string_arg(args, 2, ctx)?;
+    match core::regex_replace_map(&text, &pattern, &transform) {
+        Ok(replaced) => Ok(JsValue::from(js_string!(replaced))),
+        Err(e) => Err(JsNativeError::error().with_message(e).into()),
+    }
+}
+
+fn remove_lines_matching(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let s = get_string_arg(args, 0, ctx)?;
+    let pattern = get_string_arg(args, 1, ctx)?;
+    Ok(JsValue::from(js_string!(core::remove_lines_matching(
+        &s, &pattern
+    ))))
+}
+
 fn regex_find(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
     let text = get_string_arg(args, 0, ctx)?;
     let pattern = get_string_arg(args, 1, ctx)?;
@@ -303,6 +435,11 @@ fn html_decode(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsV
     Ok(JsValue::from(js_string!(core::html_decode(&s))))
 }
 
+fn decode_all(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let s = get_string_arg(args, 0, ctx)?;
+    Ok(JsValue::from(js_string!(core::decode_all(&s))))
+}
+
 fn hex_encode(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
     let s = get_string_arg(args, 0, ctx)?;
     Ok(JsValue::from(js_string!(core::hex_encode(&s))))
@@ -335,6 +472,34 @@ fn sha256(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue>
     Ok(JsValue::from(js_string!(core::sha256(&s))))
 }
 
+fn rsa_encrypt(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let data = get_string_arg(args, 0, ctx)?;
+    let public_key = get_string_arg(args, 1, ctx)?;
+    match core::rsa_encrypt(&data, &public_key) {
+        Ok(encrypted) => Ok(JsValue::from(js_string!(encrypted))),
+        Err(e) => Err(JsNativeError::error().with_message(e).into()),
+    }
+}
+
+fn rsa_decrypt(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let data = get_string_arg(args, 0, ctx)?;
+    let private_key = get_string_arg(args, 1, ctx)?;
+    match core::rsa_decrypt(&data, &private_key) {
+        Ok(decrypted) => Ok(JsValue::from(js_string!(decrypted))),
+        Err(e) => Err(JsNativeError::error().with_message(e).into()),
+    }
+}
+
+fn rsa_sign(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let data = get_string_arg(args, 0, ctx)?;
+    let private_key = get_string_arg(args, 1, ctx)?;
+    let hash = get_optional_string_arg(args, 2, ctx)?.unwrap_or_else(|| "sha256".to_string());
+    match core::rsa_sign(&data, &private_key, &hash) {
+        Ok(signature) => Ok(JsValue::from(js_string!(signature))),
+        Err(e) => Err(JsNativeError::error().with_message(e).into()),
+    }
+}
+
 // ============================================
 // 中文处理函数实现
 // ============================================
@@ -381,7 +546,19 @@ fn is_hans(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue
 
 fn to_num_chapter(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
     let s = get_string_arg(args, 0, ctx)?;
-    Ok(JsValue::from(js_string!(core::to_num_chapter(&s))))
+    // 第二个参数为自定义模式数组，省略时使用默认模式
+    if args.get(1).is_none_or(|v| v.is_null_or_undefined()) {
+        return Ok(JsValue::from(js_string!(core::to_num_chapter(&s))));
+    }
+    let patterns: Vec<String> = get_array_arg(args, 1, ctx)?
+        .iter()
+        .map(core::to_string)
+        .collect();
+    let patterns: Vec<&str> = patterns.iter().map(String::as_str).collect();
+    match core::to_num_chapter_with(&s, &patterns) {
+        Ok(converted) => Ok(JsValue::from(js_string!(converted))),
+        Err(e) => Err(JsNativeError::error().with_message(e).into()),
+    }
 }
 
 fn cn_to_num(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
@@ -389,6 +566,89 @@ fn cn_to_num(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsVal
     Ok(JsValue::from(core::cn_to_num(&s) as i32))
 }
 
+fn cn_to_float(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let s = get_string_arg(args, 0, ctx)?;
+    Ok(JsValue::from(core::cn_to_float(&s)))
+}
+
+fn num_to_cn(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    // 数字按 JS 的字符串形式转换，字符串参数可保留小数末尾的零
+    let s = get_string_arg(args, 0, ctx)?;
+    Ok(JsValue::from(js_string!(
+        core::decimal_to_cn(&s).unwrap_or(s)
+    )))
+}
+
+// ============================================
+// 数组处理函数实现
+// ============================================
+
+fn sum(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    Ok(JsValue::from(core::sum(&get_array_arg(args, 0, ctx)?)))
+}
+
+fn avg(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    Ok(JsValue::from(core::avg(&get_array_arg(args, 0, ctx)?)))
+}
+
+fn min(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    Ok(core::min(&get_array_arg(args, 0, ctx)?)
+        .map(JsValue::from)
+        .unwrap_or(JsValue::null()))
+}
+
+fn max(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    Ok(core::max(&get_array_arg(args, 0, ctx)?)
+        .map(JsValue::from)
+        .unwrap_or(JsValue::null()))
+}
+
+// ============================================
+// 类型转换函数实现
+// ============================================
+
+fn to_base(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let n = args
+        .first()
+        .ok_or_else(|| JsNativeError::typ().with_message("Missing argument"))?
+        .to_number(ctx)?;
+    let base = get_int_arg(args, 1, ctx)?;
+    Ok(JsValue::from(js_string!(core::to_base(
+        n as i64,
+        base.clamp(0, u32::MAX as i64) as u32
+    ))))
+}
+
+fn from_base(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let s = get_string_arg(args, 0, ctx)?;
+    let base = get_int_arg(args, 1, ctx)?;
+    match core::from_base(&s, base.clamp(0, u32::MAX as i64) as u32) {
+        Some(n) => Ok(JsValue::from(n as f64)),
+        None => Ok(JsValue::null()),
+    }
+}
+
+/// 辅助函数: 获取任意类型参数，缺失或 undefined 视为 null
+fn get_json_arg(args: &[JsValue], index: usize, ctx: &mut Context) -> JsResult<serde_json::Value> {
+    js_to_json(args.get(index).unwrap_or(&JsValue::undefined()), ctx)
+}
+
+fn is_nil(_: &JsValue, args: &[JsValue], _ctx: &mut Context) -> JsResult<JsValue> {
+    Ok(JsValue::from(
+        args.first().is_none_or(|v| v.is_null_or_undefined()),
+    ))
+}
+
+fn is_empty(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let value = get_json_arg(args, 0, ctx)?;
+    Ok(JsValue::from(core::is_empty(&value)))
+}
+
+fn truthy(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let value = get_json_arg(args, 0, ctx)?;
+    Ok(JsValue::from(core::truthy(&value)))
+}
+
 // ============================================
 // JSON 处理函数实现
 // ============================================
@@ -403,6 +663,11 @@ fn json_parse(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsVa
     }
 }
 
+fn maybe_json(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let s = get_string_arg(args, 0, ctx)?;
+    json_to_js(ctx, &core::maybe_json(&s))
+}
+
 fn json_stringify(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
     let value = args
         .first()
@@ -411,6 +676,29 @@ fn json_stringify(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<
     Ok(JsValue::from(js_string!(core::json_stringify(&json_value))))
 }
 
+fn strip_jsonp(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let s = get_string_arg(args, 0, ctx)?;
+    Ok(JsValue::from(js_string!(core::strip_jsonp(&s))))
+}
+
+fn json_path_all(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let value = get_json_arg(args, 0, ctx)?;
+    let path = get_string_arg(args, 1, ctx)?;
+    json_to_js(
+        ctx,
+        &serde_json::Value::Array(core::json_path_all(&value, &path)),
+    )
+}
+
+fn json_path_first(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let value = get_json_arg(args, 0, ctx)?;
+    let path = get_string_arg(args, 1, ctx)?;
+    match core::json_path_first(&value, &path) {
+        Some(value) => json_to_js(ctx, &value),
+        None => Ok(JsValue::null()),
+    }
+}
+
 // ============================================
 // URL 处理函数实现
 // ============================================
@@ -421,6 +709,11 @@ fn join_url(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValu
     Ok(JsValue::from(js_string!(core::join_url(&base, &path))))
 }
 
+fn normalize_url(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let url = get_string_arg(args, 0, ctx)?;
+    Ok(JsValue::from(js_string!(core::normalize_url(&url))))
+}
+
 fn get_query_param(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
     let url = get_string_arg(args, 0, ctx)?;
     let key = get_string_arg(args, 1, ctx)?;
@@ -430,6 +723,34 @@ fn get_query_param(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult
     }
 }
 
+// ============================================
+// 播放线路函数实现
+// ============================================
+
+fn rank_quality(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let label = get_string_arg(args, 0, ctx)?;
+    Ok(JsValue::from(core::rank_quality(&label)))
+}
+
+fn sort_play_lines(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let lines = match js_to_json(args.first().unwrap_or(&JsValue::undefined()), ctx)? {
+        serde_json::Value::Array(lines) => lines,
+        _ => {
+            return Err(JsNativeError::typ()
+                .with_message("sort_play_lines expects an array")
+                .into());
+        }
+    };
+    let prefer = match args.get(1) {
+        Some(value) if !value.is_null_or_undefined() => get_string_arg(args, 1, ctx)?,
+        _ => String::new(),
+    };
+    json_to_js(
+        ctx,
+        &serde_json::Value::Array(core::sort_play_lines(&lines, &prefer)),
+    )
+}
+
 // ============================================
 // 工具函数实现
 // ============================================
@@ -448,6 +769,90 @@ fn timestamp_millis(_: &JsValue, _args: &[JsValue], _ctx: &mut Context) -> JsRes
     Ok(JsValue::from(core::timestamp_millis() as f64))
 }
 
+/// 辅助函数: 获取时间戳参数
+///
+/// 毫秒及以上精度的时间戳超出 i32 范围，按 number 读取
+fn get_timestamp_arg(args: &[JsValue], index: usize, context: &mut Context) -> JsResult<i64> {
+    args.get(index)
+        .ok_or_else(|| JsNativeError::typ().with_message("Missing argument").into())
+        .and_then(|v| v.to_number(context))
+        .map(|n| n as i64)
+}
+
+fn format_timestamp(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let ts = get_timestamp_arg(args, 0, ctx)?;
+    let format = get_string_arg(args, 1, ctx)?;
+    Ok(JsValue::from(js_string!(core::format_timestamp(
+        ts, &format
+    ))))
+}
+
+fn normalize_timestamp(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let ts = get_timestamp_arg(args, 0, ctx)?;
+    Ok(JsValue::from(core::normalize_timestamp(ts) as f64))
+}
+
+fn format_timestamp_auto(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let ts = get_timestamp_arg(args, 0, ctx)?;
+    let format = get_string_arg(args, 1, ctx)?;
+    Ok(JsValue::from(js_string!(core::format_timestamp_auto(
+        ts, &format
+    ))))
+}
+
+fn http_get(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let url = get_string_arg(args, 0, ctx)?;
+    match core::http_get(&url) {
+        Ok(body) => Ok(JsValue::from(js_string!(body))),
+        Err(e) => Err(JsNativeError::error().with_message(e).into()),
+    }
+}
+
+fn http_post(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let url = get_string_arg(args, 0, ctx)?;
+    let body = get_string_arg(args, 1, ctx)?;
+    match core::http_post(&url, &body) {
+        Ok(body) => Ok(JsValue::from(js_string!(body))),
+        Err(e) => Err(JsNativeError::error().with_message(e).into()),
+    }
+}
+
+fn format_timestamp_tz(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let ts = get_timestamp_arg(args, 0, ctx)?;
+    let format = get_string_arg(args, 1, ctx)?;
+    let tz = get_string_arg(args, 2, ctx)?;
+    Ok(core::format_timestamp_tz(ts, &format, &tz)
+        .map(|s| JsValue::from(js_string!(s)))
+        .unwrap_or_else(JsValue::null))
+}
+
+fn parse_date(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let s = get_string_arg(args, 0, ctx)?;
+    let format = get_string_arg(args, 1, ctx)?;
+    Ok(core::parse_date(&s, &format)
+        .map(|ts| JsValue::from(ts as f64))
+        .unwrap_or_else(JsValue::null))
+}
+
+fn parse_date_any(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let s = get_string_arg(args, 0, ctx)?;
+    let formats: Vec<String> = get_array_arg(args, 1, ctx)?
+        .iter()
+        .filter_map(|f| f.as_str().map(str::to_string))
+        .collect();
+    Ok(core::parse_date_any(&s, &formats)
+        .map(|ts| JsValue::from(ts as f64))
+        .unwrap_or_else(JsValue::null))
+}
+
+fn parse_relative_time(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let s = get_string_arg(args, 0, ctx)?;
+    match core::parse_relative_time(&s) {
+        Ok(ts) => Ok(JsValue::from(ts as f64)),
+        Err(e) => Err(JsNativeError::error().with_message(e).into()),
+    }
+}
+
 fn log(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
     let msg = get_string_arg(args, 0, ctx)?;
     core::log(&msg);
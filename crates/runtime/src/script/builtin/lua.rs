@@ -27,6 +27,66 @@ pub fn register_builtin_functions(lua: &Lua) -> LuaResult<()> {
     })?;
     globals.set("split", split_fn)?;
 
+    let extract_between_fn =
+        lua.create_function(|_, (s, start, end): (String, String, String)| {
+            Ok(super::core::extract_between(&s, &start, &end))
+        })?;
+    globals.set("extract_between", extract_between_fn)?;
+
+    let extract_all_between_fn =
+        lua.create_function(|_, (s, start, end): (String, String, String)| {
+            Ok(super::core::extract_all_between(&s, &start, &end))
+        })?;
+    globals.set("extract_all_between", extract_all_between_fn)?;
+
+    // 相似度函数
+    let levenshtein_fn =
+        lua.create_function(|_, (a, b): (String, String)| Ok(super::core::levenshtein(&a, &b)))?;
+    globals.set("levenshtein", levenshtein_fn)?;
+
+    let similarity_fn =
+        lua.create_function(|_, (a, b): (String, String)| Ok(super::core::similarity(&a, &b)))?;
+    globals.set("similarity", similarity_fn)?;
+
+    // 日期时间函数
+    let format_timestamp_tz_fn =
+        lua.create_function(|_, (ts, format, tz): (i64, String, String)| {
+            Ok(super::core::format_timestamp_tz(ts, &format, &tz))
+        })?;
+    globals.set("format_timestamp_tz", format_timestamp_tz_fn)?;
+
+    let parse_date_any_fn = lua.create_function(|_, (s, formats): (String, Vec<String>)| {
+        Ok(super::core::parse_date_any(&s, &formats))
+    })?;
+    globals.set("parse_date_any", parse_date_any_fn)?;
+
+    let parse_relative_time_fn = lua.create_function(|_, s: String| {
+        super::core::parse_relative_time(&s).map_err(mlua::Error::RuntimeError)
+    })?;
+    globals.set("parse_relative_time", parse_relative_time_fn)?;
+
+    // 网络请求函数
+    let http_get_fn = lua.create_function(|_, url: String| {
+        super::core::http_get(&url).map_err(mlua::Error::RuntimeError)
+    })?;
+    globals.set("http_get", http_get_fn)?;
+
+    let http_post_fn = lua.create_function(|_, (url, body): (String, String)| {
+        super::core::http_post(&url, &body).map_err(mlua::Error::RuntimeError)
+    })?;
+    globals.set("http_post", http_post_fn)?;
+
+    // 判空/判真函数
+    let is_nil_fn = lua.create_function(|_, v: Value| Ok(v.is_nil()))?;
+    globals.set("is_nil", is_nil_fn)?;
+
+    let is_empty_fn =
+        lua.create_function(|_, v: Value| Ok(super::core::is_empty(&lua_to_json(&v))))?;
+    globals.set("is_empty", is_empty_fn)?;
+
+    let truthy_fn = lua.create_function(|_, v: Value| Ok(super::core::truthy(&lua_to_json(&v))))?;
+    globals.set("truthy", truthy_fn)?;
+
     // JSON 解析
     let json_parse_fn = lua.create_function(|lua, s: String| {
         let value: serde_json::Value = serde_json::from_str(&s)
@@ -35,6 +95,31 @@ pub fn register_builtin_functions(lua: &Lua) -> LuaResult<()> {
     })?;
     globals.set("json_parse", json_parse_fn)?;
 
+    let maybe_json_fn =
+        lua.create_function(|lua, s: String| json_to_lua(lua, &super::core::maybe_json(&s)))?;
+    globals.set("maybe_json", maybe_json_fn)?;
+
+    let strip_jsonp_fn = lua.create_function(|_, s: String| Ok(super::core::strip_jsonp(&s)))?;
+    globals.set("strip_jsonp", strip_jsonp_fn)?;
+
+    let json_path_all_fn = lua.create_function(|lua, (v, path): (Value, String)| {
+        let results = super::core::json_path_all(&lua_to_json(&v), &path);
+        json_to_lua(lua, &serde_json::Value::Array(results))
+    })?;
+    globals.set("json_path_all", json_path_all_fn)?;
+
+    let json_path_first_fn =
+        lua.create_function(
+            |lua, (v, path): (Value, String)| match super::core::json_path_first(
+                &lua_to_json(&v),
+                &path,
+            ) {
+                Some(value) => json_to_lua(lua, &value),
+                None => Ok(Value::Nil),
+            },
+        )?;
+    globals.set("json_path_first", json_path_first_fn)?;
+
     // 编码函数
     let base64_encode_fn = lua.create_function(|_, s: String| {
         use base64::Engine;
@@ -46,12 +131,33 @@ pub fn register_builtin_functions(lua: &Lua) -> LuaResult<()> {
         lua.create_function(|_, s: String| Ok(urlencoding::encode(&s).to_string()))?;
     globals.set("url_encode", url_encode_fn)?;
 
+    let decode_all_fn = lua.create_function(|_, s: String| Ok(super::core::decode_all(&s)))?;
+    globals.set("decode_all", decode_all_fn)?;
+
     let md5_fn = lua.create_function(|_, s: String| {
         let digest = md5::compute(s.as_bytes());
         Ok(format!("{:x}", digest))
     })?;
     globals.set("md5", md5_fn)?;
 
+    let rsa_encrypt_fn = lua.create_function(|_, (data, public_key): (String, String)| {
+        super::core::rsa_encrypt(&data, &public_key).map_err(mlua::Error::RuntimeError)
+    })?;
+    globals.set("rsa_encrypt", rsa_encrypt_fn)?;
+
+    let rsa_decrypt_fn = lua.create_function(|_, (data, private_key): (String, String)| {
+        super::core::rsa_decrypt(&data, &private_key).map_err(mlua::Error::RuntimeError)
+    })?;
+    globals.set("rsa_decrypt", rsa_decrypt_fn)?;
+
+    let rsa_sign_fn = lua.create_function(
+        |_, (data, private_key, hash): (String, String, Option<String>)| {
+            let hash = hash.as_deref().unwrap_or("sha256");
+            super::core::rsa_sign(&data, &private_key, hash).map_err(mlua::Error::RuntimeError)
+        },
+    )?;
+    globals.set("rsa_sign", rsa_sign_fn)?;
+
     // 正则匹配
     let regex_match_fn = lua.create_function(|lua, (text, pattern): (String, String)| {
         let re = regex::Regex::new(&pattern)
@@ -71,6 +177,17 @@ pub fn register_builtin_functions(lua: &Lua) -> LuaResult<()> {
     })?;
     globals.set("regex_match", regex_match_fn)?;
 
+    let regex_replace_map_fn =
+        lua.create_function(|_, (text, pattern, transform): (String, String, String)| {
+            super::core::regex_replace_map(&text, &pattern, &transform)
+                .map_err(mlua::Error::RuntimeError)
+        })?;
+    globals.set("regex_replace_map", regex_replace_map_fn)?;
+
+    let normalize_url_fn =
+        lua.create_function(|_, url: String| Ok(super::core::normalize_url(&url)))?;
+    globals.set("normalize_url", normalize_url_fn)?;
+
     Ok(())
 }
 
@@ -105,3 +222,33 @@ fn json_to_lua(lua: &Lua, value: &serde_json::Value) -> LuaResult<Value> {
         }
     }
 }
+
+/// 将 Lua Value 转换为 serde_json::Value
+///
+/// 序列（`#t > 0`）转为数组，其余表转为对象，函数等无法表示的值转为 null
+fn lua_to_json(value: &Value) -> serde_json::Value {
+    match value {
+        Value::Nil => serde_json::Value::Null,
+        Value::Boolean(b) => serde_json::Value::Bool(*b),
+        Value::Integer(i) => serde_json::Value::from(*i),
+        Value::Number(n) => serde_json::json!(n),
+        Value::String(s) => serde_json::Value::String(s.to_string_lossy()),
+        Value::Table(table) if table.raw_len() > 0 => serde_json::Value::Array(
+            table
+                .clone()
+                .sequence_values::<Value>()
+                .filter_map(|v| v.ok())
+                .map(|v| lua_to_json(&v))
+                .collect(),
+        ),
+        Value::Table(table) => serde_json::Value::Object(
+            table
+                .clone()
+                .pairs::<String, Value>()
+                .filter_map(|pair| pair.ok())
+                .map(|(k, v)| (k, lua_to_json(&v)))
+                .collect(),
+        ),
+        _ => serde_json::Value::Null,
+    }
+}
@@ -11,6 +11,27 @@
 // 6. url_decode(text: str) -> str
 // 7. md5(text: str) -> str
 // 8. regex_match(pattern: str, text: str) -> List[str]
+// 9. levenshtein(a: str, b: str) -> int
+// 10. similarity(a: str, b: str) -> float
+// 11. maybe_json(text: str) -> Any
+// 12. format_timestamp_tz(ts: int, format: str, tz: str) -> Optional[str]
+// 13. parse_date_any(text: str, formats: List[str]) -> Optional[int]
+// 14. parse_relative_time(text: str) -> int
+// 15. is_nil(value: Any) -> bool
+// 16. is_empty(value: Any) -> bool
+// 17. truthy(value: Any) -> bool
+// 18. http_get(url: str) -> str
+// 19. http_post(url: str, body: str) -> str
+// 20. decode_all(text: str) -> str
+// 21. regex_replace_map(text: str, pattern: str, transform: str) -> str
+// 22. normalize_url(url: str) -> str
+// 23. extract_between(text: str, start: str, end: str) -> str
+// 24. extract_all_between(text: str, start: str, end: str) -> List[str]
+// 25. json_path_all(value: Any, path: str) -> List[Any]
+// 26. json_path_first(value: Any, path: str) -> Optional[Any]
+// 27. rsa_encrypt(data: str, public_key_pem: str) -> str
+// 28. rsa_decrypt(data: str, private_key_pem: str) -> str
+// 29. rsa_sign(data: str, private_key_pem: str, hash: str = "sha256") -> str
 //
 // 示例代码:
 // ```python
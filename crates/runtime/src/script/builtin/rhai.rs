@@ -3,7 +3,7 @@
 //! 将核心层的内置函数绑定到 Rhai 引擎
 
 use super::core;
-use rhai::{Dynamic, Engine, EvalAltResult, Map};
+use rhai::{Dynamic, Engine, EvalAltResult, FnPtr, Map, NativeCallContext};
 
 /// 注册所有内置函数到 Rhai 引擎
 pub fn register_all(engine: &mut Engine) {
@@ -16,7 +16,9 @@ pub fn register_all(engine: &mut Engine) {
     register_array_functions(engine);
     register_type_functions(engine);
     register_datetime_functions(engine);
+    register_http_functions(engine);
     register_url_functions(engine);
+    register_play_line_functions(engine);
     register_util_functions(engine);
 }
 
@@ -42,6 +44,18 @@ fn register_string_functions(engine: &mut Engine) {
     engine.register_fn("substring", |s: &str, start: i64, end: i64| {
         core::substring(s, start as usize, Some(end as usize))
     });
+    engine.register_fn("extract_between", |s: &str, start: &str, end: &str| {
+        core::extract_between(s, start, end)
+    });
+    engine.register_fn(
+        "extract_all_between",
+        |s: &str, start: &str, end: &str| -> rhai::Array {
+            core::extract_all_between(s, start, end)
+                .into_iter()
+                .map(Dynamic::from)
+                .collect()
+        },
+    );
     engine.register_fn("contains", |s: &str, pattern: &str| {
         core::contains(s, pattern)
     });
@@ -65,6 +79,20 @@ fn register_string_functions(engine: &mut Engine) {
     engine.register_fn("pad_end", |s: &str, len: i64, pad: &str| {
         core::pad_end(s, len as usize, pad)
     });
+    engine.register_fn("truncate", |s: &str, width: i64, ellipsis: &str| {
+        core::truncate(s, width.max(0) as usize, ellipsis)
+    });
+    engine.register_fn("truncate", |s: &str, width: i64| {
+        core::truncate(s, width.max(0) as usize, "…")
+    });
+    engine.register_fn("levenshtein", |a: &str, b: &str| {
+        core::levenshtein(a, b) as i64
+    });
+    engine.register_fn("similarity", |a: &str, b: &str| core::similarity(a, b));
+    engine.register_fn("dedup_lines", |s: &str| core::dedup_lines(s));
+    engine.register_fn("format_str", |template: &str, args: Dynamic| {
+        core::format_str(template, &json_from_dynamic(args))
+    });
 }
 
 /// 注册正则表达式函数
@@ -72,12 +100,21 @@ fn register_regex_functions(engine: &mut Engine) {
     engine.register_fn("regex_match", |pattern: &str, text: &str| {
         core::regex_match(pattern, text)
     });
+    engine.register_fn("remove_lines_matching", |s: &str, pattern: &str| {
+        core::remove_lines_matching(s, pattern)
+    });
     engine.register_fn(
         "regex_replace",
         |text: &str, pattern: &str, replacement: &str| {
             core::regex_replace(text, pattern, replacement)
         },
     );
+    engine.register_fn(
+        "regex_replace_map",
+        |text: &str, pattern: &str, transform: &str| -> Result<String, Box<EvalAltResult>> {
+            core::regex_replace_map(text, pattern, transform).map_err(|e| e.into())
+        },
+    );
     engine.register_fn("regex_find", |text: &str, pattern: &str| -> Dynamic {
         core::regex_find(text, pattern)
             .map(Dynamic::from)
@@ -109,6 +146,7 @@ fn register_encoding_functions(engine: &mut Engine) {
     );
     engine.register_fn("html_encode", |s: &str| core::html_encode(s));
     engine.register_fn("html_decode", |s: &str| core::html_decode(s));
+    engine.register_fn("decode_all", |s: &str| core::decode_all(s));
     engine.register_fn("hex_encode", |s: &str| core::hex_encode(s));
     engine.register_fn(
         "hex_decode",
@@ -123,6 +161,30 @@ fn register_hash_functions(engine: &mut Engine) {
     engine.register_fn("md5", |s: &str| core::md5(s));
     engine.register_fn("sha256", |s: &str| core::sha256(s));
     engine.register_fn("sha1", |s: &str| core::sha1(s));
+    engine.register_fn(
+        "rsa_encrypt",
+        |data: &str, public_key: &str| -> Result<String, Box<EvalAltResult>> {
+            core::rsa_encrypt(data, public_key).map_err(|e| e.into())
+        },
+    );
+    engine.register_fn(
+        "rsa_decrypt",
+        |data: &str, private_key: &str| -> Result<String, Box<EvalAltResult>> {
+            core::rsa_decrypt(data, private_key).map_err(|e| e.into())
+        },
+    );
+    engine.register_fn(
+        "rsa_sign",
+        |data: &str, private_key: &str| -> Result<String, Box<EvalAltResult>> {
+            core::rsa_sign(data, private_key, "sha256").map_err(|e| e.into())
+        },
+    );
+    engine.register_fn(
+        "rsa_sign",
+        |data: &str, private_key: &str, hash: &str| -> Result<String, Box<EvalAltResult>> {
+            core::rsa_sign(data, private_key, hash).map_err(|e| e.into())
+        },
+    );
 }
 
 /// 注册中文处理函数
@@ -136,8 +198,21 @@ fn register_chinese_functions(engine: &mut Engine) {
     engine.register_fn("to_zh_hans", |s: &str| core::to_zh_hans(s));
     engine.register_fn("is_hans", |s: &str| core::is_hans(s));
     engine.register_fn("to_num_chapter", |s: &str| core::to_num_chapter(s));
+    engine.register_fn(
+        "to_num_chapter",
+        |s: &str, patterns: rhai::Array| -> Result<String, Box<EvalAltResult>> {
+            let patterns: Vec<String> = patterns.into_iter().map(|p| p.to_string()).collect();
+            let patterns: Vec<&str> = patterns.iter().map(String::as_str).collect();
+            core::to_num_chapter_with(s, &patterns).map_err(|e| e.into())
+        },
+    );
     engine.register_fn("cn_to_num", |s: &str| core::cn_to_num(s));
+    engine.register_fn("cn_to_float", |s: &str| core::cn_to_float(s));
     engine.register_fn("num_to_cn", |n: i64| core::num_to_cn(n));
+    engine.register_fn("num_to_cn", |n: f64| core::float_to_cn(n));
+    engine.register_fn("num_to_cn", |s: &str| {
+        core::decimal_to_cn(s).unwrap_or_else(|| s.to_string())
+    });
 }
 
 /// 注册 JSON 处理函数
@@ -150,6 +225,10 @@ fn register_json_functions(engine: &mut Engine) {
                 .map_err(|e| e.into())
         },
     );
+    engine.register_fn("maybe_json", |s: &str| {
+        dynamic_from_json(core::maybe_json(s))
+    });
+    engine.register_fn("strip_jsonp", |s: &str| core::strip_jsonp(s));
     engine.register_fn("json_stringify", |d: Dynamic| {
         let value = json_from_dynamic(d);
         core::json_stringify(&value)
@@ -158,6 +237,17 @@ fn register_json_functions(engine: &mut Engine) {
         let value = json_from_dynamic(d);
         core::json_stringify_pretty(&value)
     });
+    engine.register_fn("json_path_all", |d: Dynamic, path: &str| -> rhai::Array {
+        core::json_path_all(&json_from_dynamic(d), path)
+            .into_iter()
+            .map(dynamic_from_json)
+            .collect()
+    });
+    engine.register_fn("json_path_first", |d: Dynamic, path: &str| -> Dynamic {
+        core::json_path_first(&json_from_dynamic(d), path)
+            .map(dynamic_from_json)
+            .unwrap_or(Dynamic::UNIT)
+    });
 }
 
 /// 注册数组处理函数
@@ -204,6 +294,80 @@ fn register_array_functions(engine: &mut Engine) {
             .filter(|v| seen.insert(v.to_string()))
             .collect()
     });
+
+    // 高阶函数：闭包在调用方的上下文中执行，可使用脚本函数与其他内置函数，
+    // 如 `arr.array_map(|x| trim(x)).array_filter(|x| x != "")`
+    engine.register_fn(
+        "array_map",
+        |ctx: NativeCallContext,
+         arr: rhai::Array,
+         f: FnPtr|
+         -> Result<rhai::Array, Box<EvalAltResult>> {
+            arr.into_iter()
+                .map(|item| f.call_within_context(&ctx, (item,)))
+                .collect()
+        },
+    );
+    engine.register_fn(
+        "array_filter",
+        |ctx: NativeCallContext,
+         arr: rhai::Array,
+         f: FnPtr|
+         -> Result<rhai::Array, Box<EvalAltResult>> {
+            let mut result = rhai::Array::new();
+            for item in arr {
+                let keep: Dynamic = f.call_within_context(&ctx, (item.clone(),))?;
+                if core::truthy(&json_from_dynamic(keep)) {
+                    result.push(item);
+                }
+            }
+            Ok(result)
+        },
+    );
+    engine.register_fn(
+        "array_find",
+        |ctx: NativeCallContext,
+         arr: rhai::Array,
+         f: FnPtr|
+         -> Result<Dynamic, Box<EvalAltResult>> {
+            for item in arr {
+                let found: Dynamic = f.call_within_context(&ctx, (item.clone(),))?;
+                if core::truthy(&json_from_dynamic(found)) {
+                    return Ok(item);
+                }
+            }
+            Ok(Dynamic::UNIT)
+        },
+    );
+    engine.register_fn(
+        "array_reduce",
+        |ctx: NativeCallContext,
+         arr: rhai::Array,
+         f: FnPtr,
+         initial: Dynamic|
+         -> Result<Dynamic, Box<EvalAltResult>> {
+            arr.into_iter().try_fold(initial, |acc, item| {
+                f.call_within_context(&ctx, (acc, item))
+            })
+        },
+    );
+
+    // 数字统计
+    let numbers = |arr: rhai::Array| -> Vec<serde_json::Value> {
+        arr.into_iter().map(json_from_dynamic).collect()
+    };
+    engine.register_fn("sum", move |arr: rhai::Array| core::sum(&numbers(arr)));
+    engine.register_fn("avg", move |arr: rhai::Array| core::avg(&numbers(arr)));
+    engine.register_fn("min", move |arr: rhai::Array| -> Dynamic {
+        core::min(&numbers(arr))
+            .map(Dynamic::from)
+            .unwrap_or(Dynamic::UNIT)
+    });
+    engine.register_fn("max", move |arr: rhai::Array| -> Dynamic {
+        core::max(&numbers(arr))
+            .map(Dynamic::from)
+            .unwrap_or(Dynamic::UNIT)
+    });
 }
 
 /// 注册类型转换函数
@@ -218,6 +382,35 @@ fn register_type_functions(engine: &mut Engine) {
     });
     engine.register_fn("to_string", |d: Dynamic| d.to_string());
     engine.register_fn("to_bool", |s: &str| core::to_bool(s));
+    engine.register_fn("is_nil", |d: Dynamic| d.is_unit());
+    engine.register_fn("is_empty", |d: Dynamic| {
+        core::is_empty(&json_from_dynamic(d))
+    });
+    engine.register_fn("truthy", |d: Dynamic| core::truthy(&json_from_dynamic(d)));
+    engine.register_fn("to_base", |n: i64, base: i64| {
+        core::to_base(n, base.clamp(0, u32::MAX as i64) as u32)
+    });
+    engine.register_fn("from_base", |s: &str, base: i64| -> Dynamic {
+        core::from_base(s, base.clamp(0, u32::MAX as i64) as u32)
+            .map(Dynamic::from)
+            .unwrap_or(Dynamic::UNIT)
+    });
+}
+
+/// 注册网络请求函数
+fn register_http_functions(engine: &mut Engine) {
+    engine.register_fn(
+        "http_get",
+        |url: &str| -> Result<String, Box<EvalAltResult>> {
+            core::http_get(url).map_err(|e| e.into())
+        },
+    );
+    engine.register_fn(
+        "http_post",
+        |url: &str, body: &str| -> Result<String, Box<EvalAltResult>> {
+            core::http_post(url, body).map_err(|e| e.into())
+        },
+    );
 }
 
 /// 注册日期时间函数
@@ -227,11 +420,38 @@ fn register_datetime_functions(engine: &mut Engine) {
     engine.register_fn("format_timestamp", |ts: i64, format: &str| {
         core::format_timestamp(ts, format)
     });
+    engine.register_fn("normalize_timestamp", core::normalize_timestamp);
+    engine.register_fn("format_timestamp_auto", |ts: i64, format: &str| {
+        core::format_timestamp_auto(ts, format)
+    });
+    engine.register_fn(
+        "format_timestamp_tz",
+        |ts: i64, format: &str, tz: &str| -> Dynamic {
+            core::format_timestamp_tz(ts, format, tz)
+                .map(Dynamic::from)
+                .unwrap_or(Dynamic::UNIT)
+        },
+    );
     engine.register_fn("parse_date", |s: &str, format: &str| -> Dynamic {
         core::parse_date(s, format)
             .map(Dynamic::from)
             .unwrap_or(Dynamic::UNIT)
     });
+    engine.register_fn(
+        "parse_relative_time",
+        |s: &str| -> Result<i64, Box<EvalAltResult>> {
+            core::parse_relative_time(s).map_err(|e| e.into())
+        },
+    );
+    engine.register_fn(
+        "parse_date_any",
+        |s: &str, formats: rhai::Array| -> Dynamic {
+            let formats: Vec<String> = formats.iter().map(|f| f.to_string()).collect();
+            core::parse_date_any(s, &formats)
+                .map(Dynamic::from)
+                .unwrap_or(Dynamic::UNIT)
+        },
+    );
 }
 
 /// 注册 URL 处理函数
@@ -246,6 +466,7 @@ fn register_url_functions(engine: &mut Engine) {
     engine.register_fn("join_url", |base: &str, path: &str| {
         core::join_url(base, path)
     });
+    engine.register_fn("normalize_url", |url: &str| core::normalize_url(url));
     engine.register_fn("get_query_param", |url: &str, key: &str| -> Dynamic {
         core::get_query_param(url, key)
             .map(Dynamic::from)
@@ -256,6 +477,23 @@ fn register_url_functions(engine: &mut Engine) {
     });
 }
 
+/// 注册播放线路函数
+fn register_play_line_functions(engine: &mut Engine) {
+    engine.register_fn("rank_quality", |label: &str| {
+        core::rank_quality(label) as i64
+    });
+    engine.register_fn(
+        "sort_play_lines",
+        |lines: rhai::Array, prefer: &str| -> rhai::Array {
+            let lines: Vec<serde_json::Value> = lines.into_iter().map(json_from_dynamic).collect();
+            core::sort_play_lines(&lines, prefer)
+                .into_iter()
+                .map(dynamic_from_json)
+                .collect()
+        },
+    );
+}
+
 /// 注册工具函数
 fn register_util_functions(engine: &mut Engine) {
     engine.register_fn("uuid", core::uuid);
@@ -1,11 +1,26 @@
 //! 脚本执行上下文
 
-use serde_json::Value;
-use std::collections::HashMap;
+use crate::{
+    context::RequestCounter,
+    http::{HttpClient, parse_set_cookie},
+};
+use serde_json::{Map, Value};
+use std::{collections::HashMap, sync::Arc};
+
+/// 当前响应的最终 URL（无响应时为当前页面 URL）
+pub const URL_VAR: &str = "__url";
+/// 当前响应的状态码
+pub const STATUS_VAR: &str = "__status";
+/// 当前响应的响应头（规范大小写名称 → 值）
+pub const HEADERS_VAR: &str = "__headers";
+/// 当前响应 `Set-Cookie` 设置的 Cookie（名称 → 值）
+pub const COOKIES_VAR: &str = "__cookies";
 
 /// 脚本执行上下文
 ///
-/// 包含脚本执行时可访问的所有数据和服务
+/// 包含脚本执行时可访问的所有数据和服务。
+/// 流程已发出请求时，当前请求/响应的元信息以只读变量注入：
+/// `__url`、`__status`、`__headers`、`__cookies`，脚本中修改它们不会影响流程
 #[derive(Debug, Clone, Default)]
 pub struct ScriptContext {
     /// 当前输入值（提取流程的中间结果）
@@ -13,8 +28,15 @@ pub struct ScriptContext {
 
     /// 上下文变量（模板变量、提取的字段等）
     pub variables: HashMap<String, Value>,
+
+    /// 内置函数 `http_get` / `http_post` 使用的 HTTP 客户端
+    ///
+    /// 仅在脚本安全配置允许网络访问时提供，None 时调用报错
+    pub http_client: Option<Arc<HttpClient>>,
+
+    /// 脚本内 HTTP 请求计入的请求计数器（用于 `limits.max_requests`）
+    pub request_counter: Option<RequestCounter>,
     // TODO: 添加更多服务
-    // pub http_client: Arc<HttpClient>,
     // pub cookie_jar: Arc<CookieJar>,
     // pub cache: Arc<RwLock<HashMap<String, Value>>>,
 }
@@ -22,7 +44,12 @@ pub struct ScriptContext {
 impl ScriptContext {
     /// 创建新的脚本上下文
     pub fn new(input: String, variables: HashMap<String, Value>) -> Self {
-        Self { input, variables }
+        Self {
+            input,
+            variables,
+            http_client: None,
+            request_counter: None,
+        }
     }
 
     /// 设置输入值
@@ -31,6 +58,56 @@ impl ScriptContext {
         self
     }
 
+    /// 设置脚本内 HTTP 请求使用的客户端
+    pub fn with_http_client(mut self, client: Arc<HttpClient>) -> Self {
+        self.http_client = Some(client);
+        self
+    }
+
+    /// 设置脚本内 HTTP 请求计入的请求计数器
+    pub fn with_request_counter(mut self, counter: RequestCounter) -> Self {
+        self.request_counter = Some(counter);
+        self
+    }
+
+    /// 注入当前请求/响应的元信息
+    ///
+    /// `response` 为流程变量中的响应对象（见 [`crate::http::RESPONSE_VAR`]），
+    /// 其中没有 URL 时使用 `page_url`
+    pub fn with_response(mut self, response: Option<&Value>, page_url: Option<&str>) -> Self {
+        let url = response
+            .and_then(|r| r.get("url"))
+            .and_then(Value::as_str)
+            .or(page_url);
+        if let Some(url) = url {
+            self.variables.insert(URL_VAR.to_string(), Value::from(url));
+        }
+
+        let Some(response) = response else {
+            return self;
+        };
+        if let Some(status) = response.get("status") {
+            self.variables
+                .insert(STATUS_VAR.to_string(), status.clone());
+        }
+        let headers = response
+            .get("headers")
+            .cloned()
+            .unwrap_or_else(|| Value::Object(Map::new()));
+        let cookies: Map<String, Value> = headers
+            .get("Set-Cookie")
+            .and_then(Value::as_str)
+            .map(parse_set_cookie)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(name, value)| (name, Value::String(value)))
+            .collect();
+        self.variables.insert(HEADERS_VAR.to_string(), headers);
+        self.variables
+            .insert(COOKIES_VAR.to_string(), Value::Object(cookies));
+        self
+    }
+
     /// 添加变量
     pub fn with_variable(mut self, key: String, value: Value) -> Self {
         self.variables.insert(key, value);
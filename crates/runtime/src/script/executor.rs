@@ -7,9 +7,10 @@ use crate::{
     context::{FlowContext, RuntimeContext},
     error::RuntimeError,
     extractor::{SharedValue, value::ExtractValueData},
-    script::{ScriptContext, ScriptEngine, ScriptEngineFactory, ScriptLanguage},
+    http::RESPONSE_VAR,
+    script::{ScriptContext, ScriptLanguage},
 };
-use crawler_schema::script::{Script, ScriptEngine as SchemaScriptEngine, ScriptSource};
+use crawler_schema::script::{Script, ScriptSource};
 use std::{collections::HashMap, sync::Arc};
 
 /// 脚本执行器
@@ -20,17 +21,45 @@ impl ScriptExecutor {
     pub fn execute(
         script: &Script,
         input: &ExtractValueData,
-        _runtime_context: &RuntimeContext,
+        runtime_context: &RuntimeContext,
         flow_context: &FlowContext,
     ) -> Result<SharedValue> {
-        // 1. 加载脚本代码
-        let code = Self::load_script_code(script)?;
+        // 1. 加载脚本代码，确定脚本引擎，并链接脚本中以 `模块名.函数名(...)` 调用的其他模块；
+        //    模块首次使用时才加载，链接结果与模块入口代码均被缓存
+        let fetch_url = |url: &str| runtime_context.remote_script(url);
+        let modules = runtime_context.script_modules();
+        let (code, language) = match script.source() {
+            ScriptSource::Module(name) => {
+                let module = modules.get(name, fetch_url)?;
+                let language = Self::language(module.language, script, runtime_context);
+                let call = script
+                    .function()
+                    .map(|function| Self::call_expression(language, function));
+                (modules.entry(name, language, call, fetch_url)?, language)
+            }
+            ScriptSource::Code(code) => {
+                let language = Self::language(None, script, runtime_context);
+                (modules.link(code, None, language, fetch_url)?, language)
+            }
+            ScriptSource::Url(url) => {
+                let language = Self::language(None, script, runtime_context);
+                let code = runtime_context.remote_script(url)?;
+                (modules.link(&code, None, language, fetch_url)?, language)
+            }
+        };
 
         // 2. 获取脚本引擎
-        let engine = Self::get_engine(script);
+        let engine = runtime_context.script_engine(language);
 
-        // 3. 转换输入
+        // 3. 转换输入，检查大小上限
+        let security = script
+            .security()
+            .cloned()
+            .unwrap_or_default()
+            .merge_with(runtime_context.rule().script_security.as_ref());
+        let max_data = security.max_data_bytes();
         let input_str = Self::value_to_input(input);
+        Self::check_size("输入", &input_str, max_data)?;
 
         // 4. 构建变量上下文
         let mut variables: HashMap<String, serde_json::Value> = HashMap::new();
@@ -47,39 +76,53 @@ impl ScriptExecutor {
             variables.insert(key.clone(), value.clone());
         }
 
-        // 5. 创建脚本上下文
-        let script_context = ScriptContext::new(input_str, variables);
+        // 5. 创建脚本上下文，允许网络访问时才提供 HTTP 客户端
+        let mut script_context = ScriptContext::new(input_str, variables).with_response(
+            flow_context.data().get(RESPONSE_VAR),
+            flow_context.page_url(),
+        );
+        if security.allow_network {
+            script_context = script_context
+                .with_http_client(runtime_context.http_client().clone())
+                .with_request_counter(flow_context.request_counter());
+        }
 
         // 6. 执行脚本
         let result = engine.execute(&code, &script_context)?;
+        Self::check_size("返回值", &result, max_data)?;
 
         // 7. 解析输出
         Ok(Self::parse_output(result, input))
     }
 
-    /// 获取脚本使用的引擎
-    fn get_engine(script: &Script) -> Arc<dyn ScriptEngine> {
-        match script.engine() {
-            SchemaScriptEngine::Rhai => ScriptEngineFactory::create(ScriptLanguage::Rhai),
-            SchemaScriptEngine::JavaScript => {
-                ScriptEngineFactory::create(ScriptLanguage::JavaScript)
-            }
-            SchemaScriptEngine::Lua => ScriptEngineFactory::create(ScriptLanguage::Lua),
-            SchemaScriptEngine::Python => ScriptEngineFactory::create(ScriptLanguage::Python),
+    /// 确定脚本引擎：模块指定的引擎优先，其次是脚本步骤指定的引擎，最后使用运行时默认引擎
+    fn language(
+        module_language: Option<ScriptLanguage>,
+        script: &Script,
+        runtime_context: &RuntimeContext,
+    ) -> ScriptLanguage {
+        module_language
+            .or(script.engine.map(ScriptLanguage::from))
+            .unwrap_or_else(|| runtime_context.default_script_language())
+    }
+
+    /// 检查脚本数据大小
+    fn check_size(target: &str, data: &str, limit: Option<usize>) -> Result<()> {
+        match limit {
+            Some(limit) if data.len() > limit => Err(RuntimeError::ScriptDataTooLarge {
+                target: target.to_string(),
+                size: data.len(),
+                limit,
+            }),
+            _ => Ok(()),
         }
     }
 
-    /// 加载脚本代码
-    fn load_script_code(script: &Script) -> Result<String> {
-        match script.source() {
-            ScriptSource::Code(code) => Ok(code.to_string()),
-            ScriptSource::Url(url) => {
-                // URL 加载需要异步，暂时不支持
-                Err(RuntimeError::ScriptRuntime(format!(
-                    "从 URL 加载脚本暂未实现: {}",
-                    url
-                )))
-            }
+    /// 生成以 `input` 调用模块函数的语句
+    fn call_expression(language: ScriptLanguage, function: &str) -> String {
+        match language {
+            ScriptLanguage::Lua => format!("return {}(input)", function),
+            _ => format!("{}(input)", function),
         }
     }
 
@@ -94,7 +137,7 @@ impl ScriptExecutor {
                     v.to_string()
                 }
             }
-            ExtractValueData::Html(h) => h.to_string(),
+            ExtractValueData::Html(h) | ExtractValueData::Xml(h) => h.to_string(),
             ExtractValueData::Array(arr) => {
                 let json_arr: Vec<serde_json::Value> =
                     arr.iter().map(|v| v.to_owned_json()).collect();
@@ -105,31 +148,33 @@ impl ScriptExecutor {
     }
 
     /// 解析脚本输出为 ExtractValueData
+    ///
+    /// 合法 JSON 保留其类型（数字、布尔等标量不会变成字符串），
+    /// 非 JSON 输出按输入类型作为字符串/HTML/XML 返回
     fn parse_output(output: String, input: &ExtractValueData) -> SharedValue {
-        // 尝试解析为 JSON
-        if let Ok(json) = serde_json::from_str::<serde_json::Value>(&output) {
-            match json {
-                serde_json::Value::String(s) => {
-                    Arc::new(ExtractValueData::String(Arc::from(s.into_boxed_str())))
-                }
-                serde_json::Value::Array(arr) => {
-                    let items: Vec<SharedValue> = arr
-                        .iter()
-                        .map(|v| Arc::new(ExtractValueData::from_json(v)))
-                        .collect();
-                    Arc::new(ExtractValueData::Array(Arc::new(items)))
-                }
-                serde_json::Value::Null => Arc::new(ExtractValueData::Null),
-                other => Arc::new(ExtractValueData::Json(Arc::new(other))),
+        match serde_json::from_str::<serde_json::Value>(&output) {
+            Ok(serde_json::Value::String(s)) => {
+                Arc::new(ExtractValueData::String(Arc::from(s.into_boxed_str())))
+            }
+            Ok(serde_json::Value::Array(arr)) => {
+                let items: Vec<SharedValue> = arr
+                    .iter()
+                    .map(|v| Arc::new(ExtractValueData::from_json(v)))
+                    .collect();
+                Arc::new(ExtractValueData::Array(Arc::new(items)))
             }
-        } else {
-            // 如果不是 JSON，根据输入类型决定输出类型
-            match input {
+            Ok(serde_json::Value::Null) => Arc::new(ExtractValueData::Null),
+            Ok(other) => Arc::new(ExtractValueData::Json(Arc::new(other))),
+            // 不是 JSON，根据输入类型决定输出类型
+            Err(_) => match input {
                 ExtractValueData::Html(_) => {
                     Arc::new(ExtractValueData::Html(Arc::from(output.into_boxed_str())))
                 }
+                ExtractValueData::Xml(_) => {
+                    Arc::new(ExtractValueData::Xml(Arc::from(output.into_boxed_str())))
+                }
                 _ => Arc::new(ExtractValueData::String(Arc::from(output.into_boxed_str()))),
-            }
+            },
         }
     }
 }
@@ -1,6 +1,7 @@
 //! 脚本引擎工厂
 
 use crate::script::*;
+use crawler_schema::script::ScriptEngine as SchemaScriptEngine;
 use std::{str::FromStr, sync::Arc};
 
 /// 脚本语言类型
@@ -28,6 +29,17 @@ impl ScriptLanguage {
     }
 }
 
+impl From<SchemaScriptEngine> for ScriptLanguage {
+    fn from(engine: SchemaScriptEngine) -> Self {
+        match engine {
+            SchemaScriptEngine::Rhai => Self::Rhai,
+            SchemaScriptEngine::JavaScript => Self::JavaScript,
+            SchemaScriptEngine::Lua => Self::Lua,
+            SchemaScriptEngine::Python => Self::Python,
+        }
+    }
+}
+
 impl FromStr for ScriptLanguage {
     type Err = ();
 
@@ -0,0 +1,155 @@
+//! # 脚本 HTTP 桥接
+//!
+//! 脚本引擎是同步的，内置函数 `http_get` / `http_post` 通过本模块
+//! 将请求桥接到运行时的 [`HttpClient`]，返回响应体字符串。
+//!
+//! 请求在专用线程上执行，调用线程只阻塞等待结果：
+//! - 在多线程运行时中调用时，专用线程通过 `Handle::block_on` 交由运行时驱动 IO
+//! - 在 `current_thread` 运行时的线程中调用会导致死锁，直接报错
+//! - 不在运行时中调用时，专用线程临时创建运行时
+//!
+//! 引擎执行脚本时通过 [`enter`] 登记当前上下文的客户端与请求计数器。
+//! 脚本安全配置 `allow_network = false`（默认）时不提供客户端，调用 HTTP 函数会报错。
+//!
+//! 每次请求计入流程的 `limits.max_requests`。内置函数的错误会被引擎转为脚本异常，
+//! 超出限制、请求超时等中断类错误由 [`ScriptHttpGuard::finish`] 还原后返回
+
+use crate::{
+    Result,
+    context::RequestCounter,
+    error::RuntimeError,
+    http::HttpClient,
+    script::ScriptContext,
+};
+use std::{cell::RefCell, sync::Arc};
+use tokio::runtime::{Handle, RuntimeFlavor};
+
+/// 脚本执行期间登记的 HTTP 状态
+#[derive(Default)]
+struct Registration {
+    client: Option<Arc<HttpClient>>,
+    counter: Option<RequestCounter>,
+    /// 请求遇到的首个中断类错误
+    interruption: Option<RuntimeError>,
+}
+
+thread_local! {
+    /// 当前线程正在执行的脚本的 HTTP 登记
+    static CURRENT: RefCell<Registration> = RefCell::default();
+}
+
+/// 登记脚本 HTTP 客户端的守卫，离开作用域时恢复之前的登记
+pub struct ScriptHttpGuard {
+    previous: Option<Registration>,
+}
+
+impl ScriptHttpGuard {
+    /// 返回脚本执行结果，脚本内请求遇到中断类错误时以该错误替代
+    ///
+    /// 脚本捕获了请求异常时同样返回中断错误，保证限制与超时不被脚本吞掉
+    pub fn finish<T>(&self, result: Result<T>) -> Result<T> {
+        match CURRENT.with(|current| current.borrow_mut().interruption.take()) {
+            Some(e) => Err(e),
+            None => result,
+        }
+    }
+}
+
+impl Drop for ScriptHttpGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take().unwrap_or_default();
+        CURRENT.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+/// 为当前线程登记脚本上下文中的 HTTP 客户端与请求计数器，直到守卫被丢弃
+pub fn enter(context: &ScriptContext) -> ScriptHttpGuard {
+    let registration = Registration {
+        client: context.http_client.clone(),
+        counter: context.request_counter.clone(),
+        interruption: None,
+    };
+    let previous = CURRENT.with(|current| current.replace(registration));
+    ScriptHttpGuard {
+        previous: Some(previous),
+    }
+}
+
+/// 发起 GET 请求，返回响应体
+pub fn get(url: &str) -> Result<String> {
+    request(url, None)
+}
+
+/// 发起 POST 请求，返回响应体
+pub fn post(url: &str, body: &str) -> Result<String> {
+    request(url, Some(body))
+}
+
+fn request(url: &str, body: Option<&str>) -> Result<String> {
+    let (client, counter) = CURRENT.with(|current| {
+        let current = current.borrow();
+        (current.client.clone(), current.counter.clone())
+    });
+    let client = client.ok_or_else(|| {
+        RuntimeError::ScriptRuntime(
+            "脚本网络访问已禁用，需在 script_security 中设置 allow_network = true".into(),
+        )
+    })?;
+
+    let result = counter
+        .as_ref()
+        .map_or(Ok(()), RequestCounter::record)
+        .and_then(|()| {
+            block_on(async {
+                let response = match body {
+                    Some(body) => client.post(url, body.to_string()).await?,
+                    None => client.get(url).await?,
+                };
+                Ok(client.read_response(response).await?.body)
+            })
+        });
+    if let Err(e) = &result
+        && e.is_interruption()
+    {
+        CURRENT.with(|current| {
+            current
+                .borrow_mut()
+                .interruption
+                .get_or_insert_with(|| e.clone());
+        });
+    }
+    result
+}
+
+/// 在同步代码中等待异步请求完成
+///
+/// 请求在专用线程上执行，规则见模块文档
+pub(crate) fn block_on<T: Send>(fetch: impl Future<Output = Result<T>> + Send) -> Result<T> {
+    let handle = Handle::try_current().ok();
+    if handle
+        .as_ref()
+        .is_some_and(|h| h.runtime_flavor() == RuntimeFlavor::CurrentThread)
+    {
+        return Err(RuntimeError::ScriptRuntime(
+            "current_thread 运行时中无法在脚本内发起 HTTP 请求，请使用多线程运行时".into(),
+        ));
+    }
+
+    std::thread::scope(|scope| {
+        scope
+            .spawn(|| match handle {
+                Some(handle) => handle.block_on(fetch),
+                None => tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .map_err(|e| RuntimeError::ScriptRuntime(format!("创建运行时失败: {}", e)))?
+                    .block_on(fetch),
+            })
+            .join()
+            .unwrap_or_else(|_| {
+                Err(RuntimeError::ScriptRuntime(
+                    "脚本 HTTP 请求线程异常退出".into(),
+                ))
+            })
+    })
+}
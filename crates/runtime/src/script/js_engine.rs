@@ -73,11 +73,13 @@ impl ScriptEngine for JsScriptEngine {
     fn execute(&self, script: &str, context: &ScriptContext) -> Result<String> {
         let mut ctx = self.create_context()?;
         self.inject_context(&mut ctx, context)?;
+        let http = super::http::enter(context);
 
         let source = Source::from_bytes(script);
-        let result = ctx
-            .eval(source)
-            .map_err(|e| RuntimeError::ScriptRuntime(format!("[JS] {}", e)))?;
+        let result = http.finish(
+            ctx.eval(source)
+                .map_err(|e| RuntimeError::ScriptRuntime(format!("[JS] {}", e))),
+        )?;
 
         // 将结果转换为字符串
         let result_str = result
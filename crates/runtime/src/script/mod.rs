@@ -10,6 +10,9 @@ pub mod context;
 pub mod engine;
 pub mod executor;
 pub mod factory;
+pub mod http;
+pub mod module;
+pub mod remote;
 
 // 各引擎实现
 pub mod js_engine;
@@ -26,5 +29,7 @@ pub use executor::ScriptExecutor;
 pub use factory::{ScriptEngineFactory, ScriptLanguage};
 pub use js_engine::JsScriptEngine;
 pub use lua_engine::LuaScriptEngine;
+pub use module::{LoadedModule, ScriptModules};
 pub use python_engine::PythonScriptEngine;
+pub use remote::{RemoteScript, RemoteScripts};
 pub use rhai_engine::RhaiScriptEngine;
@@ -0,0 +1,648 @@
+//! 脚本模块
+//!
+//! 管理 `scripting.modules` 中定义的模块。模块在首次被调用时才加载，
+//! 未用到的模块不产生任何开销。
+//!
+//! 脚本中的 `模块名.函数名(...)` 调用由 [`ScriptModules::link`] 处理：
+//! 被调用的模块（及其间接依赖）的代码拼接在脚本之前，
+//! 依赖模块中定义的函数统一改名为 `mod__模块名__函数名`，调用处同步改写，
+//! 因此不同模块的同名函数不会冲突。
+//!
+//! 可识别的函数定义：JS/Lua 的 `function f(`、Rhai 的 `fn f(`、Python 的 `def f(`，
+//! JS 的 `const/let/var f = function` 与箭头函数 `const f = (...) =>`，
+//! 以及 Lua 模块表函数 `function M.f(`（模块内的 `M.f(` 调用同步改写）。
+//! 调用已定义模块中不存在的函数时链接失败，返回 [`RuntimeError::UndefinedScriptFunction`]。
+//!
+//! 改名只作用于字符串与注释以外的函数定义和 `函数名(` 形式的调用，
+//! 同名的字符串、对象键、成员访问保持原样。链接结果按脚本代码缓存，
+//! 模块作为脚本入口时的完整代码按模块名缓存，同一脚本再次执行时不重复复制与链接。
+//!
+//! 编译结果由各脚本引擎自行缓存：Rhai 引擎按代码缓存 AST，缓存的链接结果
+//! 使其再次执行时不重新解析；JS 引擎每次执行创建新的上下文，仍会重新解析
+
+use crate::{Result, error::RuntimeError, script::ScriptLanguage};
+use crawler_schema::script::{ScriptModule, ScriptSource, ScriptingConfig};
+use dashmap::DashMap;
+use regex::Regex;
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+    sync::{
+        Arc,
+        OnceLock,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+/// 已加载的脚本模块
+#[derive(Debug)]
+pub struct LoadedModule {
+    /// 模块代码
+    pub code: Arc<str>,
+    /// 模块指定的脚本语言（None 表示使用调用方的语言）
+    pub language: Option<ScriptLanguage>,
+}
+
+/// 脚本模块注册表
+#[derive(Debug, Default)]
+pub struct ScriptModules {
+    /// 模块定义
+    definitions: HashMap<String, ScriptModule>,
+    /// 已加载的模块
+    loaded: DashMap<String, Arc<LoadedModule>>,
+    /// 链接结果（按入口模块、语言与脚本代码）
+    linked: DashMap<(Option<String>, ScriptLanguage, String), Arc<str>>,
+    /// 模块作为入口时的完整代码（按模块名、语言与调用表达式）
+    entries: DashMap<(String, ScriptLanguage, Option<String>), Arc<str>>,
+    /// 累计加载次数
+    load_count: AtomicUsize,
+    /// 累计链接次数
+    link_count: AtomicUsize,
+}
+
+impl ScriptModules {
+    /// 从规则的脚本配置创建注册表
+    pub fn new(config: Option<&ScriptingConfig>) -> Self {
+        Self {
+            definitions: config.map(|c| c.modules.clone()).unwrap_or_default(),
+            ..Default::default()
+        }
+    }
+
+    /// 获取模块，首次访问时加载
+    ///
+    /// 来源为 URL 的模块通过 `fetch_url` 获取代码
+    pub fn get(
+        &self,
+        name: &str,
+        fetch_url: impl FnOnce(&str) -> Result<Arc<str>>,
+    ) -> Result<Arc<LoadedModule>> {
+        if let Some(module) = self.loaded.get(name) {
+            return Ok(module.clone());
+        }
+
+        let definition =
+            self.definitions
+                .get(name)
+                .ok_or_else(|| RuntimeError::UndefinedScriptModule {
+                    module: name.to_string(),
+                })?;
+        // 并发首次访问时只保留一份加载结果
+        let module = match self.loaded.entry(name.to_string()) {
+            dashmap::Entry::Occupied(entry) => entry.get().clone(),
+            dashmap::Entry::Vacant(entry) => {
+                let module = Arc::new(Self::load(name, definition, fetch_url)?);
+                self.load_count.fetch_add(1, Ordering::Relaxed);
+                entry.insert(module).clone()
+            }
+        };
+        Ok(module)
+    }
+
+    /// 加载模块代码
+    fn load(
+        name: &str,
+        definition: &ScriptModule,
+        fetch_url: impl FnOnce(&str) -> Result<Arc<str>>,
+    ) -> Result<LoadedModule> {
+        let code = match &definition.source {
+            ScriptSource::Code(code) => Arc::from(code.as_str()),
+            ScriptSource::Url(url) => fetch_url(url)?,
+            ScriptSource::Module(other) => {
+                return Err(RuntimeError::ScriptRuntime(format!(
+                    "脚本模块 '{}' 不能引用其他模块 '{}'",
+                    name, other
+                )));
+            }
+        };
+        Ok(LoadedModule {
+            code,
+            language: definition.engine.map(ScriptLanguage::from),
+        })
+    }
+
+    /// 链接脚本引用的其他模块
+    ///
+    /// `entry` 为脚本自身所属的模块（脚本来源为模块时），其函数保持原名；
+    /// 返回拼接依赖模块后的完整代码，未引用其他模块时原样返回
+    pub fn link(
+        &self,
+        code: &str,
+        entry: Option<&str>,
+        language: ScriptLanguage,
+        fetch_url: impl Fn(&str) -> Result<Arc<str>>,
+    ) -> Result<Arc<str>> {
+        let key = (entry.map(str::to_string), language, code.to_string());
+        if let Some(linked) = self.linked.get(&key) {
+            return Ok(linked.clone());
+        }
+        let linked: Arc<str> = Arc::from(self.link_uncached(code, entry, language, fetch_url)?);
+        self.linked.insert(key, linked.clone());
+        Ok(linked)
+    }
+
+    /// 获取模块作为脚本入口时的完整代码
+    ///
+    /// 在链接结果之后追加 `call`（调用模块函数的表达式）；结果按模块名、语言与调用表达式缓存，
+    /// 再次执行时直接返回，不再复制模块代码或查找链接缓存
+    pub fn entry(
+        &self,
+        name: &str,
+        language: ScriptLanguage,
+        call: Option<String>,
+        fetch_url: impl Fn(&str) -> Result<Arc<str>>,
+    ) -> Result<Arc<str>> {
+        let key = (name.to_string(), language, call);
+        if let Some(code) = self.entries.get(&key) {
+            return Ok(code.clone());
+        }
+        let module = self.get(name, &fetch_url)?;
+        let linked = self.link(&module.code, Some(name), language, fetch_url)?;
+        let code: Arc<str> = match &key.2 {
+            Some(call) => Arc::from(format!("{}\n;{}", linked, call)),
+            None => linked,
+        };
+        self.entries.insert(key, code.clone());
+        Ok(code)
+    }
+
+    fn link_uncached(
+        &self,
+        code: &str,
+        entry: Option<&str>,
+        language: ScriptLanguage,
+        fetch_url: impl Fn(&str) -> Result<Arc<str>>,
+    ) -> Result<String> {
+        self.link_count.fetch_add(1, Ordering::Relaxed);
+        // 按引用顺序收集依赖（含间接依赖）
+        let mut order: Vec<(&str, Arc<LoadedModule>)> = Vec::new();
+        let mut seen: HashSet<&str> = entry.into_iter().collect();
+        let mut pending = self.references(code, language);
+        while let Some(name) = pending.pop() {
+            if !seen.insert(name) {
+                continue;
+            }
+            let module = self.get(name, &fetch_url)?;
+            if module.language.is_some_and(|l| l != language) {
+                return Err(RuntimeError::ScriptRuntime(format!(
+                    "脚本模块 '{}' 的语言与调用方不一致，无法跨语言调用",
+                    name
+                )));
+            }
+            pending.extend(self.references(&module.code, language));
+            order.push((name, module));
+        }
+        // 入口模块可能以 `模块名.函数名(` 调用自身，仍需改写
+        if order.is_empty() && entry.is_none() {
+            return Ok(code.to_string());
+        }
+
+        // 依赖模块的函数改名为 `mod__模块名__函数名`，入口模块保持原名
+        let defined: Vec<(&str, Vec<DefinedFunction>)> = order
+            .iter()
+            .map(|(name, module)| (*name, defined_functions(&module.code, language)))
+            .collect();
+        let renamed = |name: &str, f: &DefinedFunction| format!("mod__{}__{}", name, f.name);
+        let renames: HashMap<&str, Vec<(String, String)>> = defined
+            .iter()
+            .map(|(name, functions)| {
+                let functions = functions
+                    .iter()
+                    .map(|f| (f.path.to_string(), renamed(name, f)))
+                    .collect();
+                (*name, functions)
+            })
+            .collect();
+        // `模块名.函数名(` 的改写目标，入口模块改写为定义处的写法
+        let mut targets: HashMap<&str, Vec<(&str, String)>> = defined
+            .iter()
+            .map(|(name, functions)| {
+                let functions = functions
+                    .iter()
+                    .map(|f| (f.name, renamed(name, f)))
+                    .collect();
+                (*name, functions)
+            })
+            .collect();
+        if let Some(entry) = entry {
+            let functions = defined_functions(code, language)
+                .into_iter()
+                .map(|f| (f.name, f.path.to_string()))
+                .collect();
+            targets.insert(entry, functions);
+        }
+
+        let mut linked = String::new();
+        for (name, module) in order.iter().rev() {
+            let own = renames.get(name).map(Vec::as_slice).unwrap_or_default();
+            let renamed = rename_functions(&module.code, own, language);
+            linked.push_str(&Self::rewrite_calls(&renamed, &targets, language)?);
+            linked.push('\n');
+        }
+        linked.push_str(&Self::rewrite_calls(code, &targets, language)?);
+        Ok(linked)
+    }
+
+    /// 代码中以 `模块名.函数名(` 形式引用的已定义模块
+    fn references<'a>(&'a self, code: &str, language: ScriptLanguage) -> Vec<&'a str> {
+        qualified_calls(code, language)
+            .into_iter()
+            .filter_map(|(_, module, _)| self.definitions.get_key_value(module))
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    /// 将 `模块名.函数名(` 改写为 `targets` 中模块函数的调用名
+    ///
+    /// 模块不在 `targets` 中时保持原样；模块中没有该函数时返回
+    /// [`RuntimeError::UndefinedScriptFunction`]
+    fn rewrite_calls(
+        code: &str,
+        targets: &HashMap<&str, Vec<(&str, String)>>,
+        language: ScriptLanguage,
+    ) -> Result<String> {
+        let mut result = String::with_capacity(code.len());
+        let mut last = 0;
+        for (range, module, function) in qualified_calls(code, language) {
+            let Some(functions) = targets.get(module) else {
+                continue;
+            };
+            let (_, target) = functions
+                .iter()
+                .find(|(f, _)| *f == function)
+                .ok_or_else(|| RuntimeError::UndefinedScriptFunction {
+                    module: module.to_string(),
+                    function: function.to_string(),
+                })?;
+            result.push_str(&code[last..range.start]);
+            result.push_str(target);
+            last = range.end;
+        }
+        result.push_str(&code[last..]);
+        Ok(result)
+    }
+
+    /// 是否定义了指定模块
+    pub fn contains(&self, name: &str) -> bool {
+        self.definitions.contains_key(name)
+    }
+
+    /// 指定模块是否已加载
+    pub fn is_loaded(&self, name: &str) -> bool {
+        self.loaded.contains_key(name)
+    }
+
+    /// 累计加载的模块数量
+    pub fn load_count(&self) -> usize {
+        self.load_count.load(Ordering::Relaxed)
+    }
+
+    /// 累计链接次数（命中缓存的不计）
+    pub fn link_count(&self) -> usize {
+        self.link_count.load(Ordering::Relaxed)
+    }
+}
+
+/// 查找 `模块名.函数名(` 形式的调用，返回 `模块名.函数名` 的范围、模块名与函数名
+///
+/// 模块名前为 `.` 时是成员访问链（如 `a.b.c(`），不视为模块调用；字符串与注释中的内容被忽略
+fn qualified_calls(code: &str, language: ScriptLanguage) -> Vec<(Range<usize>, &str, &str)> {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    let re = RE.get_or_init(|| Regex::new(r"\b([A-Za-z_]\w*)\.([A-Za-z_]\w*)\s*\(").unwrap());
+    let spans = CodeSpans::new(code, language);
+    re.captures_iter(code)
+        .filter_map(|caps| {
+            let (module, function) = (caps.get(1)?, caps.get(2)?);
+            (spans.contains(module.start()) && !code[..module.start()].ends_with('.')).then(|| {
+                (
+                    module.start()..function.end(),
+                    module.as_str(),
+                    function.as_str(),
+                )
+            })
+        })
+        .collect()
+}
+
+/// 模块中定义的函数
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct DefinedFunction<'a> {
+    /// 函数名，即模块外 `模块名.函数名(` 中的函数名
+    name: &'a str,
+    /// 定义处的写法，Lua 模块表函数为 `M.f`，其余与函数名相同
+    path: &'a str,
+}
+
+/// 模块中定义的函数
+///
+/// 识别 JS/Lua 的 `function`（含 Lua 的 `function M.f`）、Rhai 的 `fn`、Python 的 `def`，
+/// 以及 JS 中赋值给 `const/let/var` 的函数表达式与箭头函数
+fn defined_functions(code: &str, language: ScriptLanguage) -> Vec<DefinedFunction<'_>> {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    let re = RE.get_or_init(|| {
+        Regex::new(
+            r"(?m)(?:\bfunction|\bfn|^\s*def)\s+([A-Za-z_]\w*(?:\.[A-Za-z_]\w*)?)\s*\(|\b(?:const|let|var)\s+([A-Za-z_]\w*)\s*=\s*(?:async\s+)?(?:function\b|\([^()]*\)\s*=>|[A-Za-z_]\w*\s*=>)",
+        )
+        .unwrap()
+    });
+    let spans = CodeSpans::new(code, language);
+    let mut functions: Vec<DefinedFunction> = re
+        .captures_iter(code)
+        .filter_map(|caps| caps.get(1).or_else(|| caps.get(2)))
+        .filter(|m| spans.contains(m.start()))
+        .map(|m| DefinedFunction {
+            name: m.as_str().rsplit('.').next().unwrap_or_default(),
+            path: m.as_str(),
+        })
+        .collect();
+    functions.sort_unstable();
+    functions.dedup();
+    functions
+}
+
+/// 将代码中函数的定义与调用改名，`renames` 为定义处的写法到新名称的映射
+///
+/// 改写 `函数名(`（含 Lua 的 `M.f(`）形式的定义与调用，以及 `const/let/var 函数名 =` 形式的定义；
+/// 不改写 `obj.name(` 形式的成员调用，以及字符串、注释、对象键等非调用位置的同名标识符
+fn rename_functions(code: &str, renames: &[(String, String)], language: ScriptLanguage) -> String {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    let re = RE.get_or_init(|| {
+        Regex::new(
+            r"\b([A-Za-z_]\w*(?:\.[A-Za-z_]\w*)?)\s*\(|\b(?:const|let|var)\s+([A-Za-z_]\w*)\s*=",
+        )
+        .unwrap()
+    });
+    let spans = CodeSpans::new(code, language);
+    let mut result = String::with_capacity(code.len());
+    let mut last = 0;
+    for m in re
+        .captures_iter(code)
+        .filter_map(|caps| caps.get(1).or_else(|| caps.get(2)))
+    {
+        let is_member = code[..m.start()].ends_with('.');
+        if let Some((_, renamed)) = renames.iter().find(|(f, _)| f == m.as_str())
+            && !is_member
+            && spans.contains(m.start())
+        {
+            result.push_str(&code[last..m.start()]);
+            result.push_str(renamed);
+            last = m.end();
+        }
+    }
+    result.push_str(&code[last..]);
+    result
+}
+
+/// 代码中字符串字面量与注释以外的区间
+struct CodeSpans(Vec<Range<usize>>);
+
+impl CodeSpans {
+    fn new(code: &str, language: ScriptLanguage) -> Self {
+        let bytes = code.as_bytes();
+        let mut spans = Vec::new();
+        let (mut start, mut i) = (0, 0);
+        while i < bytes.len() {
+            match literal_len(&bytes[i..], language) {
+                Some(len) => {
+                    if start < i {
+                        spans.push(start..i);
+                    }
+                    i += len;
+                    start = i;
+                }
+                None => i += 1,
+            }
+        }
+        if start < bytes.len() {
+            spans.push(start..bytes.len());
+        }
+        Self(spans)
+    }
+
+    /// 位置是否在代码区间内
+    fn contains(&self, pos: usize) -> bool {
+        self.0.iter().any(|span| span.contains(&pos))
+    }
+}
+
+/// 以字符串字面量或注释开头时返回其字节长度
+fn literal_len(rest: &[u8], language: ScriptLanguage) -> Option<usize> {
+    // 查找结束标记，未闭合时延伸到代码末尾
+    let until = |open: usize, close: &[u8]| {
+        rest[open..]
+            .windows(close.len())
+            .position(|w| w == close)
+            .map_or(rest.len(), |pos| open + pos + close.len())
+    };
+    let quoted = |quote: u8| {
+        let mut i = 1;
+        while i < rest.len() {
+            match rest[i] {
+                b'\\' => i += 2,
+                c if c == quote => return i + 1,
+                _ => i += 1,
+            }
+        }
+        rest.len()
+    };
+
+    match language {
+        ScriptLanguage::Python => match rest {
+            [b'#', ..] => Some(until(1, b"\n")),
+            [b'"', b'"', b'"', ..] => Some(until(3, b"\"\"\"")),
+            [b'\'', b'\'', b'\'', ..] => Some(until(3, b"'''")),
+            [q @ (b'"' | b'\''), ..] => Some(quoted(*q)),
+            _ => None,
+        },
+        ScriptLanguage::Lua => match rest {
+            [b'-', b'-', b'[', b'[', ..] => Some(until(4, b"]]")),
+            [b'-', b'-', ..] => Some(until(2, b"\n")),
+            [b'[', b'[', ..] => Some(until(2, b"]]")),
+            [q @ (b'"' | b'\''), ..] => Some(quoted(*q)),
+            _ => None,
+        },
+        ScriptLanguage::JavaScript | ScriptLanguage::Rhai => match rest {
+            [b'/', b'/', ..] => Some(until(2, b"\n")),
+            [b'/', b'*', ..] => Some(until(2, b"*/")),
+            [q @ (b'"' | b'\'' | b'`'), ..] => Some(quoted(*q)),
+            _ => None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn renames(name: &str) -> Vec<(String, String)> {
+        vec![(name.to_string(), format!("mod__m__{}", name))]
+    }
+
+    #[test]
+    fn rename_skips_strings_keys_and_members() {
+        let code = r#"function title(x) { return { title: x, s: "title(", t: obj["title"], u: o.title(x) }; } // title(
+title(1);"#;
+        let renamed = rename_functions(code, &renames("title"), ScriptLanguage::JavaScript);
+        assert_eq!(
+            renamed,
+            r#"function mod__m__title(x) { return { title: x, s: "title(", t: obj["title"], u: o.title(x) }; } // title(
+mod__m__title(1);"#
+        );
+    }
+
+    #[test]
+    fn comment_syntax_follows_language() {
+        let lua = "-- helper(1)\nlocal s = [[helper(2)]]\nreturn helper(3)";
+        assert_eq!(
+            rename_functions(lua, &renames("helper"), ScriptLanguage::Lua),
+            "-- helper(1)\nlocal s = [[helper(2)]]\nreturn mod__m__helper(3)"
+        );
+        let python = "# helper(1)\ns = '''helper(2)'''\nhelper(3)";
+        assert_eq!(
+            rename_functions(python, &renames("helper"), ScriptLanguage::Python),
+            "# helper(1)\ns = '''helper(2)'''\nmod__m__helper(3)"
+        );
+    }
+
+    #[test]
+    fn qualified_calls_ignore_literals() {
+        let code = r#"let a = util.trim(x); let b = "util.trim(y)"; /* util.pad( */"#;
+        let calls = qualified_calls(code, ScriptLanguage::Rhai);
+        assert_eq!(calls.len(), 1);
+        assert_eq!((calls[0].1, calls[0].2), ("util", "trim"));
+    }
+
+    #[test]
+    fn defined_functions_cover_assignments_and_module_tables() {
+        let js = "function a() {} const b = (x) => x; let c = function (y) {}; var d = async x => x; const e = 1;";
+        let names: Vec<&str> = defined_functions(js, ScriptLanguage::JavaScript)
+            .iter()
+            .map(|f| f.name)
+            .collect();
+        assert_eq!(names, ["a", "b", "c", "d"]);
+
+        let lua = "local function a(x) return x end\nfunction M.b(x) return a(x) end";
+        let functions = defined_functions(lua, ScriptLanguage::Lua);
+        assert_eq!(
+            functions,
+            [
+                DefinedFunction {
+                    name: "a",
+                    path: "a"
+                },
+                DefinedFunction {
+                    name: "b",
+                    path: "M.b"
+                },
+            ]
+        );
+    }
+
+    /// 由 TOML 定义模块的注册表
+    fn modules(config: &str) -> ScriptModules {
+        let config: ScriptingConfig = toml::from_str(config).unwrap();
+        ScriptModules::new(Some(&config))
+    }
+
+    fn no_fetch(_: &str) -> Result<Arc<str>> {
+        unreachable!()
+    }
+
+    #[test]
+    fn lua_module_table_functions_are_linked() {
+        let modules = modules(
+            r#"
+[modules.util]
+engine = "lua"
+code = '''
+local M = {}
+function M.trim(s) return s end
+function M.pad(s) return M.trim(s) .. " " end'''
+"#,
+        );
+
+        let linked = modules
+            .link(
+                "return util.pad(input)",
+                None,
+                ScriptLanguage::Lua,
+                no_fetch,
+            )
+            .unwrap();
+        assert!(
+            linked.contains("function mod__util__trim(s) return s end"),
+            "{}",
+            linked
+        );
+        assert!(
+            linked.contains("function mod__util__pad(s) return mod__util__trim(s) .. \" \" end"),
+            "{}",
+            linked
+        );
+        assert!(
+            linked.ends_with("return mod__util__pad(input)"),
+            "{}",
+            linked
+        );
+    }
+
+    #[test]
+    fn calling_an_undefined_module_function_fails_to_link() {
+        let modules = modules(
+            r#"
+[modules.util]
+code = "const trim = (s) => s.trim();"
+
+[modules.page]
+code = "function title(s) { return page.clean(s); }"
+"#,
+        );
+
+        let err = modules
+            .link(
+                "util.pad(input)",
+                None,
+                ScriptLanguage::JavaScript,
+                no_fetch,
+            )
+            .unwrap_err();
+        assert!(
+            matches!(&err, RuntimeError::UndefinedScriptFunction { module, function }
+                if module == "util" && function == "pad"),
+            "{}",
+            err
+        );
+        // 入口模块调用自身未定义的函数同样报错
+        let err = modules
+            .entry("page", ScriptLanguage::JavaScript, None, no_fetch)
+            .unwrap_err();
+        assert!(
+            matches!(err, RuntimeError::UndefinedScriptFunction { .. }),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn link_result_is_cached() {
+        let config: ScriptingConfig = toml::from_str(
+            r#"
+[modules.util]
+code = "fn trim(s) { s.trim(); s }"
+"#,
+        )
+        .unwrap();
+        let modules = ScriptModules::new(Some(&config));
+        let fetch = |_: &str| -> Result<Arc<str>> { unreachable!() };
+
+        let first = modules
+            .link("util.trim(input)", None, ScriptLanguage::Rhai, fetch)
+            .unwrap();
+        let second = modules
+            .link("util.trim(input)", None, ScriptLanguage::Rhai, fetch)
+            .unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(first.ends_with("mod__util__trim(input)"));
+        assert_eq!(modules.load_count(), 1);
+    }
+}
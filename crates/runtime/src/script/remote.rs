@@ -0,0 +1,239 @@
+//! 远程脚本
+//!
+//! 加载 `ScriptSource::Url` 指向的脚本。脚本按 URL 缓存，同一运行时内只下载一次；
+//! 脚本引擎是同步执行的，因此规则引用的远程脚本在流程开始前异步下载（见
+//! [`RuntimeContext::load_remote_scripts`](crate::context::RuntimeContext::load_remote_scripts)），
+//! 执行时只读取缓存。
+//!
+//! 配置缓存目录后，脚本连同 ETag 与内容哈希持久化到磁盘：
+//! - 再次启动时带 `If-None-Match` 重新验证，服务端返回 304 时直接使用磁盘缓存
+//! - 下载失败（如离线）时回退到上次缓存的版本
+
+use crate::{
+    Result,
+    error::RuntimeError,
+    http::{HttpClient, HttpResponse, PreparedRequest},
+};
+use crawler_schema::{
+    config::HttpMethod,
+    core::CrawlerRule,
+    script::{Script, ScriptSource},
+};
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+/// 已加载的远程脚本
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteScript {
+    /// 脚本地址
+    pub url: String,
+    /// 脚本代码
+    pub code: Arc<str>,
+    /// 服务端返回的 ETag
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    /// 代码内容的 MD5
+    pub hash: String,
+}
+
+impl RemoteScript {
+    fn new(url: &str, code: String, etag: Option<String>) -> Self {
+        Self {
+            url: url.to_string(),
+            hash: format!("{:x}", md5::compute(&code)),
+            code: Arc::from(code),
+            etag,
+        }
+    }
+}
+
+/// 远程脚本缓存
+#[derive(Debug, Default)]
+pub struct RemoteScripts {
+    /// 已加载的脚本（按 URL）
+    loaded: DashMap<String, Arc<RemoteScript>>,
+    /// 磁盘缓存目录
+    cache_dir: Option<PathBuf>,
+    /// 累计发出的下载请求数（含 304）
+    fetch_count: AtomicUsize,
+}
+
+impl RemoteScripts {
+    /// 创建只在内存中缓存的远程脚本缓存
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置磁盘缓存目录
+    pub fn with_cache_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = Some(dir.into());
+        self
+    }
+
+    /// 获取已加载的脚本，不发起请求
+    pub fn get(&self, url: &str) -> Option<Arc<RemoteScript>> {
+        self.loaded.get(url).map(|script| script.clone())
+    }
+
+    /// 加载脚本，首次访问时下载
+    pub async fn load(&self, url: &str, client: &HttpClient) -> Result<Arc<RemoteScript>> {
+        if let Some(script) = self.get(url) {
+            return Ok(script);
+        }
+
+        let cached = self.read_disk(url);
+        let script = match self.fetch(url, client, cached.as_ref()).await {
+            Ok(Some(script)) => {
+                if cached.as_ref().is_some_and(|c| c.hash != script.hash) {
+                    tracing::info!("远程脚本已更新: {}", url);
+                }
+                self.write_disk(&script);
+                script
+            }
+            // 304：磁盘缓存仍有效
+            Ok(None) => cached.ok_or_else(|| {
+                RuntimeError::ScriptRuntime(format!("远程脚本 '{}' 返回 304 但没有本地缓存", url))
+            })?,
+            Err(e) => match cached {
+                Some(cached) => {
+                    tracing::warn!("下载远程脚本失败，使用上次缓存的版本 ({}): {}", url, e);
+                    cached
+                }
+                None => return Err(e),
+            },
+        };
+
+        // 并发首次访问时只保留一份加载结果
+        Ok(self
+            .loaded
+            .entry(url.to_string())
+            .or_insert_with(|| Arc::new(script))
+            .clone())
+    }
+
+    /// 清除内存中的缓存，下次访问时重新验证
+    pub fn clear(&self) {
+        self.loaded.clear();
+    }
+
+    /// 累计发出的下载请求数
+    pub fn fetch_count(&self) -> usize {
+        self.fetch_count.load(Ordering::Relaxed)
+    }
+
+    /// 下载脚本，服务端返回 304 时为 None
+    async fn fetch(
+        &self,
+        url: &str,
+        client: &HttpClient,
+        cached: Option<&RemoteScript>,
+    ) -> Result<Option<RemoteScript>> {
+        let mut headers = HashMap::new();
+        if let Some(etag) = cached.and_then(|c| c.etag.as_ref()) {
+            headers.insert("If-None-Match".to_string(), etag.clone());
+        }
+        let request = PreparedRequest {
+            method: HttpMethod::Get,
+            url: url.to_string(),
+            headers,
+            body: None,
+        };
+
+        self.fetch_count.fetch_add(1, Ordering::Relaxed);
+        let response = HttpResponse::read(client.send(request).await?).await?;
+        if response.status == 304 {
+            return Ok(None);
+        }
+        response.error_for_status()?;
+
+        let etag = response.header("ETag").map(str::to_string);
+        Ok(Some(RemoteScript::new(url, response.body, etag)))
+    }
+
+    /// 磁盘缓存文件路径
+    fn cache_path(&self, url: &str) -> Option<PathBuf> {
+        let dir = self.cache_dir.as_ref()?;
+        Some(dir.join(format!("{:x}.json", md5::compute(url))))
+    }
+
+    fn read_disk(&self, url: &str) -> Option<RemoteScript> {
+        let path = self.cache_path(url)?;
+        let content = std::fs::read_to_string(&path).ok()?;
+        serde_json::from_str::<RemoteScript>(&content)
+            .ok()
+            .filter(|script| script.url == url)
+    }
+
+    /// 写入磁盘缓存（先写临时文件再替换），失败只记录日志
+    fn write_disk(&self, script: &RemoteScript) {
+        let Some(path) = self.cache_path(&script.url) else {
+            return;
+        };
+        let write = |path: &Path| -> std::io::Result<()> {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let content = serde_json::to_string(script).map_err(std::io::Error::other)?;
+            let tmp = path.with_extension("tmp");
+            std::fs::write(&tmp, content)?;
+            std::fs::rename(&tmp, path)
+        };
+        if let Err(e) = write(&path) {
+            tracing::warn!("写入远程脚本缓存 '{}' 失败: {}", path.display(), e);
+        }
+    }
+}
+
+/// 规则引用的远程脚本地址（去重，按出现顺序）
+///
+/// 包括来源为 URL 的脚本模块，以及脚本步骤、登录与验证流程中的 `script`、`*_script`、`action` 脚本
+pub fn script_urls(rule: &CrawlerRule) -> Vec<String> {
+    let mut urls = Vec::new();
+    if let Some(scripting) = &rule.scripting {
+        for module in scripting.modules.values() {
+            if let ScriptSource::Url(url) = &module.source {
+                urls.push(url.clone());
+            }
+        }
+    }
+    if let Ok(tree) = serde_json::to_value(rule) {
+        collect_script_urls(&tree, &mut urls);
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    urls.retain(|url| seen.insert(url.clone()));
+    urls
+}
+
+/// 收集 JSON 树中脚本字段的远程地址
+fn collect_script_urls(value: &Value, urls: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                let is_script = key == "script" || key == "action" || key.ends_with("_script");
+                if is_script
+                    && child.get("url").is_some_and(Value::is_string)
+                    && let Ok(script) = serde_json::from_value::<Script>(child.clone())
+                    && let ScriptSource::Url(url) = script.source
+                {
+                    urls.push(url);
+                    continue;
+                }
+                collect_script_urls(child, urls);
+            }
+        }
+        Value::Array(arr) => arr
+            .iter()
+            .for_each(|child| collect_script_urls(child, urls)),
+        _ => {}
+    }
+}
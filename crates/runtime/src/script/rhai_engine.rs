@@ -7,16 +7,13 @@ use crate::{
 };
 use quick_cache::sync::Cache;
 use rhai::{AST, Dynamic, Engine, Scope};
-use std::{
-    sync::{Arc, Mutex},
-    time::Duration,
-};
+use std::{sync::Arc, time::Duration};
 
 /// Rhai 脚本引擎
 #[derive(Debug)]
 pub struct RhaiScriptEngine {
-    /// Rhai 引擎实例
-    engine: Arc<Mutex<Engine>>,
+    /// Rhai 引擎实例（启用 `sync` 特性，可被多个线程同时使用）
+    engine: Engine,
     /// 编译缓存
     ast_cache: Cache<String, Arc<AST>>,
     /// 执行超时设置
@@ -37,7 +34,7 @@ impl RhaiScriptEngine {
         super::builtin::rhai::register_all(&mut engine);
 
         Self {
-            engine: Arc::new(Mutex::new(engine)),
+            engine,
             ast_cache: Cache::new(128),
             timeout: Duration::from_secs(5),
         }
@@ -49,8 +46,8 @@ impl RhaiScriptEngine {
             return Ok(ast);
         }
 
-        let engine = self.engine.lock().unwrap();
-        let ast = engine
+        let ast = self
+            .engine
             .compile(script)
             .map_err(|e| RuntimeError::ScriptSyntax(format!("[Rhai] {}", e)))?;
 
@@ -79,11 +76,13 @@ impl ScriptEngine for RhaiScriptEngine {
     fn execute(&self, script: &str, context: &ScriptContext) -> Result<String> {
         let ast = self.compile_cached(script)?;
         let mut scope = self.create_scope(context);
-        let engine = self.engine.lock().unwrap();
+        let http = super::http::enter(context);
 
-        let result: Dynamic = engine
-            .eval_ast_with_scope(&mut scope, &ast)
-            .map_err(|e| RuntimeError::ScriptRuntime(format!("[Rhai] {}", e)))?;
+        let result: Dynamic = http.finish(
+            self.engine
+                .eval_ast_with_scope(&mut scope, &ast)
+                .map_err(|e| RuntimeError::ScriptRuntime(format!("[Rhai] {}", e))),
+        )?;
 
         Ok(result.to_string())
     }
@@ -2,8 +2,9 @@
 //!
 //! 提供模板渲染和验证功能
 
-use crate::{Result, RuntimeError, context::FlowContext};
+use crate::{Result, RuntimeError, context::FlowContext, script::builtin::core};
 use crawler_schema::template::Template;
+use std::{collections::HashMap, sync::OnceLock};
 use tera::Tera;
 
 /// 模板渲染扩展 trait
@@ -12,6 +13,8 @@ use tera::Tera;
 pub trait TemplateExt {
     /// 渲染模板
     ///
+    /// 模板多用于 URL、请求体与提取结果，变量值不做 HTML 转义
+    ///
     /// # 参数
     ///
     /// - `ctx`: 流程上下文，包含 Flow 变量和 Runtime 全局变量
@@ -27,10 +30,205 @@ pub trait TemplateExt {
 
 impl TemplateExt for Template {
     fn render(&self, flow_context: &FlowContext) -> Result<String> {
-        Tera::one_off(self.as_str(), &flow_context.to_tera_context()?, true).map_err(|e| {
-            RuntimeError::TemplateError {
-                error: e.to_string(),
+        render_str(self.as_str(), &flow_context.to_tera_context()?)
+    }
+}
+
+/// 使用统一的 Tera 实例渲染模板字符串
+///
+/// 除 Tera 自带过滤器外，内置转换（见 [`core::TRANSFORM_NAMES`]）也可作为过滤器使用，
+/// 如 `{{ chapter | cn_to_num }}`、`{{ title | t2s }}`；与 Tera 自带过滤器同名的保留 Tera 实现
+pub fn render_str(template: &str, context: &tera::Context) -> Result<String> {
+    static BASE: OnceLock<Tera> = OnceLock::new();
+    let base = BASE.get_or_init(|| {
+        let mut tera = Tera::default();
+        for &name in core::TRANSFORM_NAMES {
+            if matches!(name, "upper" | "lower" | "trim") {
+                continue;
             }
-        })
+            tera.register_filter(
+                name,
+                move |value: &tera::Value, _: &HashMap<String, tera::Value>| {
+                    let text = match value {
+                        tera::Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    };
+                    core::transform_text(name, &text)
+                        .map(tera::Value::String)
+                        .ok_or_else(|| tera::Error::msg(format!("未知的转换: {}", name)))
+                },
+            );
+        }
+        tera
+    });
+
+    // render_str 需要可变引用；单次模板名不匹配任何自动转义后缀，不做 HTML 转义
+    let mut tera = base.clone();
+    tera.render_str(template, context).map_err(|e| {
+        // Tera 的顶层错误只有模板名，具体原因在 source 链中
+        let mut error = e.to_string();
+        let mut source = std::error::Error::source(&e);
+        while let Some(cause) = source {
+            error.push_str(": ");
+            error.push_str(&cause.to_string());
+            source = cause.source();
+        }
+        RuntimeError::TemplateError { error }
+    })
+}
+
+/// 模板表达式中的关键字与字面量
+const EXPR_KEYWORDS: &[&str] = &[
+    "and", "or", "not", "in", "is", "true", "false", "True", "False", "loop",
+];
+
+/// 获取模板引用的根变量
+///
+/// - 普通变量返回变量名，如 `{{ user.name }}` 返回 `user`
+/// - 全局变量返回 `$.` 前缀形式，如 `{{ $.base_url }}` 返回 `$.base_url`
+/// - `{% for %}`、`{% set %}` 定义的局部变量、函数调用、过滤器名与字符串字面量不计入
+/// - 经 `is defined` / `is undefined` 判断过的变量视为可选，不计入
+pub fn referenced_variables(template: &str) -> Vec<String> {
+    collect_variables(template, false)
+}
+
+/// 获取模板渲染时必须已定义的根变量
+///
+/// 在 [`referenced_variables`] 的基础上，带 `default` 过滤器的表达式中的变量也视为可选
+pub fn required_variables(template: &str) -> Vec<String> {
+    collect_variables(template, true)
+}
+
+fn collect_variables(template: &str, defaulted_optional: bool) -> Vec<String> {
+    let mut locals: Vec<String> = Vec::new();
+    let mut vars: Vec<String> = Vec::new();
+
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let after = &rest[start..];
+        let (close, is_tag) = if after.starts_with("{{") {
+            ("}}", false)
+        } else if after.starts_with("{%") {
+            ("%}", true)
+        } else {
+            rest = &after[1..];
+            continue;
+        };
+        let Some(end) = after.find(close) else {
+            break;
+        };
+        let inner = after[2..end].trim_matches(|c: char| c == '-' || c.is_whitespace());
+        rest = &after[end + 2..];
+
+        let expr = if is_tag {
+            let (tag, body) = inner.split_once(char::is_whitespace).unwrap_or((inner, ""));
+            match tag {
+                "for" => match body.split_once(" in ") {
+                    Some((targets, iterable)) => {
+                        locals.extend(targets.split(',').map(|t| t.trim().to_string()));
+                        iterable
+                    }
+                    None => continue,
+                },
+                "set" | "set_global" => match body.split_once('=') {
+                    Some((name, value)) => {
+                        locals.push(name.trim().to_string());
+                        value
+                    }
+                    None => continue,
+                },
+                "if" | "elif" => body,
+                _ => continue,
+            }
+        } else {
+            inner
+        };
+
+        // 已做存在性判断的变量视为可选
+        if expr.contains(" is defined")
+            || expr.contains(" is undefined")
+            || (defaulted_optional && expr.contains("default("))
+        {
+            locals.extend(expression_roots(expr));
+            continue;
+        }
+        for var in expression_roots(expr) {
+            if !locals.contains(&var) && !vars.contains(&var) {
+                vars.push(var);
+            }
+        }
+    }
+
+    vars
+}
+
+/// 提取表达式中的根变量
+fn expression_roots(expr: &str) -> Vec<String> {
+    let chars: Vec<char> = expr.chars().collect();
+    let mut roots = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        // 跳过字符串字面量
+        if matches!(c, '"' | '\'' | '`') {
+            i += 1;
+            while i < chars.len() && chars[i] != c {
+                i += 1;
+            }
+            i += 1;
+            continue;
+        }
+
+        if c == '$' || c.is_alphabetic() || c == '_' {
+            let start = i;
+            if c == '$' && chars.get(i + 1) == Some(&'.') {
+                i += 2;
+            }
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let token: String = chars[start..i].iter().collect();
+
+            let prev = chars[..start].iter().rev().find(|c| !c.is_whitespace());
+            let next = chars[i..].iter().find(|c| !c.is_whitespace());
+            let next_two: String = chars[i..]
+                .iter()
+                .skip_while(|c| c.is_whitespace())
+                .take(2)
+                .collect();
+
+            let is_attribute = matches!(prev, Some('.' | '|'));
+            let is_call = next == Some(&'(');
+            let is_kwarg = next == Some(&'=') && next_two != "==";
+            let after_is = chars[..start]
+                .iter()
+                .collect::<String>()
+                .trim_end()
+                .ends_with(" is");
+            if !is_attribute
+                && !is_call
+                && !is_kwarg
+                && !after_is
+                && token != "$"
+                && !EXPR_KEYWORDS.contains(&token.as_str())
+            {
+                roots.push(token);
+            }
+            continue;
+        }
+
+        // 跳过数字字面量（含小数）
+        if c.is_ascii_digit() {
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '.') {
+                i += 1;
+            }
+            continue;
+        }
+
+        i += 1;
     }
+
+    roots
 }
@@ -1,6 +1,72 @@
 //! # 缓存工具
 //!
-//! 提供 LRU 缓存等缓存机制
+//! 提供可注入的缓存存储抽象及默认的内存实现
 
-// TODO: 实现 LRU 缓存
-// 可以使用 lru crate
+use quick_cache::sync::Cache;
+use std::{
+    fmt::Debug,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// 默认内存缓存容量（条目数）
+const DEFAULT_CAPACITY: usize = 1024;
+
+/// 缓存存储接口
+///
+/// 集成方可实现此 trait 将缓存落到磁盘、Redis 等介质，
+/// 通过 `CrawlerRuntimeBuilder::with_cache_store` 注入
+pub trait CacheStore: Send + Sync + Debug {
+    /// 读取缓存，不存在或已过期时返回 None
+    fn get(&self, key: &str) -> Option<String>;
+
+    /// 写入缓存，`ttl` 为 None 表示不过期
+    fn set(&self, key: &str, value: String, ttl: Option<Duration>);
+
+    /// 删除缓存
+    fn remove(&self, key: &str);
+}
+
+/// 共享的缓存存储
+pub type SharedCacheStore = Arc<dyn CacheStore>;
+
+/// 内存缓存存储（基于 quick_cache，按容量淘汰）
+#[derive(Debug)]
+pub struct MemoryCacheStore {
+    cache: Cache<String, (String, Option<Instant>)>,
+}
+
+impl MemoryCacheStore {
+    /// 创建指定容量的内存缓存
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            cache: Cache::new(capacity),
+        }
+    }
+}
+
+impl Default for MemoryCacheStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl CacheStore for MemoryCacheStore {
+    fn get(&self, key: &str) -> Option<String> {
+        let (value, expires_at) = self.cache.get(key)?;
+        if expires_at.is_some_and(|t| t <= Instant::now()) {
+            self.cache.remove(key);
+            return None;
+        }
+        Some(value)
+    }
+
+    fn set(&self, key: &str, value: String, ttl: Option<Duration>) {
+        let expires_at = ttl.map(|ttl| Instant::now() + ttl);
+        self.cache.insert(key.to_string(), (value, expires_at));
+    }
+
+    fn remove(&self, key: &str) {
+        self.cache.remove(key);
+    }
+}
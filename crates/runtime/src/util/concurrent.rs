@@ -1,6 +1,39 @@
 //! # 并发控制工具
 //!
-//! 提供并发限制、信号量等功能
+//! 提供有界并发执行等功能
 
-// TODO: 实现并发控制
-// 可以使用 tokio::sync::Semaphore
+use std::{future::Future, sync::Arc};
+use tokio::{sync::Semaphore, task::JoinSet};
+
+/// 以有界并发对每个输入执行异步任务，结果按输入顺序返回
+///
+/// 同时运行的任务数不超过 `concurrency`（为 0 时按 1 处理）；
+/// 任务在 tokio 运行时上派生执行，任务 panic 时在调用方重新抛出
+pub async fn map_bounded<I, T, F, Fut>(items: Vec<I>, concurrency: usize, f: F) -> Vec<T>
+where
+    F: Fn(I) -> Fut,
+    Fut: Future<Output = T> + Send + 'static,
+    T: Send + 'static,
+{
+    let semaphore = Arc::new(Semaphore::new(concurrency.max(1)));
+    let mut tasks = JoinSet::new();
+    let len = items.len();
+    for (index, item) in items.into_iter().enumerate() {
+        let semaphore = semaphore.clone();
+        let task = f(item);
+        tasks.spawn(async move {
+            // 信号量不会被关闭，获取失败时直接执行
+            let _permit = semaphore.acquire_owned().await.ok();
+            (index, task.await)
+        });
+    }
+
+    let mut results: Vec<Option<T>> = (0..len).map(|_| None).collect();
+    while let Some(joined) = tasks.join_next().await {
+        match joined {
+            Ok((index, result)) => results[index] = Some(result),
+            Err(e) => std::panic::resume_unwind(e.into_panic()),
+        }
+    }
+    results.into_iter().flatten().collect()
+}
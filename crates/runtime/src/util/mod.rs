@@ -2,5 +2,7 @@
 
 pub mod cache;
 pub mod concurrent;
+pub mod progress;
 
-// 预留：缓存、并发控制等工具
+pub use cache::{CacheStore, MemoryCacheStore, SharedCacheStore};
+pub use progress::{ProgressListener, SharedProgressListener};
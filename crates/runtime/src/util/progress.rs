@@ -0,0 +1,30 @@
+//! # 进度事件
+//!
+//! 长流程（分页、批量详情）通过 [`ProgressListener`] 向宿主报告进度，
+//! 便于 App 展示进度条与日志
+
+use crate::challenge::ChallengeType;
+use serde_json::Value;
+use std::{fmt::Debug, sync::Arc};
+
+/// 进度事件监听器
+///
+/// 所有方法默认不做任何处理，集成方按需实现，
+/// 通过 `CrawlerRuntimeBuilder::with_progress_listener` 注入。
+/// 回调在流程执行路径上同步调用，应避免耗时操作
+pub trait ProgressListener: Send + Sync + Debug {
+    /// 即将发起流程请求
+    fn on_request(&self, _url: &str) {}
+
+    /// 完成一页列表的提取
+    fn on_page(&self, _page: u32, _items_count: usize) {}
+
+    /// 提取到一个结果项（列表项的原始数据）
+    fn on_item(&self, _item: &Value) {}
+
+    /// 检测到人机验证
+    fn on_challenge(&self, _challenge_type: &ChallengeType) {}
+}
+
+/// 共享的进度事件监听器
+pub type SharedProgressListener = Arc<dyn ProgressListener>;
@@ -0,0 +1,379 @@
+//! 基于 Chromium 的无头 WebView 实现
+//!
+//! 需启用 `headless` feature，通过 Chrome DevTools Protocol 驱动本机 Chromium/Chrome
+
+use super::{WebViewCloseReason, WebViewProvider, WebViewRequest, WebViewResponse};
+use crate::{Result, error::RuntimeError};
+use async_trait::async_trait;
+use chromiumoxide::{
+    Browser,
+    BrowserConfig,
+    Page,
+    cdp::browser_protocol::network::{CookieParam, Headers, SetExtraHttpHeadersParams},
+};
+use futures_util::StreamExt;
+use std::{collections::HashMap, path::PathBuf};
+
+/// Chromium 无头 WebView 提供者
+///
+/// 每次 `open` 启动独立的浏览器实例，Cookie 等状态互不影响；
+/// 不支持 `allow_redirects = false`，重定向始终跟随。
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// let provider = ChromiumWebViewProvider::new().with_no_sandbox();
+/// let runtime = CrawlerRuntime::builder(rule)
+///     .with_webview_provider(Arc::new(provider))
+///     .build()?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct ChromiumWebViewProvider {
+    /// 浏览器可执行文件路径（未指定时自动检测）
+    executable: Option<PathBuf>,
+    /// 是否以无头模式运行
+    headless: bool,
+    /// 是否禁用沙箱（容器内以 root 运行时需要）
+    no_sandbox: bool,
+}
+
+impl Default for ChromiumWebViewProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChromiumWebViewProvider {
+    /// 创建提供者（无头模式，自动检测浏览器）
+    pub fn new() -> Self {
+        Self {
+            executable: None,
+            headless: true,
+            no_sandbox: false,
+        }
+    }
+
+    /// 指定浏览器可执行文件
+    pub fn with_executable(mut self, path: impl Into<PathBuf>) -> Self {
+        self.executable = Some(path.into());
+        self
+    }
+
+    /// 以有界面模式运行（便于调试）
+    pub fn with_head(mut self) -> Self {
+        self.headless = false;
+        self
+    }
+
+    /// 禁用沙箱
+    pub fn with_no_sandbox(mut self) -> Self {
+        self.no_sandbox = true;
+        self
+    }
+
+    /// 按请求构建浏览器配置
+    fn browser_config(&self, request: &WebViewRequest) -> Result<BrowserConfig> {
+        let mut builder = BrowserConfig::builder();
+        if !self.headless {
+            builder = builder.with_head();
+        }
+        if self.no_sandbox {
+            builder = builder.no_sandbox();
+        }
+        if let Some(path) = &self.executable {
+            builder = builder.chrome_executable(path);
+        }
+        if let Some((width, height)) = request.window_size {
+            builder = builder.window_size(width, height);
+        }
+        builder.build().map_err(RuntimeError::WebViewUnavailable)
+    }
+
+    /// 页面加载前的准备：User-Agent、请求头、Cookie、注入脚本
+    async fn prepare(browser: &Browser, page: &Page, request: &WebViewRequest) -> Result<()> {
+        if let Some(ua) = &request.user_agent {
+            page.set_user_agent(ua.as_str())
+                .await
+                .map_err(webview_error)?;
+        }
+        if !request.initial_headers.is_empty() {
+            let headers = Headers::new(serde_json::json!(request.initial_headers));
+            page.execute(SetExtraHttpHeadersParams::new(headers))
+                .await
+                .map_err(webview_error)?;
+        }
+        if !request.initial_cookies.is_empty() {
+            let cookies = request
+                .initial_cookies
+                .iter()
+                .map(|(name, value)| {
+                    let mut cookie = CookieParam::new(name, value);
+                    cookie.url = Some(request.url.clone());
+                    cookie
+                })
+                .collect();
+            browser.set_cookies(cookies).await.map_err(webview_error)?;
+        }
+        if let Some(script) = &request.inject_script {
+            page.evaluate_on_new_document(script.as_str())
+                .await
+                .map_err(webview_error)?;
+        }
+        Ok(())
+    }
+
+    /// 打开页面并等待 success_check 通过
+    ///
+    /// 未配置 success_check 时页面加载完成即视为成功
+    async fn run(browser: &Browser, request: &WebViewRequest) -> Result<WebViewResponse> {
+        let page = browser
+            .new_page("about:blank")
+            .await
+            .map_err(webview_error)?;
+        Self::prepare(browser, &page, request).await?;
+        page.goto(request.url.as_str())
+            .await
+            .map_err(webview_error)?;
+
+        if let Some(check) = &request.success_check {
+            loop {
+                // 页面跳转期间执行上下文可能失效，出错视为未通过
+                let passed = match page.evaluate(as_expression(check)).await {
+                    Ok(result) => result.into_value::<bool>().unwrap_or(false),
+                    Err(_) => false,
+                };
+                if passed {
+                    break;
+                }
+                tokio::time::sleep(request.check_interval).await;
+            }
+        }
+
+        let mut response = WebViewResponse::success()
+            .with_cookies(Self::extract_cookies(&page, request.extract_cookies.as_deref()).await?);
+        if !request.extract_local_storage.is_empty() {
+            response = response.with_local_storage(
+                Self::extract_local_storage(&page, &request.extract_local_storage).await?,
+            );
+        }
+        if let Ok(Some(url)) = page.url().await {
+            response = response.with_final_url(url);
+        }
+        if let Some(script) = &request.finish_script {
+            let result = page
+                .evaluate(as_expression(script))
+                .await
+                .map_err(webview_error)?;
+            let result = match result.value() {
+                Some(serde_json::Value::String(s)) => s.clone(),
+                Some(value) => value.to_string(),
+                None => String::new(),
+            };
+            response = response.with_script_result(result);
+        }
+        response.html = page.content().await.ok();
+        Ok(response)
+    }
+
+    /// 提取 localStorage 中指定键的值，不存在的键会被忽略
+    async fn extract_local_storage(
+        page: &Page,
+        keys: &[String],
+    ) -> Result<HashMap<String, String>> {
+        let script = format!(
+            "(() => {{ const keys = {}; const out = {{}}; \
+             for (const k of keys) {{ const v = localStorage.getItem(k); if (v !== null) out[k] = v; }} \
+             return out; }})()",
+            serde_json::json!(keys)
+        );
+        page.evaluate(script)
+            .await
+            .map_err(webview_error)?
+            .into_value()
+            .map_err(|e| RuntimeError::WebViewError(format!("解析 localStorage 失败: {}", e)))
+    }
+
+    /// 提取 Cookie，`names` 为 None 时返回全部
+    async fn extract_cookies(
+        page: &Page,
+        names: Option<&[String]>,
+    ) -> Result<HashMap<String, String>> {
+        let cookies = page.get_cookies().await.map_err(webview_error)?;
+        Ok(cookies
+            .into_iter()
+            .filter(|c| names.is_none_or(|names| names.contains(&c.name)))
+            .map(|c| (c.name, c.value))
+            .collect())
+    }
+}
+
+#[async_trait]
+impl WebViewProvider for ChromiumWebViewProvider {
+    async fn open(&self, request: WebViewRequest) -> Result<WebViewResponse> {
+        let config = self.browser_config(&request)?;
+        let (mut browser, mut handler) = Browser::launch(config)
+            .await
+            .map_err(|e| RuntimeError::WebViewUnavailable(e.to_string()))?;
+
+        // 驱动 CDP 事件循环
+        let handler_task = tokio::spawn(async move {
+            while let Some(event) = handler.next().await {
+                if event.is_err() {
+                    break;
+                }
+            }
+        });
+
+        let response = match tokio::time::timeout(request.timeout, Self::run(&browser, &request))
+            .await
+        {
+            Ok(Ok(response)) => response,
+            Ok(Err(e)) => WebViewResponse::failure(WebViewCloseReason::Error, Some(e.to_string())),
+            Err(_) => WebViewResponse::failure(WebViewCloseReason::Timeout, None),
+        };
+
+        let _ = browser.close().await;
+        let _ = browser.wait().await;
+        handler_task.abort();
+
+        Ok(response)
+    }
+
+    fn supports_headless(&self) -> bool {
+        self.headless
+    }
+
+    fn name(&self) -> &str {
+        "ChromiumWebViewProvider"
+    }
+}
+
+/// 将脚本包装为可求值的表达式
+///
+/// 检测/完成脚本常以 `return` 返回结果，含顶层 `return` 语句时需包装为立即执行函数
+fn as_expression(script: &str) -> String {
+    if has_top_level_return(script) {
+        format!("(() => {{ {} }})()", script)
+    } else {
+        script.to_string()
+    }
+}
+
+/// 脚本是否含顶层 `return` 语句
+///
+/// 跳过字符串、模板字符串与注释，只识别函数体外的完整 `return` 标识符，
+/// `returnUrl`、`"return"` 及内部函数中的 `return` 不算
+fn has_top_level_return(script: &str) -> bool {
+    let bytes = script.as_bytes();
+    let is_ident = |b: u8| b.is_ascii_alphanumeric() || b == b'_' || b == b'$';
+    // 每层花括号是否为函数体
+    let mut braces: Vec<bool> = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            quote @ (b'\'' | b'"' | b'`') => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != quote {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = script[i + 2..]
+                    .find("*/")
+                    .map_or(bytes.len(), |end| i + 2 + end + 1);
+            }
+            b'{' => braces.push(opens_function_body(&script[..i])),
+            b'}' => {
+                braces.pop();
+            }
+            b if is_ident(b) => {
+                let start = i;
+                while i < bytes.len() && is_ident(bytes[i]) {
+                    i += 1;
+                }
+                if &script[start..i] == "return"
+                    && !script[..start].trim_end().ends_with('.')
+                    && !braces.contains(&true)
+                {
+                    return true;
+                }
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    false
+}
+
+/// 位于 `before` 之后的 `{` 是否开启函数体
+///
+/// 箭头函数 `=> {` 与 `name(...) {` 视为函数体，`if`/`for`/`while` 等语句块及对象字面量不是
+fn opens_function_body(before: &str) -> bool {
+    let before = before.trim_end();
+    if before.ends_with("=>") {
+        return true;
+    }
+    let Some(head) = before.strip_suffix(')') else {
+        return false;
+    };
+
+    let mut depth = 1;
+    let Some(open) = head.rfind(|c: char| {
+        match c {
+            ')' => depth += 1,
+            '(' => depth -= 1,
+            _ => {}
+        }
+        depth == 0
+    }) else {
+        return false;
+    };
+    let keyword = head[..open]
+        .trim_end()
+        .rsplit(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '$'))
+        .next()
+        .unwrap_or_default();
+    !matches!(
+        keyword,
+        "if" | "for" | "while" | "switch" | "catch" | "with"
+    )
+}
+
+/// 转换 CDP 错误
+fn webview_error(e: chromiumoxide::error::CdpError) -> RuntimeError {
+    RuntimeError::WebViewError(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_top_level_return() {
+        assert!(has_top_level_return("return document.title"));
+        assert!(has_top_level_return(
+            "const a = 1;\nif (a) { x() }\nreturn a"
+        ));
+        assert!(has_top_level_return(
+            "if (ready()) { return true } else { return false }"
+        ));
+    }
+
+    #[test]
+    fn ignores_return_in_identifiers_strings_and_functions() {
+        assert!(!has_top_level_return("window.returnUrl"));
+        assert!(!has_top_level_return("document.title.includes('return')"));
+        assert!(!has_top_level_return("`${a} return` // return"));
+        assert!(!has_top_level_return(
+            "[1, 2].map(function (x) { return x * 2 })"
+        ));
+        assert!(!has_top_level_return("[1, 2].map((x) => { return x * 2 })"));
+        assert_eq!(as_expression("location.href"), "location.href");
+    }
+}
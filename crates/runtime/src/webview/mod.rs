@@ -7,6 +7,8 @@
 //! Runtime 不直接依赖任何 WebView 库（如 wry、tauri），
 //! 而是通过 trait 抽象，让调用方注入具体实现。
 //!
+//! 启用 `headless` feature 后提供基于 Chromium 的默认实现 `ChromiumWebViewProvider`。
+//!
 //! ## 使用示例
 //!
 //! ```rust,ignore
@@ -26,10 +28,14 @@
 //!     .build()?;
 //! ```
 
+#[cfg(feature = "headless")]
+mod chromium;
 mod provider;
 mod request;
 mod response;
 
+#[cfg(feature = "headless")]
+pub use chromium::ChromiumWebViewProvider;
 pub use provider::*;
 pub use request::*;
 pub use response::*;
@@ -44,6 +44,9 @@ pub struct WebViewRequest {
     /// 需要提取的 Cookie 名称
     pub extract_cookies: Option<Vec<String>>,
 
+    /// 需要提取的 localStorage 键名
+    pub extract_local_storage: Vec<String>,
+
     /// 窗口尺寸
     pub window_size: Option<(u32, u32)>,
 
@@ -66,6 +69,7 @@ impl Default for WebViewRequest {
             check_interval: Duration::from_millis(500),
             finish_script: None,
             extract_cookies: None,
+            extract_local_storage: Vec::new(),
             window_size: None,
             allow_redirects: true,
         }
@@ -123,6 +127,12 @@ impl WebViewRequest {
         self
     }
 
+    /// 设置需要提取的 localStorage 键名
+    pub fn with_extract_local_storage(mut self, keys: Vec<String>) -> Self {
+        self.extract_local_storage = keys;
+        self
+    }
+
     /// 添加初始 Cookie
     pub fn with_cookie(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
         self.initial_cookies.insert(name.into(), value.into());
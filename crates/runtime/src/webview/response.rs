@@ -1,5 +1,6 @@
 //! WebView 响应类型
 
+use serde_json::Value;
 use std::collections::HashMap;
 
 /// WebView 响应
@@ -16,6 +17,9 @@ pub struct WebViewResponse {
     /// 提取的 Cookie
     pub cookies: HashMap<String, String>,
 
+    /// 提取的 localStorage 数据
+    pub local_storage: HashMap<String, String>,
+
     /// 提取的 Header（如果有）
     pub headers: HashMap<String, String>,
 
@@ -72,6 +76,12 @@ impl WebViewResponse {
         self
     }
 
+    /// 设置 localStorage 数据
+    pub fn with_local_storage(mut self, local_storage: HashMap<String, String>) -> Self {
+        self.local_storage = local_storage;
+        self
+    }
+
     /// 设置 Header
     pub fn with_headers(mut self, headers: HashMap<String, String>) -> Self {
         self.headers = headers;
@@ -89,4 +99,34 @@ impl WebViewResponse {
         self.script_result = Some(result.into());
         self
     }
+
+    /// 将 finish_script 返回的 JSON 对象解析为键值对
+    ///
+    /// 字符串值原样保留，数字、布尔、数组等其他值转为 JSON 文本，null 值忽略；
+    /// 没有脚本结果时返回空表，结果不是 JSON 对象时记录警告并返回空表
+    pub fn script_result_map(&self) -> HashMap<String, String> {
+        let Some(result) = &self.script_result else {
+            return HashMap::new();
+        };
+        let object = match serde_json::from_str::<Value>(result) {
+            Ok(Value::Object(object)) => object,
+            Ok(other) => {
+                tracing::warn!("finish_script 结果不是 JSON 对象，已忽略: {}", other);
+                return HashMap::new();
+            }
+            Err(e) => {
+                tracing::warn!("finish_script 结果不是有效的 JSON，已忽略: {}", e);
+                return HashMap::new();
+            }
+        };
+
+        object
+            .into_iter()
+            .filter_map(|(key, value)| match value {
+                Value::Null => None,
+                Value::String(s) => Some((key, s)),
+                other => Some((key, other.to_string())),
+            })
+            .collect()
+    }
 }
@@ -0,0 +1,370 @@
+//! 人机验证集成测试
+
+mod common;
+
+use async_trait::async_trait;
+use common::{MockServer, rule_for};
+use crawler_runtime::{
+    Result,
+    challenge::{
+        ChallengeCredentials,
+        ChallengeManager,
+        ChallengeType,
+        CredentialsCache,
+        CredentialsRefresher,
+        ResponseContext,
+    },
+    crawler::CrawlerRuntime,
+    http::HttpClient,
+    util::ProgressListener,
+    webview::noop_provider,
+};
+use crawler_schema::config::{ChallengeConfig, HttpConfig};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+const CHALLENGE: &str = r#"
+[challenge]
+detectors = [{ type = "custom", status_codes = [403] }]
+handler = { type = "retry", delay_ms = 0, max_retries = 1 }
+max_attempts = 1
+"#;
+
+#[derive(Debug, Default)]
+struct Recorder(Mutex<Vec<ChallengeType>>);
+
+impl ProgressListener for Recorder {
+    fn on_challenge(&self, challenge_type: &ChallengeType) {
+        self.0.lock().unwrap().push(challenge_type.clone());
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn challenge_manager_reports_to_progress_listener() {
+    let server = MockServer::html("ok");
+    let recorder = Arc::new(Recorder::default());
+    let runtime = CrawlerRuntime::builder(rule_for(&server, CHALLENGE))
+        .with_progress_listener(recorder.clone())
+        .build()
+        .unwrap();
+
+    let manager = runtime
+        .runtime_ctx()
+        .challenge_manager()
+        .expect("应创建验证管理器");
+    let url = format!("{}/book/1", server.url);
+    let response = ResponseContext {
+        status_code: 403,
+        headers: HashMap::new(),
+        body: String::new(),
+        final_url: url.clone(),
+    };
+    let _ = manager.handle(&url, response).await;
+
+    assert_eq!(recorder.0.lock().unwrap().len(), 1);
+}
+
+#[test]
+fn no_challenge_manager_without_config() {
+    let server = MockServer::html("ok");
+    let runtime = CrawlerRuntime::builder(rule_for(&server, ""))
+        .build()
+        .unwrap();
+    assert!(runtime.runtime_ctx().challenge_manager().is_none());
+}
+
+#[test]
+fn credentials_below_threshold_need_refresh() {
+    let credentials = ChallengeCredentials::new().with_ttl(60);
+
+    assert!(credentials.needs_refresh(Duration::from_secs(120)));
+    assert!(!credentials.needs_refresh(Duration::from_secs(10)));
+    assert!(!ChallengeCredentials::new().needs_refresh(Duration::from_secs(120)));
+}
+
+/// 返回带新 Cookie 的凭证
+struct Refresher;
+
+#[async_trait]
+impl CredentialsRefresher for Refresher {
+    async fn refresh(
+        &self,
+        _domain: &str,
+        _current: &ChallengeCredentials,
+    ) -> Result<ChallengeCredentials> {
+        Ok(ChallengeCredentials::new().with_cookie("token", "new"))
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn expiring_credentials_are_refreshed() {
+    let cache = Arc::new(CredentialsCache::new());
+    let threshold = Duration::from_secs(120);
+    cache
+        .set(
+            "a.com",
+            ChallengeCredentials::new()
+                .with_cookie("token", "old")
+                .with_ttl(60),
+        )
+        .await;
+    cache
+        .set("b.com", ChallengeCredentials::new().with_ttl(3600))
+        .await;
+    assert!(cache.needs_refresh("a.com", threshold).await);
+    assert!(!cache.needs_refresh("b.com", threshold).await);
+    assert!(!cache.needs_refresh("c.com", threshold).await);
+
+    let config: ChallengeConfig = toml::from_str(
+        r#"
+detectors = [{ type = "cloudflare" }]
+handler = { type = "retry" }
+cache_duration = 3600
+"#,
+    )
+    .unwrap();
+    let manager =
+        ChallengeManager::new(config, noop_provider()).with_credentials_cache(cache.clone());
+    assert_eq!(manager.refresh_expiring(threshold, &Refresher).await, 1);
+
+    let refreshed = cache.get("a.com").await.unwrap();
+    assert_eq!(refreshed.cookies["token"], "new");
+    assert!(!cache.needs_refresh("a.com", threshold).await);
+}
+
+/// 测试用凭证文件路径
+fn credentials_path(name: &str) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "crawler-credentials-{}-{}.json",
+        std::process::id(),
+        name
+    ));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn persistent_credentials_survive_restart() {
+    let path = credentials_path("restart");
+    let cache = CredentialsCache::persistent(&path).unwrap();
+    cache
+        .set(
+            "a.com",
+            ChallengeCredentials::new()
+                .with_cookie("cf_clearance", "abc")
+                .with_header("X-Token", "t")
+                .with_ttl(3600),
+        )
+        .await;
+    cache
+        .set("b.com", ChallengeCredentials::new().with_cookie("sid", "1"))
+        .await;
+    cache.remove("b.com").await;
+    drop(cache);
+
+    let reloaded = CredentialsCache::persistent(&path).unwrap();
+    let credentials = reloaded.get("a.com").await.unwrap();
+    assert_eq!(credentials.cookies["cf_clearance"], "abc");
+    assert_eq!(credentials.headers["X-Token"], "t");
+    assert_eq!(credentials.ttl_seconds, Some(3600));
+    assert!(!credentials.needs_refresh(Duration::from_secs(60)));
+    assert!(reloaded.get("b.com").await.is_none());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn expired_credentials_are_dropped_on_load() {
+    let path = credentials_path("expired");
+    std::fs::write(
+        &path,
+        r#"{
+  "old.com": { "cookies": { "sid": "1" }, "obtained_at": 1000, "ttl_seconds": 60 },
+  "keep.com": { "cookies": { "sid": "2" } }
+}"#,
+    )
+    .unwrap();
+
+    let cache = CredentialsCache::persistent(&path).unwrap();
+    assert!(cache.get("old.com").await.is_none());
+    assert_eq!(cache.get("keep.com").await.unwrap().cookies["sid"], "2");
+    assert!(
+        CredentialsCache::persistent(credentials_path("missing"))
+            .unwrap()
+            .get("keep.com")
+            .await
+            .is_none()
+    );
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn cached_credentials_are_injected_into_requests() {
+    let server = MockServer::html("ok");
+    let cache = Arc::new(CredentialsCache::new());
+    cache
+        .set(
+            "127.0.0.1",
+            ChallengeCredentials::new()
+                .with_cookie("cf_clearance", "abc")
+                .with_header("User-Agent", "solver-ua")
+                .with_ttl(3600),
+        )
+        .await;
+    let config: HttpConfig = toml::from_str(
+        r#"
+user_agent = "rule-ua"
+[request]
+headers = { Cookie = "sid=1; cf_clearance=old" }
+"#,
+    )
+    .unwrap();
+    let client = HttpClient::new(config)
+        .unwrap()
+        .with_credentials_provider(cache);
+
+    client.get(&server.url).await.unwrap();
+    let request = &server.requests()[0];
+    assert_eq!(request.header("cookie"), Some("sid=1; cf_clearance=abc"));
+    assert_eq!(request.header("user-agent"), Some("solver-ua"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn challenge_manager_provides_credentials_per_host() {
+    let server = MockServer::html("ok");
+    let cache = Arc::new(CredentialsCache::new());
+    cache
+        .set(
+            "other.com",
+            ChallengeCredentials::new().with_cookie("cf_clearance", "x"),
+        )
+        .await;
+    let config: ChallengeConfig = toml::from_str(
+        r#"
+detectors = [{ type = "cloudflare" }]
+handler = { type = "retry" }
+"#,
+    )
+    .unwrap();
+    let manager = Arc::new(
+        ChallengeManager::new(config, noop_provider()).with_credentials_cache(cache.clone()),
+    );
+    let client = HttpClient::new(HttpConfig::default())
+        .unwrap()
+        .with_credentials_provider(manager);
+
+    // 其他域名的凭证不会注入
+    client.get(&server.url).await.unwrap();
+    assert_eq!(server.requests()[0].header("cookie"), None);
+
+    cache
+        .set(
+            "127.0.0.1",
+            ChallengeCredentials::new().with_cookie("cf_clearance", "abc"),
+        )
+        .await;
+    client.get(&server.url).await.unwrap();
+    assert_eq!(
+        server.requests()[1].header("cookie"),
+        Some("cf_clearance=abc")
+    );
+}
+
+/// 只启用 Cloudflare 检测的验证管理器
+fn cloudflare_manager() -> ChallengeManager {
+    let config: ChallengeConfig = toml::from_str(
+        r#"
+detectors = [{ type = "cloudflare" }]
+handler = { type = "retry" }
+"#,
+    )
+    .unwrap();
+    ChallengeManager::new(config, noop_provider())
+}
+
+fn page(status_code: u16, body: &str) -> ResponseContext {
+    ResponseContext::new(
+        status_code,
+        HashMap::new(),
+        body.to_string(),
+        "https://a.com/".to_string(),
+    )
+}
+
+#[test]
+fn turnstile_widget_params_are_extracted() {
+    let body = r#"<html><script src="https://challenges.cloudflare.com/turnstile/v0/api.js"></script>
+<button data-action="other">x</button>
+<div class="cf-turnstile" data-sitekey="0x4AAAAAAAwidget" data-action="login" data-cdata="sess-1"></div>
+</html>"#;
+
+    let result = cloudflare_manager().detect(&page(403, body));
+    assert_eq!(
+        result.challenge_type,
+        Some(ChallengeType::CloudflareTurnstile)
+    );
+    assert_eq!(result.extra_info["site_key"], "0x4AAAAAAAwidget");
+    assert_eq!(result.extra_info["action"], "login");
+    assert_eq!(result.extra_info["cdata"], "sess-1");
+}
+
+#[test]
+fn turnstile_render_options_are_extracted() {
+    let body = r#"<div id="box"></div><script>
+turnstile.render('#box', { sitekey: '0x4AAAAAAArender', action: "search", cData: 'c-2' });
+</script><!-- challenges.cloudflare.com/turnstile -->"#;
+
+    let result = cloudflare_manager().detect(&page(200, body));
+    assert_eq!(
+        result.challenge_type,
+        Some(ChallengeType::CloudflareTurnstile)
+    );
+    assert_eq!(result.extra_info["site_key"], "0x4AAAAAAArender");
+    assert_eq!(result.extra_info["action"], "search");
+    assert_eq!(result.extra_info["cdata"], "c-2");
+
+    let result = cloudflare_manager().detect(&page(403, "<title>Just a moment...</title>"));
+    assert_eq!(result.challenge_type, Some(ChallengeType::CloudflareJs));
+    assert!(!result.extra_info.contains_key("site_key"));
+}
+
+#[test]
+fn cf_mitigated_header_is_detected_without_body() {
+    let manager = cloudflare_manager();
+    let headers = HashMap::from([
+        ("CF-Mitigated".to_string(), "challenge".to_string()),
+        ("cf-ray".to_string(), "8abc-HKG".to_string()),
+    ]);
+
+    // 即使状态码为 200 也能仅凭 cf-mitigated 命中
+    let result = manager.detect_headers_only(200, &headers);
+    assert!(result.detected);
+    assert_eq!(result.challenge_type, Some(ChallengeType::CloudflareJs));
+    assert_eq!(result.extra_info["cf_ray"], "8abc-HKG");
+
+    let cdn_only = HashMap::from([("cf-cache-status".to_string(), "HIT".to_string())]);
+    assert!(!manager.detect_headers_only(200, &cdn_only).detected);
+    assert!(manager.detect_headers_only(503, &cdn_only).detected);
+    assert!(!manager.detect_headers_only(503, &HashMap::new()).detected);
+}
+
+#[test]
+fn header_only_detection_skips_body_based_detectors() {
+    let config: ChallengeConfig = toml::from_str(
+        r#"
+detectors = [
+    { type = "custom", body_patterns = ["verify"] },
+    { type = "custom", status_codes = [429] },
+]
+handler = { type = "retry" }
+"#,
+    )
+    .unwrap();
+    let manager = ChallengeManager::new(config, noop_provider());
+
+    assert!(manager.detect_headers_only(429, &HashMap::new()).detected);
+    assert!(!manager.detect_headers_only(403, &HashMap::new()).detected);
+    assert!(manager.detect(&page(403, "please verify")).detected);
+}
@@ -0,0 +1,277 @@
+//! 集成测试公用工具：最小规则、字段提取与本地 HTTP 服务
+
+#![allow(dead_code)]
+
+use crawler_runtime::{
+    Result,
+    context::{FlowContext, RuntimeContext},
+    extractor::{ExtractEngine, ExtractValueData, SharedValue},
+};
+use crawler_schema::{core::CrawlerRule, extract::FieldExtractor};
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{
+        Arc,
+        Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+/// 最小可用规则，`{{ base_url }}` 由测试注入
+pub const BASE_RULE: &str = r##"
+[meta]
+name = "test"
+author = "test"
+version = "1.0.0"
+spec_version = "1.0.0"
+domain = "127.0.0.1"
+media_type = "book"
+script_engine = "rhai"
+
+[search]
+url = "{{ base_url }}/search?kw={{ keyword }}"
+
+[search.list]
+steps = [{ css = { expr = "li", all = true } }]
+
+[search.fields.title]
+steps = [{ css = "a" }, { attr = "text" }]
+
+[search.fields.url]
+steps = [{ css = "a" }, { attr = "href" }]
+
+[detail]
+url = "{{ detail_url }}"
+
+[detail.fields]
+media_type = "book"
+
+[detail.fields.title]
+steps = [{ css = "h1" }, { attr = "text" }]
+
+[detail.fields.author]
+steps = [{ css = ".author" }, { attr = "text" }]
+
+[detail.fields.chapters]
+list.steps = [{ css = { expr = "#chapters a", all = true } }]
+title.steps = [{ attr = "text" }]
+url.steps = [{ attr = "href" }]
+"##;
+
+/// 符合最小规则详情字段的页面
+pub const DETAIL_PAGE: &str = r#"<h1>title</h1><p class="author">author</p>
+<div id="chapters"><a href="/c/1">c1</a><a href="/c/2">c2</a></div>"#;
+
+/// 解析最小规则并追加 `extra` 中的 TOML 片段
+pub fn rule(extra: &str) -> CrawlerRule {
+    toml::from_str(&format!("{}\n{}", BASE_RULE, extra)).expect("测试规则无效")
+}
+
+/// 以本地服务地址作为 `domain`（即 `{{ base_url }}`）的规则
+pub fn rule_for(server: &MockServer, extra: &str) -> CrawlerRule {
+    let mut rule = rule(extra);
+    rule.meta.domain = server.url.clone();
+    rule
+}
+
+/// 创建运行时上下文
+pub fn runtime_context(rule: CrawlerRule) -> Arc<RuntimeContext> {
+    Arc::new(RuntimeContext::new(rule).expect("创建运行时上下文失败"))
+}
+
+/// 解析字段提取规则（TOML 表形式，如 `steps = [{ css = "a" }]`）
+pub fn field(toml_src: &str) -> FieldExtractor {
+    toml::from_str(toml_src).expect("字段提取规则无效")
+}
+
+/// 以 HTML 为输入提取字段
+pub fn extract_html(
+    runtime: &Arc<RuntimeContext>,
+    flow: &FlowContext,
+    field_src: &str,
+    html: &str,
+) -> Result<SharedValue> {
+    let input = ExtractValueData::Html(Arc::from(html));
+    ExtractEngine::extract_field(&field(field_src), &input, runtime, flow)
+}
+
+/// 本地 HTTP 服务收到的请求
+#[derive(Debug, Clone)]
+pub struct Request {
+    /// 请求方法
+    pub method: String,
+    /// 路径（含查询）
+    pub path: String,
+    /// 请求头（名称小写）
+    pub headers: Vec<(String, String)>,
+    /// 请求体
+    pub body: String,
+}
+
+impl Request {
+    /// 按名称获取请求头的所有值
+    pub fn header_values(&self, name: &str) -> Vec<&str> {
+        let name = name.to_ascii_lowercase();
+        self.headers
+            .iter()
+            .filter(|(k, _)| *k == name)
+            .map(|(_, v)| v.as_str())
+            .collect()
+    }
+
+    /// 按名称获取第一个请求头
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.header_values(name).into_iter().next()
+    }
+}
+
+/// 本地 HTTP 服务的响应
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    /// 200 HTML 响应
+    pub fn html(body: impl Into<String>) -> Self {
+        Self {
+            status: 200,
+            headers: vec![("Content-Type".into(), "text/html; charset=utf-8".into())],
+            body: body.into().into_bytes(),
+        }
+    }
+
+    /// 指定状态码的空响应
+    pub fn status(status: u16) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: Vec::new(),
+        }
+    }
+
+    /// 不返回任何内容直接断开连接
+    pub fn abort() -> Self {
+        Self::status(0)
+    }
+
+    /// 添加响应头
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+}
+
+type Handler = dyn Fn(&Request) -> Response + Send + Sync;
+
+/// 在后台线程运行的本地 HTTP 服务，每个连接处理一个请求
+pub struct MockServer {
+    /// 服务地址，如 `http://127.0.0.1:12345`
+    pub url: String,
+    requests: Arc<Mutex<Vec<Request>>>,
+    hits: Arc<AtomicUsize>,
+}
+
+impl MockServer {
+    /// 启动服务
+    pub fn start(handler: impl Fn(&Request) -> Response + Send + Sync + 'static) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let hits = Arc::new(AtomicUsize::new(0));
+        let handler: Arc<Handler> = Arc::new(handler);
+
+        let (log, counter) = (requests.clone(), hits.clone());
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let (handler, log, counter) = (handler.clone(), log.clone(), counter.clone());
+                std::thread::spawn(move || {
+                    if let Some(request) = read_request(&stream) {
+                        counter.fetch_add(1, Ordering::SeqCst);
+                        let response = handler(&request);
+                        log.lock().unwrap().push(request);
+                        write_response(stream, &response);
+                    }
+                });
+            }
+        });
+
+        Self {
+            url,
+            requests,
+            hits,
+        }
+    }
+
+    /// 总是返回同一个 HTML 页面的服务
+    pub fn html(body: &'static str) -> Self {
+        Self::start(move |_| Response::html(body))
+    }
+
+    /// 已处理的请求数
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::SeqCst)
+    }
+
+    /// 已收到的请求
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+fn read_request(stream: &TcpStream) -> Option<Request> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line).ok()?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next()?.to_string();
+    let path = parts.next()?.to_string();
+
+    let mut headers = Vec::new();
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).ok()?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+        }
+    }
+
+    let length = headers
+        .iter()
+        .find(|(k, _)| k == "content-length")
+        .and_then(|(_, v)| v.parse::<usize>().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).ok()?;
+
+    Some(Request {
+        method,
+        path,
+        headers,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
+
+fn write_response(mut stream: TcpStream, response: &Response) {
+    if response.status == 0 {
+        return;
+    }
+    let mut head = format!(
+        "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n",
+        response.status,
+        response.body.len()
+    );
+    for (name, value) in &response.headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    head.push_str("\r\n");
+    let _ = stream.write_all(head.as_bytes());
+    let _ = stream.write_all(&response.body);
+}
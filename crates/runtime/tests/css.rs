@@ -0,0 +1,139 @@
+//! CSS 选择器集成测试
+
+mod common;
+
+use common::{extract_html, rule, runtime_context};
+use crawler_runtime::{Result, context::FlowContext, extractor::ExtractValueData};
+
+const PAGE: &str = r#"<div class="a"><span>one</span></div>
+<div class="b"><span>two</span></div>
+<p>three</p><a>x one</a><a>y</a>"#;
+
+/// 带属性的链接
+const LINKS: &str = r#"<a href="/book/1" data-id="1">b1</a><a href="/author/2">a2</a>
+<a href="https://x.com/book/3.html" data-id="3">b3</a><a href="/list.html">l4</a>"#;
+
+/// 在 `PAGE` 中选择所有匹配元素并返回其文本
+fn texts(selector: &str) -> Result<Vec<String>> {
+    texts_in(PAGE, selector)
+}
+
+/// 选择所有匹配元素并返回其文本
+fn texts_in(page: &str, selector: &str) -> Result<Vec<String>> {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let field = format!(
+        "steps = [{{ css = {{ expr = '{}', all = true }} }}, {{ attr = \"text\" }}]",
+        selector
+    );
+    let value = extract_html(&runtime, &flow, &field, page)?;
+    Ok(match value.as_ref() {
+        ExtractValueData::Array(items) => items
+            .iter()
+            .map(|item| item.as_str().unwrap_or_default().to_string())
+            .collect(),
+        other => vec![other.as_str().unwrap_or_default().to_string()],
+    })
+}
+
+#[test]
+fn contains_applies_to_its_own_group() {
+    assert_eq!(texts("a:contains(one), p").unwrap(), ["three", "x one"]);
+    assert_eq!(texts("p, a:contains(one)").unwrap(), ["three", "x one"]);
+}
+
+#[test]
+fn contains_groups_are_filtered_independently() {
+    assert_eq!(
+        texts("div span:contains(two), a:contains(y)").unwrap(),
+        ["two", "y"]
+    );
+}
+
+#[test]
+fn contains_before_combinator_is_rejected() {
+    assert!(texts("div:contains(one) > span").is_err());
+    assert!(texts("div:contains(one) > span:contains(one)").is_err());
+}
+
+#[test]
+fn contains_inside_has_with_groups() {
+    assert_eq!(texts("div:has(span:contains(two), b)").unwrap(), ["two"]);
+}
+
+#[test]
+fn attribute_prefix_selects_matching_links() {
+    assert_eq!(texts_in(LINKS, r#"a[href^="/book/"]"#).unwrap(), ["b1"]);
+}
+
+#[test]
+fn attribute_operators() {
+    assert_eq!(texts_in(LINKS, "a[data-id]").unwrap(), ["b1", "b3"]);
+    assert_eq!(
+        texts_in(LINKS, r#"a[href$=".html"]"#).unwrap(),
+        ["b3", "l4"]
+    );
+    assert_eq!(texts_in(LINKS, r#"a[href*="book"]"#).unwrap(), ["b1", "b3"]);
+    assert_eq!(
+        texts_in(LINKS, "a[data-id]:contains(3), a[href^=\"/author\"]").unwrap(),
+        ["a2", "b3"]
+    );
+}
+
+/// 播放线路列表，部分 div 含 `.play` 子元素
+const LINES: &str = r#"<div class="line"><h3>线路一</h3><a class="play">1</a></div>
+<div class="line"><h3>广告</h3></div>
+<div class="line vip"><h3>线路二</h3><p><a class="play">2</a></p></div>"#;
+
+#[test]
+fn has_selects_parents_with_matching_children() {
+    assert_eq!(
+        texts_in(LINES, "div:has(.play) > h3").unwrap(),
+        ["线路一", "线路二"]
+    );
+    assert_eq!(
+        texts_in(LINES, "div:has(> .play) > h3").unwrap(),
+        ["线路一"]
+    );
+}
+
+#[test]
+fn not_excludes_matching_elements() {
+    assert_eq!(
+        texts_in(LINES, "div:not(.vip) > h3").unwrap(),
+        ["线路一", "广告"]
+    );
+    assert_eq!(
+        texts_in(LINES, "div:not(:has(.play)) > h3").unwrap(),
+        ["广告"]
+    );
+}
+
+#[test]
+fn has_and_not_with_contains_are_filtered_at_runtime() {
+    assert_eq!(
+        texts_in(LINES, "div:has(h3:contains(线路))").unwrap(),
+        ["线路一1", "线路二2"]
+    );
+    assert_eq!(
+        texts_in(LINES, "div.line:not(:has(h3:contains(广告)))").unwrap(),
+        ["线路一1", "线路二2"]
+    );
+    assert_eq!(
+        texts_in(LINES, "div:not(:contains(线路))").unwrap(),
+        ["广告"]
+    );
+    // 运行时筛选只能作用于最后一个复合选择器
+    let err = texts_in(LINES, "div:has(h3:contains(线路)) > h3")
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("last compound selector"), "{}", err);
+}
+
+#[test]
+fn jquery_pseudo_classes_suggest_alternatives() {
+    let err = texts_in(LINES, "div:first").unwrap_err().to_string();
+    assert!(err.contains("index step"), "{}", err);
+    let err = texts_in(LINES, "div:even").unwrap_err().to_string();
+    assert!(err.contains(":nth-child(odd)"), "{}", err);
+}
@@ -0,0 +1,208 @@
+//! 详情流程集成测试
+
+mod common;
+
+use common::{DETAIL_PAGE, MockServer, Response, rule_for};
+use crawler_runtime::{crawler::CrawlerRuntime, flow::detail::DetailResponse};
+use crawler_schema::{config::MediaType, fields::DetailFields};
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+/// 以 `fields` 替换最小规则的详情字段
+fn runtime(server: &MockServer, fields: &str) -> CrawlerRuntime {
+    let mut rule = rule_for(server, "");
+    rule.detail.fields = toml::from_str::<DetailFields>(fields).expect("详情字段无效");
+    CrawlerRuntime::new(rule, None).unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn video_detail_extracts_play_lines() {
+    let server = MockServer::html(
+        r#"<h1>movie</h1><p class="director">someone</p>
+<div class="line"><b>line1</b><a href="/e/1">ep1</a><a href="/e/2">ep2</a></div>
+<div class="line"><b>line2</b><a href="/e/3">ep1</a></div>"#,
+    );
+    let runtime = runtime(
+        &server,
+        r#"
+media_type = "video"
+title.steps = [{ css = "h1" }, { attr = "text" }]
+director.steps = [{ css = ".director" }, { attr = "text" }]
+
+[play_lines]
+lines.steps = [{ css = { expr = ".line", all = true } }]
+line_name.steps = [{ css = "b" }, { attr = "text" }]
+episodes.list.steps = [{ css = { expr = "a", all = true } }]
+episodes.name.steps = [{ attr = "text" }]
+episodes.url.steps = [{ attr = "href" }]
+"#,
+    );
+
+    let DetailResponse::Video(video) = runtime.detail(&server.url).await.unwrap() else {
+        panic!("应返回视频详情");
+    };
+    assert_eq!(video.title, "movie");
+    assert_eq!(video.director.as_deref(), Some("someone"));
+    assert_eq!(video.play_lines.len(), 2);
+    assert_eq!(video.play_lines[0].name, "line1");
+    assert_eq!(video.play_lines[0].episodes[1].url, "/e/2");
+    assert_eq!(video.play_lines[1].episodes.len(), 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn audio_detail_extracts_tracks() {
+    let server = MockServer::html(
+        r#"<h1>album</h1><p class="artist">singer</p>
+<ul><li><a href="/t/1">one</a><i>3:00</i></li><li><a href="/t/2">two</a></li></ul>"#,
+    );
+    let runtime = runtime(
+        &server,
+        r#"
+media_type = "audio"
+title.steps = [{ css = "h1" }, { attr = "text" }]
+artist.steps = [{ css = ".artist" }, { attr = "text" }]
+
+[tracks]
+list.steps = [{ css = { expr = "li", all = true } }]
+name.steps = [{ css = "a" }, { attr = "text" }]
+url.steps = [{ css = "a" }, { attr = "href" }]
+duration.steps = [{ css = "i" }, { attr = "text" }]
+"#,
+    );
+
+    let DetailResponse::Audio(audio) = runtime.detail(&server.url).await.unwrap() else {
+        panic!("应返回音频详情");
+    };
+    assert_eq!(audio.artist.as_deref(), Some("singer"));
+    assert_eq!(audio.tracks.len(), 2);
+    assert_eq!(audio.tracks[0].duration.as_deref(), Some("3:00"));
+    assert_eq!(audio.tracks[1].duration, None);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn manga_detail_keeps_chapters_as_a_line() {
+    let server = MockServer::html(
+        r#"<h1>comic</h1><p class="last">ch2</p>
+<div id="chapters"><a href="/c/1">ch1</a><a href="/c/2">ch2</a></div>"#,
+    );
+    let runtime = runtime(
+        &server,
+        r##"
+media_type = "manga"
+title.steps = [{ css = "h1" }, { attr = "text" }]
+last_chapter.steps = [{ css = ".last" }, { attr = "text" }]
+
+[chapters]
+list.steps = [{ css = { expr = "#chapters a", all = true } }]
+title.steps = [{ attr = "text" }]
+url.steps = [{ attr = "href" }]
+"##,
+    );
+
+    let response = runtime.detail(&server.url).await.unwrap();
+    let DetailResponse::Manga(manga) = &response else {
+        panic!("应返回漫画详情");
+    };
+    assert_eq!(manga.play_lines.len(), 1);
+    assert_eq!(manga.play_lines[0].episodes.len(), 2);
+    assert_eq!(manga.raw["last_chapter"], "ch2");
+
+    let item = response.into_item_detail(&server.url);
+    assert_eq!(item.title, "comic");
+    assert_eq!(item.latest.as_deref(), Some("ch2"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn missing_title_fails_typed_detail() {
+    let server = MockServer::html("<p>no title</p>");
+    let runtime = runtime(
+        &server,
+        r#"
+media_type = "video"
+title.steps = [{ css = "h1" }, { attr = "text" }]
+"#,
+    );
+
+    assert!(runtime.detail(&server.url).await.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn book_detail_maps_to_item_detail() {
+    let server = MockServer::html(
+        r#"<h1>斗破苍穹</h1><p class="author">天蚕土豆</p><p class="intro">简介</p>
+<span class="status">完结</span><a class="latest">第1623章</a><b>玄幻</b>"#,
+    );
+    let runtime = runtime(
+        &server,
+        r#"
+media_type = "book"
+title.steps = [{ css = "h1" }, { attr = "text" }]
+author.steps = [{ css = ".author" }, { attr = "text" }]
+intro.steps = [{ css = ".intro" }, { attr = "text" }]
+status.steps = [{ css = ".status" }, { attr = "text" }]
+last_chapter.steps = [{ css = ".latest" }, { attr = "text" }]
+category.steps = [{ css = "b" }, { attr = "text" }]
+"#,
+    );
+
+    let response = runtime.detail(&server.url).await.unwrap();
+    assert!(matches!(response, DetailResponse::Book(_)));
+    let item = response.into_item_detail(&server.url);
+    assert_eq!(item.media_type, MediaType::Book);
+    assert_eq!(item.url, server.url);
+    assert_eq!(item.title, "斗破苍穹");
+    assert_eq!(item.author.as_deref(), Some("天蚕土豆"));
+    assert_eq!(item.intro.as_deref(), Some("简介"));
+    assert_eq!(item.status.as_deref(), Some("完结"));
+    assert_eq!(item.latest.as_deref(), Some("第1623章"));
+    assert_eq!(item.tags, ["玄幻"]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn details_keep_order_and_isolate_failures() {
+    let (active, peak) = (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+    let (in_flight, max_seen) = (active.clone(), peak.clone());
+    let server = MockServer::start(move |request| {
+        let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        max_seen.fetch_max(now, Ordering::SeqCst);
+        std::thread::sleep(Duration::from_millis(50));
+        in_flight.fetch_sub(1, Ordering::SeqCst);
+        match request.path.as_str() {
+            "/missing" => Response::status(404),
+            "/untitled" => Response::html("<p>no title</p>"),
+            path => Response::html(DETAIL_PAGE.replace("title", &path[1..])),
+        }
+    });
+    let runtime = CrawlerRuntime::new(rule_for(&server, ""), None).unwrap();
+
+    let urls = ["/a", "/missing", "/b", "/untitled", "/c", "/d"]
+        .iter()
+        .map(|path| format!("{}{}", server.url, path))
+        .collect();
+    let results = runtime.details(urls, 2).await;
+
+    let titles: Vec<_> = results
+        .iter()
+        .map(|result| match result {
+            Ok(DetailResponse::Book(book)) => Some(book.title.as_str()),
+            Ok(other) => panic!("应返回书籍详情: {:?}", other),
+            Err(_) => None,
+        })
+        .collect();
+    assert_eq!(
+        titles,
+        [Some("a"), None, Some("b"), None, Some("c"), Some("d")]
+    );
+    assert_eq!(server.hits(), 6);
+    assert!(peak.load(Ordering::SeqCst) <= 2);
+
+    // 并发数为 0 时按 1 处理
+    let results = runtime.details(vec![server.url.clone() + "/e"], 0).await;
+    assert!(results[0].is_ok());
+    assert!(runtime.details(Vec::new(), 4).await.is_empty());
+}
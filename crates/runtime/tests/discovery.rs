@@ -0,0 +1,180 @@
+//! 发现页流程集成测试
+
+mod common;
+
+use common::{MockServer, Response, rule_for};
+use crawler_runtime::crawler::CrawlerRuntime;
+use std::collections::HashMap;
+
+const LIST_PAGE: &str =
+    r#"<ul><li><a href="/v/1">星际穿越</a></li><li><a href="/v/2">盗梦空间</a></li></ul>"#;
+
+/// 带分类与年份两组静态筛选器的发现流程
+const STATIC_FILTERS: &str = r#"
+[discovery]
+url = "{{ base_url }}/list?category={{ category }}&year={{ year }}&page={{ page }}"
+
+[[discovery.filters]]
+name = "分类"
+key = "category"
+options = [{ name = "全部", value = "all" }, { name = "电影", value = "movie" }]
+
+[[discovery.filters]]
+name = "年份"
+key = "year"
+options = [{ name = "全部", value = "" }, { name = "2014", value = "2014" }]
+
+[discovery.list]
+steps = [{ css = { expr = "li", all = true } }]
+
+[discovery.fields.title]
+steps = [{ css = "a" }, { attr = "text" }]
+
+[discovery.fields.url]
+steps = [{ css = "a" }, { attr = "href" }]
+"#;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn selected_filters_render_the_list_url() {
+    let server = MockServer::html(LIST_PAGE);
+    let runtime = CrawlerRuntime::new(rule_for(&server, STATIC_FILTERS), None).unwrap();
+
+    let filters = HashMap::from([
+        ("category".to_string(), "movie".to_string()),
+        ("year".to_string(), "2014".to_string()),
+    ]);
+    let response = runtime.discover(filters, 2).await.unwrap();
+    assert_eq!(
+        server.requests()[0].path,
+        "/list?category=movie&year=2014&page=2"
+    );
+    let titles: Vec<_> = response.items.iter().map(|i| i.title.as_str()).collect();
+    assert_eq!(titles, ["星际穿越", "盗梦空间"]);
+    assert_eq!(response.items[1].url, format!("{}/v/2", server.url));
+
+    // 未选择的筛选器取第一个选项的值
+    let filters = HashMap::from([("year".to_string(), "2014".to_string())]);
+    runtime.discover(filters, 1).await.unwrap();
+    assert_eq!(
+        server.requests()[1].path,
+        "/list?category=all&year=2014&page=1"
+    );
+
+    let groups = runtime.discovery_filters().await.unwrap();
+    let keys: Vec<_> = groups.iter().map(|g| g.key.as_str()).collect();
+    assert_eq!(keys, ["category", "year"]);
+    assert_eq!(server.hits(), 2);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn dynamic_categories_are_fetched() {
+    let server = MockServer::start(|request| match request.path.as_str() {
+        "/categories" => Response::html(
+            r#"<nav><a data-id="movie" href="/c/movie">电影</a><a data-id="tv">电视剧</a><a>无标识</a></nav>"#,
+        ),
+        _ => Response::html(LIST_PAGE),
+    });
+    let extra = r#"
+[discovery.categories]
+url = "{{ base_url }}/categories"
+list.steps = [{ css = { expr = "nav a", all = true } }]
+fields.key.steps = [{ attr = "data-id" }]
+fields.label.steps = [{ attr = "text" }]
+fields.value.steps = [{ attr = "href" }]
+"#;
+    let runtime = CrawlerRuntime::new(
+        rule_for(&server, &format!("{}{}", STATIC_FILTERS, extra)),
+        None,
+    )
+    .unwrap();
+
+    let categories = runtime.discovery_categories().await.unwrap();
+    let pairs: Vec<_> = categories
+        .iter()
+        .map(|c| (c.key.as_str(), c.label.as_str(), c.value.as_deref()))
+        .collect();
+    assert_eq!(
+        pairs,
+        [("movie", "电影", Some("/c/movie")), ("tv", "电视剧", None)]
+    );
+    assert_eq!(server.requests()[0].path, "/categories");
+}
+
+/// 动态筛选器数据源页面
+const FILTER_PAGE: &str = r#"
+<div class="group" data-key="area"><b>地区</b><i data-value="">全部</i><i data-value="cn">中国</i></div>
+<div class="group" data-key="sort" data-multi="true"><b>排序</b><i data-value="hot">最热</i></div>"#;
+
+/// 从 `/filters` 动态抓取筛选器，缓存 `cache_duration` 秒
+fn dynamic_filters_runtime(server: &MockServer, cache_duration: u32) -> CrawlerRuntime {
+    let extra = format!(
+        r#"
+[discovery]
+url = "{{{{ base_url }}}}/list?area={{{{ area }}}}"
+list.steps = [{{ css = {{ expr = "li", all = true }} }}]
+fields.title.steps = [{{ css = "a" }}, {{ attr = "text" }}]
+fields.url.steps = [{{ css = "a" }}, {{ attr = "href" }}]
+
+[discovery.filters]
+url = "{{{{ base_url }}}}/filters"
+cache_duration = {}
+list.steps = [{{ css = {{ expr = ".group", all = true }} }}]
+fields.key.steps = [{{ attr = "data-key" }}]
+fields.name.steps = [{{ css = "b" }}, {{ attr = "text" }}]
+fields.multiselect.steps = [{{ attr = "data-multi" }}]
+fields.options.list.steps = [{{ css = {{ expr = "i", all = true }} }}]
+fields.options.fields.key.steps = [{{ attr = "data-value" }}]
+fields.options.fields.name.steps = [{{ attr = "text" }}]
+"#,
+        cache_duration
+    );
+    CrawlerRuntime::new(rule_for(server, &extra), None).unwrap()
+}
+
+fn filter_server() -> MockServer {
+    MockServer::start(|request| match request.path.as_str() {
+        "/filters" => Response::html(FILTER_PAGE),
+        _ => Response::html(LIST_PAGE),
+    })
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn dynamic_filters_are_fetched_once_and_cached() {
+    let server = filter_server();
+    let runtime = dynamic_filters_runtime(&server, 3600);
+
+    let groups = runtime.discovery_filters().await.unwrap();
+    assert_eq!(groups.len(), 2);
+    assert_eq!(
+        (groups[0].key.as_str(), groups[0].name.as_str()),
+        ("area", "地区")
+    );
+    let options: Vec<_> = groups[0]
+        .options
+        .iter()
+        .map(|o| (o.name.as_str(), o.value.as_str()))
+        .collect();
+    assert_eq!(options, [("全部", ""), ("中国", "cn")]);
+    assert!(!groups[0].multiselect);
+    assert!(groups[1].multiselect);
+
+    let cached = runtime.discovery_filters().await.unwrap();
+    assert_eq!(cached.len(), 2);
+    assert_eq!(cached[0].options[1].value, "cn");
+    assert_eq!(server.hits(), 1);
+
+    // 动态筛选器的选中值同样注入列表 URL
+    let filters = HashMap::from([("area".to_string(), "cn".to_string())]);
+    runtime.discover(filters, 1).await.unwrap();
+    assert_eq!(server.requests()[1].path, "/list?area=cn");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn zero_cache_duration_refetches_filters() {
+    let server = filter_server();
+    let runtime = dynamic_filters_runtime(&server, 0);
+
+    runtime.discovery_filters().await.unwrap();
+    runtime.discovery_filters().await.unwrap();
+    assert_eq!(server.hits(), 2);
+}
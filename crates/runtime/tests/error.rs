@@ -0,0 +1,170 @@
+//! 结构化错误与错误码测试
+
+mod common;
+
+use common::{MockServer, Response, extract_html, rule, rule_for, runtime_context};
+use crawler_runtime::{
+    RuntimeError,
+    context::FlowContext,
+    crawler::CrawlerRuntime,
+    http::HttpClient,
+};
+use crawler_schema::config::HttpConfig;
+use std::{
+    net::TcpListener,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::Duration,
+};
+
+#[test]
+fn error_codes_are_stable() {
+    let cases = [
+        (
+            RuntimeError::FieldEmpty {
+                field: "title".into(),
+            },
+            "EXTRACT_EMPTY",
+            "字段 'title' 提取结果为空",
+        ),
+        (
+            RuntimeError::StepFailed {
+                step: 2,
+                kind: "index".into(),
+                reason: "越界".into(),
+            },
+            "EXTRACT_STEP_FAILED",
+            "第 2 个步骤 (index) 执行失败: 越界",
+        ),
+        (
+            RuntimeError::HttpStatus {
+                status: 404,
+                url: "http://a/b".into(),
+            },
+            "HTTP_STATUS",
+            "HTTP 状态码 404: http://a/b",
+        ),
+        (
+            RuntimeError::HttpTimeout {
+                url: "http://a/b".into(),
+            },
+            "HTTP_TIMEOUT",
+            "HTTP 请求超时: http://a/b",
+        ),
+        (
+            RuntimeError::Extraction("x".into()),
+            "EXTRACT_FAILED",
+            "数据提取错误: x",
+        ),
+        (
+            RuntimeError::ScriptTimeout,
+            "SCRIPT_TIMEOUT",
+            "脚本执行超时",
+        ),
+    ];
+
+    for (error, code, message) in cases {
+        assert_eq!(error.error_code(), code);
+        assert_eq!(error.to_string(), message);
+    }
+}
+
+#[test]
+fn step_errors_carry_their_position() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let field = "steps = [{ css = \"h1\" }, { attr = \"text\" }, { index = 0 }]";
+
+    let err = extract_html(&runtime, &flow, field, "<h1>title</h1>").unwrap_err();
+    assert_eq!(err.error_code(), "EXTRACT_STEP_FAILED");
+    assert!(
+        matches!(&err, RuntimeError::StepFailed { step: 2, kind, .. } if kind == "index"),
+        "{:?}",
+        err
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn missing_required_field_reports_its_name() {
+    let server = MockServer::html("<p>no title</p>");
+    let runtime = CrawlerRuntime::new(rule_for(&server, ""), None).unwrap();
+
+    let err = runtime.detail(&server.url).await.unwrap_err();
+    assert_eq!(err.error_code(), "EXTRACT_EMPTY");
+    assert!(
+        matches!(&err, RuntimeError::FieldEmpty { field } if field == "title"),
+        "{:?}",
+        err
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn http_errors_carry_status_and_url() {
+    let server = MockServer::start(|_| Response::status(404));
+    let client = HttpClient::new(HttpConfig::default()).unwrap();
+    let url = format!("{}/cover.jpg", server.url);
+
+    let err = client.get_bytes(&url).await.unwrap_err();
+    assert_eq!(err.error_code(), "HTTP_STATUS");
+    assert!(
+        matches!(&err, RuntimeError::HttpStatus { status: 404, url: u } if *u == url),
+        "{:?}",
+        err
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn slow_responses_time_out() {
+    let server = MockServer::start(|_| {
+        thread::sleep(Duration::from_millis(1500));
+        Response::html("late")
+    });
+    let config: HttpConfig = toml::from_str("timeout = 1\nretry_count = 0").unwrap();
+    let client = HttpClient::new(config).unwrap();
+
+    let err = client.get(&server.url).await.unwrap_err();
+    assert_eq!(err.error_code(), "HTTP_TIMEOUT");
+    assert!(err.is_interruption(), "{}", err);
+    assert!(err.is_retryable());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn timeouts_are_retried() {
+    let calls = AtomicUsize::new(0);
+    let server = MockServer::start(move |_| {
+        if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+            thread::sleep(Duration::from_millis(1500));
+        }
+        Response::html("ok")
+    });
+    let config: HttpConfig =
+        toml::from_str("timeout = 1\nretry_count = 1\nretry_delay = 10").unwrap();
+    let client = HttpClient::new(config).unwrap();
+
+    let response = client.get(&server.url).await.unwrap();
+    assert_eq!(response.text().await.unwrap(), "ok");
+    assert_eq!(server.hits(), 2);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn refused_connections_are_classified() {
+    // 绑定后立即释放，得到一个无人监听的端口
+    let port = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let config: HttpConfig = toml::from_str("retry_count = 0").unwrap();
+    let client = HttpClient::new(config).unwrap();
+
+    let err = client
+        .get(&format!("http://127.0.0.1:{}/", port))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&err, RuntimeError::HttpConnect { .. }),
+        "{:?}",
+        err
+    );
+    assert!(err.is_retryable());
+}
@@ -0,0 +1,913 @@
+//! 字段提取集成测试
+
+mod common;
+
+use common::{MockServer, Response, extract_html, field, rule, rule_for, runtime_context};
+use crawler_runtime::{
+    RuntimeError,
+    context::FlowContext,
+    crawler::CrawlerRuntime,
+    extractor::{
+        ExtractEngine,
+        ExtractValueData,
+        StepDebugger,
+        StepEvent,
+        StepHook,
+        StepPhase,
+        StepTrace,
+    },
+};
+use serde_json::{Value, json};
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+/// 自我递归的组件，超过 `max_depth` 时触发 `LimitExceeded`
+const RECURSIVE_COMPONENT: &str = r#"
+[limits]
+max_depth = 2
+
+[components.endless]
+extractor.steps = [{ use_component = "endless" }]
+"#;
+
+#[test]
+fn interruption_skips_fallback_and_default() {
+    let runtime = runtime_context(rule(RECURSIVE_COMPONENT));
+    let flow = FlowContext::new(runtime.clone());
+    let field = r#"
+steps = [{ use_component = "endless" }]
+fallback = [[{ css = "h1" }, { attr = "text" }]]
+default = "default"
+nullable = true
+"#;
+
+    let err = extract_html(&runtime, &flow, field, "<h1>title</h1>").unwrap_err();
+    assert!(matches!(err, RuntimeError::LimitExceeded { .. }), "{}", err);
+}
+
+#[test]
+fn ordinary_error_uses_fallback() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let field = r#"
+steps = [{ css = ".missing" }, { attr = "text" }]
+fallback = [[{ css = "h1" }, { attr = "text" }]]
+"#;
+
+    let value = extract_html(&runtime, &flow, field, "<h1>title</h1>").unwrap();
+    assert_eq!(value.as_str(), Some("title"));
+}
+
+#[test]
+fn default_is_used_after_all_fallbacks_fail() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let field = r#"
+steps = [{ css = ".missing" }, { attr = "text" }]
+fallback = [[{ css = ".gone" }, { attr = "text" }], [{ css = "h2" }, { attr = "text" }]]
+default = "未知"
+"#;
+
+    let value = extract_html(&runtime, &flow, field, "<h1>title</h1>").unwrap();
+    assert_eq!(value.as_str(), Some("未知"));
+}
+
+#[test]
+fn nullable_decides_between_null_and_error() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let field = r#"
+steps = [{ css = ".missing" }, { attr = "text" }]
+fallback = [[{ css = "h2" }, { attr = "text" }]]
+"#;
+
+    assert!(extract_html(&runtime, &flow, field, "<h1>title</h1>").is_err());
+    let nullable = format!("{}nullable = true", field);
+    let value = extract_html(&runtime, &flow, &nullable, "<h1>title</h1>").unwrap();
+    assert!(value.is_null(), "{:?}", value);
+}
+
+#[test]
+fn results_are_trimmed_by_default() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let html = r#"<h1 title="  title  ">x</h1>"#;
+    let field = r#"steps = [{ css = "h1" }, { attr = "title" }]"#;
+
+    let value = extract_html(&runtime, &flow, field, html).unwrap();
+    assert_eq!(value.as_str(), Some("title"));
+
+    let raw = format!("{}\nauto_trim = false", field);
+    let value = extract_html(&runtime, &flow, &raw, html).unwrap();
+    assert_eq!(value.as_str(), Some("  title  "));
+}
+
+#[test]
+fn blank_results_fall_back_when_trimmed() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let field = r#"
+steps = [{ css = "h2" }, { attr = "title" }]
+default = "无"
+"#;
+
+    let value = extract_html(&runtime, &flow, field, r#"<h2 title="   ">x</h2>"#).unwrap();
+    assert_eq!(value.as_str(), Some("无"));
+}
+
+#[test]
+fn set_var_is_visible_to_later_steps() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let field = r#"
+steps = [
+    { css = "h1" }, { attr = "text" }, { set_var = { name = "heading" } },
+    { template = "[{{ heading }}]" },
+]
+"#;
+
+    let value = extract_html(&runtime, &flow, field, "<h1>title</h1>").unwrap();
+    assert_eq!(value.as_str(), Some("[title]"));
+    assert!(flow.get("heading").is_none());
+}
+
+#[test]
+fn failed_try_does_not_leak_variables() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let field = r#"
+steps = [
+    { try = { try = [{ set_var = { name = "leak" } }, { css = ".missing" }, { attr = "text" }], catch = [] } },
+    { template = "{{ leak | default(value='none') }} {{ error | default(value='none') }}" },
+]
+"#;
+
+    let value = extract_html(&runtime, &flow, field, "<h1>title</h1>").unwrap();
+    assert_eq!(value.as_str(), Some("none none"));
+}
+
+/// 每个元素都先读取 `prev` 再写入 `cur`、`prev` 的 map 步骤
+const MAP_WITH_VARS: &str = r#"{ map = [
+    { attr = "text" }, { set_var = { name = "cur" } },
+    { template = "{{ prev | default(value='-') }}>{{ cur }}" }, { set_var = { name = "prev" } },
+] }"#;
+
+#[test]
+fn loop_variables_do_not_leak_between_items_or_outside() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let html = "<ul><li>a</li><li>b</li></ul>";
+    let list = r#"{ css = { expr = "li", all = true } }"#;
+
+    let field = format!("steps = [{}, {}]", list, MAP_WITH_VARS);
+    let value = extract_html(&runtime, &flow, &field, html).unwrap();
+    assert_eq!(value.to_owned_json(), json!(["->a", "->b"]));
+
+    let field = format!(
+        r#"steps = [{}, {}, {{ template = "{{{{ cur | default(value='none') }}}}" }}]"#,
+        list, MAP_WITH_VARS
+    );
+    let value = extract_html(&runtime, &flow, &field, html).unwrap();
+    assert_eq!(value.as_str(), Some("none"));
+    assert!(flow.get("cur").is_none());
+}
+
+#[test]
+fn snapshot_restores_flow_variables() {
+    let runtime = runtime_context(rule(""));
+    let mut flow = FlowContext::new(runtime);
+    flow.set("kept", json!(1));
+
+    let snapshot = flow.snapshot();
+    flow.set("kept", json!(2));
+    flow.set("temp", json!(3));
+    flow.restore(snapshot);
+    assert_eq!(flow.get("kept"), Some(&json!(1)));
+    assert!(flow.get("temp").is_none());
+
+    let seen = flow.scoped(|flow| {
+        flow.set("temp", json!(4));
+        flow.get("temp").cloned()
+    });
+    assert_eq!(seen, Some(json!(4)));
+    assert!(flow.get("temp").is_none());
+}
+
+#[test]
+fn successful_try_keeps_variables() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let field = r#"
+steps = [
+    { try = { try = [{ css = "h1" }, { attr = "text" }, { set_var = { name = "kept" } }] } },
+    { template = "{{ kept }}" },
+]
+"#;
+
+    let value = extract_html(&runtime, &flow, field, "<h1>title</h1>").unwrap();
+    assert_eq!(value.as_str(), Some("title"));
+}
+
+#[test]
+fn catch_recovers_with_the_error_message() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let field = r#"
+steps = [
+    { try = { try = [{ css = ".missing" }, { attr = "text" }], catch = [{ template = "[{{ error }}]" }] } },
+    { filter = "upper" },
+]
+"#;
+
+    let value = extract_html(&runtime, &flow, field, "<h1>title</h1>").unwrap();
+    let text = value.as_str().unwrap();
+    // catch 的结果继续交给后续步骤
+    assert!(text.starts_with("[第 1 个步骤 (ATTR) 执行失败"), "{}", text);
+}
+
+#[test]
+fn try_skips_interruptions_unless_configured() {
+    let runtime = runtime_context(rule(RECURSIVE_COMPONENT));
+    let flow = FlowContext::new(runtime.clone());
+    let field = r#"
+steps = [{ try = { try = [{ use_component = "endless" }], catch = [{ template = "caught" }] } }]
+"#;
+    let err = extract_html(&runtime, &flow, field, "").unwrap_err();
+    assert!(matches!(err, RuntimeError::LimitExceeded { .. }), "{}", err);
+
+    let field = r#"
+steps = [{ try = { try = [{ use_component = "endless" }], catch = [{ template = "caught" }], catch_timeout = true } }]
+"#;
+    let value = extract_html(&runtime, &flow, field, "").unwrap();
+    assert_eq!(value.as_str(), Some("caught"));
+}
+
+#[test]
+fn log_render_error_does_not_fail_chain() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let field = r#"
+steps = [
+    { css = "h1" }, { attr = "text" },
+    { log = { message = "{{ undefined_var }}", context = { page = "{{ missing }}" } } },
+]
+"#;
+
+    let value = extract_html(&runtime, &flow, field, "<h1>title</h1>").unwrap();
+    assert_eq!(value.as_str(), Some("title"));
+}
+
+#[test]
+fn assert_with_undefined_variable_fails_as_assertion() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let field = r#"
+steps = [{ assert = { condition = "{{ not_set > 1 }}", message = "not_set must be set" } }]
+"#;
+
+    let err = extract_html(&runtime, &flow, field, "<h1>title</h1>").unwrap_err();
+    match err {
+        RuntimeError::AssertionFailed { message } => {
+            assert!(message.contains("not_set must be set"), "{}", message)
+        }
+        other => panic!("应为断言失败: {}", other),
+    }
+}
+
+#[test]
+fn passing_assert_keeps_the_value() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let field = r#"
+steps = [
+    { css = "h1" }, { attr = "text" },
+    { assert = { condition = "{{ value | length > 3 }}", message = "标题过短" } },
+    { filter = "upper" },
+]
+"#;
+
+    let value = extract_html(&runtime, &flow, field, "<h1>title</h1>").unwrap();
+    assert_eq!(value.as_str(), Some("TITLE"));
+}
+
+#[test]
+fn failing_assert_stops_with_its_message() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let field = r#"
+steps = [
+    { css = "h1" }, { attr = "text" },
+    { assert = { condition = "{{ value | length > 10 }}", message = "标题过短" } },
+    { filter = "upper" },
+]
+"#;
+
+    let err = extract_html(&runtime, &flow, field, "<h1>title</h1>").unwrap_err();
+    assert!(
+        matches!(&err, RuntimeError::AssertionFailed { message } if message == "标题过短"),
+        "{}",
+        err
+    );
+
+    // 未配置 message 时使用条件模板
+    let field = r#"steps = [{ assert = { condition = "{{ false }}" } }]"#;
+    let err = extract_html(&runtime, &flow, field, "").unwrap_err();
+    assert!(err.to_string().contains("{{ false }}"), "{}", err);
+}
+
+#[test]
+fn return_stops_later_steps() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let field =
+        r#"steps = [{ css = ".title" }, { attr = "text" }, "return", { filter = "upper" }]"#;
+
+    let value = extract_html(&runtime, &flow, field, r#"<p class="title">ab</p>"#).unwrap();
+    assert_eq!(value.as_str(), Some("ab"));
+}
+
+#[test]
+fn return_in_condition_ends_outer_chain() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let field = r#"
+steps = [
+    { css = ".title" }, { attr = "text" },
+    { condition = { when = [{ regex = "(x)" }], then = [], otherwise = ["return"] } },
+    { filter = "upper" },
+]
+"#;
+
+    let value = extract_html(&runtime, &flow, field, r#"<p class="title">ab</p>"#).unwrap();
+    assert_eq!(value.as_str(), Some("ab"));
+    let value = extract_html(&runtime, &flow, field, r#"<p class="title">xy</p>"#).unwrap();
+    assert_eq!(value.as_str(), Some("XY"));
+}
+
+#[test]
+fn goto_skips_to_the_target_step() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let field = r#"steps = [{ css = ".title" }, { attr = "text" }, { goto = 4 }, { filter = "upper" }, { filter = "trim" }]"#;
+
+    let value = extract_html(&runtime, &flow, field, r#"<p class="title"> ab </p>"#).unwrap();
+    assert_eq!(value.as_str(), Some("ab"));
+}
+
+#[test]
+fn strip_jsonp_filter_parses_object() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let field = r#"steps = [{ filter = "strip_jsonp" }, { json = "$.a" }]"#;
+
+    let value = extract_html(&runtime, &flow, field, r#"cb({"a":1})"#).unwrap();
+    assert_eq!(value.to_owned_json(), json!(1));
+}
+
+#[test]
+fn traced_extraction_records_each_step() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let extractor = field(
+        r#"
+steps = [{ css = ".missing" }, { attr = "text" }]
+fallback = [[{ css = "h1" }, { attr = "text" }, { filter = "upper" }]]
+"#,
+    );
+    let input = ExtractValueData::Html(Arc::from("<h1>title</h1>"));
+
+    let (value, traces) = ExtractEngine::extract_field_traced(&extractor, &input, &runtime, &flow);
+    assert_eq!(value.unwrap().as_str(), Some("TITLE"));
+    let steps: Vec<_> = traces
+        .iter()
+        .map(|t| (t.step, t.fallback, t.is_failed()))
+        .collect();
+    assert_eq!(
+        steps,
+        [
+            ("css", None, false),
+            ("attr", None, true),
+            ("css", Some(0), false),
+            ("attr", Some(0), false),
+            ("filter", Some(0), false),
+        ]
+    );
+    assert_eq!(traces[4].output.as_deref(), Some("string(5): TITLE"));
+}
+
+#[test]
+fn fields_share_the_page_instead_of_copying_it() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let page: Arc<str> = Arc::from(format!("<div>{}</div>", "x".repeat(1 << 20)));
+    let input = ExtractValueData::Html(page.clone());
+
+    let values: Vec<_> = (0..20)
+        .map(|i| {
+            let extractor = field(&format!(
+                r#"steps = [{{ log = {{ message = "field {}" }} }}]"#,
+                i
+            ));
+            ExtractEngine::extract_field(&extractor, &input, &runtime, &flow).unwrap()
+        })
+        .collect();
+
+    for value in &values {
+        assert!(matches!(value.as_ref(), ExtractValueData::Html(h) if Arc::ptr_eq(h, &page)));
+    }
+    assert_eq!(Arc::strong_count(&page), 2 + values.len());
+}
+
+#[test]
+fn trace_summaries_do_not_copy_the_page() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let input = ExtractValueData::Html(Arc::from(format!("<h1>t</h1>{}", "x".repeat(1 << 20))));
+    let extractor = field(r#"steps = [{ css = "h1" }, { attr = "text" }]"#);
+
+    let (_, traces) = ExtractEngine::extract_field_traced(&extractor, &input, &runtime, &flow);
+    assert!(traces[0].input.len() < 200, "{}", traces[0].input.len());
+    assert!(traces[0].input.ends_with("..."));
+}
+
+#[test]
+fn json_numbers_and_bools_keep_their_type() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let input = ExtractValueData::from(json!({ "count": 12, "ok": true, "name": "a" }));
+    let extract = |path: &str| {
+        let extractor = field(&format!("steps = [{{ json = \"{}\" }}]", path));
+        ExtractEngine::extract_field(&extractor, &input, &runtime, &flow).unwrap()
+    };
+
+    let count = extract("$.count");
+    assert!(
+        matches!(count.as_ref(), ExtractValueData::Json(v) if **v == json!(12)),
+        "{:?}",
+        count
+    );
+    assert_eq!(count.to_str_lossy().as_deref(), Some("12"));
+    let ok = extract("$.ok");
+    assert_eq!(ok.to_owned_json(), json!(true));
+    assert_eq!(ok.to_str_lossy().as_deref(), Some("true"));
+    assert_eq!(extract("$.name").to_owned_json(), json!("a"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn numeric_json_fields_become_strings_only_in_the_model() {
+    let server = MockServer::html(r#"{"list":[{"name":1984,"id":7},{"name":"b","id":8}]}"#);
+    let mut rule = rule_for(&server, "");
+    rule.search.list = field(r#"steps = [{ json = "$.list[*]" }]"#);
+    rule.search.fields.title = toml::from_str(r#"steps = [{ json = "$.name" }]"#).unwrap();
+    rule.search.fields.url = toml::from_str(r#"steps = [{ json = "$.id" }]"#).unwrap();
+    let runtime = CrawlerRuntime::new(rule, None).unwrap();
+
+    let response = runtime.search("kw", 1).await.unwrap();
+    assert_eq!(response.items[0].title, "1984");
+    assert_eq!(response.items[0].url, format!("{}/7", server.url));
+    assert_eq!(response.items[1].title, "b");
+}
+
+/// 对 `[1, 2, 3, 4, 5]` 执行 `index` 步骤
+fn index(expr: &str) -> crawler_runtime::Result<Value> {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let field = format!(
+        "steps = [{{ script = {{ code = '[1, 2, 3, 4, 5]' }} }}, {{ index = {} }}]",
+        expr
+    );
+    extract_html(&runtime, &flow, &field, "").map(|v| v.to_owned_json())
+}
+
+#[test]
+fn negative_indices_count_from_the_end() {
+    assert_eq!(index("0").unwrap(), json!(1));
+    assert_eq!(index("-1").unwrap(), json!(5));
+    assert_eq!(index("\"-2\"").unwrap(), json!(4));
+    assert!(index("5").is_err());
+    assert!(index("-6").is_err());
+}
+
+#[test]
+fn slices_support_omitted_bounds_and_steps() {
+    let slice = |expr: &str| index(&format!("\"{}\"", expr)).unwrap();
+    assert_eq!(slice("1:"), json!([2, 3, 4, 5]));
+    assert_eq!(slice(":-1"), json!([1, 2, 3, 4]));
+    assert_eq!(slice("1:3"), json!([2, 3]));
+    assert_eq!(slice("::2"), json!([1, 3, 5]));
+    assert_eq!(slice("::-1"), json!([5, 4, 3, 2, 1]));
+    assert_eq!(slice("-2:"), json!([4, 5]));
+    assert!(index("\"::0\"").is_err());
+    assert!(index("\"a:b\"").is_err());
+}
+
+#[test]
+fn index_requires_array_input() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let field = "steps = [{ css = \"h1\" }, { attr = \"text\" }, { index = 0 }]";
+
+    let err = extract_html(&runtime, &flow, field, "<h1>title</h1>").unwrap_err();
+    assert!(err.to_string().contains("需要数组输入"), "{}", err);
+}
+
+#[test]
+fn enumerate_pairs_items_with_their_index() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let html = "<ul><li>a</li><li>b</li><li>c</li></ul>";
+    let field = r#"steps = [
+        { css = { expr = "li", all = true } },
+        { map = [{ attr = "text" }] },
+        "enumerate",
+    ]"#;
+
+    let value = extract_html(&runtime, &flow, field, html).unwrap();
+    assert_eq!(
+        value.to_owned_json(),
+        json!([
+            { "index": 0, "value": "a" },
+            { "index": 1, "value": "b" },
+            { "index": 2, "value": "c" },
+        ])
+    );
+
+    let field = "steps = [{ css = \"li\" }, { attr = \"text\" }, \"enumerate\"]";
+    let err = extract_html(&runtime, &flow, field, html).unwrap_err();
+    assert!(err.to_string().contains("需要数组输入"), "{}", err);
+}
+
+/// 保存 `heading` 变量后再渲染模板的字段
+const HEADING_FIELD: &str = r#"
+steps = [
+    { css = "h1" }, { attr = "text" }, { set_var = { name = "heading" } },
+    { template = "[{{ heading }}]" },
+]
+"#;
+
+/// 记录每次回调的阶段、步骤与 `heading` 变量
+#[derive(Default)]
+struct Recorder(Vec<(&'static str, &'static str, Option<Value>)>);
+
+impl StepHook for Recorder {
+    fn before_step(&mut self, event: &StepEvent<'_>) {
+        self.0.push((
+            "before",
+            event.step,
+            event.variables.get("heading").cloned(),
+        ));
+    }
+
+    fn after_step(&mut self, event: &StepEvent<'_>, _trace: StepTrace) {
+        self.0
+            .push(("after", event.step, event.variables.get("heading").cloned()));
+    }
+}
+
+#[test]
+fn step_hook_is_called_before_and_after_each_step() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let input = ExtractValueData::Html(Arc::from("<h1>title</h1>"));
+    let mut recorder = Recorder::default();
+
+    let value = ExtractEngine::extract_field_with_hook(
+        &field(HEADING_FIELD),
+        &input,
+        &runtime,
+        &flow,
+        &mut recorder,
+    )
+    .unwrap();
+    assert_eq!(value.as_str(), Some("[title]"));
+    assert_eq!(
+        recorder.0,
+        [
+            ("before", "css", None),
+            ("after", "css", None),
+            ("before", "attr", None),
+            ("after", "attr", None),
+            ("before", "set_var", None),
+            ("after", "set_var", Some(json!("title"))),
+            ("before", "template", Some(json!("title"))),
+            ("after", "template", Some(json!("title"))),
+        ]
+    );
+}
+
+#[test]
+fn step_debugger_pauses_until_resumed() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let input = ExtractValueData::Html(Arc::from("<h1>title</h1>"));
+    let extractor = field(HEADING_FIELD);
+    let (debugger, controller) = StepDebugger::new();
+
+    std::thread::scope(|scope| {
+        let handle = scope.spawn(|| {
+            // 钩子随线程结束被丢弃，控制端随后收到 None
+            let mut debugger = debugger;
+            ExtractEngine::extract_field_with_hook(
+                &extractor,
+                &input,
+                &runtime,
+                &flow,
+                &mut debugger,
+            )
+        });
+
+        let mut snapshots = Vec::new();
+        while let Some(snapshot) = controller.next() {
+            if snapshot.phase == StepPhase::Before {
+                controller.resume();
+            }
+            snapshots.push(snapshot);
+        }
+        assert_eq!(handle.join().unwrap().unwrap().as_str(), Some("[title]"));
+
+        let phases: Vec<_> = snapshots
+            .iter()
+            .map(|s| (s.phase, s.index, s.step))
+            .collect();
+        assert_eq!(phases.len(), 8);
+        assert_eq!(phases[0], (StepPhase::Before, 0, "css"));
+        assert_eq!(phases[5], (StepPhase::After, 2, "set_var"));
+        assert!(!snapshots[4].variables.contains_key("heading"));
+        assert_eq!(snapshots[5].variables["heading"], json!("title"));
+        assert_eq!(snapshots[3].output.as_deref(), Some("string(5): title"));
+    });
+}
+
+#[test]
+fn dropped_controller_stops_pausing() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let input = ExtractValueData::Html(Arc::from("<h1>title</h1>"));
+    let (mut debugger, controller) = StepDebugger::new();
+    drop(controller);
+
+    let value = ExtractEngine::extract_field_with_hook(
+        &field(HEADING_FIELD),
+        &input,
+        &runtime,
+        &flow,
+        &mut debugger,
+    )
+    .unwrap();
+    assert_eq!(value.as_str(), Some("[title]"));
+}
+
+/// 以 `concurrency = 3` 对 5 个元素请求 `/item/<n>`，子步骤脚本为 `code`
+///
+/// 越靠前的元素响应越慢，返回结果与同时处理中的最大请求数
+fn fetch_items_concurrently(code: &str, engine: &str) -> (Value, usize) {
+    let in_flight = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+    let (current, max) = (in_flight.clone(), peak.clone());
+    let server = MockServer::start(move |req| {
+        let n: u64 = req.path.trim_start_matches("/item/").parse().unwrap();
+        max.fetch_max(current.fetch_add(1, Ordering::SeqCst) + 1, Ordering::SeqCst);
+        std::thread::sleep(Duration::from_millis((6 - n) * 40));
+        current.fetch_sub(1, Ordering::SeqCst);
+        Response::html(format!("item-{}", n))
+    });
+    let runtime = runtime_context(rule("[script_security]\nallow_network = true"));
+    let flow = FlowContext::new(runtime.clone());
+    let field = format!(
+        "steps = [{{ script = {{ code = '[1, 2, 3, 4, 5]' }} }}, \
+         {{ map = {{ steps = [{{ script = {{ code = '{}', engine = \"{}\" }} }}], \
+         concurrency = 3 }} }}]",
+        code.replace("{url}", &server.url),
+        engine
+    );
+
+    let value = extract_html(&runtime, &flow, &field, "").unwrap();
+    assert_eq!(server.hits(), 5);
+    (value.to_owned_json(), peak.load(Ordering::SeqCst))
+}
+
+#[test]
+fn concurrent_map_keeps_item_order() {
+    let (value, peak) =
+        fetch_items_concurrently(r#"http_get("{url}/item/" + input)"#, "javascript");
+    assert_eq!(
+        value,
+        json!(["item-1", "item-2", "item-3", "item-4", "item-5"])
+    );
+    assert_eq!(peak, 3);
+}
+
+#[test]
+fn concurrent_map_runs_rhai_scripts_in_parallel() {
+    let (value, peak) = fetch_items_concurrently("http_get(`{url}/item/` + input)", "rhai");
+    assert_eq!(
+        value,
+        json!(["item-1", "item-2", "item-3", "item-4", "item-5"])
+    );
+    assert_eq!(peak, 3);
+}
+
+#[test]
+fn concurrent_map_stops_dispatching_after_an_interruption() {
+    let server = MockServer::start(|_| {
+        std::thread::sleep(Duration::from_millis(100));
+        Response::html("item")
+    });
+    let runtime = runtime_context(rule(&format!(
+        "[script_security]\nallow_network = true\n{}",
+        RECURSIVE_COMPONENT
+    )));
+    let flow = FlowContext::new(runtime.clone());
+    // 首个元素触发 `LimitExceeded`，其余元素发起请求
+    let field = format!(
+        "steps = [{{ script = {{ code = '[1, 2, 3, 4, 5, 6]' }} }}, \
+         {{ map = {{ steps = [{{ condition = {{ when = [{{ script = {{ code = 'input == \"1\"' }} }}], \
+         then = [{{ use_component = \"endless\" }}], \
+         otherwise = [{{ script = {{ code = 'http_get(\"{}/item\")' }} }}] }} }}], \
+         concurrency = 2 }} }}]",
+        server.url
+    );
+
+    let err = extract_html(&runtime, &flow, &field, "").unwrap_err();
+    assert!(matches!(err, RuntimeError::LimitExceeded { .. }), "{}", err);
+    // 中断后只有已领取的元素会继续完成
+    assert!(server.hits() <= 1, "hits = {}", server.hits());
+}
+
+#[test]
+fn map_without_concurrency_runs_in_order() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let field = "steps = [{ script = { code = '[1, 2, 3]' } }, { map = [{ script = { code = '`#` + input' } }] }]";
+
+    let value = extract_html(&runtime, &flow, field, "").unwrap();
+    assert_eq!(value.to_owned_json(), json!(["#1", "#2", "#3"]));
+}
+
+/// 带默认参数 `sep` 与必填参数 `prefix` 的组件
+const JOIN_COMPONENT: &str = r#"
+[components.join]
+inputs = { sep = "-" }
+required = ["prefix"]
+extractor.steps = [{ attr = "text" }, { template = "{{ prefix }}{{ sep }}{{ value }}" }]
+"#;
+
+#[test]
+fn component_args_override_input_defaults() {
+    let runtime = runtime_context(rule(JOIN_COMPONENT));
+    let mut flow = FlowContext::new(runtime.clone());
+    flow.set("kw", json!("k"));
+    let call = |args: &str| {
+        let field = format!(
+            r#"steps = [{{ css = "h1" }}, {{ use_component = {{ name = "join", args = {} }} }}]"#,
+            args
+        );
+        extract_html(&runtime, &flow, &field, "<h1>x</h1>").map(|v| v.to_owned_json())
+    };
+
+    assert_eq!(call(r#"{ prefix = "a" }"#).unwrap(), json!("a-x"));
+    assert_eq!(
+        call(r#"{ prefix = "a", sep = "+" }"#).unwrap(),
+        json!("a+x")
+    );
+    assert_eq!(call(r#"{ prefix = "{{ kw }}" }"#).unwrap(), json!("k-x"));
+    assert!(flow.get("prefix").is_none());
+
+    let err = call(r#"{ sep = "+" }"#).unwrap_err();
+    assert!(
+        matches!(&err, RuntimeError::MissingComponentArg { component, arg }
+            if component == "join" && arg == "prefix"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn expect_type_array_wraps_single_values() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let html = r#"<p class="tag">玄幻</p><p class="num">42</p><p class="flag">yes</p>"#;
+    let extract = |steps: &str, expect: &str| {
+        let field = format!("steps = {}\nexpect_type = \"{}\"", steps, expect);
+        extract_html(&runtime, &flow, &field, html).map(|v| v.to_owned_json())
+    };
+
+    let single = r#"[{ css = ".tag" }, { attr = "text" }]"#;
+    assert_eq!(extract(single, "array").unwrap(), json!(["玄幻"]));
+    let all = r#"[{ css = { expr = "p", all = true } }, { attr = "text" }]"#;
+    assert_eq!(extract(all, "array").unwrap(), json!(["玄幻", "42", "yes"]));
+
+    let num = r#"[{ css = ".num" }, { attr = "text" }]"#;
+    assert_eq!(extract(num, "number").unwrap(), json!(42));
+    assert_eq!(extract(num, "string").unwrap(), json!("42"));
+    let flag = r#"[{ css = ".flag" }, { attr = "text" }]"#;
+    assert_eq!(extract(flag, "bool").unwrap(), json!(true));
+
+    let err = extract(single, "number").unwrap_err();
+    assert!(err.to_string().contains("无法转换为 number"), "{}", err);
+}
+
+#[test]
+fn expect_type_unwraps_single_element_arrays() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let input: ExtractValueData = json!({ "ids": ["7"], "tags": ["a", "b"] }).into();
+    let extract = |path: &str, expect: &str| {
+        let extractor = field(&format!(
+            "steps = [{{ json = {{ expr = \"{}\", all = true }} }}]\nexpect_type = \"{}\"",
+            path, expect
+        ));
+        ExtractEngine::extract_field(&extractor, &input, &runtime, &flow).map(|v| v.to_owned_json())
+    };
+
+    assert_eq!(extract("$.ids[*]", "number").unwrap(), json!(7));
+    assert_eq!(extract("$.tags[*]", "array").unwrap(), json!(["a", "b"]));
+    assert!(extract("$.tags[*]", "string").is_err());
+}
+
+#[test]
+fn template_step_combines_variables_with_filters() {
+    let runtime = runtime_context(rule(""));
+    let mut flow = FlowContext::new(runtime.clone());
+    flow.set("book", json!({ "name": "  凡人&修仙 " }));
+    flow.set("volume", json!(" 卷二 "));
+    let html = r#"<p class="chapter">十二</p>"#;
+    let field = r#"
+steps = [
+    { css = ".chapter" }, { attr = "text" },
+    { template = "{{ book.name | trim }}·{{ volume | trim }}·第{{ value | cn_to_num }}章" },
+]
+"#;
+
+    let value = extract_html(&runtime, &flow, field, html).unwrap();
+    // 结果不做 HTML 转义
+    assert_eq!(value.as_str(), Some("凡人&修仙·卷二·第12章"));
+
+    let field = r#"steps = [{ template = "{{ book.name | trim | t2s | length }}-{{ missing | default(value='无') | upper }}" }]"#;
+    let value = extract_html(&runtime, &flow, field, "").unwrap();
+    assert_eq!(value.as_str(), Some("5-无"));
+}
+
+/// 在影视列表 JSON 上执行 `{ json = { expr, all = true } }`
+fn json_filter(expr: &str) -> Value {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let input = ExtractValueData::from(json!({
+        "items": [
+            { "title": "星际穿越", "type": "movie", "score": 9.4, "tags": ["科幻", "完结"] },
+            { "title": "三体", "type": "tv", "score": 8.7, "tags": ["科幻"] },
+            { "title": "盗梦空间", "type": "movie", "score": 9.3, "tags": [] },
+        ]
+    }));
+    let extractor = field(&format!(
+        "steps = [{{ json = {{ expr = \"{}\", all = true }} }}]",
+        expr
+    ));
+    ExtractEngine::extract_field(&extractor, &input, &runtime, &flow)
+        .unwrap()
+        .to_owned_json()
+}
+
+#[test]
+fn json_filters_select_items_by_field_value() {
+    assert_eq!(
+        json_filter("$.items[?(@.type == 'movie')].title"),
+        json!(["星际穿越", "盗梦空间"])
+    );
+    assert_eq!(
+        json_filter("$.items[?(@.type != 'movie')].title"),
+        json!(["三体"])
+    );
+    assert_eq!(
+        json_filter("$.items[?(@.score > 9.3)].title"),
+        json!(["星际穿越"])
+    );
+    assert_eq!(
+        json_filter("$.items[?(@.score < 9.4 && @.type == 'movie')].title"),
+        json!(["盗梦空间"])
+    );
+}
+
+#[test]
+fn json_filters_support_contains() {
+    // 数组元素
+    assert_eq!(
+        json_filter("$.items[?(@.tags contains '科幻')].title"),
+        json!(["星际穿越", "三体"])
+    );
+    // 字符串子串
+    assert_eq!(
+        json_filter("$.items[?(@.title contains '空间')].type"),
+        json!(["movie"])
+    );
+    // 正则元字符按字面匹配
+    assert_eq!(
+        json_filter("$.items[?(@.title contains '星.' || @.type == 'tv')].title"),
+        json!(["三体"])
+    );
+}
@@ -0,0 +1,386 @@
+//! 过滤器测试
+
+mod common;
+
+use common::{MockServer, extract_html, field, rule, rule_for, runtime_context};
+use crawler_runtime::{
+    context::FlowContext,
+    crawler::CrawlerRuntime,
+    extractor::{
+        ExtractEngine,
+        ExtractValueData,
+        filter::{parse_pipeline, registry::global_registry},
+    },
+};
+use serde_json::{Value, json};
+use std::sync::Arc;
+
+/// 对字符串输入应用过滤器，返回 JSON 结果
+fn apply(name: &str, input: &str, args: &[Value]) -> crawler_runtime::Result<Value> {
+    apply_json(name, ExtractValueData::from(input), args)
+}
+
+/// 对任意输入应用过滤器，返回 JSON 结果
+fn apply_json(
+    name: &str,
+    input: impl Into<ExtractValueData>,
+    args: &[Value],
+) -> crawler_runtime::Result<Value> {
+    global_registry()
+        .apply(name, Arc::new(input.into()), args)
+        .map(|v| v.to_owned_json())
+}
+
+/// 在提取步骤中对 JSON 输入执行过滤器管道
+fn pipeline(data: Value, filter: &str) -> Value {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let extractor = field(&format!("steps = [{{ filter = '{}' }}]", filter));
+    ExtractEngine::extract_field(&extractor, &data.into(), &runtime, &flow)
+        .unwrap()
+        .to_owned_json()
+}
+
+#[test]
+fn regex_extract_returns_capture_group() {
+    let text = "第12章 共345字";
+    assert_eq!(
+        apply("regex_extract", text, &[json!(r"第(\d+)章")]).unwrap(),
+        json!("12")
+    );
+    assert_eq!(
+        apply(
+            "regex_extract",
+            text,
+            &[json!(r"(\d+)章 共(\d+)字"), json!(2)]
+        )
+        .unwrap(),
+        json!("345")
+    );
+    assert_eq!(
+        apply("regex_extract", text, &[json!(r"第(\d+)章"), json!("0")]).unwrap(),
+        json!("第12章")
+    );
+    assert_eq!(
+        apply("regex_extract", text, &[json!(r"卷(\d+)")]).unwrap(),
+        Value::Null
+    );
+    assert!(apply("regex_extract", text, &[json!("(")]).is_err());
+}
+
+#[test]
+fn regex_match_returns_bool() {
+    assert_eq!(
+        apply("regex_match", "已完结", &[json!("完结|完本")]).unwrap(),
+        json!(true)
+    );
+    assert_eq!(
+        apply("regex_match", "连载中", &[json!("完结|完本")]).unwrap(),
+        json!(false)
+    );
+    assert!(apply("regex_match", "连载中", &[]).is_err());
+}
+
+#[test]
+fn regex_find_all_returns_every_match() {
+    let text = "tags: a1, b22, c333";
+    assert_eq!(
+        apply("regex_find_all", text, &[json!(r"\d+")]).unwrap(),
+        json!(["1", "22", "333"])
+    );
+    assert_eq!(
+        apply("regex_find_all", text, &[json!(r"([a-z])\d+"), json!(1)]).unwrap(),
+        json!(["a", "b", "c"])
+    );
+    assert_eq!(
+        apply("regex_find_all", text, &[json!(r"x\d")]).unwrap(),
+        json!([])
+    );
+}
+
+#[test]
+fn regex_filters_work_in_pipelines() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let field = r#"steps = [{ css = "p" }, { attr = "text" }, { filter = 'regex_find_all("\d+") | join(",")' }]"#;
+
+    let value = extract_html(&runtime, &flow, field, "<p>1、22、333</p>").unwrap();
+    assert_eq!(value.as_str(), Some("1,22,333"));
+}
+
+#[test]
+fn sort_orders_numbers_numerically() {
+    let numbers = json!([10, 9, "100", 1.5]);
+    assert_eq!(
+        apply_json("sort", numbers.clone(), &[]).unwrap(),
+        json!([1.5, 9, 10, "100"])
+    );
+    assert_eq!(
+        apply_json("sort", numbers.clone(), &[json!("desc")]).unwrap(),
+        json!(["100", 10, 9, 1.5])
+    );
+    // 按字符串比较时 "10" < "9"
+    assert_eq!(
+        apply_json("sort", numbers, &[json!("type=string")]).unwrap(),
+        json!([1.5, 10, "100", 9])
+    );
+}
+
+#[test]
+fn sort_objects_by_field() {
+    let books =
+        json!([{ "title": "b", "score": 7 }, { "title": "c" }, { "title": "a", "score": 9.5 }]);
+    assert_eq!(
+        pipeline(books.clone(), r#"sort(by="title")"#),
+        json!([
+            { "title": "a", "score": 9.5 },
+            { "title": "b", "score": 7 },
+            { "title": "c" }
+        ])
+    );
+    // 缺少排序字段的元素排在末尾
+    assert_eq!(
+        pipeline(books.clone(), r#"sort(by="score", order=desc)"#),
+        json!([
+            { "title": "a", "score": 9.5 },
+            { "title": "b", "score": 7 },
+            { "title": "c" }
+        ])
+    );
+    assert_eq!(
+        pipeline(books, "sort(score, number, asc)"),
+        json!([
+            { "title": "b", "score": 7 },
+            { "title": "a", "score": 9.5 },
+            { "title": "c" }
+        ])
+    );
+}
+
+#[test]
+fn sort_rejects_invalid_arguments() {
+    assert!(apply_json("sort", json!([1, 2]), &[json!("order=up")]).is_err());
+    assert!(apply_json("sort", json!([1, 2]), &[json!("type=date")]).is_err());
+    assert!(apply("sort", "abc", &[]).is_err());
+}
+
+#[cfg(feature = "collation")]
+#[test]
+fn sort_by_pinyin() {
+    assert_eq!(
+        apply_json("sort", json!(["张三", "李四", "王五"]), &[json!("pinyin")]).unwrap(),
+        json!(["李四", "王五", "张三"])
+    );
+}
+
+#[cfg(not(feature = "collation"))]
+#[test]
+fn sort_by_pinyin_requires_collation_feature() {
+    let err = apply_json("sort", json!(["张三", "李四"]), &[json!("pinyin")]).unwrap_err();
+    assert!(err.to_string().contains("collation"), "{}", err);
+}
+
+/// 以 `page_url` 为当前页面，对每个链接执行 `absolute_url`
+fn absolute_urls(page_url: Option<&str>, links: &str) -> crawler_runtime::Result<Value> {
+    let runtime = runtime_context(rule(""));
+    let mut flow = FlowContext::new(runtime.clone());
+    if let Some(url) = page_url {
+        flow.set("request_url", json!(url));
+    }
+    let field = r#"
+steps = [
+    { css = { expr = "a", all = true } },
+    { map = [{ attr = "href" }, { filter = "absolute_url" }] },
+]
+"#;
+    extract_html(&runtime, &flow, field, links).map(|v| v.to_owned_json())
+}
+
+#[test]
+fn absolute_url_uses_the_page_url() {
+    let links = r#"<a href="2.html">relative</a><a href="/book/3">root</a>
+<a href="//cdn.b.com/c.jpg">protocol</a><a href="http://c.com/x">absolute</a>"#;
+
+    assert_eq!(
+        absolute_urls(Some("https://a.com/book/1/index.html"), links).unwrap(),
+        json!([
+            "https://a.com/book/1/2.html",
+            "https://a.com/book/3",
+            "https://cdn.b.com/c.jpg",
+            "http://c.com/x"
+        ])
+    );
+    // 协议相对 URL 沿用当前页面的协议
+    assert_eq!(
+        absolute_urls(
+            Some("http://a.com/"),
+            r#"<a href="//cdn.b.com/c.jpg">x</a>"#
+        )
+        .unwrap(),
+        json!(["http://cdn.b.com/c.jpg"])
+    );
+}
+
+#[test]
+fn absolute_url_argument_overrides_the_page_url() {
+    assert_eq!(
+        apply("absolute_url", "/b/1", &[json!("https://m.a.com/x/")]).unwrap(),
+        json!("https://m.a.com/b/1")
+    );
+    assert!(apply("absolute_url", "/b/1", &[]).is_err());
+    assert_eq!(
+        apply("absolute_url", "//a.com/b", &[]).unwrap(),
+        json!("https://a.com/b")
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn search_links_resolve_against_the_request_url() {
+    let server = MockServer::html(r#"<li><a href="../book/1.html">one</a></li>"#);
+    let mut rule = rule_for(&server, "");
+    rule.search.url = "{{ base_url }}/s/search.php?kw={{ keyword }}".into();
+    rule.search.fields.url = toml::from_str(
+        r#"steps = [{ css = "a" }, { attr = "href" }, { filter = "absolute_url" }]"#,
+    )
+    .unwrap();
+    let runtime = CrawlerRuntime::new(rule, None).unwrap();
+
+    let response = runtime.search("kw", 1).await.unwrap();
+    assert_eq!(response.items[0].url, format!("{}/book/1.html", server.url));
+}
+
+#[test]
+fn decode_all_filter_decodes_entities_and_unicode() {
+    assert_eq!(
+        apply("decode_all", r" Caf&#233; \u00e9&nbsp;", &[]).unwrap(),
+        json!("Café é")
+    );
+}
+
+/// 解析管道，返回每个过滤器的名称与字符串参数
+fn parsed(pipeline: &str) -> Vec<(String, Vec<String>)> {
+    parse_pipeline(pipeline)
+        .unwrap()
+        .into_iter()
+        .map(|call| {
+            let args = call
+                .args
+                .iter()
+                .map(|a| a.as_str().unwrap().to_string())
+                .collect();
+            (call.name, args)
+        })
+        .collect()
+}
+
+fn call(name: &str, args: &[&str]) -> (String, Vec<String>) {
+    (
+        name.to_string(),
+        args.iter().map(|a| a.to_string()).collect(),
+    )
+}
+
+#[test]
+fn pipeline_args_may_be_quoted() {
+    assert_eq!(
+        parsed(r#"trim | replace("a, b", "c") | join(" | ")"#),
+        [
+            call("trim", &[]),
+            call("replace", &["a, b", "c"]),
+            call("join", &[" | "]),
+        ]
+    );
+    // 引号内的空白原样保留，未加引号的参数去除首尾空白
+    assert_eq!(
+        parsed(r#"replace(" x ",  '' ) | default( none )"#),
+        [call("replace", &[" x ", ""]), call("default", &["none"])]
+    );
+}
+
+#[test]
+fn pipeline_quotes_support_escapes() {
+    assert_eq!(
+        parsed(r#"replace("say \"hi\"", 'it\'s \\ ok')"#),
+        [call("replace", &[r#"say "hi""#, r"it's \ ok"])]
+    );
+    // 其他反斜杠原样保留，便于书写正则
+    assert_eq!(
+        parsed(r#"regex_replace("\d+", "N")"#),
+        [call("regex_replace", &[r"\d+", "N"])]
+    );
+    // 词中的引号不开启字符串
+    assert_eq!(
+        parsed("replace(it's, its)"),
+        [call("replace", &["it's", "its"])]
+    );
+}
+
+#[test]
+fn pipeline_args_may_contain_parentheses() {
+    assert_eq!(
+        parsed(r"regex_replace((\d+)年, $1) | trim"),
+        [
+            call("regex_replace", &[r"(\d+)年", "$1"]),
+            call("trim", &[])
+        ]
+    );
+    assert_eq!(
+        parsed(r#"replace("a)", "(b")"#),
+        [call("replace", &["a)", "(b"])]
+    );
+}
+
+#[test]
+fn malformed_pipelines_are_rejected() {
+    for pipeline in [r#"replace("a, b)"#, "replace(a, b", "trim | | lower"] {
+        let err = parse_pipeline(pipeline).unwrap_err();
+        assert!(err.to_string().contains("解析失败"), "{}", err);
+    }
+}
+
+#[test]
+fn quoted_args_reach_the_filter() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let field =
+        r#"steps = [{ css = "p" }, { attr = "text" }, { filter = 'replace("a, b", "x")' }]"#;
+    let value = extract_html(&runtime, &flow, field, "<p>a, b!</p>").unwrap();
+    assert_eq!(value.as_str(), Some("x!"));
+}
+
+#[test]
+fn capitalize_lowers_the_rest_or_titles_each_word() {
+    assert_eq!(
+        apply("capitalize", "hELLO wORLD", &[]).unwrap(),
+        json!("Hello world")
+    );
+    assert_eq!(
+        apply("capitalize", "hELLO  wORLD\tfoo", &[json!("title")]).unwrap(),
+        json!("Hello  World\tFoo")
+    );
+    assert_eq!(apply("capitalize", "", &[]).unwrap(), json!(""));
+    assert!(apply("capitalize", "abc", &[json!("upper")]).is_err());
+}
+
+#[test]
+fn collapse_whitespace_folds_tabs_newlines_and_fullwidth_spaces() {
+    assert_eq!(
+        apply(
+            "collapse_whitespace",
+            " \t第一章\n\n  风起\u{3000}\u{3000}云涌\r\n",
+            &[]
+        )
+        .unwrap(),
+        json!("第一章 风起 云涌")
+    );
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let value = extract_html(
+        &runtime,
+        &flow,
+        r#"steps = [{ css = "p" }, { attr = "text" }, { filter = "collapse_whitespace | upper" }]"#,
+        "<p>\n  a&nbsp;&nbsp;b\t</p>",
+    )
+    .unwrap();
+    assert_eq!(value.as_str(), Some("A B"));
+}
@@ -0,0 +1,65 @@
+//! 无头浏览器 WebView 集成测试
+//!
+//! 需启用 `headless` feature，并在本机安装 Chromium/Chrome
+
+#![cfg(feature = "headless")]
+
+mod common;
+
+use common::MockServer;
+use crawler_runtime::webview::{
+    ChromiumWebViewProvider,
+    WebViewCloseReason,
+    WebViewProvider,
+    WebViewRequest,
+};
+use std::time::Duration;
+
+/// 稍后写入 Cookie 与 localStorage 并修改标题的静态页面
+const PAGE: &str = r#"<html><head><title>checking</title></head><body><script>
+setTimeout(() => {
+    document.cookie = "cf_clearance=passed; path=/";
+    localStorage.setItem("token", "abc");
+    document.title = "done";
+}, 200);
+</script></body></html>"#;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn success_check_passes_and_extracts_cookie() {
+    let server = MockServer::html(PAGE);
+    let request = WebViewRequest::new(&server.url)
+        .with_timeout(Duration::from_secs(30))
+        .with_success_check("document.title === 'done'")
+        .with_check_interval(Duration::from_millis(50))
+        .with_extract_cookies(vec!["cf_clearance".to_string()])
+        .with_extract_local_storage(vec!["token".to_string()])
+        .with_finish_script("return document.title");
+
+    let response = ChromiumWebViewProvider::new()
+        .with_no_sandbox()
+        .open(request)
+        .await
+        .unwrap();
+
+    assert!(response.success, "{:?}", response.error);
+    assert_eq!(response.cookies["cf_clearance"], "passed");
+    assert_eq!(response.local_storage["token"], "abc");
+    assert_eq!(response.script_result.as_deref(), Some("done"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn success_check_times_out() {
+    let server = MockServer::html("<title>checking</title>");
+    let request = WebViewRequest::new(&server.url)
+        .with_timeout(Duration::from_secs(2))
+        .with_success_check("document.title === 'done'");
+
+    let response = ChromiumWebViewProvider::new()
+        .with_no_sandbox()
+        .open(request)
+        .await
+        .unwrap();
+
+    assert!(!response.success);
+    assert_eq!(response.close_reason, WebViewCloseReason::Timeout);
+}
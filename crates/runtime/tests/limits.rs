@@ -0,0 +1,118 @@
+//! 资源上限集成测试
+
+mod common;
+
+use common::{DETAIL_PAGE, MockServer, extract_html, rule, rule_for, runtime_context};
+use crawler_runtime::{RuntimeError, context::FlowContext, crawler::CrawlerRuntime};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn request_limit_is_per_call() {
+    let server = MockServer::html(DETAIL_PAGE);
+    let rule = rule_for(&server, "[limits]\nmax_requests = 1");
+    let runtime = CrawlerRuntime::new(rule, None).unwrap();
+    let url = format!("{}/book/1", server.url);
+
+    runtime.detail(&url).await.unwrap();
+    runtime.detail(&url).await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn request_limit_covers_whole_batch() {
+    let server = MockServer::html(DETAIL_PAGE);
+    let rule = rule_for(&server, "[limits]\nmax_requests = 1");
+    let runtime = CrawlerRuntime::new(rule, None).unwrap();
+    let urls = vec![
+        format!("{}/book/1", server.url),
+        format!("{}/book/2", server.url),
+    ];
+
+    let results = runtime.details(urls, 1).await;
+    assert!(results[0].is_ok());
+    assert!(matches!(
+        results[1],
+        Err(RuntimeError::LimitExceeded { .. })
+    ));
+}
+
+/// 在 `max_requests = 2` 下对 5 个元素执行 `map`，子步骤以脚本 `code` 请求 `/item`
+fn map_over_requests(code: &str, concurrency: usize) -> (RuntimeError, usize) {
+    let server = MockServer::html("item");
+    let runtime = runtime_context(rule(
+        "[limits]\nmax_requests = 2\n\n[script_security]\nallow_network = true",
+    ));
+    let flow = FlowContext::new(runtime.clone());
+    let field = format!(
+        "steps = [{{ script = {{ code = '[1, 2, 3, 4, 5]' }} }}, \
+         {{ map = {{ steps = [{{ script = {{ code = '{}', engine = \"javascript\" }} }}], \
+         concurrency = {} }} }}]",
+        code.replace("{url}", &server.url),
+        concurrency
+    );
+
+    let err = extract_html(&runtime, &flow, &field, "").unwrap_err();
+    (err, server.hits())
+}
+
+#[test]
+fn map_over_requests_stops_at_max_requests() {
+    for concurrency in [1, 3] {
+        let (err, hits) = map_over_requests(r#"http_get("{url}/item")"#, concurrency);
+        assert!(
+            matches!(err, RuntimeError::LimitExceeded { max: 2, .. }),
+            "{}",
+            err
+        );
+        assert_eq!(hits, 2);
+    }
+}
+
+#[test]
+fn scripts_cannot_swallow_the_request_limit() {
+    let (err, hits) = map_over_requests(
+        r#"try { http_get("{url}/item") } catch (e) { "caught" }"#,
+        1,
+    );
+    assert!(matches!(err, RuntimeError::LimitExceeded { .. }), "{}", err);
+    assert_eq!(hits, 2);
+}
+
+/// 每页两项、总页数未知的分页搜索规则
+fn paged_runtime(server: &MockServer, extra: &str) -> CrawlerRuntime {
+    let extra = format!("[search.pagination]\ntype = \"page_number\"\n{}", extra);
+    let mut rule = rule_for(server, &extra);
+    rule.search.url = "{{ base_url }}/search?kw={{ keyword }}&page={{ page }}".into();
+    CrawlerRuntime::new(rule, None).unwrap()
+}
+
+const LIST_PAGE: &str = r#"<ul><li><a href="/b/1">1</a></li><li><a href="/b/2">2</a></li></ul>"#;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pages_beyond_max_pages_are_cut_off() {
+    let server = MockServer::html(LIST_PAGE);
+    let runtime = paged_runtime(&server, "\n[limits]\nmax_pages = 2");
+
+    assert!(runtime.search("kw", 1).await.unwrap().has_next);
+    assert!(!runtime.search("kw", 2).await.unwrap().has_next);
+    let err = runtime.search("kw", 3).await.unwrap_err();
+    assert!(
+        matches!(&err, RuntimeError::LimitExceeded { max: 2, .. }),
+        "{}",
+        err
+    );
+    assert_eq!(server.hits(), 2);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn stricter_pagination_limit_wins() {
+    let server = MockServer::html(LIST_PAGE);
+    let runtime = paged_runtime(&server, "max_pages = 1\n\n[limits]\nmax_pages = 5");
+
+    assert!(!runtime.search("kw", 1).await.unwrap().has_next);
+    let err = runtime.search("kw", 2).await.unwrap_err();
+    assert!(
+        matches!(&err, RuntimeError::LimitExceeded { max: 1, .. }),
+        "{}",
+        err
+    );
+    assert_eq!(server.hits(), 1);
+}
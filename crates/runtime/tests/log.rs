@@ -0,0 +1,130 @@
+//! 日志步骤集成测试
+
+mod common;
+
+use common::{extract_html, rule, runtime_context};
+use crawler_runtime::context::FlowContext;
+use serde_json::json;
+use std::{
+    fmt::Debug,
+    sync::{Arc, Mutex},
+};
+use tracing::{
+    Event,
+    Level,
+    Metadata,
+    Subscriber,
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+};
+
+/// 记录到的日志事件：级别、消息、上下文字段
+type Captured = Vec<(Level, String, String)>;
+
+/// 收集日志事件的订阅者
+#[derive(Clone, Default)]
+struct Capture(Arc<Mutex<Captured>>);
+
+#[derive(Default)]
+struct Fields {
+    message: String,
+    context: String,
+}
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        match field.name() {
+            "message" => self.message = format!("{:?}", value),
+            "context" => self.context = format!("{:?}", value),
+            _ => {}
+        }
+    }
+}
+
+impl Subscriber for Capture {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        self.0
+            .lock()
+            .unwrap()
+            .push((*event.metadata().level(), fields.message, fields.context));
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+/// 执行字段提取并返回期间输出的日志
+fn captured_logs(steps: &str) -> Captured {
+    let runtime = runtime_context(rule(""));
+    let mut flow = FlowContext::new(runtime.clone());
+    flow.set("page", json!(3));
+    let capture = Capture::default();
+
+    let value = tracing::subscriber::with_default(capture.clone(), || {
+        extract_html(&runtime, &flow, steps, "<h1>title</h1>").unwrap()
+    });
+    assert_eq!(value.as_str(), Some("title"));
+    Arc::try_unwrap(capture.0).unwrap().into_inner().unwrap()
+}
+
+#[test]
+fn log_levels_map_to_tracing_levels() {
+    let logs = captured_logs(
+        r#"
+steps = [
+    { css = "h1" }, { attr = "text" },
+    { log = { message = "d", level = "debug" } },
+    { log = { message = "i" } },
+    { log = { message = "w", level = "warn" } },
+    { log = { message = "e", level = "error" } },
+]
+"#,
+    );
+
+    let levels: Vec<_> = logs
+        .iter()
+        .map(|(level, msg, _)| (*level, msg.as_str()))
+        .collect();
+    assert_eq!(
+        levels,
+        [
+            (Level::DEBUG, "d"),
+            (Level::INFO, "i"),
+            (Level::WARN, "w"),
+            (Level::ERROR, "e"),
+        ]
+    );
+}
+
+#[test]
+fn log_message_and_context_are_rendered() {
+    let logs = captured_logs(
+        r#"
+steps = [
+    { css = "h1" }, { attr = "text" },
+    { log = { message = "page {{ page }}: {{ value }}", context = { page = "{{ page }}", value = "{{ value | upper }}" } } },
+]
+"#,
+    );
+
+    let (level, message, context) = &logs[0];
+    assert_eq!(*level, Level::INFO);
+    assert_eq!(message, "page 3: title");
+    let context: serde_json::Value = serde_json::from_str(context).unwrap();
+    assert_eq!(context, json!({ "page": "3", "value": "TITLE" }));
+}
@@ -0,0 +1,54 @@
+//! 标准化输出模型测试
+
+use crawler_runtime::model::{BookDetail, SearchItem};
+use crawler_schema::config::MediaType;
+use serde_json::json;
+
+#[test]
+fn search_item_converts_to_summary() {
+    let mut item = SearchItem::new("斗破苍穹".into(), "https://a.com/b/1".into());
+    item.author = Some("天蚕土豆".into());
+    item.score = Some("9.2分".into());
+    item.category = Some("玄幻，热血/ 完结".into());
+
+    let summary = item.into_item_summary(MediaType::Book);
+    assert_eq!(
+        summary.id,
+        format!("{:x}", md5::compute("https://a.com/b/1"))
+    );
+    assert_eq!(summary.media_type, MediaType::Book);
+    assert_eq!(summary.title, "斗破苍穹");
+    assert_eq!(summary.url, "https://a.com/b/1");
+    assert_eq!(summary.author.as_deref(), Some("天蚕土豆"));
+    assert_eq!(summary.score, Some(9.2));
+    assert_eq!(summary.tags, ["玄幻", "热血", "完结"]);
+}
+
+#[test]
+fn explicit_id_is_preferred() {
+    let mut item = SearchItem::new("t".into(), "https://a.com/b/1".into());
+    item.raw = json!({ "id": 42 });
+
+    assert_eq!(item.into_item_summary(MediaType::Video).id, "42");
+}
+
+#[test]
+fn book_detail_merges_tags() {
+    let detail: BookDetail = serde_json::from_value(json!({
+        "title": "斗破苍穹",
+        "author": "",
+        "category": "玄幻",
+        "tags": "热血、玄幻|升级",
+        "last_chapter": "第1623章",
+        "raw": { "id": "dp" },
+    }))
+    .unwrap();
+
+    let detail = detail.into_item_detail("https://a.com/b/1");
+    assert_eq!(detail.id, "dp");
+    assert_eq!(detail.media_type, MediaType::Book);
+    assert_eq!(detail.url, "https://a.com/b/1");
+    assert_eq!(detail.author, None);
+    assert_eq!(detail.latest.as_deref(), Some("第1623章"));
+    assert_eq!(detail.tags, ["玄幻", "热血", "升级"]);
+}
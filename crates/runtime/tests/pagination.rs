@@ -0,0 +1,232 @@
+//! 分页集成测试
+
+mod common;
+
+use common::{MockServer, Request, Response, rule, rule_for, runtime_context};
+use crawler_runtime::{
+    crawler::CrawlerRuntime,
+    flow::{SearchPager, SearchPagerState},
+    model::SearchItem,
+};
+use crawler_schema::flow::Pagination;
+use serde_json::{Value, json};
+
+/// 共 25 条，当前页只有 5 条（如末页）
+const SHORT_PAGE: &str = r#"<span class="total">25</span><ul>
+<li><a href="/b/1">1</a></li><li><a href="/b/2">2</a></li><li><a href="/b/3">3</a></li>
+<li><a href="/b/4">4</a></li><li><a href="/b/5">5</a></li></ul>"#;
+
+fn runtime(server: &MockServer, page_size: Option<u32>) -> CrawlerRuntime {
+    let page_size = page_size.map_or(String::new(), |n| format!("page_size = {}", n));
+    let extra = format!(
+        r#"
+[search.pagination]
+type = "page_number"
+total_items.steps = [{{ css = ".total" }}, {{ attr = "text" }}, {{ filter = "to_int" }}]
+{}
+"#,
+        page_size
+    );
+    CrawlerRuntime::new(rule_for(server, &extra), None).unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn short_last_page_uses_configured_page_size() {
+    let server = MockServer::html(SHORT_PAGE);
+    let response = runtime(&server, Some(10)).search("kw", 3).await.unwrap();
+
+    assert_eq!(response.total_pages, Some(3));
+    assert!(!response.has_next);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn later_pages_do_not_estimate_from_their_own_count() {
+    let server = MockServer::html(SHORT_PAGE);
+    let response = runtime(&server, None).search("kw", 3).await.unwrap();
+
+    assert_eq!(response.total_pages, None);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn first_page_count_estimates_total_pages() {
+    let server = MockServer::html(SHORT_PAGE);
+    let response = runtime(&server, None).search("kw", 1).await.unwrap();
+
+    assert_eq!(response.total_pages, Some(5));
+    assert!(response.has_next);
+}
+
+/// 末尾重复了一条的页面
+const DUPLICATED_PAGE: &str = r#"<span class="total">25</span><ul>
+<li><a href="/b/1">1</a></li><li><a href="/b/2">2</a></li><li><a href="/b/3">3</a></li>
+<li><a href="/b/4">4</a></li><li><a href="/b/5">5</a></li><li><a href="/b/5">5</a></li></ul>"#;
+
+/// 与搜索流程相同的列表与分页配置
+const DISCOVERY: &str = r#"
+[discovery]
+url = "list?page={{ page }}"
+
+[discovery.pagination]
+type = "page_number"
+dedup_by = "url"
+total_items.steps = [{ css = ".total" }, { attr = "text" }, { filter = "to_int" }]
+
+[discovery.list]
+steps = [{ css = { expr = "li", all = true } }]
+
+[discovery.fields.title]
+steps = [{ css = "a" }, { attr = "text" }]
+
+[discovery.fields.url]
+steps = [{ css = "a" }, { attr = "href" }]
+"#;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn discovery_pages_like_search() {
+    let server = MockServer::html(DUPLICATED_PAGE);
+    let runtime = CrawlerRuntime::new(rule_for(&server, DISCOVERY), None).unwrap();
+
+    let response = runtime.discover(Default::default(), 1).await.unwrap();
+
+    assert_eq!(server.requests()[0].path, "/list?page=1");
+    assert_eq!(response.items.len(), 5);
+    assert_eq!(response.items[0].url, format!("{}/b/1", server.url));
+    assert_eq!(response.total_pages, Some(5));
+    assert!(response.has_next);
+}
+
+fn item(url: &str, raw: serde_json::Value) -> SearchItem {
+    SearchItem {
+        raw,
+        ..SearchItem::new(url.to_string(), url.to_string())
+    }
+}
+
+fn urls(items: &[SearchItem]) -> Vec<&str> {
+    items.iter().map(|item| item.url.as_str()).collect()
+}
+
+/// 第 2 页与第 1 页重复了 `/b/2`
+fn overlapping_pages(request: &Request) -> Response {
+    let items: &[u32] = match request.path.as_str() {
+        "/search?kw=kw&page=1" => &[1, 2],
+        _ => &[2, 3],
+    };
+    let list: String = items
+        .iter()
+        .map(|n| format!(r#"<li><a href="/b/{0}">{0}</a></li>"#, n))
+        .collect();
+    Response::html(format!("<ul>{}</ul>", list))
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn items_repeated_on_later_pages_are_dropped() {
+    let server = MockServer::start(overlapping_pages);
+    let mut rule = rule_for(
+        &server,
+        "[search.pagination]\ntype = \"page_number\"\ndedup_by = \"url\"",
+    );
+    rule.search.url = "{{ base_url }}/search?kw={{ keyword }}&page={{ page }}".into();
+    let runtime = CrawlerRuntime::new(rule, None).unwrap();
+    let url = |n: u32| format!("{}/b/{}", server.url, n);
+
+    let mut pager = runtime.search_pager("kw");
+    let first = runtime.search_page(&mut pager).await.unwrap();
+    assert_eq!(urls(&first.items), [url(1), url(2)]);
+
+    // 翻页后仍记得之前出现过的条目
+    let mut pager = pager.next_page_pager().unwrap();
+    let second = runtime.search_page(&mut pager).await.unwrap();
+    assert_eq!(urls(&second.items), [url(3)]);
+    assert_eq!(second.raw_items.len(), 1);
+    assert_eq!(server.hits(), 2);
+
+    // 单独调用 `search` 不跨页去重
+    let page = runtime.search("kw", 2).await.unwrap();
+    assert_eq!(urls(&page.items), [url(2), url(3)]);
+}
+
+#[test]
+fn items_without_dedup_key_are_kept() {
+    let pagination: Pagination =
+        toml::from_str("type = \"page_number\"\ndedup_by = \"id\"").unwrap();
+    let context = runtime_context(rule(""));
+    let mut pager = SearchPager::new(context, Some(pagination), SearchPagerState::new("kw", 1));
+
+    let first = pager.dedup_items(vec![
+        item("/b/1", json!({ "id": 1 })),
+        item("/b/2", Value::Null),
+    ]);
+    let mut pager = pager.next_page_pager().unwrap();
+    let second = pager.dedup_items(vec![
+        item("/b/3", json!({ "id": 1 })),
+        item("/b/4", Value::Null),
+        item("/b/5", json!({ "id": "" })),
+    ]);
+
+    assert_eq!(urls(&first), ["/b/1", "/b/2"]);
+    assert_eq!(urls(&second), ["/b/4", "/b/5"]);
+}
+
+#[test]
+fn pages_are_not_deduplicated_without_dedup_by() {
+    let context = runtime_context(rule(""));
+    let mut pager = SearchPager::new(context, None, SearchPagerState::new("kw", 1));
+
+    let items = pager.dedup_items(vec![item("/b/1", Value::Null), item("/b/1", Value::Null)]);
+    assert_eq!(urls(&items), ["/b/1", "/b/1"]);
+}
+
+/// 共 3 页，每页都有结果
+const THREE_PAGES: &str = r#"<span class="pages">3</span><ul>
+<li><a href="/b/1">1</a></li><li><a href="/b/2">2</a></li></ul>"#;
+
+fn total_pages_runtime(server: &MockServer, total_pages: &str) -> CrawlerRuntime {
+    let extra = format!(
+        "[search.pagination]\ntype = \"page_number\"\ntotal_pages.steps = {}",
+        total_pages
+    );
+    let mut rule = rule_for(server, &extra);
+    rule.search.url = "{{ base_url }}/search?kw={{ keyword }}&page={{ page }}".into();
+    CrawlerRuntime::new(rule, None).unwrap()
+}
+
+/// 按 `has_next` 逐页抓取，最多 `limit` 页，返回抓取的页数
+async fn crawl_pages(runtime: &CrawlerRuntime, limit: u32) -> u32 {
+    let mut page = 1;
+    while page < limit && runtime.search("kw", page).await.unwrap().has_next {
+        page += 1;
+    }
+    page
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn crawling_stops_after_the_extracted_total_pages() {
+    let server = MockServer::html(THREE_PAGES);
+    let runtime = total_pages_runtime(
+        &server,
+        r#"[{ css = ".pages" }, { attr = "text" }, { filter = "to_int" }]"#,
+    );
+
+    assert_eq!(crawl_pages(&runtime, 10).await, 3);
+    let paths: Vec<_> = server.requests().into_iter().map(|r| r.path).collect();
+    assert_eq!(
+        paths,
+        [
+            "/search?kw=kw&page=1",
+            "/search?kw=kw&page=2",
+            "/search?kw=kw&page=3"
+        ]
+    );
+    assert_eq!(runtime.search("kw", 3).await.unwrap().total_pages, Some(3));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failed_total_extraction_falls_back_to_item_count() {
+    let server = MockServer::html(THREE_PAGES);
+    let runtime = total_pages_runtime(&server, r#"[{ css = ".missing" }, { attr = "text" }]"#);
+
+    let response = runtime.search("kw", 3).await.unwrap();
+    assert_eq!(response.total_pages, None);
+    assert!(response.has_next);
+}
@@ -0,0 +1,79 @@
+//! 进度事件测试
+
+mod common;
+
+use common::{DETAIL_PAGE, MockServer, Response, rule_for};
+use crawler_runtime::{crawler::CrawlerRuntime, util::ProgressListener};
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+
+/// 按顺序记录进度事件
+#[derive(Debug, Default)]
+struct Recorder(Mutex<Vec<String>>);
+
+impl Recorder {
+    fn events(&self) -> Vec<String> {
+        self.0.lock().unwrap().clone()
+    }
+
+    fn push(&self, event: String) {
+        self.0.lock().unwrap().push(event);
+    }
+}
+
+impl ProgressListener for Recorder {
+    fn on_request(&self, url: &str) {
+        let path = url.splitn(4, '/').nth(3).unwrap_or_default();
+        self.push(format!("request /{}", path));
+    }
+
+    fn on_page(&self, page: u32, items_count: usize) {
+        self.push(format!("page {} ({})", page, items_count));
+    }
+
+    fn on_item(&self, item: &Value) {
+        self.push(format!("item {}", item["title"].as_str().unwrap_or("?")));
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn listener_receives_events_in_order() {
+    let server = MockServer::start(|request| {
+        if request.path.starts_with("/search") {
+            Response::html(r#"<li><a href="/b/1">one</a></li><li><a href="/b/2">two</a></li>"#)
+        } else {
+            Response::html(DETAIL_PAGE)
+        }
+    });
+    let recorder = Arc::new(Recorder::default());
+    let runtime = CrawlerRuntime::builder(rule_for(&server, ""))
+        .with_progress_listener(recorder.clone())
+        .build()
+        .unwrap();
+
+    runtime.search("kw", 1).await.unwrap();
+    runtime
+        .detail(&format!("{}/b/1", server.url))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        recorder.events(),
+        [
+            "request /search?kw=kw",
+            "item one",
+            "item two",
+            "page 1 (2)",
+            "request /b/1",
+        ]
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn runtime_works_without_listener() {
+    let server = MockServer::html(r#"<li><a href="/b/1">one</a></li>"#);
+    let runtime = CrawlerRuntime::new(rule_for(&server, ""), None).unwrap();
+
+    assert!(runtime.runtime_ctx().progress_listener().is_none());
+    assert_eq!(runtime.search("kw", 1).await.unwrap().items.len(), 1);
+}
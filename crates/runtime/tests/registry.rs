@@ -0,0 +1,74 @@
+//! 多规则注册表测试
+
+mod common;
+
+use common::{MockServer, Response, rule, rule_for};
+use crawler_runtime::{
+    crawler::{CrawlerRuntime, RuleRegistry},
+    rule::{RuleFile, RuleFormat},
+};
+use crawler_schema::{config::MediaType, core::CrawlerRule};
+
+fn rule_file(rule: CrawlerRule) -> RuleFile {
+    RuleFile {
+        rule,
+        format: RuleFormat::Toml,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn search_all_merges_results_from_every_rule() {
+    let first = MockServer::html(r#"<li><a href="/b/1">one</a></li>"#);
+    let second =
+        MockServer::html(r#"<li><a href="/b/2">two</a></li><li><a href="/b/3">three</a></li>"#);
+    let broken = MockServer::start(|_| Response::abort());
+
+    let mut registry = RuleRegistry::new();
+    registry
+        .register("b", rule_file(rule_for(&second, "")))
+        .unwrap();
+    registry
+        .register("a", rule_file(rule_for(&first, "")))
+        .unwrap();
+    registry.register_runtime(
+        "c",
+        CrawlerRuntime::new(rule_for(&broken, "[http]\nretry_count = 0"), None).unwrap(),
+    );
+    assert_eq!(registry.len(), 3);
+
+    let aggregated = registry.search_all("kw").await;
+    let items: Vec<_> = aggregated
+        .items()
+        .map(|(id, item)| (id, item.title.as_str()))
+        .collect();
+    assert_eq!(items, [("a", "one"), ("b", "two"), ("b", "three")]);
+    assert_eq!(aggregated.errors.len(), 1);
+    assert_eq!(aggregated.errors[0].0, "c");
+    assert_eq!(first.hits() + second.hits(), 2);
+}
+
+#[test]
+fn rules_are_found_by_id_domain_and_media_type() {
+    let mut book = rule("");
+    book.meta.domain = "www.Book.test".into();
+    let mut video = rule("");
+    video.meta.domain = "video.test".into();
+    video.meta.media_type = MediaType::Video;
+
+    let mut registry = RuleRegistry::new();
+    registry.register("book", rule_file(book)).unwrap();
+    registry.register("video", rule_file(video)).unwrap();
+
+    assert!(registry.get("book").is_some());
+    let ids = |found: Vec<(&str, &CrawlerRuntime)>| -> Vec<String> {
+        found.into_iter().map(|(id, _)| id.to_string()).collect()
+    };
+    assert_eq!(ids(registry.find_by_domain("book.test")), ["book"]);
+    assert_eq!(
+        ids(registry.find_by_media_type(MediaType::Video)),
+        ["video"]
+    );
+
+    assert!(registry.remove("book").is_some());
+    assert_eq!(registry.ids().collect::<Vec<_>>(), ["video"]);
+}
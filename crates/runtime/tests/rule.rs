@@ -0,0 +1,210 @@
+//! 规则文件加载测试
+
+mod common;
+
+use common::BASE_RULE;
+use crawler_runtime::{
+    RuntimeError,
+    rule::{RuleFile, RuleFormat, validate_against_schema},
+};
+use std::path::PathBuf;
+
+/// 在临时目录写入规则文件
+fn write_rule(name: &str, content: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("crawler-rule-{}-{}", std::process::id(), name));
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+fn json_rule() -> String {
+    let value: serde_json::Value = toml::from_str(BASE_RULE).unwrap();
+    serde_json::to_string(&value).unwrap()
+}
+
+#[test]
+fn loads_toml_and_json_by_extension() {
+    let toml_path = write_rule("a.toml", BASE_RULE);
+    let json_path = write_rule("a.JSON", &json_rule());
+
+    let from_toml = RuleFile::from_path(&toml_path).unwrap();
+    let from_json = RuleFile::from_path(&json_path).unwrap();
+    assert_eq!(from_toml.format, RuleFormat::Toml);
+    assert_eq!(from_json.format, RuleFormat::Json);
+    assert_eq!(from_toml.rule.meta.name, "test");
+    assert_eq!(from_json.rule.meta.name, "test");
+
+    std::fs::remove_file(toml_path).unwrap();
+    std::fs::remove_file(json_path).unwrap();
+}
+
+#[test]
+fn unknown_extension_is_rejected() {
+    let path = write_rule("a.yaml", BASE_RULE);
+
+    let err = RuleFile::from_path(&path).unwrap_err();
+    assert!(matches!(err, RuntimeError::Config(_)), "{}", err);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn bom_is_stripped_and_encoding_normalized() {
+    let source = format!(
+        "\u{feff}{}",
+        BASE_RULE.replace("[meta]\n", "[meta]\nencoding = \"GBK\"\n")
+    );
+
+    let file = RuleFile::from_str(&source, RuleFormat::Toml).unwrap();
+    assert_eq!(file.rule.meta.encoding.as_deref(), Some("gbk"));
+    let response = file.rule.http.and_then(|h| h.response).unwrap();
+    assert!(response.encoding.is_some());
+}
+
+#[test]
+fn parse_and_validation_errors_are_reported() {
+    let err = RuleFile::from_str("[meta]\nname = 1", RuleFormat::Toml).unwrap_err();
+    assert!(
+        matches!(&err, RuntimeError::RuleParse { format, .. } if format == "toml"),
+        "{}",
+        err
+    );
+
+    let err = RuleFile::from_str("{", RuleFormat::Json).unwrap_err();
+    assert!(
+        matches!(&err, RuntimeError::RuleParse { format, .. } if format == "json"),
+        "{}",
+        err
+    );
+
+    let invalid = BASE_RULE.replace(
+        r#"url = "{{ base_url }}/search"#,
+        r#"url = "{{ nope }}/search"#,
+    );
+    let err = RuleFile::from_str(&invalid, RuleFormat::Toml).unwrap_err();
+    assert!(
+        matches!(err, RuntimeError::TemplateValidation { .. }),
+        "{}",
+        err
+    );
+}
+
+/// 以指定规范版本加载最小规则
+fn load_with_spec(version: &str) -> crawler_runtime::Result<RuleFile> {
+    let source = BASE_RULE.replace(
+        r#"spec_version = "1.0.0""#,
+        &format!(r#"spec_version = "{}""#, version),
+    );
+    RuleFile::from_str(&source, RuleFormat::Toml)
+}
+
+#[test]
+fn compatible_spec_versions_are_accepted() {
+    for version in ["1.0.0", "1.0", "1", "v1.0.7", " 1.0.3 "] {
+        assert!(load_with_spec(version).is_ok(), "{}", version);
+    }
+}
+
+#[test]
+fn spec_versions_outside_the_range_are_rejected() {
+    for version in ["0.9.9", "0.1", "1.1.0", "2.0.0"] {
+        let err = load_with_spec(version).unwrap_err();
+        match &err {
+            RuntimeError::IncompatibleSpecVersion {
+                required,
+                supported,
+            } => {
+                assert_eq!(required, version);
+                assert_eq!(supported, "1.0.0 ~ 1.0.x");
+            }
+            _ => panic!("{}: {}", version, err),
+        }
+        assert_eq!(err.error_code(), "RULE_INCOMPATIBLE_SPEC");
+    }
+
+    // 加载文件时同样检查
+    let path = write_rule(
+        "future.toml",
+        &BASE_RULE.replace(r#"spec_version = "1.0.0""#, r#"spec_version = "2.0""#),
+    );
+    let err = RuleFile::from_path(&path).unwrap_err();
+    assert!(
+        matches!(err, RuntimeError::IncompatibleSpecVersion { .. }),
+        "{}",
+        err
+    );
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn malformed_spec_version_is_a_config_error() {
+    for version in ["1.x", "1.0.0.0", "latest"] {
+        let err = load_with_spec(version).unwrap_err();
+        assert!(
+            matches!(&err, RuntimeError::InvalidConfigValue { field, .. } if field == "meta.spec_version"),
+            "{}: {}",
+            version,
+            err
+        );
+    }
+}
+
+fn rule_value(source: &str) -> serde_json::Value {
+    toml::from_str(source).unwrap()
+}
+
+#[test]
+fn valid_rule_passes_schema_check() {
+    let result = validate_against_schema(&rule_value(BASE_RULE));
+    assert!(result.is_valid(), "{}", result);
+}
+
+#[test]
+fn schema_check_reports_field_paths() {
+    let mut value = rule_value(BASE_RULE);
+    value["meta"].as_object_mut().unwrap().remove("name");
+    value["search"]["fields"]["title"]["nullable"] = "yes".into();
+
+    let result = validate_against_schema(&value);
+    let missing: Vec<_> = result.errors_at("meta.name").collect();
+    assert_eq!(missing.len(), 1, "{}", result);
+    assert_eq!(missing[0].message, "缺少必填字段");
+    assert_eq!(missing[0].to_string(), "meta.name: 缺少必填字段");
+    assert!(
+        result
+            .errors_at("search.fields.title.nullable")
+            .next()
+            .is_some(),
+        "{}",
+        result
+    );
+}
+
+#[test]
+fn schema_check_locates_unknown_fields_and_array_items() {
+    let mut value = rule_value(BASE_RULE);
+    value["meta"]["bogus"] = true.into();
+    value["search"]["fields"]["title"]["steps"][1] = 42.into();
+
+    let result = validate_against_schema(&value);
+    let unknown: Vec<_> = result.errors_at("meta.bogus").collect();
+    assert_eq!(unknown.len(), 1, "{}", result);
+    assert_eq!(unknown[0].message, "未知字段");
+    assert!(
+        result
+            .errors
+            .iter()
+            .any(|e| e.path.starts_with("search.fields.title.steps[1]")),
+        "{}",
+        result
+    );
+}
+
+#[test]
+fn parse_errors_are_explained_by_the_schema() {
+    let source = BASE_RULE.replace("name = \"test\"\n", "");
+
+    let err = RuleFile::from_str(&source, RuleFormat::Toml).unwrap_err();
+    let RuntimeError::RuleParse { error, .. } = err else {
+        panic!("{}", err);
+    };
+    assert!(error.contains("meta.name: 缺少必填字段"), "{}", error);
+}
@@ -0,0 +1,112 @@
+//! 运行时高层 API 集成测试
+
+mod common;
+
+use common::{BASE_RULE, DETAIL_PAGE, MockServer, Response, rule_for};
+use crawler_runtime::{
+    RuntimeError,
+    crawler::CrawlerRuntime,
+    rule::{RuleFile, RuleFormat},
+};
+use std::collections::HashMap;
+
+fn runtime(server: &MockServer) -> CrawlerRuntime {
+    let source = BASE_RULE.replace(
+        r#"domain = "127.0.0.1""#,
+        &format!(r#"domain = "{}""#, server.url),
+    );
+    let file = RuleFile::from_str(&source, RuleFormat::Toml).unwrap();
+    CrawlerRuntime::new(file.into_rule(), None).unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn rule_file_runs_search() {
+    let server = MockServer::html(r#"<ul><li><a href="/b/1">斗破苍穹</a></li></ul>"#);
+
+    let response = runtime(&server).search("斗破", 1).await.unwrap();
+    assert_eq!(response.items.len(), 1);
+    assert_eq!(response.items[0].title, "斗破苍穹");
+    assert_eq!(response.items[0].url, format!("{}/b/1", server.url));
+    assert_eq!(server.requests()[0].path, "/search?kw=%E6%96%97%E7%A0%B4");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn undefined_flows_are_reported() {
+    let server = MockServer::html("");
+    let runtime = runtime(&server);
+
+    let err = runtime.content("/c/1").await.unwrap_err();
+    assert!(
+        matches!(&err, RuntimeError::UndefinedFlow { flow } if flow == "content"),
+        "{}",
+        err
+    );
+    let err = runtime.discover(HashMap::new(), 1).await.unwrap_err();
+    assert!(
+        matches!(&err, RuntimeError::UndefinedFlow { flow } if flow == "discovery"),
+        "{}",
+        err
+    );
+    assert_eq!(server.hits(), 0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn book_content_keeps_html_and_plain_text() {
+    let server = MockServer::html(
+        r#"<h2>第一章</h2><div id="content"><p>　　第一段</p><br><p>第二段 &amp; 更多</p>
+<script>ads()</script></div><a id="next" href="/c/2">下一页</a>"#,
+    );
+    let rule = rule_for(
+        &server,
+        r##"
+[content]
+url = "{{ base_url }}{{ content_url }}"
+
+[content.fields]
+media_type = "book"
+
+[content.fields.content]
+steps = [{ css = "#content" }, { attr = "html" }]
+
+[content.fields.title]
+steps = [{ css = "h2" }, { attr = "text" }]
+
+[content.fields.next_url]
+steps = [{ css = "#next" }, { attr = "href" }]
+"##,
+    );
+    let runtime = CrawlerRuntime::new(rule, None).unwrap();
+
+    let data = runtime.content("/c/1").await.unwrap().data;
+    let html = data["content_html"].as_str().unwrap();
+    assert!(html.contains("<p>"), "{}", html);
+    assert_eq!(data["content_text"], "第一段\n第二段 & 更多");
+    assert_eq!(data["title"], "第一章");
+    assert_eq!(data["next_url"], "/c/2");
+    assert!(data.get("prev_url").is_none());
+    assert_eq!(server.requests()[0].path, "/c/1");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn responses_serialize_to_snake_case_json() {
+    let server = MockServer::start(|request| match request.path.as_str() {
+        "/book/1" => Response::html(DETAIL_PAGE),
+        _ => Response::html(r#"<ul><li><a href="/book/1">title</a></li></ul>"#),
+    });
+    let runtime = runtime(&server);
+
+    let search = runtime.search("kw", 1).await.unwrap().to_json();
+    assert_eq!(search["items"][0]["title"], "title");
+    assert_eq!(search["has_next"], true);
+    assert_eq!(search["skipped"], 0);
+    assert!(search["raw_items"].is_array());
+
+    let url = format!("{}/book/1", server.url);
+    let detail = runtime.detail(&url).await.unwrap().to_json();
+    let book = &detail["book"];
+    assert_eq!(book["title"], "title");
+    assert_eq!(book["author"], "author");
+    assert_eq!(book["chapters"][1]["title"], "c2");
+    assert_eq!(book["chapters"][1]["url"], "/c/2");
+    assert!(book.get("cover").is_none(), "{}", book);
+}
@@ -0,0 +1,374 @@
+//! 脚本步骤集成测试
+
+mod common;
+
+use common::{MockServer, Response, extract_html, rule, rule_for, runtime_context};
+use crawler_runtime::{
+    RuntimeError,
+    context::FlowContext,
+    crawler::CrawlerRuntime,
+    extractor::ExtractValueData,
+};
+use serde_json::json;
+
+fn fetch_field(server: &MockServer) -> String {
+    format!(
+        "steps = [{{ script = {{ code = 'http_get(`{}/data`)' }} }}]",
+        server.url
+    )
+}
+
+#[test]
+fn http_get_is_rejected_when_network_disabled() {
+    let server = MockServer::html("ok");
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+
+    let err = extract_html(&runtime, &flow, &fetch_field(&server), "").unwrap_err();
+    assert!(err.to_string().contains("allow_network"), "{}", err);
+    assert_eq!(server.hits(), 0);
+}
+
+#[test]
+fn http_get_works_when_network_allowed() {
+    let server = MockServer::html("ok");
+    let runtime = runtime_context(rule("[script_security]\nallow_network = true"));
+    let flow = FlowContext::new(runtime.clone());
+
+    let value = extract_html(&runtime, &flow, &fetch_field(&server), "").unwrap();
+    assert_eq!(value.as_str(), Some("ok"));
+}
+
+#[test]
+fn http_post_sends_the_body() {
+    let server = MockServer::start(|request| Response::html(format!("got {}", request.body)));
+    let runtime = runtime_context(rule("[script_security]\nallow_network = true"));
+    let flow = FlowContext::new(runtime.clone());
+    let field = format!(
+        "steps = [{{ script = {{ code = 'http_post(\"{}/token\", \"id=1\")', engine = \"javascript\" }} }}]",
+        server.url
+    );
+
+    let value = extract_html(&runtime, &flow, &field, "").unwrap();
+    assert_eq!(value.as_str(), Some("got id=1"));
+    assert_eq!(server.requests()[0].method, "POST");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn http_get_works_inside_multi_thread_runtime() {
+    let server = MockServer::html("ok");
+    let runtime = runtime_context(rule("[script_security]\nallow_network = true"));
+    let flow = FlowContext::new(runtime.clone());
+
+    let value = extract_html(&runtime, &flow, &fetch_field(&server), "").unwrap();
+    assert_eq!(value.as_str(), Some("ok"));
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn http_get_is_rejected_on_current_thread_runtime() {
+    let server = MockServer::html("ok");
+    let runtime = runtime_context(rule("[script_security]\nallow_network = true"));
+    let flow = FlowContext::new(runtime.clone());
+
+    let err = extract_html(&runtime, &flow, &fetch_field(&server), "").unwrap_err();
+    assert!(err.to_string().contains("current_thread"), "{}", err);
+    assert_eq!(server.hits(), 0);
+}
+
+fn script_field(code: &str) -> String {
+    format!("steps = [{{ script = {{ code = '{}' }} }}]", code)
+}
+
+#[test]
+fn scalar_results_keep_json_type() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+
+    let number = extract_html(&runtime, &flow, &script_field("40 + 2"), "").unwrap();
+    assert!(
+        matches!(number.as_ref(), ExtractValueData::Json(v) if **v == json!(42)),
+        "{:?}",
+        number
+    );
+    let flag = extract_html(&runtime, &flow, &script_field("1 < 2"), "").unwrap();
+    assert!(
+        matches!(flag.as_ref(), ExtractValueData::Json(v) if **v == json!(true)),
+        "{:?}",
+        flag
+    );
+}
+
+#[test]
+fn non_json_results_stay_strings() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+
+    let value = extract_html(&runtime, &flow, &script_field("`hello world`"), "").unwrap();
+    assert_eq!(value.as_str(), Some("hello world"));
+}
+
+#[test]
+fn json_text_results_are_parsed() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+
+    let value = extract_html(&runtime, &flow, &script_field(r#"`{"a":1}`"#), "").unwrap();
+    assert_eq!(value.to_owned_json(), json!({ "a": 1 }));
+}
+
+#[test]
+fn parse_date_any_accepts_date_only_formats() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let code = "parse_date_any(`2024/01/02`, [`%Y-%m-%d %H:%M`, `%Y/%m/%d`])";
+
+    let value = extract_html(&runtime, &flow, &script_field(code), "").unwrap();
+    assert!(
+        matches!(value.as_ref(), ExtractValueData::Json(v) if **v == json!(1704153600)),
+        "{:?}",
+        value
+    );
+}
+
+#[test]
+fn unused_modules_are_not_loaded() {
+    let runtime = runtime_context(rule(
+        r#"
+[scripting.modules.text]
+engine = "rhai"
+code = 'fn shout(s) { s.to_upper() }'
+
+[scripting.modules.broken]
+engine = "rhai"
+code = 'fn oops( {'
+"#,
+    ));
+    let flow = FlowContext::new(runtime.clone());
+    assert_eq!(runtime.script_modules().load_count(), 0);
+
+    let field = "steps = [{ script = { module = 'text', function = 'shout' } }]";
+    let value = extract_html(&runtime, &flow, field, "abc").unwrap();
+    assert_eq!(value.as_str(), Some("ABC"));
+    // 语法错误的模块未被调用，也就不会被编译
+    assert_eq!(runtime.script_modules().load_count(), 1);
+    assert!(runtime.script_modules().is_loaded("text"));
+    assert!(!runtime.script_modules().is_loaded("broken"));
+
+    let field = "steps = [{ script = { module = 'broken', function = 'oops' } }]";
+    assert!(extract_html(&runtime, &flow, field, "abc").is_err());
+}
+
+#[test]
+fn module_scripts_are_linked_once() {
+    let runtime = runtime_context(rule(
+        r#"
+[scripting.modules.text]
+engine = "rhai"
+code = 'fn shout(s) { s.to_upper() }'
+
+[scripting.modules.book]
+engine = "rhai"
+code = 'fn title(s) { text.shout(s) + `!` }'
+"#,
+    ));
+    let flow = FlowContext::new(runtime.clone());
+    let field = "steps = [{ script = { module = 'book', function = 'title' } }]";
+
+    for _ in 0..3 {
+        let value = extract_html(&runtime, &flow, field, "abc").unwrap();
+        assert_eq!(value.as_str(), Some("ABC!"), "{:?}", value);
+    }
+    assert_eq!(runtime.script_modules().load_count(), 2);
+    assert_eq!(runtime.script_modules().link_count(), 1);
+}
+
+#[test]
+fn same_name_functions_in_two_modules_do_not_collide() {
+    let runtime = runtime_context(rule(
+        r#"
+[scripting.modules.trim]
+engine = "javascript"
+code = 'function clean(s) { return s.trim(); }'
+
+[scripting.modules.shout]
+engine = "javascript"
+code = 'const clean = (s) => s.toUpperCase();'
+"#,
+    ));
+    let flow = FlowContext::new(runtime.clone());
+    let field = r#"steps = [{ script = { code = 'shout.clean(trim.clean(input)) + "!"', engine = "javascript" } }]"#;
+
+    let value = extract_html(&runtime, &flow, field, "  abc ").unwrap();
+    assert_eq!(value.as_str(), Some("ABC!"), "{:?}", value);
+}
+
+#[test]
+fn javascript_modules_link_function_expressions() {
+    let runtime = runtime_context(rule(
+        r#"
+[scripting.modules.text]
+engine = "javascript"
+code = """
+let wrap = function (s) { return "[" + s + "]"; };
+const title = s => text.wrap(s.trim());
+"""
+"#,
+    ));
+    let flow = FlowContext::new(runtime.clone());
+
+    // 模块作为入口时，`text.wrap(` 改写为模块自身的函数
+    let field = "steps = [{ script = { module = 'text', function = 'title' } }]";
+    let value = extract_html(&runtime, &flow, field, " abc ").unwrap();
+    assert_eq!(value.as_str(), Some("[abc]"), "{:?}", value);
+
+    let field = r#"steps = [{ script = { code = 'text.title(input)', engine = "javascript" } }]"#;
+    let value = extract_html(&runtime, &flow, field, " abc ").unwrap();
+    assert_eq!(value.as_str(), Some("[abc]"), "{:?}", value);
+}
+
+#[test]
+fn calling_a_missing_module_function_is_reported() {
+    let runtime = runtime_context(rule(
+        r#"
+[scripting.modules.text]
+engine = "rhai"
+code = 'fn shout(s) { s.to_upper() }'
+"#,
+    ));
+    let flow = FlowContext::new(runtime.clone());
+    let field = "steps = [{ script = { code = 'text.whisper(input)' } }]";
+
+    let err = extract_html(&runtime, &flow, field, "abc").unwrap_err();
+    assert!(
+        matches!(&err, RuntimeError::UndefinedScriptFunction { module, function }
+            if module == "text" && function == "whisper"),
+        "{}",
+        err
+    );
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn remote_scripts_load_on_current_thread_runtime() {
+    let server = MockServer::start(|request| match request.path.as_str() {
+        "/title.rhai" => Response::html("input.to_upper()"),
+        _ => Response::html(r#"<li><a href="/b/1">one</a></li>"#),
+    });
+    let title = format!(
+        "steps = [{{ css = 'a' }}, {{ attr = 'text' }}, {{ script = {{ url = '{}/title.rhai' }} }}]",
+        server.url
+    );
+    let mut rule = rule_for(&server, "");
+    rule.search.fields.title = toml::from_str(&title).expect("字段规则无效");
+    let runtime = CrawlerRuntime::new(rule, None).unwrap();
+
+    for _ in 0..2 {
+        let response = runtime.search("kw", 1).await.unwrap();
+        assert_eq!(response.items[0].title, "ONE");
+    }
+    assert_eq!(runtime.runtime_ctx().remote_scripts().fetch_count(), 1);
+}
+
+/// 生成 2000 个字符的字符串
+const LONG_RESULT: &str = "let s = ``; for i in 0..2000 { s += `x` } s";
+
+#[test]
+fn oversized_script_results_are_rejected() {
+    let runtime = runtime_context(rule("[script_security]\nmax_data_kb = 1"));
+    let flow = FlowContext::new(runtime.clone());
+
+    let err = extract_html(&runtime, &flow, &script_field(LONG_RESULT), "").unwrap_err();
+    assert!(
+        matches!(
+            err,
+            RuntimeError::ScriptDataTooLarge { ref target, size: 2000, limit: 1024 } if target == "返回值"
+        ),
+        "{}",
+        err
+    );
+
+    let value = extract_html(&runtime, &flow, &script_field("`short`"), "").unwrap();
+    assert_eq!(value.as_str(), Some("short"));
+}
+
+#[test]
+fn oversized_script_input_is_rejected() {
+    let runtime = runtime_context(rule("[script_security]\nmax_data_kb = 1"));
+    let flow = FlowContext::new(runtime.clone());
+    let html = "x".repeat(2000);
+
+    let err = extract_html(&runtime, &flow, &script_field("`ok`"), &html).unwrap_err();
+    assert!(
+        matches!(err, RuntimeError::ScriptDataTooLarge { ref target, .. } if target == "输入"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn script_security_can_lift_the_data_limit() {
+    let runtime = runtime_context(rule("[script_security]\nmax_data_kb = 1"));
+    let flow = FlowContext::new(runtime.clone());
+    let field = format!(
+        "steps = [{{ script = {{ code = '{}', security = {{ max_data_kb = 0 }} }} }}]",
+        LONG_RESULT
+    );
+
+    let value = extract_html(&runtime, &flow, &field, "").unwrap();
+    assert_eq!(value.as_str().map(str::len), Some(2000));
+}
+
+/// 以脚本计算搜索结果标题的运行时，响应带自定义响应头与 Cookie
+async fn search_title_by_script(code: &str) -> (MockServer, String) {
+    let server = MockServer::start(|_| {
+        Response::html(r#"<li><a href="/b/1">斗破苍穹</a></li>"#)
+            .header("x-token", "t-42")
+            .header(
+                "Set-Cookie",
+                "sid=abc; Path=/; Expires=Wed, 21 Oct 2026 07:28:00 GMT",
+            )
+            .header("Set-Cookie", "lang=zh; HttpOnly")
+    });
+    let mut rule = rule_for(&server, "");
+    rule.search.fields.title = toml::from_str(&format!(
+        r#"steps = [{{ css = "a" }}, {{ attr = "text" }}, {{ script = {{ code = '{}' }} }}]"#,
+        code
+    ))
+    .unwrap();
+    let runtime = CrawlerRuntime::new(rule, None).unwrap();
+
+    let response = runtime.search("kw", 1).await.unwrap();
+    let title = response.items[0].title.clone();
+    (server, title)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn scripts_read_the_response_status_and_headers() {
+    let (_, title) = search_title_by_script(
+        "if __status == 200 { input + `|` + __headers[`X-Token`] } else { `blocked` }",
+    )
+    .await;
+    assert_eq!(title, "斗破苍穹|t-42");
+
+    let (_, title) = search_title_by_script(
+        "__cookies[`sid`] + `,` + __cookies[`lang`] + `,` + __cookies.len()",
+    )
+    .await;
+    assert_eq!(title, "abc,zh,2");
+
+    let (server, title) = search_title_by_script("__url").await;
+    assert_eq!(title, format!("{}/search?kw=kw", server.url));
+}
+
+#[test]
+fn response_variables_are_absent_without_a_request() {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let value = extract_html(
+        &runtime,
+        &flow,
+        &script_field("is_def_var(`__status`) + `,` + is_def_var(`__headers`)"),
+        "",
+    )
+    .unwrap();
+    assert_eq!(value.as_str(), Some("false,false"));
+}
@@ -0,0 +1,75 @@
+//! 搜索流程集成测试
+
+mod common;
+
+use common::{MockServer, extract_html, field, rule, rule_for, runtime_context};
+use crawler_runtime::{context::FlowContext, crawler::CrawlerRuntime};
+use serde_json::json;
+
+/// 中间一项是缺少链接的广告
+const AD_PAGE: &str = r#"<ul><li><a href="/b/1">1</a></li><li><span>广告</span></li>
+<li><a href="/b/2">2</a></li></ul>"#;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn items_missing_required_fields_are_skipped() {
+    let server = MockServer::html(AD_PAGE);
+    let runtime = CrawlerRuntime::new(rule_for(&server, ""), None).unwrap();
+
+    let response = runtime.search("kw", 1).await.unwrap();
+    let urls: Vec<_> = response.items.iter().map(|i| i.url.as_str()).collect();
+    assert_eq!(
+        urls,
+        [format!("{}/b/1", server.url), format!("{}/b/2", server.url)]
+    );
+    assert_eq!(response.skipped, 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn items_missing_required_fields_fail_when_not_skipped() {
+    let server = MockServer::html(AD_PAGE);
+    let mut rule = rule_for(&server, "");
+    rule.search.skip_invalid_items = false;
+    let runtime = CrawlerRuntime::new(rule, None).unwrap();
+
+    assert!(runtime.search("kw", 1).await.is_err());
+}
+
+/// 列表与字段均为 JSONPath 的搜索运行时
+fn json_api_runtime(server: &MockServer) -> CrawlerRuntime {
+    let mut rule = rule_for(server, "");
+    rule.search.list = field(r#"steps = [{ json = "$.data.list[*]" }]"#);
+    rule.search.fields.title = toml::from_str(r#"steps = [{ json = "$.title" }]"#).unwrap();
+    rule.search.fields.url = toml::from_str(r#"steps = [{ json = "$.url" }]"#).unwrap();
+    CrawlerRuntime::new(rule, None).unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn json_api_responses_are_queried_directly() {
+    let body = r#"{"data":{"list":[{"title":"斗破苍穹","url":"/b/1"},{"title":"凡人修仙传","url":"/b/2"}]}}"#;
+    let server = MockServer::html(body);
+
+    // 未声明 JSON 类型的响应体也按 JSON 查询，结果为数组
+    let context = runtime_context(rule(""));
+    let flow = FlowContext::new(context.clone());
+    let titles = extract_html(
+        &context,
+        &flow,
+        r#"steps = [{ json = "$.data.list[*].title" }]"#,
+        body,
+    )
+    .unwrap();
+    assert_eq!(titles.to_owned_json(), json!(["斗破苍穹", "凡人修仙传"]));
+
+    let response = json_api_runtime(&server).search("kw", 1).await.unwrap();
+    let titles: Vec<_> = response.items.iter().map(|i| i.title.as_str()).collect();
+    assert_eq!(titles, ["斗破苍穹", "凡人修仙传"]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn single_json_result_is_one_item() {
+    let server = MockServer::html(r#"{"data":{"list":[{"title":"斗破苍穹","url":"/b/1"}]}}"#);
+
+    let response = json_api_runtime(&server).search("kw", 1).await.unwrap();
+    assert_eq!(response.items.len(), 1);
+    assert_eq!(response.items[0].url, format!("{}/b/1", server.url));
+}
@@ -0,0 +1,52 @@
+//! 规则冒烟测试
+
+mod common;
+
+use common::{DETAIL_PAGE, MockServer, Response, rule_for};
+use crawler_runtime::crawler::{smoke::SmokeStatus, smoke_test};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn smoke_test_reports_each_flow() {
+    let server = MockServer::start(|req| {
+        if req.path.starts_with("/search") {
+            Response::html(r#"<ul><li><a href="/b/1">斗破苍穹</a></li></ul>"#)
+        } else {
+            Response::html(DETAIL_PAGE)
+        }
+    });
+
+    let report = smoke_test(rule_for(&server, ""), "斗破").await;
+    assert!(report.is_passed(), "{:?}", report);
+    assert!(report.search.fields["title"]);
+    assert!(!report.search.fields["cover"]);
+    assert!(report.detail.fields["chapters"]);
+    assert!(!report.detail.fields["intro"]);
+    assert_eq!(server.requests()[1].path, "/b/1");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn empty_search_skips_detail() {
+    let server = MockServer::html("<ul></ul>");
+    let mut rule = rule_for(&server, "");
+    rule.search.list.nullable = true;
+
+    let report = smoke_test(rule, "斗破").await;
+    assert_eq!(report.search.status, SmokeStatus::Empty);
+    assert_eq!(report.detail.status, SmokeStatus::Skipped);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failed_detail_is_reported() {
+    let server = MockServer::start(|req| {
+        if req.path.starts_with("/search") {
+            Response::html(r#"<ul><li><a href="/b/1">斗破苍穹</a></li></ul>"#)
+        } else {
+            Response::status(404)
+        }
+    });
+
+    let report = smoke_test(rule_for(&server, ""), "斗破").await;
+    assert_eq!(report.search.status, SmokeStatus::Passed);
+    assert_eq!(report.detail.status, SmokeStatus::Failed);
+    assert!(report.detail.error.is_some());
+}
@@ -0,0 +1,122 @@
+//! 流式 JSON 提取测试
+
+mod common;
+
+use common::{MockServer, Response};
+use crawler_runtime::http::{for_each_json_item, stream_json_items};
+use serde_json::json;
+use std::{
+    io::Read,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+/// 按需生成 `{"total":N,"items":[{"id":0},...]}` 的读取器，记录已读取的字节数
+struct ItemsReader {
+    total: usize,
+    next: usize,
+    pending: Vec<u8>,
+    read: Arc<AtomicUsize>,
+}
+
+impl ItemsReader {
+    fn new(total: usize, read: Arc<AtomicUsize>) -> Self {
+        Self {
+            total,
+            next: 0,
+            pending: format!(r#"{{"total":{},"items":["#, total).into_bytes(),
+            read,
+        }
+    }
+}
+
+impl Read for ItemsReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.pending.is_empty() && self.next <= self.total {
+            let chunk = match self.next {
+                n if n == self.total => "]}".to_string(),
+                0 => r#"{"id":0}"#.to_string(),
+                n => format!(r#",{{"id":{}}}"#, n),
+            };
+            self.pending = chunk.into_bytes();
+            self.next += 1;
+        }
+        let n = buf.len().min(self.pending.len());
+        buf[..n].copy_from_slice(&self.pending[..n]);
+        self.pending.drain(..n);
+        self.read.fetch_add(n, Ordering::SeqCst);
+        Ok(n)
+    }
+}
+
+#[test]
+fn large_array_is_extracted_item_by_item() {
+    const TOTAL: usize = 200_000;
+    let read = Arc::new(AtomicUsize::new(0));
+    let mut read_at_first = None;
+    let mut next_id = 0;
+
+    let count = for_each_json_item(
+        ItemsReader::new(TOTAL, read.clone()),
+        "$.items[*]",
+        |item| {
+            assert_eq!(item, json!({ "id": next_id }));
+            next_id += 1;
+            read_at_first.get_or_insert(read.load(Ordering::SeqCst));
+            true
+        },
+    )
+    .unwrap();
+
+    assert_eq!(count, TOTAL);
+    // 首个元素在读入少量数据后即产出，而不是等整个响应解析完
+    let total_bytes = read.load(Ordering::SeqCst);
+    assert!(
+        read_at_first.unwrap() < total_bytes / 100,
+        "{:?}",
+        read_at_first
+    );
+}
+
+#[test]
+fn stopping_early_leaves_the_rest_unread() {
+    let read = Arc::new(AtomicUsize::new(0));
+    let mut seen = 0;
+
+    let count = for_each_json_item(
+        ItemsReader::new(100_000, read.clone()),
+        "$.items[*]",
+        |_| {
+            seen += 1;
+            seen < 3
+        },
+    )
+    .unwrap();
+
+    assert_eq!(count, 3);
+    assert!(read.load(Ordering::SeqCst) < 64 * 1024);
+}
+
+#[test]
+fn invalid_paths_are_rejected() {
+    assert!(for_each_json_item(&b"[]"[..], "$.items", |_| true).is_err());
+    assert!(for_each_json_item(&b"[]"[..], "$[*].id", |_| true).is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn http_response_is_streamed() {
+    let server = MockServer::start(|_| {
+        let items: Vec<_> = (0..1000).map(|i| json!({ "id": i })).collect();
+        Response::html(json!({ "data": { "list": items } }).to_string())
+    });
+
+    let response = reqwest::get(&server.url).await.unwrap();
+    let mut stream = stream_json_items(response, "$.data.list[*]").unwrap();
+    let mut ids = Vec::new();
+    while let Some(item) = stream.next().await {
+        ids.push(item.unwrap()["id"].as_u64().unwrap());
+    }
+    assert_eq!(ids, (0..1000).collect::<Vec<_>>());
+}
@@ -0,0 +1,278 @@
+//! 规则校验集成测试
+
+mod common;
+
+use common::{BASE_RULE, rule};
+use crawler_runtime::{
+    RuntimeError,
+    rule::{RuleFile, RuleFormat, ValidationWarning, validator::validate},
+};
+use crawler_schema::core::CrawlerRule;
+
+/// 以 `steps` 替换最小规则的搜索标题字段
+fn rule_with_title(steps: &str) -> CrawlerRule {
+    let mut rule = rule("");
+    rule.search.fields.title = toml::from_str(&format!("steps = {}", steps)).expect("字段规则无效");
+    rule
+}
+
+#[test]
+fn step_template_with_undefined_variable_is_rejected() {
+    let rule = rule_with_title(r#"[{ css = "a" }, { template = "{{ missing }}" }]"#);
+
+    let err = validate(&rule).unwrap_err();
+    assert!(
+        matches!(err, RuntimeError::TemplateValidation { .. }),
+        "{}",
+        err
+    );
+    assert!(
+        err.to_string().contains("search.fields.title.steps[1]"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn assert_and_log_templates_are_checked() {
+    let assert = rule_with_title(r#"[{ assert = { condition = "{{ nope }}" } }]"#);
+    assert!(validate(&assert).is_err());
+
+    let log = rule_with_title(r#"[{ log = { message = "{{ nope }}" } }]"#);
+    assert!(validate(&log).is_err());
+}
+
+#[test]
+fn step_templates_accept_flow_and_written_variables() {
+    let rule = rule_with_title(
+        r#"[
+    { css = "a" }, { attr = "text" }, { set_var = { name = "text" } },
+    { template = "{{ keyword }} {{ text }} {{ value }} {{ response.status }} {{ other | default(value='') }}" },
+]"#,
+    );
+
+    validate(&rule).unwrap();
+}
+
+/// 断言模板校验失败，且错误指向 `path`
+fn assert_undefined(rule: &CrawlerRule, path: &str, var: &str) {
+    let err = validate(rule).unwrap_err();
+    assert!(
+        matches!(err, RuntimeError::TemplateValidation { .. }),
+        "{}",
+        err
+    );
+    let message = err.to_string();
+    assert!(message.contains(path), "{}", message);
+    assert!(message.contains(&format!("'{}'", var)), "{}", message);
+}
+
+#[test]
+fn variables_must_be_written_before_use() {
+    let rule = rule_with_title(r#"[{ template = "{{ x }}" }, { set_var = { name = "x" } }]"#);
+    assert_undefined(&rule, "search.fields.title.steps[0]", "x");
+
+    let rule = rule_with_title(r#"[{ set_var = { name = "x" } }, { template = "{{ x }}" }]"#);
+    validate(&rule).unwrap();
+}
+
+#[test]
+fn variables_written_in_other_chains_are_not_visible() {
+    // 其他字段写入的流程变量
+    let mut rule = rule_with_title(r#"[{ css = "a" }, { set_var = { name = "node" } }]"#);
+    rule.search.fields.url = toml::from_str(r#"steps = [{ template = "{{ node }}" }]"#).unwrap();
+    assert_undefined(&rule, "search.fields.url.steps[0]", "node");
+
+    // 主链写入的变量对回退链不可见
+    let rule = rule_with_title(
+        r#"[{ set_var = { name = "node" } }, { css = "a" }]
+fallback = [[{ template = "{{ node }}" }]]"#,
+    );
+    assert_undefined(&rule, "search.fields.title.fallback[0].steps[0]", "node");
+}
+
+#[test]
+fn scoped_steps_follow_runtime_visibility() {
+    // map 与 when 内写入的变量不外泄
+    let rule = rule_with_title(
+        r#"[{ map = [{ set_var = { name = "item" } }] }, { template = "{{ item }}" }]"#,
+    );
+    assert_undefined(&rule, "search.fields.title.steps[1]", "item");
+    let rule = rule_with_title(
+        r#"[{ condition = { when = [{ set_var = { name = "flag" } }], then = [] } }, { log = { message = "{{ flag }}" } }]"#,
+    );
+    assert_undefined(&rule, "search.fields.title.steps[1].log.message", "flag");
+
+    // 内层步骤可读取外层变量，分支写入的变量合并回外层
+    let rule = rule_with_title(
+        r#"[
+    { set_var = { name = "base" } },
+    { map = [{ template = "{{ base }}" }] },
+    { condition = { when = [{ template = "{{ base }}" }], then = [{ set_var = { name = "a" } }], otherwise = [{ set_var = { name = "b" } }] } },
+    { try = { try = [{ set_var = { name = "c" } }], catch = [{ template = "{{ error }} {{ base }}" }] } },
+    { template = "{{ a }} {{ b }} {{ c }}" },
+]"#,
+    );
+    validate(&rule).unwrap();
+}
+
+#[test]
+fn duplicate_variable_writes_are_reported_as_warnings() {
+    let rule = rule_with_title(
+        r#"[
+    { css = "a" }, { set_var = { name = "node" } },
+    { attr = "text" }, { set_var = { name = "node" } },
+]"#,
+    );
+
+    let report = validate(&rule).unwrap();
+    assert_eq!(
+        report.warnings,
+        [ValidationWarning {
+            path: "search.fields.title".to_string(),
+            message: "变量 'node' 被重复写入".to_string(),
+        }]
+    );
+    assert_eq!(
+        report.warnings[0].to_string(),
+        "search.fields.title: 变量 'node' 被重复写入"
+    );
+}
+
+#[test]
+fn writes_in_exclusive_branches_and_map_scopes_are_not_duplicates() {
+    let rule = rule_with_title(
+        r#"[
+    { set_var = { name = "node" } },
+    { condition = { when = [{ css = "b" }], then = [{ set_var = { name = "text" } }], otherwise = [{ set_var = { name = "text" } }] } },
+    { try = { try = [{ set_var = { name = "url" } }], catch = [{ set_var = { name = "url" } }] } },
+    { css = { expr = "a", all = true } },
+    { map = [{ set_var = { name = "node" } }, { attr = "text" }] },
+]"#,
+    );
+    assert!(validate(&rule).unwrap().is_clean());
+
+    // 分支之后再次写入才算重复
+    let rule = rule_with_title(
+        r#"[
+    { condition = { when = [{ css = "b" }], then = [{ set_var = { name = "text" } }] } },
+    { set_var = { name = "text" } },
+]"#,
+    );
+    let report = validate(&rule).unwrap();
+    assert_eq!(report.warnings.len(), 1);
+    assert_eq!(report.warnings[0].path, "search.fields.title");
+}
+
+#[test]
+fn detail_fields_must_match_media_type() {
+    let mut rule = rule("");
+    rule.detail.fields = toml::from_str(
+        r#"
+media_type = "video"
+title.steps = [{ css = "h1" }]
+"#,
+    )
+    .unwrap();
+
+    let err = validate(&rule).unwrap_err();
+    assert!(
+        matches!(&err, RuntimeError::InvalidFieldMapping { field, .. } if field == "detail.fields"),
+        "{}",
+        err
+    );
+}
+
+/// 断言字段映射校验失败，且指向 `path`
+fn assert_unmapped(rule: &CrawlerRule, path: &str) {
+    let err = validate(rule).unwrap_err();
+    assert!(
+        matches!(&err, RuntimeError::InvalidFieldMapping { field, .. } if field == path),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn missing_title_mapping_is_reported() {
+    let mut book = rule("");
+    book.detail.fields = toml::from_str(
+        r#"
+media_type = "book"
+title.steps = []
+author.steps = [{ css = ".author" }]
+"#,
+    )
+    .unwrap();
+    assert_unmapped(&book, "detail.fields.title");
+
+    let mut search = rule_with_title("[]");
+    assert_unmapped(&search, "search.fields.title");
+    // 回退链或默认值同样视为已映射
+    search.search.fields.title = toml::from_str(
+        r#"
+steps = []
+fallback = [[{ css = "a" }]]
+"#,
+    )
+    .unwrap();
+    validate(&search).unwrap();
+    search.search.fields.title = toml::from_str(
+        r#"
+steps = []
+default = "未命名"
+"#,
+    )
+    .unwrap();
+    validate(&search).unwrap();
+
+    let mut content = rule(
+        r#"
+[content]
+url = "{{ content_url }}"
+fields.media_type = "book"
+fields.content.steps = []
+"#,
+    );
+    assert_unmapped(&content, "content.fields.content");
+    content.content = None;
+    validate(&content).unwrap();
+}
+
+#[test]
+fn absent_required_field_is_a_parse_error() {
+    let source = BASE_RULE.replace(
+        "[detail.fields.title]\nsteps = [{ css = \"h1\" }, { attr = \"text\" }]\n",
+        "",
+    );
+    assert_ne!(source, BASE_RULE);
+
+    let err = RuleFile::from_str(&source, RuleFormat::Toml).unwrap_err();
+    assert!(matches!(err, RuntimeError::RuleParse { .. }), "{}", err);
+    assert!(err.to_string().contains("detail.fields.title"), "{}", err);
+}
+
+#[test]
+fn component_references_must_pass_required_args() {
+    let components = r#"
+[components.join]
+required = ["prefix"]
+extractor.steps = [{ template = "{{ prefix }}" }]
+"#;
+    let mut ok = rule(components);
+    ok.search.fields.title = toml::from_str(
+        r#"steps = [{ use_component = { name = "join", args = { prefix = "a" } } }]"#,
+    )
+    .unwrap();
+    validate(&ok).unwrap();
+
+    let mut missing = rule(components);
+    missing.search.fields.title =
+        toml::from_str(r#"steps = [{ use_component = "join" }]"#).unwrap();
+    let err = validate(&missing).unwrap_err();
+    assert!(
+        matches!(&err, RuntimeError::MissingComponentArg { arg, .. } if arg == "prefix"),
+        "{}",
+        err
+    );
+}
@@ -0,0 +1,75 @@
+//! 提取值访问方法测试
+
+use crawler_runtime::extractor::ExtractValueData;
+use serde_json::json;
+use std::sync::Arc;
+
+#[test]
+fn text_values_convert_to_numbers_and_bools() {
+    let value = ExtractValueData::from(" 42 ");
+    assert_eq!(value.as_text().as_deref(), Some("42"));
+    assert_eq!(value.as_i64(), Some(42));
+    assert_eq!(value.as_f64(), Some(42.0));
+    assert_eq!(ExtractValueData::from("YES").as_bool(), Some(true));
+    assert_eq!(ExtractValueData::from("0").as_bool(), Some(false));
+    assert_eq!(ExtractValueData::from("abc").as_i64(), None);
+    assert_eq!(ExtractValueData::from("abc").as_bool(), None);
+}
+
+#[test]
+fn html_is_read_as_trimmed_text() {
+    let value = ExtractValueData::Html(Arc::from("<span>\n  <b>1024</b> \n</span>"));
+    assert_eq!(value.as_text().as_deref(), Some("1024"));
+    assert_eq!(value.as_i64(), Some(1024));
+    assert!(!value.is_null());
+}
+
+#[test]
+fn json_numbers_and_bools_are_read_directly() {
+    let value = ExtractValueData::from(json!(3.0));
+    assert_eq!(value.as_i64(), Some(3));
+    assert_eq!(ExtractValueData::from(json!(2.5)).as_i64(), None);
+    assert_eq!(ExtractValueData::from(json!(2.5)).as_f64(), Some(2.5));
+    assert_eq!(ExtractValueData::from(json!(true)).as_bool(), Some(true));
+    assert_eq!(ExtractValueData::from(json!("7")).as_i64(), Some(7));
+}
+
+#[test]
+fn json_objects_support_chained_get() {
+    let value = ExtractValueData::from(json!({
+        "data": { "title": "斗破苍穹", "count": 3, "tags": ["玄幻", "热血"] }
+    }));
+    let data = value.get("data").unwrap();
+    assert_eq!(data.len(), 3);
+    assert_eq!(
+        data.get("title").and_then(|v| v.as_text()).as_deref(),
+        Some("斗破苍穹")
+    );
+    assert_eq!(data.get("count").and_then(|v| v.as_i64()), Some(3));
+    assert!(data.get("missing").is_none());
+    assert!(ExtractValueData::from("text").get("data").is_none());
+
+    let tags = data.get("tags").unwrap().as_array().unwrap();
+    assert_eq!(tags.len(), 2);
+    assert_eq!(tags[1].as_text().as_deref(), Some("热血"));
+}
+
+#[test]
+fn arrays_share_items_and_report_length() {
+    let item = Arc::new(ExtractValueData::from("a"));
+    let value = ExtractValueData::Array(Arc::new(vec![item.clone(), item.clone()]));
+    assert_eq!(value.len(), 2);
+    assert!(Arc::ptr_eq(&value.as_array().unwrap()[0], &item));
+    assert_eq!(value.as_text(), None);
+    assert_eq!(ExtractValueData::from("斗破").len(), 2);
+}
+
+#[test]
+fn null_checks() {
+    assert!(ExtractValueData::Null.is_null());
+    assert!(ExtractValueData::from(json!(null)).is_null());
+    assert!(!ExtractValueData::from("").is_null());
+    assert_eq!(ExtractValueData::Null.len(), 0);
+    assert_eq!(ExtractValueData::Null.as_text(), None);
+    assert!(ExtractValueData::Null.as_array().is_none());
+}
@@ -0,0 +1,190 @@
+//! WebView 请求与响应处理测试
+
+mod common;
+
+use async_trait::async_trait;
+use common::rule;
+use crawler_runtime::{
+    Result,
+    challenge::{ChallengeManager, ResponseContext},
+    context::{FlowContext, RuntimeContext},
+    flow::login::{LoginFlowExecutor, LoginRequest},
+    webview::{WebViewProvider, WebViewRequest, WebViewResponse},
+};
+use crawler_schema::{config::ChallengeConfig, flow::LoginFlow};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+/// 记录请求并返回固定响应的 WebView
+#[derive(Debug)]
+struct Recorder {
+    requests: Mutex<Vec<WebViewRequest>>,
+    response: WebViewResponse,
+}
+
+impl Recorder {
+    /// 返回 localStorage 与完成脚本结果的成功响应
+    fn new() -> Arc<Self> {
+        let response = WebViewResponse::success()
+            .with_cookies(HashMap::from([("sid".into(), "s1".into())]))
+            .with_local_storage(HashMap::from([
+                ("token".into(), "from-storage".into()),
+                ("uid".into(), "1".into()),
+            ]))
+            .with_script_result(r#"{"token":"from-script"}"#);
+        Arc::new(Self {
+            requests: Mutex::new(Vec::new()),
+            response,
+        })
+    }
+
+    fn request(&self) -> WebViewRequest {
+        self.requests.lock().unwrap()[0].clone()
+    }
+}
+
+#[async_trait]
+impl WebViewProvider for Recorder {
+    async fn open(&self, request: WebViewRequest) -> Result<WebViewResponse> {
+        self.requests.lock().unwrap().push(request);
+        Ok(self.response.clone())
+    }
+}
+
+#[test]
+fn request_builder_sets_local_storage_and_finish_script() {
+    let request = WebViewRequest::new("https://a.com")
+        .with_extract_local_storage(vec!["token".into()])
+        .with_finish_script("return localStorage.getItem('token')");
+
+    assert_eq!(request.extract_local_storage, ["token"]);
+    assert_eq!(
+        request.finish_script.as_deref(),
+        Some("return localStorage.getItem('token')")
+    );
+    assert!(
+        WebViewRequest::new("https://a.com")
+            .extract_local_storage
+            .is_empty()
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn challenge_merges_local_storage_into_credentials() {
+    let provider = Recorder::new();
+    let config: ChallengeConfig = toml::from_str(
+        r#"
+detectors = [{ type = "custom", status_codes = [403] }]
+max_attempts = 1
+
+[handler]
+type = "webview"
+extract_local_storage = ["token", "uid"]
+finish_script = { code = "return JSON.stringify({ token: window.token })" }
+"#,
+    )
+    .unwrap();
+    let manager = ChallengeManager::new(config, provider.clone());
+    let response = ResponseContext {
+        status_code: 403,
+        headers: HashMap::new(),
+        body: String::new(),
+        final_url: "https://a.com/book/1".into(),
+    };
+
+    let credentials = manager
+        .handle("https://a.com/book/1", response)
+        .await
+        .unwrap();
+    let request = provider.request();
+    assert_eq!(request.extract_local_storage, ["token", "uid"]);
+    assert_eq!(
+        request.finish_script.as_deref(),
+        Some("return JSON.stringify({ token: window.token })")
+    );
+    assert_eq!(credentials.cookies["sid"], "s1");
+    // 完成脚本返回的同名键覆盖 localStorage
+    assert_eq!(credentials.extra["token"], "from-script");
+    assert_eq!(credentials.extra["uid"], "1");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn webview_login_returns_local_storage_in_session() {
+    let provider = Recorder::new();
+    let runtime =
+        Arc::new(RuntimeContext::with_webview_provider(rule(""), provider.clone()).unwrap());
+    let flow: LoginFlow = toml::from_str(
+        r#"
+type = "webview"
+start_url = "https://a.com/login?u={{ username }}"
+extract_local_storage = ["token"]
+finish_script = { code = "return JSON.stringify({ token: 'x' })" }
+"#,
+    )
+    .unwrap();
+    let mut flow_context = FlowContext::new(runtime.clone());
+    let input = LoginRequest {
+        username: "me".into(),
+        password: "pw".into(),
+    };
+
+    let response = LoginFlowExecutor::execute(input, &flow, &runtime, &mut flow_context)
+        .await
+        .unwrap();
+    assert!(response.success);
+    assert_eq!(provider.request().url, "https://a.com/login?u=me");
+    assert_eq!(provider.request().extract_local_storage, ["token"]);
+    let session = response.session.unwrap();
+    assert_eq!(session["cookies"]["sid"], "s1");
+    assert_eq!(session["extra"]["token"], "from-script");
+    assert_eq!(session["extra"]["uid"], "1");
+}
+
+/// 以指定的完成脚本结果执行网页登录，返回会话中的 extra
+async fn login_extra(script_result: &str) -> serde_json::Value {
+    let provider = Arc::new(Recorder {
+        requests: Mutex::new(Vec::new()),
+        response: WebViewResponse::success()
+            .with_local_storage(HashMap::from([("uid".into(), "1".into())]))
+            .with_script_result(script_result),
+    });
+    let runtime = Arc::new(RuntimeContext::with_webview_provider(rule(""), provider).unwrap());
+    let flow: LoginFlow = toml::from_str(
+        r#"
+type = "webview"
+start_url = "https://a.com/login"
+"#,
+    )
+    .unwrap();
+    let mut flow_context = FlowContext::new(runtime.clone());
+    let input = LoginRequest {
+        username: "me".into(),
+        password: "pw".into(),
+    };
+
+    let response = LoginFlowExecutor::execute(input, &flow, &runtime, &mut flow_context)
+        .await
+        .unwrap();
+    response.session.unwrap()["extra"].clone()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn non_string_script_results_are_stringified() {
+    let extra =
+        login_extra(r#"{"token":"t","uid":42,"vip":true,"roles":["a"],"expires":null}"#).await;
+    assert_eq!(
+        extra,
+        serde_json::json!({ "token": "t", "uid": "42", "vip": "true", "roles": "[\"a\"]" })
+    );
+
+    // 非对象结果被忽略，保留 localStorage
+    assert_eq!(login_extra("done").await, serde_json::json!({ "uid": "1" }));
+    assert_eq!(login_extra("[1]").await, serde_json::json!({ "uid": "1" }));
+}
+
+#[test]
+fn script_result_map_is_empty_without_result() {
+    assert!(WebViewResponse::success().script_result_map().is_empty());
+}
@@ -0,0 +1,89 @@
+//! XML 输入与 XPath 选择器测试
+
+mod common;
+
+use common::{MockServer, Response, field, rule, rule_for, runtime_context};
+use crawler_runtime::{
+    context::FlowContext,
+    crawler::CrawlerRuntime,
+    extractor::{ExtractEngine, ExtractValueData},
+};
+use serde_json::{Value, json};
+use std::sync::Arc;
+
+const RSS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0" xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <channel>
+    <title>书库更新</title>
+    <item>
+      <title>斗破苍穹</title>
+      <link>/b/1</link>
+      <dc:creator>天蚕土豆</dc:creator>
+      <enclosure url="/cover/1.jpg" type="image/jpeg"/>
+    </item>
+    <item>
+      <title>凡人修仙传</title>
+      <link>/b/2</link>
+      <dc:creator>忘语</dc:creator>
+      <enclosure url="/cover/2.jpg" type="image/jpeg"/>
+    </item>
+  </channel>
+</rss>"#;
+
+/// 在 RSS 文档上执行提取步骤
+fn extract_rss(steps: &str) -> Value {
+    let runtime = runtime_context(rule(""));
+    let flow = FlowContext::new(runtime.clone());
+    let input = ExtractValueData::Xml(Arc::from(RSS));
+    ExtractEngine::extract_field(&field(steps), &input, &runtime, &flow)
+        .unwrap()
+        .to_owned_json()
+}
+
+#[test]
+fn xpath_selects_every_item_title() {
+    assert_eq!(
+        extract_rss(r#"steps = [{ xpath = { expr = "//item/title/text()", all = true } }]"#),
+        json!(["斗破苍穹", "凡人修仙传"])
+    );
+    // 频道标题不在 item 下，不会被选中
+    assert_eq!(
+        extract_rss(r#"steps = [{ xpath = "/rss/channel/title/text()" }]"#),
+        json!("书库更新")
+    );
+}
+
+#[test]
+fn xpath_supports_attributes_predicates_and_namespaces() {
+    assert_eq!(
+        extract_rss(r#"steps = [{ xpath = { expr = "//enclosure/@url", all = true } }]"#),
+        json!(["/cover/1.jpg", "/cover/2.jpg"])
+    );
+    assert_eq!(
+        extract_rss(r#"steps = [{ xpath = "//item[last()]/dc:creator/text()" }]"#),
+        json!("忘语")
+    );
+    assert_eq!(
+        extract_rss(r#"steps = [{ xpath = "//item[title='斗破苍穹']/link/text()" }]"#),
+        json!("/b/1")
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn rss_responses_are_parsed_as_xml() {
+    let server = MockServer::start(|_| Response {
+        status: 200,
+        headers: vec![("Content-Type".into(), "application/rss+xml".into())],
+        body: RSS.into(),
+    });
+    let mut rule = rule_for(&server, "");
+    rule.search.list = field(r#"steps = [{ xpath = { expr = "//item", all = true } }]"#);
+    rule.search.fields.title = toml::from_str(r#"steps = [{ xpath = "title/text()" }]"#).unwrap();
+    rule.search.fields.url = toml::from_str(r#"steps = [{ xpath = "link/text()" }]"#).unwrap();
+    let runtime = CrawlerRuntime::new(rule, None).unwrap();
+
+    let response = runtime.search("kw", 1).await.unwrap();
+    let titles: Vec<_> = response.items.iter().map(|i| i.title.as_str()).collect();
+    assert_eq!(titles, ["斗破苍穹", "凡人修仙传"]);
+    assert_eq!(response.items[1].url, format!("{}/b/2", server.url));
+}
@@ -228,6 +228,11 @@ pub struct WebviewHandler {
     /// 验证通过后自动保存这些 Cookie
     #[serde(skip_serializing_if = "Option::is_none")]
     pub extract_cookies: Option<Vec<String>>,
+
+    /// 需要提取的 localStorage 键名
+    /// 验证通过后保存到凭证的额外数据中
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extract_local_storage: Option<Vec<String>>,
 }
 
 /// 自动重试处理器
@@ -0,0 +1,52 @@
+//! 运行时资源限制配置
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// 默认值常量
+// ============================================================================
+
+/// 组件调用的默认最大嵌套深度
+pub const DEFAULT_MAX_DEPTH: u32 = 16;
+
+// ============================================================================
+// 资源限制配置
+// ============================================================================
+
+/// 运行时资源限制配置
+///
+/// 约束单个规则的抓取规模，防止错误的规则无限翻页、无限递归或发出过多请求。
+/// 超限时运行时返回 `LimitExceeded` 错误（翻页到达页数上限时停止翻页）。
+///
+/// # 示例
+///
+/// ```toml
+/// [limits]
+/// max_pages = 50       # 最多翻 50 页
+/// max_requests = 500   # 单次调用最多发出 500 个请求
+/// max_depth = 8        # 组件最多嵌套 8 层
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct LimitsConfig {
+    /// 最大页数
+    ///
+    /// 与分页配置中的页数限制（如 `max_pages`、`max_offset`）同时生效，取较小值。
+    /// 默认无限制
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_pages: Option<u32>,
+
+    /// 最大请求数
+    ///
+    /// 单次调用（一次搜索、详情、发现或正文请求；批量获取详情时整批计为一次）
+    /// 发出的请求数上限，每次调用重新计数。默认无限制
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_requests: Option<u32>,
+
+    /// 组件调用的最大嵌套深度
+    ///
+    /// 组件互相引用形成递归时，超过此深度即报错。默认值：16
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_depth: Option<u32>,
+}
@@ -51,7 +51,9 @@ pub struct Meta {
     pub author: String,
     /// 规则版本号，建议遵循 SemVer (如 "1.0.2")。
     pub version: String,
-    /// 本规则遵循的规范版本号。
+    /// 本规则遵循的规范版本号，如 "1.0.0"。
+    ///
+    /// 加载规则时检查是否在运行时支持的范围内，不兼容的规则会被拒绝。
     pub spec_version: String,
     /// 目标网站的主域名。
     pub domain: String,
@@ -1,13 +1,15 @@
 //! 配置模块
 //!
-//! 包含 HTTP、Meta、Challenge、脚本安全等配置结构
+//! 包含 HTTP、Meta、Challenge、脚本安全、资源限制等配置结构
 
 pub mod challenge;
 pub mod http;
+pub mod limits;
 pub mod meta;
 pub mod script_security;
 
 pub use challenge::*;
 pub use http::*;
+pub use limits::*;
 pub use meta::*;
 pub use script_security::*;
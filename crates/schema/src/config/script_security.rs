@@ -13,6 +13,9 @@ pub const DEFAULT_MAX_MEMORY_MB: u64 = 128;
 /// 脚本安全配置的默认超时时间（秒）
 pub const DEFAULT_TIMEOUT_SECONDS: u64 = 30;
 
+/// 脚本安全配置的默认输入/返回值大小上限（KB）
+pub const DEFAULT_MAX_DATA_KB: u64 = 10 * 1024;
+
 // ============================================================================
 // 默认值函数
 // ============================================================================
@@ -38,7 +41,7 @@ fn is_false(b: &bool) -> bool {
 
 /// 脚本执行安全配置
 ///
-/// 定义脚本执行时的安全限制（内存、文件访问、网络访问、超时）。
+/// 定义脚本执行时的安全限制（内存、数据大小、文件访问、网络访问、超时）。
 /// 可在全局 (CrawlerRule) 或局部 (Script) 定义，局部配置优先级更高。
 ///
 /// # 示例
@@ -47,6 +50,7 @@ fn is_false(b: &bool) -> bool {
 /// ```toml
 /// [script_security]
 /// max_memory_mb = 128
+/// max_data_kb = 10240
 /// allow_file_access = false
 /// allow_network = false
 /// timeout_seconds = 30
@@ -59,7 +63,7 @@ fn is_false(b: &bool) -> bool {
 /// [script.security]
 /// timeout_seconds = 60  # 覆盖全局的 30 秒
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct ScriptSecurityConfig {
     /// 最大内存限制（MB）
@@ -69,6 +73,13 @@ pub struct ScriptSecurityConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_memory_mb: Option<u64>,
 
+    /// 脚本输入与返回值的大小上限（KB）
+    ///
+    /// 传入脚本的 `input` 或脚本返回值超过此大小时报错，避免超大字符串耗尽内存。
+    /// `0` 表示无限制。默认值：10240 KB（10 MB）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_data_kb: Option<u64>,
+
     /// 是否允许文件系统访问
     ///
     /// - `true`: 允许脚本调用文件 I/O 函数（如 `fs.read`）
@@ -86,7 +97,7 @@ pub struct ScriptSecurityConfig {
     /// - `false`: 禁用所有网络操作
     /// - 默认值：false
     ///
-    /// **注意**：即使禁用，脚本也可通过内置的 `http` 函数进行受控网络访问。
+    /// 禁用时内置函数 `http_get` / `http_post` 调用报错。
     #[serde(default = "default_allow_network", skip_serializing_if = "is_false")]
     pub allow_network: bool,
 
@@ -111,6 +122,9 @@ impl ScriptSecurityConfig {
             if self.max_memory_mb.is_none() {
                 self.max_memory_mb = global.max_memory_mb;
             }
+            if self.max_data_kb.is_none() {
+                self.max_data_kb = global.max_data_kb;
+            }
             if !self.allow_file_access && global.allow_file_access {
                 self.allow_file_access = true;
             }
@@ -123,4 +137,12 @@ impl ScriptSecurityConfig {
         }
         self
     }
+
+    /// 输入/返回值大小上限（字节），`None` 表示无限制
+    pub fn max_data_bytes(&self) -> Option<usize> {
+        match self.max_data_kb.unwrap_or(DEFAULT_MAX_DATA_KB) {
+            0 => None,
+            kb => Some((kb as usize).saturating_mul(1024)),
+        }
+    }
 }
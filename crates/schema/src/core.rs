@@ -4,8 +4,9 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    config::{ChallengeConfig, HttpConfig, Meta, ScriptSecurityConfig},
+    config::{ChallengeConfig, HttpConfig, LimitsConfig, Meta, ScriptSecurityConfig},
     flow::{Components, ContentFlow, DetailFlow, DiscoveryFlow, LoginFlow, SearchFlow},
+    script::ScriptingConfig,
 };
 
 /// 影视软件爬虫规则 (CrawlerRule)
@@ -26,6 +27,16 @@ pub struct CrawlerRule {
     /// 可被 Script 中的局部 `security` 配置覆盖。
     #[serde(skip_serializing_if = "Option::is_none")]
     pub script_security: Option<ScriptSecurityConfig>,
+    /// 运行时资源限制
+    ///
+    /// 限制最大页数、请求总数与组件嵌套深度
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limits: Option<LimitsConfig>,
+    /// 脚本模块配置
+    ///
+    /// 定义可复用的脚本函数库，按需加载
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scripting: Option<ScriptingConfig>,
     /// 可重用组件定义
     ///
     /// 以名称为键定义可复用的提取逻辑，可在各流程中通过 `use_component` 步骤引用
@@ -55,6 +55,7 @@
 //! | 步骤 | 说明 |
 //! |------|------|
 //! | `set_var` | 保存当前值到指定上下文 |
+//! | `template` | 渲染字符串模板 |
 //! | `script` | 自定义脚本 |
 //! | `use_component` | 引用预定义组件 |
 //!
@@ -65,9 +66,10 @@
 //! | `map` | 对数组每个元素应用步骤 |
 //! | `condition` | 条件分支执行 |
 
-use crate::{flow::ComponentRef, script::Script};
+use crate::{flow::ComponentRef, script::Script, template::Template};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 // ============================================================================
 // 核心提取器
@@ -98,6 +100,14 @@ use serde::{Deserialize, Serialize};
 ///     [{ css = ".creator" }]
 /// ]
 /// author.default = "佚名"
+///
+/// # 保留首尾空白
+/// content.steps = [{ css = "pre" }]
+/// content.auto_trim = false
+///
+/// # 约束结果类型：只匹配到一个元素时也输出数组
+/// tags.steps = [{ css = ".tag" }, { attr = "text" }]
+/// tags.expect_type = "array"
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
@@ -120,8 +130,41 @@ pub struct FieldExtractor {
     pub default: Option<serde_json::Value>,
 
     /// 是否允许空值
+    ///
+    /// 主步骤、回退和默认值都未得到结果时：为 `true` 返回 null，否则报错
     #[serde(default)]
     pub nullable: bool,
+
+    /// 是否自动去除首尾空白（默认 true）
+    ///
+    /// 开启时对字符串结果执行 trim，且仅含空白的字符串视为空值，
+    /// 会继续尝试 fallback 与 default
+    #[serde(default = "default_auto_trim")]
+    pub auto_trim: bool,
+
+    /// 期望的结果类型（可选）
+    ///
+    /// 提取完成后按类型校验并尝试转换，无法转换时报错；null 结果不做处理
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expect_type: Option<ExpectType>,
+}
+
+fn default_auto_trim() -> bool {
+    true
+}
+
+/// 期望的提取结果类型 (ExpectType)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExpectType {
+    /// 字符串：数字、布尔转为文本，单元素数组取其元素
+    String,
+    /// 数字：文本按整数或浮点数解析，单元素数组取其元素
+    Number,
+    /// 数组：JSON 数组展开，单个值包装为单元素数组
+    Array,
+    /// 布尔：文本支持 `true`/`false`/`1`/`0`/`yes`/`no`，单元素数组取其元素
+    Bool,
 }
 
 // ============================================================================
@@ -132,9 +175,10 @@ pub struct FieldExtractor {
 ///
 /// 单个原子化操作。步骤类型：
 /// - **选择步骤**：css, json, xpath, regex
-/// - **过滤步骤**：filter, attr, index
-/// - **特殊步骤**：const, var, script, use_component
-/// - **流程控制**：map, condition
+/// - **过滤步骤**：filter, attr, index, enumerate
+/// - **特殊步骤**：const, var, template, script, use_component
+/// - **流程控制**：map, condition, try, return, goto
+/// - **调试**：assert, log
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExtractStep {
@@ -143,13 +187,21 @@ pub enum ExtractStep {
     Css(SelectorStep),
 
     /// JSONPath 表达式（JSON）
+    ///
+    /// 支持过滤表达式，可用 `==`、`!=`、`>`、`<`、`&&`、`||` 与 `contains`（子串或数组元素）：
+    ///
+    /// ```toml
+    /// { json = { expr = "$.items[?(@.type == 'movie')].title", all = true } }
+    /// { json = "$.items[?(@.tags contains '完结')]" }
+    /// ```
     Json(SelectorStep),
 
     /// XPath 表达式（XML/HTML）
     ///
-    /// **注意**：Rust 原生不支持完整 XPath，Runtime 通过 trait 抽象实现：
-    /// - 在 Tauri 环境下通过调用 JS 引擎执行
-    /// - 可注入其他 XPath 实现（如 libxml2 绑定）
+    /// **注意**：Runtime 内置的是 XPath 子集，输入需为格式良好的 XML
+    /// （`response.content_type = "xml"` 或响应头为 XML 类型时自动按 XML 解析）：
+    /// - 路径 `/`、`//`、`.`、`..`，节点测试：元素名、`*`、`text()`、`@attr`
+    /// - 谓词 `[n]`、`[last()]`、`[@a]`、`[@a='v']`、`[name]`、`[name='v']`、`[text()='v']`
     ///
     /// # 示例
     ///
@@ -172,10 +224,46 @@ pub enum ExtractStep {
     /// 索引/切片
     Index(IndexStep),
 
+    /// 为数组元素附加索引
+    ///
+    /// 将 `[a, b]` 转换为 `[{ index = 0, value = a }, { index = 1, value = b }]`，
+    /// 便于在 `map` 中同时使用序号与元素
+    ///
+    /// # 示例
+    ///
+    /// ```toml
+    /// chapters.steps = [
+    ///     { css = { expr = ".chapter a", all = true } },
+    ///     { map = [{ attr = "title" }] },
+    ///     "enumerate"
+    /// ]
+    /// # 结果：[{ index = 0, value = "第一章" }, { index = 1, value = "第二章" }]
+    /// ```
+    Enumerate,
+
     // ========== 特殊步骤 ==========
     /// 保存当前值到指定上下文
+    ///
+    /// 流程变量对所在步骤链的后续步骤可见（含 `condition`/`try` 分支内设置的变量），
+    /// 不影响其他字段；`try` 失败时回滚其中设置的变量
     SetVar(SetVarStep),
 
+    /// 字符串模板
+    ///
+    /// 渲染模板生成字符串，可引用全部流程变量与 `$.` 全局变量，`value` 为当前值；
+    /// 支持 Tera 过滤器及内置转换（如 `cn_to_num`、`t2s`、`html_decode`），结果不做 HTML 转义
+    ///
+    /// # 示例
+    ///
+    /// ```toml
+    /// title.steps = [
+    ///     { css = ".volume" }, { attr = "text" }, { set_var = { name = "volume" } },
+    ///     { css = ".chapter" }, { attr = "text" },
+    ///     { template = "{{ volume | trim }} {{ value | cn_to_num }}" }
+    /// ]
+    /// ```
+    Template(Template),
+
     /// 脚本调用
     Script(Script),
 
@@ -213,8 +301,14 @@ pub enum ExtractStep {
     ///     { json = "$.items[*]" },
     ///     { map = [{ json = "$.title" }, { filter = "trim" }] }
     /// ]
+    ///
+    /// # 子步骤较慢时并发处理，结果保持原顺序
+    /// details.steps = [
+    ///     { json = "$.items[*]" },
+    ///     { map = { steps = [{ script = "fetch_detail" }], concurrency = 8 } }
+    /// ]
     /// ```
-    Map(Vec<ExtractStep>),
+    Map(MapStep),
 
     /// 条件分支
     ///
@@ -233,6 +327,86 @@ pub enum ExtractStep {
     /// }]
     /// ```
     Condition(Box<ConditionStep>),
+
+    /// 容错执行
+    ///
+    /// `try` 中任一步骤失败时，以原输入执行 `catch` 步骤，
+    /// `catch` 中可通过 `{{ error }}` 访问错误信息；未配置 `catch` 时原样返回输入。
+    /// 超时与中断类错误默认不捕获
+    ///
+    /// # 示例
+    ///
+    /// ```toml
+    /// # 评分接口偶发失败时使用页面上的评分
+    /// score.steps = [{
+    ///     try = {
+    ///         try = [{ script = "fetch_score" }],
+    ///         catch = [{ css = ".score" }, { filter = "trim" }]
+    ///     }
+    /// }]
+    /// ```
+    Try(Box<TryStep>),
+
+    /// 提前结束
+    ///
+    /// 立即结束当前步骤链，以当前值作为结果；
+    /// 位于 `condition` 分支中时，同时结束外层步骤链
+    ///
+    /// # 示例
+    ///
+    /// ```toml
+    /// # 列表为空时提前结束，不再执行后续步骤
+    /// chapters.steps = [
+    ///     { css = { expr = ".chapter a", all = true } },
+    ///     { condition = { when = [{ index = 0 }], then = [], otherwise = ["return"] } },
+    ///     { map = [{ attr = "href" }, { filter = "absolute_url" }] }
+    /// ]
+    /// ```
+    Return,
+
+    /// 受限跳转
+    ///
+    /// 跳转到当前步骤链中指定序号（从 0 开始）的步骤。
+    /// 只允许向前跳转，序号等于步骤数时直接结束步骤链
+    ///
+    /// # 示例
+    ///
+    /// ```toml
+    /// # 跳过序号为 2 的 upper 步骤，直接执行 trim
+    /// title.steps = [{ css = ".title" }, { goto = 3 }, { filter = "upper" }, { filter = "trim" }]
+    /// ```
+    Goto(usize),
+
+    // ========== 调试步骤 ==========
+    /// 断言
+    ///
+    /// 条件模板渲染结果为假时中断步骤链并报错，避免关键数据缺失时继续执行产生误导性结果。
+    /// 断言通过时原样返回输入
+    ///
+    /// # 示例
+    ///
+    /// ```toml
+    /// chapters.steps = [
+    ///     { assert = { condition = "{{ book_id }}", message = "未能获取书籍 ID" } },
+    ///     { css = { expr = ".chapter a", all = true } }
+    /// ]
+    /// ```
+    Assert(AssertStep),
+
+    /// 日志
+    ///
+    /// 按指定级别输出渲染后的消息与上下文字段，原样返回输入，便于调试规则时按级别过滤。
+    /// 消息与上下文模板中可用 `value` 引用当前值
+    ///
+    /// # 示例
+    ///
+    /// ```toml
+    /// chapters.steps = [
+    ///     { css = { expr = ".chapter a", all = true } },
+    ///     { log = { message = "章节列表已提取", level = "debug", context = { book = "{{ book_id }}" } } }
+    /// ]
+    /// ```
+    Log(LogStep),
 }
 
 /// 变量上下文类型
@@ -263,6 +437,49 @@ pub struct SetVarStep {
 // 步骤配置类型
 // ============================================================================
 
+/// 断言步骤
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AssertStep {
+    /// 断言条件模板
+    ///
+    /// 可引用流程变量，`value` 为当前值。渲染结果为空、`false`、`0`、`null`、`none`
+    /// （忽略大小写与首尾空白）时视为假
+    pub condition: Template,
+    /// 断言失败时的错误信息（默认使用条件模板）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// 日志级别
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LogLevel {
+    /// 调试
+    Debug,
+    /// 信息
+    #[default]
+    Info,
+    /// 警告
+    Warn,
+    /// 错误
+    Error,
+}
+
+/// 日志步骤
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct LogStep {
+    /// 日志消息模板
+    pub message: Template,
+    /// 日志级别（默认 `info`）
+    #[serde(default)]
+    pub level: LogLevel,
+    /// 结构化上下文字段（键: 字段名, 值: 模板）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<HashMap<String, Template>>,
+}
+
 /// 选择器步骤（CSS/JSONPath通用）
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(untagged)]
@@ -322,6 +539,39 @@ pub enum IndexStep {
     Slice(String),
 }
 
+/// 映射步骤
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum MapStep {
+    /// 子步骤数组（顺序执行）
+    Simple(Vec<ExtractStep>),
+    /// 带配置的映射
+    WithOptions {
+        /// 对每个元素执行的子步骤
+        steps: Vec<ExtractStep>,
+        /// 最大并发数（默认 1，即顺序执行）
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        concurrency: Option<usize>,
+    },
+}
+
+impl MapStep {
+    /// 子步骤
+    pub fn steps(&self) -> &[ExtractStep] {
+        match self {
+            Self::Simple(steps) | Self::WithOptions { steps, .. } => steps,
+        }
+    }
+
+    /// 最大并发数（至少为 1）
+    pub fn concurrency(&self) -> usize {
+        match self {
+            Self::Simple(_) => 1,
+            Self::WithOptions { concurrency, .. } => concurrency.unwrap_or(1).max(1),
+        }
+    }
+}
+
 /// 条件步骤配置
 ///
 /// 根据条件选择执行不同的提取逻辑
@@ -341,6 +591,23 @@ pub struct ConditionStep {
     pub otherwise: Option<Vec<ExtractStep>>,
 }
 
+/// 容错步骤配置
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TryStep {
+    /// 尝试执行的步骤
+    #[serde(rename = "try")]
+    pub steps: Vec<ExtractStep>,
+
+    /// 失败时执行的步骤（可选）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub catch: Option<Vec<ExtractStep>>,
+
+    /// 是否同时捕获超时与中断类错误（默认 false）
+    #[serde(default)]
+    pub catch_timeout: bool,
+}
+
 /// 过滤器配置（结构化形式）
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
@@ -363,6 +630,8 @@ pub struct FilterConfig {
 /// # 字符串处理
 /// - `trim` - 去首尾空白
 /// - `lower` / `upper` - 大小写转换
+/// - `capitalize` - 首字母大写，`capitalize(title)` 每个单词首字母大写
+/// - `collapse_whitespace` - 连续空白（含换行、全角空格）折叠为单个空格并去首尾空白
 /// - `replace(from, to)` - 文本替换
 /// - `strip_html` - 移除 HTML 标签
 /// - `split(sep)` / `join(sep)` - 分割/连接
@@ -370,6 +639,7 @@ pub struct FilterConfig {
 /// # 类型转换
 /// - `to_int` / `to_float` / `to_string` / `to_bool`
 /// - `from_json` / `to_json`
+/// - `strip_jsonp` - 去除 JSONP 包裹并解析为 JSON
 ///
 /// # URL 处理
 /// - `absolute_url` - 转绝对 URL
@@ -379,6 +649,7 @@ pub struct FilterConfig {
 /// # 数组处理
 /// - `first` / `last` / `nth(n)`
 /// - `slice(start, end)` / `reverse` / `unique`
+/// - `sort(order, type, by)` - 排序，如 `sort(desc, number)`、`sort(by="title")`
 ///
 /// # 条件处理
 /// - `default(value)` - 默认值
@@ -409,6 +680,7 @@ pub enum Filter {
     ToBool,
     ToJson,
     FromJson,
+    StripJsonp,
 
     // === 数值处理 ===
     Round,
@@ -434,6 +706,7 @@ pub enum Filter {
     Base64Decode,
     HtmlEncode,
     HtmlDecode,
+    DecodeAll,
     Md5,
 
     // === 正则处理 ===
@@ -30,6 +30,20 @@ pub struct FieldRule {
     pub extractor: FieldExtractor,
 }
 
+impl FieldRule {
+    /// 是否配置了取值方式：非空的主步骤、非空的回退链或默认值
+    pub fn is_mapped(&self) -> bool {
+        let extractor = &self.extractor;
+        !extractor.steps.is_empty()
+            || extractor
+                .fallback
+                .iter()
+                .flatten()
+                .any(|steps| !steps.is_empty())
+            || extractor.default.is_some()
+    }
+}
+
 /// 可选字段规则
 /// 使用 Option 包装，None 表示不提取该字段
 pub type OptionalFieldRule = Option<FieldRule>;
@@ -51,3 +51,10 @@ pub struct ItemFields {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub extra: OptionalFieldRule,
 }
+
+impl ItemFields {
+    /// 输出模型的必填字段及其规则
+    pub fn required_fields(&self) -> Vec<(&'static str, &FieldRule)> {
+        vec![("title", &self.title), ("url", &self.url)]
+    }
+}
@@ -42,6 +42,7 @@ pub use list_rules::*;
 pub use manga::*;
 pub use video::*;
 
+use crate::config::MediaType;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -66,6 +67,28 @@ pub enum DetailFields {
     Manga(Box<MangaDetailFields>),
 }
 
+impl DetailFields {
+    /// 获取字段规则对应的媒体类型
+    pub fn media_type(&self) -> MediaType {
+        match self {
+            Self::Video(_) => MediaType::Video,
+            Self::Audio(_) => MediaType::Audio,
+            Self::Book(_) => MediaType::Book,
+            Self::Manga(_) => MediaType::Manga,
+        }
+    }
+
+    /// 输出模型的必填字段及其规则
+    pub fn required_fields(&self) -> Vec<(&'static str, &FieldRule)> {
+        match self {
+            Self::Video(fields) => vec![("title", &fields.title)],
+            Self::Audio(fields) => vec![("title", &fields.title)],
+            Self::Book(fields) => vec![("title", &fields.title), ("author", &fields.author)],
+            Self::Manga(fields) => vec![("title", &fields.title)],
+        }
+    }
+}
+
 /// 内容页字段规则 (ContentFields)
 /// 用于播放页、阅读页等内容消费页面
 ///
@@ -82,3 +105,25 @@ pub enum ContentFields {
     /// 漫画阅读字段
     Manga(Box<MangaReadFields>),
 }
+
+impl ContentFields {
+    /// 获取字段规则对应的媒体类型
+    pub fn media_type(&self) -> MediaType {
+        match self {
+            Self::Video(_) => MediaType::Video,
+            Self::Audio(_) => MediaType::Audio,
+            Self::Book(_) => MediaType::Book,
+            Self::Manga(_) => MediaType::Manga,
+        }
+    }
+
+    /// 输出模型的必填字段及其规则
+    pub fn required_fields(&self) -> Vec<(&'static str, &FieldRule)> {
+        match self {
+            Self::Video(fields) => vec![("play_url", &fields.play_url)],
+            Self::Audio(fields) => vec![("play_url", &fields.play_url)],
+            Self::Book(fields) => vec![("content", &fields.content)],
+            Self::Manga(fields) => vec![("images", &fields.images)],
+        }
+    }
+}
@@ -53,6 +53,8 @@ pub struct FilterOption {
 /// type = "page_number"
 /// start = 1
 /// param = "page"
+/// dedup_by = "url"  # 可选，翻页时丢弃重复条目
+/// total_pages.steps = [{ css = ".pager .total" }, { filter = "to_int" }]  # 可选
 /// ```
 ///
 /// ## 偏移量分页
@@ -93,6 +95,66 @@ pub enum Pagination {
     None,
 }
 
+impl Pagination {
+    /// 去重依据字段
+    pub fn dedup_by(&self) -> Option<&str> {
+        match self {
+            Self::PageNumber(p) => p.dedup_by.as_deref(),
+            Self::Offset(p) => p.dedup_by.as_deref(),
+            Self::Cursor(p) => p.dedup_by.as_deref(),
+            Self::None => None,
+        }
+    }
+
+    /// 总页数提取规则
+    pub fn total_pages(&self) -> Option<&FieldExtractor> {
+        match self {
+            Self::PageNumber(p) => p.total_pages.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// 总条目数提取规则
+    pub fn total_items(&self) -> Option<&FieldExtractor> {
+        match self {
+            Self::PageNumber(p) => p.total_items.as_ref(),
+            Self::Offset(p) => p.total_count.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// 固定的每页数量（页码分页的 `page_size`、偏移量分页的 `step`），未配置时为 None
+    pub fn page_size(&self) -> Option<u32> {
+        match self {
+            Self::PageNumber(p) => p.page_size,
+            Self::Offset(p) => Some(p.step),
+            _ => None,
+        }
+    }
+
+    /// 分页配置自身的最大页数
+    ///
+    /// 页码分页取 `max_pages`，偏移量分页按 `max_offset` 换算，游标分页取 `max_requests`
+    pub fn max_pages(&self) -> Option<u32> {
+        match self {
+            Self::PageNumber(p) => p.max_pages,
+            Self::Offset(p) => p
+                .max_offset
+                .map(|max| max.saturating_sub(p.start) / p.step.max(1) + 1),
+            Self::Cursor(p) => p.max_requests,
+            Self::None => Some(1),
+        }
+    }
+
+    /// 第一页的页码（页码分页的 `start`，其他分页类型为 1）
+    pub fn first_page(&self) -> u32 {
+        match self {
+            Self::PageNumber(p) => p.start,
+            _ => 1,
+        }
+    }
+}
+
 impl Default for Pagination {
     fn default() -> Self {
         Self::PageNumber(PageNumberPagination::default())
@@ -122,6 +184,32 @@ pub struct PageNumberPagination {
     /// 如果不提供，默认当返回结果为空时停止
     #[serde(skip_serializing_if = "Option::is_none")]
     pub has_next: Option<FieldExtractor>,
+
+    /// 总页数提取规则（可选）
+    ///
+    /// 从响应中提取总页数，抓满总页数后停止；提取失败时回退到默认判断
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_pages: Option<FieldExtractor>,
+
+    /// 总条目数提取规则（可选）
+    ///
+    /// 未配置 `total_pages` 时使用，按 `page_size` 换算为总页数，
+    /// 未配置 `page_size` 时按首页条目数换算
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_items: Option<FieldExtractor>,
+
+    /// 每页数量（可选）
+    ///
+    /// 用于由 `total_items` 换算总页数
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<u32>,
+
+    /// 去重依据（可选）
+    ///
+    /// 翻页累积结果时丢弃已出现过的条目：`url` 按条目 URL 去重，
+    /// 其他值按列表项原始数据中的同名字段去重
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dedup_by: Option<String>,
 }
 
 impl Default for PageNumberPagination {
@@ -131,6 +219,10 @@ impl Default for PageNumberPagination {
             param: "page".to_string(),
             max_pages: None,
             has_next: None,
+            total_pages: None,
+            total_items: None,
+            page_size: None,
+            dedup_by: None,
         }
     }
 }
@@ -164,9 +256,16 @@ pub struct OffsetPagination {
 
     /// 总数量提取规则（可选）
     ///
-    /// 用于计算总页数
+    /// 按 `step` 换算为总页数，抓满后停止；提取失败时回退到默认判断
     #[serde(skip_serializing_if = "Option::is_none")]
     pub total_count: Option<FieldExtractor>,
+
+    /// 去重依据（可选）
+    ///
+    /// 翻页累积结果时丢弃已出现过的条目：`url` 按条目 URL 去重，
+    /// 其他值按列表项原始数据中的同名字段去重
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dedup_by: Option<String>,
 }
 
 /// 游标分页配置
@@ -193,6 +292,13 @@ pub struct CursorPagination {
     /// 最大请求次数限制（可选）
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_requests: Option<u32>,
+
+    /// 去重依据（可选）
+    ///
+    /// 翻页累积结果时丢弃已出现过的条目：`url` 按条目 URL 去重，
+    /// 其他值按列表项原始数据中的同名字段去重
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dedup_by: Option<String>,
 }
 
 // ============================================================================
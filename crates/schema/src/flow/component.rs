@@ -6,7 +6,7 @@
 //!
 //! 1. **组件定义**：在 `components` 中以名称为键定义可复用的提取逻辑
 //! 2. **组件引用**：在 `ExtractStep` 中通过 `use_component` 引用已定义的组件
-//! 3. **参数传递**：引用时可传入参数覆盖组件的默认输入
+//! 3. **参数传递**：引用时可传入参数覆盖组件的默认输入，组件内以流程变量访问参数
 //!
 //! # 示例
 //!
@@ -14,7 +14,8 @@
 //! # 定义组件
 //! [components.parse_encrypted_url]
 //! description = "解析加密的视频地址"
-//! inputs = { encrypted_url = "" }
+//! inputs = { quality = "hd" }
+//! required = ["encrypted_url"]
 //! extractor.steps = [{ script = "decrypt.parse_m3u8" }]
 //!
 //! [components.extract_cover]
@@ -64,6 +65,7 @@ pub type Components = HashMap<String, ComponentDefinition>;
 ///
 /// - `description`: 组件功能描述，便于维护
 /// - `inputs`: 组件接收的输入参数及其默认值
+/// - `required`: 没有默认值、引用时必须传入的参数
 /// - `extractor`: 组件的提取逻辑
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
@@ -78,6 +80,12 @@ pub struct ComponentDefinition {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub inputs: Option<HashMap<String, serde_json::Value>>,
 
+    /// 必填参数名列表
+    ///
+    /// 这些参数没有默认值，引用组件时未通过 `args` 传入会报错
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<Vec<String>>,
+
     /// 组件的提取逻辑
     pub extractor: FieldExtractor,
 }
@@ -107,6 +115,8 @@ pub enum ComponentRef {
         /// 组件名称
         name: String,
         /// 传递给组件的参数，会覆盖组件的默认输入
+        ///
+        /// 字符串参数按模板渲染（如 `"{{ raw_url }}"`）
         #[serde(skip_serializing_if = "Option::is_none")]
         args: Option<HashMap<String, serde_json::Value>>,
     },
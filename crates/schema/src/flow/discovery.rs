@@ -23,6 +23,9 @@ use super::common::{FilterGroup, Pagination};
 /// - 定义 `key = "category"` → 可用 `{{ category }}`
 /// - 定义 `key = "year"` → 可用 `{{ year }}`
 ///
+/// 调用方未选择的静态筛选器取其第一个选项的值；动态筛选器未选择时不注入变量，
+/// 模板中可用 `{% if year is defined %}` 判断。
+///
 /// ## Runtime 全局变量（通过 `$` 前缀访问）
 ///
 /// | 变量 | 说明 |
@@ -99,6 +102,13 @@ pub struct DiscoveryFlow {
 
     /// 将列表项映射为最终数据结构的字段提取规则
     pub fields: ItemFields,
+
+    /// 必填字段（`title`/`url`）缺失时是否跳过该项（默认 `true`）
+    ///
+    /// - `true`：跳过该项，并计入响应中的 `skipped` 计数
+    /// - `false`：任一项缺失必填字段即视为整个流程失败
+    #[serde(default = "default_true")]
+    pub skip_invalid_items: bool,
 }
 
 // ============================================================================
@@ -123,6 +133,7 @@ pub struct DiscoveryFlow {
 /// ```toml
 /// [categories]
 /// url = "https://example.com/categories"
+/// cache_duration = 86400  # 可选，结果缓存时间（秒），默认 3600
 /// list.steps = [{ css = ".category-item" }]
 ///
 /// [categories.fields]
@@ -176,6 +187,10 @@ pub struct DynamicOptionList {
 
     /// 选项字段提取规则
     pub fields: OptionFields,
+
+    /// 抓取结果按 URL 缓存的时间（秒），默认 3600，为 0 时不缓存
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_duration: Option<u32>,
 }
 
 /// 选项字段定义（用于动态提取）
@@ -224,6 +239,7 @@ pub struct OptionFields {
 /// ```toml
 /// [filters]
 /// url = "https://example.com/filters"
+/// cache_duration = 86400  # 可选，结果缓存时间（秒），默认 3600
 /// list.steps = [{ css = ".filter-group" }]
 ///
 /// [filters.fields]
@@ -259,6 +275,10 @@ pub struct DynamicFilterList {
 
     /// 筛选组字段提取规则
     pub fields: FilterGroupFields,
+
+    /// 抓取结果按 URL 缓存的时间（秒），默认 3600，为 0 时不缓存
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_duration: Option<u32>,
 }
 
 /// 筛选组字段定义（用于动态提取）
@@ -295,3 +315,11 @@ pub struct FilterOptionFields {
     /// 选项名称提取规则
     pub name: FieldExtractor,
 }
+
+// ============================================================================
+// 默认值函数
+// ============================================================================
+
+fn default_true() -> bool {
+    true
+}
@@ -256,6 +256,11 @@ pub struct WebViewLoginFlow {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub finish_script: Option<Script>,
 
+    /// 需要提取的 localStorage 键名
+    /// 登录成功后随 Cookie 一并保存
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extract_local_storage: Option<Vec<String>>,
+
     /// 登录超时时间（秒，默认 300）
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timeout_seconds: Option<u32>,
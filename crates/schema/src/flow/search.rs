@@ -83,4 +83,19 @@ pub struct SearchFlow {
 
     /// 将列表项映射为最终数据结构的字段提取规则
     pub fields: ItemFields,
+
+    /// 必填字段（`title`/`url`）缺失时是否跳过该项（默认 `true`）
+    ///
+    /// - `true`：跳过该项，并计入响应中的 `skipped` 计数
+    /// - `false`：任一项缺失必填字段即视为整个流程失败
+    #[serde(default = "default_true")]
+    pub skip_invalid_items: bool,
+}
+
+// ============================================================================
+// 默认值函数
+// ============================================================================
+
+fn default_true() -> bool {
+    true
 }
@@ -0,0 +1,28 @@
+//! # JSON Schema 导出
+//!
+//! 为可视化编辑器等外部工具提供规则文件的 JSON Schema。
+//! 结构体、枚举及其变体的 doc comment 会作为 `description` 写入 Schema
+
+use crate::core::CrawlerRule;
+use schemars::{Schema, schema_for};
+use serde_json::Value;
+
+/// Schema 版本（与 crate 版本一致）
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// 生成规则文件（`CrawlerRule`）的完整 JSON Schema
+///
+/// 附带 `$comment` 字段标注 Schema 版本
+pub fn rule_file_schema() -> Schema {
+    let mut schema = schema_for!(CrawlerRule);
+    schema.insert(
+        "$comment".to_string(),
+        Value::String(format!("Schema version: {}", VERSION)),
+    );
+    schema
+}
+
+/// 生成格式化后的规则文件 JSON Schema 字符串
+pub fn schema_json_string() -> serde_json::Result<String> {
+    serde_json::to_string_pretty(&rule_file_schema())
+}
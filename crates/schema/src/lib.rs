@@ -14,6 +14,7 @@
 //! - **选择步骤**：`css`、`json`、`xpath`、`regex` - 从文档提取数据
 //! - **过滤步骤**：`filter`、`attr`、`index` - 转换和过滤数据
 //! - **特殊步骤**：`const`、`var`、`script` - 常量、变量、脚本
+//! - **流程控制**：`map`、`condition`、`return`、`goto` - 映射、分支、提前结束、向前跳转
 //!
 //! ## 模板字符串规范
 //!
@@ -24,11 +25,17 @@
 //!   - `https://example.com/search?q={{ keyword }}`
 //!   - `User-Agent: MyBot/{{ version }}`
 //! - 支持表达式与嵌套：如 `{{ user.name }}`、`{{ items[0] }}`
+//!
+//! ## JSON Schema
+//!
+//! 通过 [`json_schema::rule_file_schema`] 或 [`json_schema::schema_json_string`] 获取规则文件的
+//! JSON Schema。
 
 pub mod config;
 pub mod core;
 pub mod extract;
 pub mod fields;
 pub mod flow;
+pub mod json_schema;
 pub mod script;
 pub mod template;
@@ -125,10 +125,10 @@ pub struct Script {
 
 /// 脚本来源
 ///
-/// 脚本代码的来源，三选一：
+/// 脚本代码的来源：
 /// - 内联代码（code）
-/// - 本地文件（file）
 /// - 远程 URL（url）
+/// - 脚本模块（module）
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ScriptSource {
@@ -136,6 +136,60 @@ pub enum ScriptSource {
     Code(String),
     /// 远程 URL
     Url(String),
+    /// 引用 `scripting.modules` 中定义的模块
+    ///
+    /// 配合 `function` 调用模块中的函数，函数以 `input` 为唯一参数；
+    /// 未指定 `function` 时直接执行模块代码
+    Module(String),
+}
+
+// ============================================================================
+// 脚本模块
+// ============================================================================
+
+/// 脚本模块配置
+///
+/// 定义可被多个脚本步骤复用的函数库，模块在首次被调用时才加载。
+/// 脚本与模块中可以 `模块名.函数名(...)` 调用其他模块的函数，
+/// 被调用的模块会随脚本一起加载，各模块的同名函数互不冲突。
+///
+/// # 示例
+///
+/// ```toml
+/// [scripting.modules.crypto]
+/// engine = "rhai"
+/// code = '''
+/// fn decode(s) { base64_decode(s) }
+/// '''
+///
+/// [scripting.modules.book]
+/// engine = "rhai"
+/// code = '''
+/// fn title(s) { crypto.decode(s).trim() }
+/// '''
+///
+/// # 在步骤中调用
+/// # url.steps = [{ script = { module = "crypto", function = "decode" } }]
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ScriptingConfig {
+    /// 脚本模块（以名称为键）
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub modules: HashMap<String, ScriptModule>,
+}
+
+/// 脚本模块
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ScriptModule {
+    /// 模块来源（code 或 url）
+    #[serde(flatten)]
+    pub source: ScriptSource,
+
+    /// 脚本引擎（可选，默认使用规则的默认引擎）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub engine: Option<ScriptEngine>,
 }
 
 // ============================================================================
@@ -66,12 +66,7 @@ use serde::{Deserialize, Serialize};
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Hash, Default)]
 #[serde(transparent)]
-pub struct Template(
-    #[schemars(pattern(
-        "{{\\s*([a-zA-Z_][a-zA-Z0-9_]*(?:\\.[a-zA-Z_][a-zA-Z0-9_]*|\\[[0-9]+\\])*)\\s*}}"
-    ))]
-    String,
-);
+pub struct Template(String);
 
 impl Template {
     /// 创建新模板
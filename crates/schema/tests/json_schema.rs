@@ -0,0 +1,35 @@
+//! JSON Schema 生成测试
+
+use crawler_schema::json_schema::{rule_file_schema, schema_json_string};
+use serde_json::Value;
+
+#[test]
+fn schema_contains_flows_and_steps() {
+    let schema = serde_json::to_value(rule_file_schema()).unwrap();
+
+    let properties = schema["properties"].as_object().unwrap();
+    for flow in ["search", "detail", "discovery", "content", "login"] {
+        assert!(properties.contains_key(flow), "缺少流程 {}", flow);
+    }
+    let step = &schema["$defs"]["ExtractStep"];
+    assert!(step.is_object());
+    assert!(
+        step["description"].as_str().unwrap().contains("提取步骤"),
+        "{}",
+        step["description"]
+    );
+    // 枚举变体的文档注释作为 description
+    assert_eq!(step["oneOf"][0]["description"], "CSS 选择器（HTML）");
+    assert!(
+        schema["$comment"]
+            .as_str()
+            .unwrap()
+            .starts_with("Schema version")
+    );
+}
+
+#[test]
+fn schema_string_is_valid_json() {
+    let json: Value = serde_json::from_str(&schema_json_string().unwrap()).unwrap();
+    assert_eq!(json["title"], "CrawlerRule");
+}